@@ -12,14 +12,19 @@ impl AmygdalaNucleus {
         Self { neurons: vec![0.0; n] }
     }
 
+    /// Leaky integration towards `input * modulation` (unit steady-state
+    /// gain, time constant of ten steps). The input is scaled by the leak
+    /// rate 0.1; unscaled, the steady state was ten times the input and any
+    /// drive above 0.1 saturated the nucleus.
     pub fn step(&mut self, input: &[f64], modulation: f64) {
         for (i, neuron) in self.neurons.iter_mut().enumerate() {
             let inp = if i < input.len() { input[i] } else { 0.0 };
-            *neuron = (*neuron * 0.9 + inp * modulation).clamp(0.0, 1.0);
+            *neuron = (*neuron * 0.9 + 0.1 * inp * modulation).clamp(0.0, 1.0);
         }
     }
 }
 
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct Amygdala {
     pub lateral: AmygdalaNucleus,   // Sensory input
     pub basal: AmygdalaNucleus,     // Associations
@@ -37,13 +42,24 @@ impl Amygdala {
         }
     }
 
+    /// Advance one step and return the central nucleus output.
+    ///
+    /// The basal nucleus receives the mean, not the sum, of the lateral
+    /// activity through `fear_weights`, so a naive CS stays below the
+    /// learning threshold instead of saturating it. The US drives the basal
+    /// nucleus directly (unit input), which is what lets the Hebbian LA -> BA
+    /// rule strengthen only the inputs active together with the US.
     pub fn step(&mut self, sensory_input: &[f64], us_present: bool) -> Vec<f64> {
         self.lateral.step(sensory_input, 1.0);
 
-        let mut basal_input = vec![0.0; self.basal.neurons.len()];
+        // Mean CS drive through the plastic LA -> BA weights; the US converges
+        // on the basal nucleus, so only CS-active inputs are strengthened
+        let n_lateral = self.lateral.neurons.len().max(1) as f64;
+        let us_drive = if us_present { 1.0 } else { 0.0 };
+        let mut basal_input = vec![us_drive; self.basal.neurons.len()];
         for (i, &lat) in self.lateral.neurons.iter().enumerate() {
             for j in 0..basal_input.len().min(self.fear_weights[i].len()) {
-                basal_input[j] += lat * self.fear_weights[i][j];
+                basal_input[j] += lat * self.fear_weights[i][j] / n_lateral;
             }
         }
         self.basal.step(&basal_input, 1.0);
@@ -73,3 +89,43 @@ impl Amygdala {
         self.central.neurons.iter().sum::<f64>() / self.central.neurons.len() as f64
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Peak fear response during a 50-step CS presentation
+    fn cs_response(amygdala: &mut Amygdala, cs: &[f64], us_present: bool) -> f64 {
+        let peak = (0..50)
+            .map(|_| {
+                amygdala.step(cs, us_present);
+                amygdala.fear_response()
+            })
+            .fold(0.0, f64::max);
+        // Inter-trial interval
+        for _ in 0..100 {
+            amygdala.step(&[], false);
+        }
+        peak
+    }
+
+    #[test]
+    fn test_fear_conditioning_is_cs_specific() {
+        let mut amygdala = Amygdala::new(10);
+        let cs_a = [1.0, 1.0, 1.0, 1.0, 1.0, 0.0, 0.0, 0.0, 0.0, 0.0];
+        let cs_b = [0.0, 0.0, 0.0, 0.0, 0.0, 1.0, 1.0, 1.0, 1.0, 1.0];
+
+        let naive_a = cs_response(&mut amygdala, &cs_a, false);
+        let naive_b = cs_response(&mut amygdala, &cs_b, false);
+        assert!(naive_a < 0.1 && (naive_a - naive_b).abs() < 1e-4);
+
+        for _ in 0..5 {
+            cs_response(&mut amygdala, &cs_a, true);
+        }
+
+        let conditioned_a = cs_response(&mut amygdala, &cs_a, false);
+        let conditioned_b = cs_response(&mut amygdala, &cs_b, false);
+        assert!(conditioned_a > 3.0 * naive_a, "{} vs {}", conditioned_a, naive_a);
+        assert!((conditioned_b - naive_b).abs() < 1e-4);
+    }
+}
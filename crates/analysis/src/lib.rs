@@ -9,12 +9,19 @@
 //! 2. **Analysis**: Cálculo de D₂ (dimensión de correlación) y λ₁ (Lyapunov)
 //! 3. **Output**: AttractorSignature → parámetros para macro_brain
 //!
+//! ## Módulos:
+//! - `attractor_analysis`: firmas de atractores (D₂, λ₁, régimen)
+//! - `spectral`: PSD de Welch y potencia por bandas EEG
+//! - `spike_trains`: estadísticas de trenes de spikes, distancias, sincronía y avalanchas
+//!
 //! ## Referencias Científicas:
 //! - Grassberger & Procaccia (1983): Correlation dimension algorithm
 //! - Rosenstein et al. (1993): Practical method for Lyapunov exponents
 //! - Stam (2005): Nonlinear dynamical analysis of EEG/MEG signals
 
 pub mod attractor_analysis;
+pub mod spectral;
+pub mod spike_trains;
 
 pub use attractor_analysis::{
     AttractorSignature,
@@ -26,3 +33,11 @@ pub use attractor_analysis::{
 
 /// Re-export for convenience
 pub use attractor_analysis::DynamicalRegime;
+
+pub use spectral::{welch_psd, FrequencyBand, PowerSpectrum};
+pub use spike_trains::{
+    avalanche_size_distribution, avalanches, cross_correlogram, fano_factor, firing_rate,
+    inter_spike_intervals, isi_statistics, population_counts, population_synchrony,
+    power_law_exponent, psth, spike_counts, spike_time_tiling_coefficient, spike_trains_from_flags,
+    van_rossum_distance, victor_purpura_distance, Avalanche, Correlogram, IsiStatistics, Psth,
+};
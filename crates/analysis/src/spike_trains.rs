@@ -0,0 +1,470 @@
+//! Spike-train and population analysis
+//!
+//! Works on plain spike trains, sorted spike times in ms, as stored in the
+//! recorder's units table. Per-step activity flags, such as a region's
+//! `Vec<bool>` in a sequence of `BrainState`s, convert with
+//! [`spike_trains_from_flags`]. Continuous traces (voltages, LFP) are plain
+//! `&[f64]` sample vectors; see [`crate::spectral`] for their spectra.
+//!
+//! - Single trains: ISI statistics and CV, Fano factor, PSTH
+//! - Pairs: cross-correlogram, spike time tiling coefficient (Cutts & Eglen
+//!   2014), Victor-Purpura (1996) and van Rossum (2001) distances
+//! - Populations: voltage synchrony χ (Golomb 2007), neuronal avalanches
+//!   (Beggs & Plenz 2003) with a power-law exponent fit (Clauset et al. 2009)
+
+use serde::{Deserialize, Serialize};
+
+/// Spike trains from per-step activity flags: `(time, flags)` with one flag
+/// per neuron, true on the steps where it fired. Each neuron's train lists
+/// the times of its active steps.
+pub fn spike_trains_from_flags<'a, I>(steps: I) -> Vec<Vec<f64>>
+where
+    I: IntoIterator<Item = (f64, &'a [bool])>,
+{
+    let mut trains: Vec<Vec<f64>> = Vec::new();
+    for (time, flags) in steps {
+        if trains.len() < flags.len() {
+            trains.resize(flags.len(), Vec::new());
+        }
+        for (train, _) in trains.iter_mut().zip(flags).filter(|(_, &fired)| fired) {
+            train.push(time);
+        }
+    }
+    trains
+}
+
+/// Mean firing rate (Hz) of a train over `duration` ms
+pub fn firing_rate(spikes: &[f64], duration: f64) -> f64 {
+    if duration > 0.0 {
+        spikes.len() as f64 * 1000.0 / duration
+    } else {
+        0.0
+    }
+}
+
+/// Inter-spike intervals (ms)
+pub fn inter_spike_intervals(spikes: &[f64]) -> Vec<f64> {
+    spikes.windows(2).map(|w| w[1] - w[0]).collect()
+}
+
+/// Summary of a train's inter-spike intervals
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct IsiStatistics {
+    /// Mean interval (ms)
+    pub mean: f64,
+    /// Standard deviation (ms)
+    pub std: f64,
+    /// Coefficient of variation std/mean: 0 regular, 1 Poisson, >1 bursty
+    pub cv: f64,
+    /// Local variation CV2, the mean of 2|I(n+1) - I(n)| / (I(n+1) + I(n)),
+    /// insensitive to slow rate changes (Holt et al. 1996)
+    pub cv2: f64,
+}
+
+/// ISI statistics, or None with fewer than three spikes
+pub fn isi_statistics(spikes: &[f64]) -> Option<IsiStatistics> {
+    let isi = inter_spike_intervals(spikes);
+    if isi.len() < 2 {
+        return None;
+    }
+
+    let mean = isi.iter().sum::<f64>() / isi.len() as f64;
+    let std = (isi.iter().map(|i| (i - mean).powi(2)).sum::<f64>() / isi.len() as f64).sqrt();
+    let cv2 = isi.windows(2).map(|w| 2.0 * (w[1] - w[0]).abs() / (w[1] + w[0])).sum::<f64>()
+        / (isi.len() - 1) as f64;
+    Some(IsiStatistics { mean, std, cv: std / mean, cv2 })
+}
+
+/// Spike counts in consecutive `bin` ms windows from `t_start` to `t_stop`
+pub fn spike_counts(spikes: &[f64], t_start: f64, t_stop: f64, bin: f64) -> Vec<usize> {
+    let bins = ((t_stop - t_start) / bin).floor().max(0.0) as usize;
+    let mut counts = vec![0; bins];
+    for &t in spikes {
+        if t >= t_start {
+            let i = ((t - t_start) / bin) as usize;
+            if i < bins {
+                counts[i] += 1;
+            }
+        }
+    }
+    counts
+}
+
+/// Fano factor var/mean of spike counts (windows of one train, or one count
+/// per trial); 1 for a Poisson process. None when no spikes were counted.
+pub fn fano_factor(counts: &[usize]) -> Option<f64> {
+    if counts.is_empty() {
+        return None;
+    }
+    let n = counts.len() as f64;
+    let mean = counts.iter().sum::<usize>() as f64 / n;
+    if mean == 0.0 {
+        return None;
+    }
+    let variance = counts.iter().map(|&c| (c as f64 - mean).powi(2)).sum::<f64>() / n;
+    Some(variance / mean)
+}
+
+/// Peri-stimulus time histogram
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Psth {
+    /// Bin centers relative to the stimulus (ms)
+    pub times: Vec<f64>,
+    /// Trial-averaged firing rate per bin (Hz)
+    pub rate: Vec<f64>,
+}
+
+/// PSTH of trials whose spike times are relative to the stimulus, binned
+/// from `t_start` to `t_stop` in `bin` ms
+pub fn psth(trials: &[Vec<f64>], t_start: f64, t_stop: f64, bin: f64) -> Psth {
+    let mut total = spike_counts(&[], t_start, t_stop, bin);
+    for trial in trials {
+        for (sum, count) in total.iter_mut().zip(spike_counts(trial, t_start, t_stop, bin)) {
+            *sum += count;
+        }
+    }
+
+    let norm = trials.len().max(1) as f64 * bin / 1000.0;
+    Psth {
+        times: (0..total.len()).map(|i| t_start + (i as f64 + 0.5) * bin).collect(),
+        rate: total.iter().map(|&c| c as f64 / norm).collect(),
+    }
+}
+
+/// Cross-correlogram of `target` relative to `reference`
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Correlogram {
+    /// Bin centers (ms); positive lags: target fires after reference
+    pub lags: Vec<f64>,
+    /// Spike pairs per bin
+    pub counts: Vec<usize>,
+}
+
+/// Histogram of `target - reference` spike time differences within
+/// [-max_lag, max_lag) in `bin` ms
+pub fn cross_correlogram(reference: &[f64], target: &[f64], max_lag: f64, bin: f64) -> Correlogram {
+    let bins = (2.0 * max_lag / bin).round() as usize;
+    let mut counts = vec![0; bins];
+
+    // Both trains are sorted: slide the window of target spikes along
+    let mut first = 0;
+    for &t in reference {
+        while first < target.len() && target[first] < t - max_lag {
+            first += 1;
+        }
+        for &s in target[first..].iter().take_while(|&&s| s < t + max_lag) {
+            let i = ((s - t + max_lag) / bin) as usize;
+            if i < bins {
+                counts[i] += 1;
+            }
+        }
+    }
+
+    Correlogram {
+        lags: (0..bins).map(|i| -max_lag + (i as f64 + 0.5) * bin).collect(),
+        counts,
+    }
+}
+
+/// Spike time tiling coefficient of two trains recorded from `t_start` to
+/// `t_stop`, with coincidence window ±`dt` ms: 1 for identical trains, about
+/// 0 for independent ones, independent of firing rate. None if either train
+/// is empty.
+pub fn spike_time_tiling_coefficient(a: &[f64], b: &[f64], dt: f64, t_start: f64, t_stop: f64) -> Option<f64> {
+    if a.is_empty() || b.is_empty() {
+        return None;
+    }
+
+    // Fraction of the recording within ±dt of a spike of the train
+    let tiled = |spikes: &[f64]| {
+        let mut covered = 0.0;
+        let mut reach = t_start;
+        for &t in spikes {
+            let (lo, hi) = ((t - dt).max(reach), (t + dt).min(t_stop));
+            if hi > lo {
+                covered += hi - lo;
+            }
+            reach = reach.max(t + dt);
+        }
+        covered / (t_stop - t_start)
+    };
+
+    // Fraction of the train's spikes within ±dt of a spike of the other
+    let coincident = |spikes: &[f64], other: &[f64]| {
+        let hits = spikes
+            .iter()
+            .filter(|&&t| {
+                let i = other.partition_point(|&s| s < t - dt);
+                i < other.len() && other[i] <= t + dt
+            })
+            .count();
+        hits as f64 / spikes.len() as f64
+    };
+
+    let (t_a, t_b) = (tiled(a), tiled(b));
+    let (p_a, p_b) = (coincident(a, b), coincident(b, a));
+    let term = |p: f64, t: f64| if p * t < 1.0 { (p - t) / (1.0 - p * t) } else { 1.0 };
+    Some(0.5 * (term(p_a, t_b) + term(p_b, t_a)))
+}
+
+/// Victor-Purpura distance: minimal cost of turning `a` into `b` by adding or
+/// deleting spikes (cost 1) and shifting them (cost `q` per ms)
+pub fn victor_purpura_distance(a: &[f64], b: &[f64], q: f64) -> f64 {
+    let mut previous: Vec<f64> = (0..=b.len()).map(|j| j as f64).collect();
+    let mut current = vec![0.0; b.len() + 1];
+
+    for (i, &ta) in a.iter().enumerate() {
+        current[0] = (i + 1) as f64;
+        for (j, &tb) in b.iter().enumerate() {
+            current[j + 1] = (previous[j + 1] + 1.0)
+                .min(current[j] + 1.0)
+                .min(previous[j] + q * (ta - tb).abs());
+        }
+        std::mem::swap(&mut previous, &mut current);
+    }
+    previous[b.len()]
+}
+
+/// Van Rossum distance with exponential kernel time constant `tau` ms,
+/// normalized so that a single unmatched spike contributes 1/sqrt(2)
+pub fn van_rossum_distance(a: &[f64], b: &[f64], tau: f64) -> f64 {
+    let overlap = |x: &[f64], y: &[f64]| {
+        x.iter().map(|&s| y.iter().map(|&t| (-(s - t).abs() / tau).exp()).sum::<f64>()).sum::<f64>()
+    };
+    let squared = 0.5 * (overlap(a, a) + overlap(b, b) - 2.0 * overlap(a, b));
+    squared.max(0.0).sqrt()
+}
+
+/// Population synchrony χ: square root of the variance of the population-mean
+/// trace over the mean variance of the individual traces; 1 for identical
+/// traces, about 1/sqrt(N) for N independent ones. Traces are equally long
+/// sample vectors (e.g. soma voltages); None without variance.
+pub fn population_synchrony(traces: &[Vec<f64>]) -> Option<f64> {
+    let samples = traces.iter().map(Vec::len).min()?;
+    if samples == 0 {
+        return None;
+    }
+
+    let variance = |x: &mut dyn Iterator<Item = f64>| {
+        let values: Vec<f64> = x.collect();
+        let mean = values.iter().sum::<f64>() / values.len() as f64;
+        values.iter().map(|v| (v - mean).powi(2)).sum::<f64>() / values.len() as f64
+    };
+
+    let n = traces.len() as f64;
+    let mean_trace_variance =
+        variance(&mut (0..samples).map(|k| traces.iter().map(|trace| trace[k]).sum::<f64>() / n));
+    let individual = traces.iter().map(|trace| variance(&mut trace[..samples].iter().copied())).sum::<f64>() / n;
+    (individual > 0.0).then(|| (mean_trace_variance / individual).sqrt())
+}
+
+/// Spikes of all trains per `bin` ms from `t_start` to `t_stop`
+pub fn population_counts(trains: &[Vec<f64>], t_start: f64, t_stop: f64, bin: f64) -> Vec<usize> {
+    let mut total = spike_counts(&[], t_start, t_stop, bin);
+    for train in trains {
+        for (sum, count) in total.iter_mut().zip(spike_counts(train, t_start, t_stop, bin)) {
+            *sum += count;
+        }
+    }
+    total
+}
+
+/// A cascade of activity between two silent bins
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Avalanche {
+    /// Total spikes
+    pub size: usize,
+    /// Number of active bins
+    pub duration: usize,
+}
+
+/// Avalanches in binned population counts. Runs cut off by the start or end
+/// of the recording are discarded.
+pub fn avalanches(counts: &[usize]) -> Vec<Avalanche> {
+    let mut found = Vec::new();
+    let mut run: Option<Avalanche> = None;
+    let mut preceded_by_silence = false;
+
+    for &count in counts {
+        match (count, run.as_mut()) {
+            (0, Some(_)) => {
+                let avalanche = run.take().unwrap();
+                if preceded_by_silence {
+                    found.push(avalanche);
+                }
+                preceded_by_silence = true;
+            }
+            (0, None) => preceded_by_silence = true,
+            (_, Some(avalanche)) => {
+                avalanche.size += count;
+                avalanche.duration += 1;
+            }
+            (_, None) => run = Some(Avalanche { size: count, duration: 1 }),
+        }
+    }
+    found
+}
+
+/// Empirical probability of each avalanche size, by increasing size
+pub fn avalanche_size_distribution(avalanches: &[Avalanche]) -> Vec<(usize, f64)> {
+    let mut sizes: Vec<usize> = avalanches.iter().map(|a| a.size).collect();
+    sizes.sort_unstable();
+
+    let n = sizes.len() as f64;
+    let mut distribution: Vec<(usize, f64)> = Vec::new();
+    for size in sizes {
+        match distribution.last_mut() {
+            Some((last, p)) if *last == size => *p += 1.0 / n,
+            _ => distribution.push((size, 1.0 / n)),
+        }
+    }
+    distribution
+}
+
+/// Maximum-likelihood exponent α of a discrete power law P(s) ~ s^-α fitted
+/// to the sizes of at least `s_min`; about 1.5 at criticality. None with
+/// fewer than two sizes in range.
+pub fn power_law_exponent(avalanches: &[Avalanche], s_min: usize) -> Option<f64> {
+    let s_min = s_min.max(1) as f64;
+    let logs: Vec<f64> = avalanches
+        .iter()
+        .map(|a| a.size as f64)
+        .filter(|&s| s >= s_min)
+        .map(|s| (s / (s_min - 0.5)).ln())
+        .collect();
+    if logs.len() < 2 {
+        return None;
+    }
+    Some(1.0 + logs.len() as f64 / logs.iter().sum::<f64>())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Poisson train at `rate` Hz over `duration` ms from a fixed LCG stream
+    fn poisson(rate: f64, duration: f64, seed: u64) -> Vec<f64> {
+        let mut state = seed;
+        let mut uniform = move || {
+            state = state.wrapping_mul(6364136223846793005).wrapping_add(1442695040888963407);
+            ((state >> 11) as f64 + 0.5) / (1u64 << 53) as f64
+        };
+
+        let mut spikes = Vec::new();
+        let mut t = 0.0;
+        loop {
+            t -= uniform().ln() * 1000.0 / rate;
+            if t >= duration {
+                return spikes;
+            }
+            spikes.push(t);
+        }
+    }
+
+    #[test]
+    fn test_regular_and_poisson_variability() {
+        let regular: Vec<f64> = (0..100).map(|i| i as f64 * 10.0).collect();
+        let stats = isi_statistics(&regular).unwrap();
+        assert!((stats.mean - 10.0).abs() < 1e-12 && stats.cv < 1e-12 && stats.cv2 < 1e-12);
+        assert_eq!(fano_factor(&spike_counts(&regular, 0.0, 1000.0, 100.0)), Some(0.0));
+
+        let train = poisson(20.0, 200_000.0, 7);
+        let stats = isi_statistics(&train).unwrap();
+        assert!((stats.cv - 1.0).abs() < 0.05, "{stats:?}");
+        assert!((firing_rate(&train, 200_000.0) - 20.0).abs() < 1.0);
+        let fano = fano_factor(&spike_counts(&train, 0.0, 200_000.0, 500.0)).unwrap();
+        assert!((fano - 1.0).abs() < 0.15, "{fano}");
+    }
+
+    #[test]
+    fn test_psth_and_correlogram() {
+        let trials = vec![vec![5.0, 12.0], vec![6.0], vec![7.0, 30.0]];
+        let histogram = psth(&trials, 0.0, 40.0, 10.0);
+        assert_eq!(histogram.times, vec![5.0, 15.0, 25.0, 35.0]);
+        // 3 spikes in 3 trials x 10 ms -> 100 Hz
+        assert!((histogram.rate[0] - 100.0).abs() < 1e-9);
+
+        // Target follows reference by 3 ms
+        let reference: Vec<f64> = (0..50).map(|i| i as f64 * 20.0).collect();
+        let target: Vec<f64> = reference.iter().map(|t| t + 3.0).collect();
+        let ccg = cross_correlogram(&reference, &target, 10.0, 2.0);
+        let peak = ccg.counts.iter().enumerate().max_by_key(|(_, &c)| c).unwrap().0;
+        assert!((ccg.lags[peak] - 3.0).abs() <= 1.0);
+        assert_eq!(ccg.counts[peak], 50);
+    }
+
+    #[test]
+    fn test_pairwise_similarity_measures() {
+        let a = poisson(10.0, 10_000.0, 1);
+        let b = poisson(10.0, 10_000.0, 2);
+        let sttc_same = spike_time_tiling_coefficient(&a, &a, 5.0, 0.0, 10_000.0).unwrap();
+        let sttc_independent = spike_time_tiling_coefficient(&a, &b, 5.0, 0.0, 10_000.0).unwrap();
+        assert!((sttc_same - 1.0).abs() < 1e-12);
+        assert!(sttc_independent.abs() < 0.1, "{sttc_independent}");
+        assert_eq!(spike_time_tiling_coefficient(&a, &[], 5.0, 0.0, 10_000.0), None);
+
+        // Shift of 2 ms costs 2q; beyond 2/q deleting and re-adding is cheaper
+        assert!((victor_purpura_distance(&[10.0], &[12.0], 0.1) - 0.2).abs() < 1e-12);
+        assert!((victor_purpura_distance(&[10.0], &[50.0], 0.1) - 2.0).abs() < 1e-12);
+        assert!((victor_purpura_distance(&[10.0, 20.0], &[], 0.1) - 2.0).abs() < 1e-12);
+
+        assert_eq!(van_rossum_distance(&a, &a, 10.0), 0.0);
+        assert!((van_rossum_distance(&[10.0], &[], 10.0) - 0.5f64.sqrt()).abs() < 1e-12);
+        assert!(van_rossum_distance(&[10.0], &[11.0], 10.0) < van_rossum_distance(&[10.0], &[30.0], 10.0));
+    }
+
+    #[test]
+    fn test_population_synchrony() {
+        let wave: Vec<f64> = (0..1000).map(|k| (k as f64 * 0.05).sin()).collect();
+        assert!((population_synchrony(&vec![wave.clone(); 10]).unwrap() - 1.0).abs() < 1e-12);
+
+        // Independent noise: chi ~ 1/sqrt(N)
+        let noise: Vec<Vec<f64>> = (0..25)
+            .map(|seed| {
+                let train = poisson(100.0, 1000.0, seed + 10);
+                let counts = spike_counts(&train, 0.0, 1000.0, 1.0);
+                counts.iter().map(|&c| c as f64).collect()
+            })
+            .collect();
+        let chi = population_synchrony(&noise).unwrap();
+        assert!(chi < 0.35, "{chi}");
+    }
+
+    #[test]
+    fn test_avalanches_between_silent_bins() {
+        let counts = [2, 0, 1, 3, 0, 0, 4, 0, 1, 1, 1, 0, 5];
+        let found = avalanches(&counts);
+        assert_eq!(
+            found,
+            vec![
+                Avalanche { size: 4, duration: 2 },
+                Avalanche { size: 4, duration: 1 },
+                Avalanche { size: 3, duration: 3 },
+            ]
+        );
+        assert_eq!(avalanche_size_distribution(&found), vec![(3, 1.0 / 3.0), (4, 2.0 / 3.0)]);
+    }
+
+    #[test]
+    fn test_power_law_exponent_recovers_alpha() {
+        // Sizes drawn from P(s) ~ s^-1.5 by inverse transform of the continuous law
+        let mut state: u64 = 99;
+        let sizes: Vec<Avalanche> = (0..20_000)
+            .map(|_| {
+                state = state.wrapping_mul(6364136223846793005).wrapping_add(1442695040888963407);
+                let u = ((state >> 11) as f64 + 0.5) / (1u64 << 53) as f64;
+                let size = (0.5 * (1.0 - u).powf(-1.0 / 0.5) + 0.5).floor() as usize;
+                Avalanche { size, duration: 1 }
+            })
+            .collect();
+        let alpha = power_law_exponent(&sizes, 1).unwrap();
+        assert!((alpha - 1.5).abs() < 0.05, "{alpha}");
+    }
+
+    #[test]
+    fn test_flags_become_spike_trains() {
+        let frames = [(0.1, vec![true, false]), (0.2, vec![false, false]), (0.3, vec![true, true])];
+        let trains = spike_trains_from_flags(frames.iter().map(|(t, flags)| (*t, flags.as_slice())));
+        assert_eq!(trains, vec![vec![0.1, 0.3], vec![0.3]]);
+    }
+}
@@ -77,6 +77,7 @@ impl MediumSpinyNeuron {
     }
 }
 
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct Striatum {
     pub d1_msns: Vec<MediumSpinyNeuron>,
     pub d2_msns: Vec<MediumSpinyNeuron>,
@@ -107,6 +108,7 @@ impl Striatum {
     }
 }
 
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct GlobalPallidus {
     pub gpe_neurons: usize,
     pub gpi_neurons: usize,
@@ -142,6 +144,7 @@ impl GlobalPallidus {
     }
 }
 
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct SubthalamicNucleus {
     pub neurons: usize,
     pub activity: Vec<bool>,
@@ -163,6 +166,7 @@ impl SubthalamicNucleus {
     }
 }
 
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct SubstantiaNigra {
     pub dopamine_neurons: usize,
     pub dopamine_level: f64,
@@ -206,6 +210,7 @@ impl SubstantiaNigra {
     }
 }
 
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct BasalGanglia {
     pub striatum: Striatum,
     pub gp: GlobalPallidus,
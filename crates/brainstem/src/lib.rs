@@ -3,6 +3,7 @@
 //! - Raphe nuclei: Serotonin (5-HT)
 //! - Locus coeruleus: Norepinephrine (NE)
 //! - Ventral tegmental area: Dopamine (DA) [in basal-ganglia, refine here]
+//! - Pontine nuclei: Corticopontine relay to the cerebellum (mossy fibers)
 
 use serde::{Deserialize, Serialize};
 
@@ -91,11 +92,48 @@ impl LocusCoeruleus {
     }
 }
 
+/// Pontine nuclei: relay of layer 5 corticopontine input to the cerebellum
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PontineNuclei {
+    /// Relay activity per corticopontine channel (0-1), the mossy-fiber output
+    pub activity: Vec<f64>,
+
+    /// Integration time constant (ms)
+    pub tau: f64,
+}
+
+impl PontineNuclei {
+    pub fn new() -> Self {
+        Self {
+            activity: Vec::new(),
+            tau: 5.0,
+        }
+    }
+
+    /// Low-pass relay of cortical drive (0-1 per channel); one pontine unit
+    /// per input channel
+    pub fn relay(&mut self, dt: f64, cortical_drive: &[f64]) -> &[f64] {
+        self.activity.resize(cortical_drive.len(), 0.0);
+        for (unit, &drive) in self.activity.iter_mut().zip(cortical_drive) {
+            *unit += (drive.clamp(0.0, 1.0) - *unit) * dt / self.tau;
+        }
+        &self.activity
+    }
+}
+
+impl Default for PontineNuclei {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
 /// Complete brainstem
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct Brainstem {
     pub raphe: RapheNuclei,
     pub locus_coeruleus: LocusCoeruleus,
+    #[serde(default = "PontineNuclei::new")]
+    pub pontine: PontineNuclei,
 }
 
 impl Brainstem {
@@ -103,6 +141,7 @@ impl Brainstem {
         Self {
             raphe: RapheNuclei::new(),
             locus_coeruleus: LocusCoeruleus::new(),
+            pontine: PontineNuclei::new(),
         }
     }
 
@@ -155,4 +194,16 @@ mod tests {
         assert_eq!(lc.firing_mode, FiringMode::Phasic);
         assert!(lc.norepinephrine > baseline);
     }
+
+    #[test]
+    fn test_pontine_relay_tracks_cortical_drive() {
+        let mut pontine = PontineNuclei::new();
+        for _ in 0..500 {
+            pontine.relay(0.1, &[1.0, 0.0, 3.0]);
+        }
+        assert!((pontine.activity[0] - 1.0).abs() < 1e-3);
+        assert_eq!(pontine.activity[1], 0.0);
+        // Drive saturates at 1
+        assert!(pontine.activity[2] <= 1.0);
+    }
 }
@@ -76,6 +76,7 @@ impl PurkinjeCell {
     }
 }
 
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct DeepCerebellarNuclei {
     pub neurons: Vec<f64>,
 }
@@ -93,10 +94,14 @@ impl DeepCerebellarNuclei {
     }
 }
 
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct Cerebellum {
     pub granule_cells: Vec<GranuleCell>,
     pub purkinje_cells: Vec<PurkinjeCell>,
     pub deep_nuclei: DeepCerebellarNuclei,
+    /// Purkinje simple spikes of the last step
+    #[serde(default)]
+    pub purkinje_spikes: Vec<bool>,
 }
 
 impl Cerebellum {
@@ -105,6 +110,7 @@ impl Cerebellum {
             granule_cells: (0..num_granule).map(|i| GranuleCell::new(i)).collect(),
             purkinje_cells: (0..num_purkinje).map(|i| PurkinjeCell::new(i, num_granule)).collect(),
             deep_nuclei: DeepCerebellarNuclei::new(num_purkinje / 2),
+            purkinje_spikes: vec![false; num_purkinje],
         }
     }
 
@@ -123,6 +129,7 @@ impl Cerebellum {
 
         // Deep nuclei receive Purkinje inhibition
         self.deep_nuclei.step(&purkinje_output);
+        self.purkinje_spikes = purkinje_output;
 
         self.deep_nuclei.neurons.clone()
     }
@@ -0,0 +1,228 @@
+//! `drug`: GABA-A receptor response and single-dose PK time courses.
+
+use clap::{Args, Subcommand, ValueEnum};
+use pharmacology::pharmacokinetics::{OneCompartmentModel, PkDatabase};
+use pharmacology::{SimulationResult, UnifiedGabaAModel};
+use serde::Serialize;
+
+use crate::output::{OutputArgs, Record};
+use crate::{CliError, Result};
+
+#[derive(Debug, Args)]
+pub struct DrugArgs {
+    #[command(subcommand)]
+    pub command: DrugCommand,
+}
+
+#[derive(Debug, Subcommand)]
+pub enum DrugCommand {
+    /// GABA-A receptor response at fixed brain concentrations
+    Response(ResponseArgs),
+    /// Plasma / brain time course after a single dose
+    Pk(PkArgs),
+}
+
+#[derive(Debug, Args)]
+pub struct ResponseArgs {
+    /// Drug name (DrugDatabase entry)
+    #[arg(long)]
+    pub drug: String,
+
+    /// Free brain concentrations to evaluate (µM)
+    #[arg(long, value_delimiter = ',', required = true)]
+    pub conc: Vec<f64>,
+
+    #[command(flatten)]
+    pub output: OutputArgs,
+}
+
+/// Dosing routes supported by the one-compartment model
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ValueEnum)]
+pub enum DoseRoute {
+    /// Intravenous bolus
+    Iv,
+    /// Oral (bioavailability-scaled)
+    Oral,
+}
+
+#[derive(Debug, Args)]
+pub struct PkArgs {
+    /// Drug name (PkDatabase entry)
+    #[arg(long)]
+    pub drug: String,
+
+    /// Dose (mg)
+    #[arg(long)]
+    pub dose: f64,
+
+    /// Route of administration
+    #[arg(long, value_enum, default_value_t = DoseRoute::Oral)]
+    pub route: DoseRoute,
+
+    /// Body weight (kg)
+    #[arg(long, default_value_t = 70.0)]
+    pub weight: f64,
+
+    /// Duration of the time course (h)
+    #[arg(long, default_value_t = 24.0)]
+    pub hours: f64,
+
+    /// Sampling interval (h)
+    #[arg(long, default_value_t = 0.5)]
+    pub interval: f64,
+
+    #[command(flatten)]
+    pub output: OutputArgs,
+}
+
+impl Record for SimulationResult {
+    fn header() -> Vec<&'static str> {
+        vec![
+            "drug", "concentration_um", "binding_site", "ki_nm", "efficacy",
+            "occupancy", "modulation", "beta_increase_pct", "sedation_pct",
+        ]
+    }
+
+    fn fields(&self) -> Vec<String> {
+        vec![
+            self.drug.clone(),
+            self.concentration_um.to_string(),
+            format!("{:?}", self.binding_site),
+            self.ki_nm.to_string(),
+            self.efficacy.to_string(),
+            self.occupancy.to_string(),
+            self.modulation.to_string(),
+            self.beta_increase_pct.to_string(),
+            self.sedation_pct.to_string(),
+        ]
+    }
+}
+
+/// One sample of a PK time course
+#[derive(Debug, Clone, Serialize)]
+pub struct PkSample {
+    pub time_h: f64,
+    pub plasma_mg_l: f64,
+    pub brain_um: f64,
+    /// GABA-A modulation at `brain_um`, for drugs in the receptor database
+    pub gaba_a_modulation: Option<f64>,
+}
+
+impl Record for PkSample {
+    fn header() -> Vec<&'static str> {
+        vec!["time_h", "plasma_mg_l", "brain_um", "gaba_a_modulation"]
+    }
+
+    fn fields(&self) -> Vec<String> {
+        vec![
+            self.time_h.to_string(),
+            self.plasma_mg_l.to_string(),
+            self.brain_um.to_string(),
+            self.gaba_a_modulation.map(|m| m.to_string()).unwrap_or_default(),
+        ]
+    }
+}
+
+/// Receptor response of `drug` at each concentration
+pub fn receptor_response(drug: &str, concentrations: &[f64]) -> Result<Vec<SimulationResult>> {
+    let mut model = UnifiedGabaAModel::database_mode();
+    concentrations
+        .iter()
+        .map(|&conc| {
+            if !conc.is_finite() || conc < 0.0 {
+                return Err(CliError::InvalidConfig(format!("concentration must be non-negative, got {}", conc)));
+            }
+            model.simulate_drug(drug, conc).map_err(|_| CliError::UnknownDrug(drug.to_string()))
+        })
+        .collect()
+}
+
+/// Single-dose one-compartment time course, coupled to the GABA-A model
+pub fn pk_time_course(args: &PkArgs) -> Result<Vec<PkSample>> {
+    if !args.interval.is_finite() || args.interval <= 0.0 {
+        return Err(CliError::InvalidConfig(format!("interval must be positive, got {}", args.interval)));
+    }
+    if !args.dose.is_finite() || args.dose < 0.0 || !args.weight.is_finite() || args.weight <= 0.0 {
+        return Err(CliError::InvalidConfig("dose and weight must be positive".to_string()));
+    }
+
+    let pk_db = PkDatabase::new();
+    let pk = pk_db.get(&args.drug).ok_or_else(|| CliError::UnknownDrug(args.drug.clone()))?;
+
+    let mut model = OneCompartmentModel::new(pk.clone(), args.weight);
+    match args.route {
+        DoseRoute::Iv => model.give_iv_bolus(args.dose),
+        DoseRoute::Oral => model.give_oral(args.dose),
+    }
+
+    let mut receptor = UnifiedGabaAModel::database_mode();
+    let on_gaba_a = receptor.database().contains(&args.drug);
+
+    let samples = (args.hours / args.interval).floor() as usize;
+    let mut trace = Vec::with_capacity(samples + 1);
+    for i in 0..=samples {
+        let time_h = i as f64 * args.interval;
+        let brain_um = model.brain_concentration_um_at(time_h);
+        let gaba_a_modulation = if on_gaba_a {
+            receptor.simulate_drug(&args.drug, brain_um).ok().map(|r| r.modulation)
+        } else {
+            None
+        };
+
+        trace.push(PkSample {
+            time_h,
+            plasma_mg_l: model.concentration_at(time_h),
+            brain_um,
+            gaba_a_modulation,
+        });
+    }
+
+    Ok(trace)
+}
+
+pub fn run(args: &DrugArgs) -> Result<()> {
+    match &args.command {
+        DrugCommand::Response(a) => a.output.write(&receptor_response(&a.drug, &a.conc)?),
+        DrugCommand::Pk(a) => a.output.write(&pk_time_course(a)?),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::output::OutputFormat;
+
+    fn pk_args(route: DoseRoute) -> PkArgs {
+        PkArgs {
+            drug: "diazepam".to_string(),
+            dose: 10.0,
+            route,
+            weight: 70.0,
+            hours: 4.0,
+            interval: 1.0,
+            output: OutputArgs { format: OutputFormat::Csv, output: None },
+        }
+    }
+
+    #[test]
+    fn test_response_is_monotonic_in_concentration() {
+        let results = receptor_response("diazepam", &[0.01, 0.1, 1.0]).unwrap();
+        assert_eq!(results.len(), 3);
+        assert!(results[0].occupancy <= results[1].occupancy);
+        assert!(results[1].occupancy <= results[2].occupancy);
+
+        assert!(matches!(receptor_response("not-a-drug", &[1.0]), Err(CliError::UnknownDrug(_))));
+    }
+
+    #[test]
+    fn test_pk_time_course_decays() {
+        let trace = pk_time_course(&pk_args(DoseRoute::Iv)).unwrap();
+        assert_eq!(trace.len(), 5);
+        assert!(trace[0].plasma_mg_l > trace[4].plasma_mg_l);
+        assert!(trace[0].gaba_a_modulation.is_some());
+
+        // Oral bioavailability < 1 lowers the peak
+        let oral = pk_time_course(&pk_args(DoseRoute::Oral)).unwrap();
+        assert!(oral[0].plasma_mg_l <= trace[0].plasma_mg_l);
+    }
+}
@@ -1,5 +1,52 @@
-//! cli module
+//! Command-line front end for HumanBrain experiments.
+//!
+//! The `humanbrain` binary exposes the simulation crates as subcommands so
+//! that an experiment is a command line (or a config file) rather than a new
+//! Rust example:
+//!
+//! - `simulate`: run a `WholeBrain` experiment from a TOML/JSON `BrainConfig`,
+//!   optionally checkpointing and resuming long runs
+//! - `neuron`: current-clamp a single `MultiCompartmentalNeuron` or SWC morphology
+//! - `drug`: GABA-A receptor response and single-dose PK time course
+//! - `validate`: PET occupancy / plasma PK validation against the literature
+//!
+//! Every subcommand writes its results as CSV or JSON.
 
-pub fn placeholder() {
-    println!("cli module - to be implemented");
+pub mod drug;
+pub mod neuron;
+pub mod output;
+pub mod simulate;
+pub mod validate;
+
+pub use output::{OutputFormat, Record};
+
+use thiserror::Error;
+
+#[derive(Debug, Error)]
+pub enum CliError {
+    #[error("I/O error: {0}")]
+    Io(#[from] std::io::Error),
+
+    #[error("JSON error: {0}")]
+    Json(#[from] serde_json::Error),
+
+    #[error("Invalid config: {0}")]
+    InvalidConfig(String),
+
+    #[error("{0}")]
+    Config(#[from] whole_brain::ConfigError),
+
+    #[error("Neuron model error: {0}")]
+    Neuron(#[from] neurons::NeuronError),
+
+    #[error("Checkpoint error: {0}")]
+    Checkpoint(#[from] whole_brain::CheckpointError),
+
+    #[error("Simulation error: {0}")]
+    Simulation(String),
+
+    #[error("Unknown drug: {0}")]
+    UnknownDrug(String),
 }
+
+pub type Result<T> = std::result::Result<T, CliError>;
@@ -0,0 +1,39 @@
+//! `humanbrain`: run HumanBrain experiments from the command line.
+
+use clap::{Parser, Subcommand};
+use cli::{drug, neuron, simulate, validate};
+
+#[derive(Debug, Parser)]
+#[command(name = "humanbrain", version, about = "HumanBrain simulation toolkit")]
+struct Cli {
+    #[command(subcommand)]
+    command: Command,
+}
+
+#[derive(Debug, Subcommand)]
+enum Command {
+    /// Run a whole-brain simulation from a config file
+    Simulate(simulate::SimulateArgs),
+    /// Current-clamp a single multi-compartmental neuron
+    Neuron(neuron::NeuronArgs),
+    /// Drug receptor response or PK time course
+    Drug(drug::DrugArgs),
+    /// Validate against PET / PK literature
+    Validate(validate::ValidateArgs),
+}
+
+fn main() {
+    let cli = Cli::parse();
+
+    let result = match &cli.command {
+        Command::Simulate(args) => simulate::run(args),
+        Command::Neuron(args) => neuron::run(args),
+        Command::Drug(args) => drug::run(args),
+        Command::Validate(args) => validate::run(args),
+    };
+
+    if let Err(e) = result {
+        eprintln!("error: {}", e);
+        std::process::exit(1);
+    }
+}
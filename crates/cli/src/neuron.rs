@@ -0,0 +1,287 @@
+//! `neuron`: current-clamp a single multi-compartmental neuron.
+
+use clap::{Args, ValueEnum};
+use neurons::compartmental::ChannelStates;
+use neurons::{Biophysics, Discretization, Integrator, MultiCompartmentalNeuron, SWCMorphology};
+use serde::Serialize;
+use std::path::{Path, PathBuf};
+
+use crate::output::{OutputArgs, Record};
+use crate::{CliError, Result};
+
+/// Built-in cell geometries
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ValueEnum)]
+pub enum CellModel {
+    /// `MultiCompartmentalNeuron::new_pyramidal` (apical, basal, AIS)
+    Pyramidal,
+    /// `MultiCompartmentalNeuron::new`: soma plus an unbranched dendrite
+    BallAndStick,
+}
+
+/// Cable equation solvers (see `neurons::Integrator`)
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ValueEnum)]
+pub enum Solver {
+    ForwardEuler,
+    BackwardEuler,
+    CrankNicolson,
+}
+
+impl From<Solver> for Integrator {
+    fn from(solver: Solver) -> Self {
+        match solver {
+            Solver::ForwardEuler => Integrator::ForwardEuler,
+            Solver::BackwardEuler => Integrator::BackwardEuler,
+            Solver::CrankNicolson => Integrator::CrankNicolson,
+        }
+    }
+}
+
+#[derive(Debug, Args)]
+pub struct NeuronArgs {
+    /// Built-in cell geometry (ignored when --swc is given)
+    #[arg(long, value_enum, default_value_t = CellModel::Pyramidal)]
+    pub model: CellModel,
+
+    /// Number of compartments for the ball-and-stick model
+    #[arg(long, default_value_t = 10)]
+    pub compartments: usize,
+
+    /// Build the cell from an SWC reconstruction instead
+    #[arg(long)]
+    pub swc: Option<PathBuf>,
+
+    /// Region-specific channel densities for --swc cells (JSON `Biophysics`)
+    #[arg(long, requires = "swc")]
+    pub biophysics: Option<PathBuf>,
+
+    /// Maximum compartment length as a fraction of the 100 Hz length constant
+    #[arg(long, default_value_t = 0.1)]
+    pub d_lambda: f64,
+
+    /// Length of axon (um) from the soma treated as axon initial segment
+    #[arg(long, default_value_t = 0.0)]
+    pub ais_length: f64,
+
+    #[command(flatten)]
+    pub clamp: CurrentClamp,
+
+    /// Time step (ms)
+    #[arg(long, default_value_t = 0.025)]
+    pub dt: f64,
+
+    /// Cable equation solver; the implicit ones stay stable on fine SWC trees
+    #[arg(long, value_enum, default_value_t = Solver::ForwardEuler)]
+    pub solver: Solver,
+
+    /// Simulated duration (ms)
+    #[arg(long, default_value_t = 200.0)]
+    pub t_stop: f64,
+
+    /// Record every n-th step
+    #[arg(long, default_value_t = 1)]
+    pub record_every: usize,
+
+    #[command(flatten)]
+    pub output: OutputArgs,
+}
+
+/// Square current-clamp pulse
+#[derive(Debug, Clone, Args)]
+pub struct CurrentClamp {
+    /// Injected current (pA)
+    #[arg(long, default_value_t = 100.0, allow_hyphen_values = true)]
+    pub amplitude: f64,
+
+    /// Pulse onset (ms)
+    #[arg(long, default_value_t = 10.0)]
+    pub delay: f64,
+
+    /// Pulse duration (ms)
+    #[arg(long, default_value_t = 100.0)]
+    pub width: f64,
+
+    /// Compartment receiving the current (0 = soma)
+    #[arg(long, default_value_t = 0)]
+    pub site: usize,
+}
+
+impl CurrentClamp {
+    /// Injected current (pA) at time `t` (ms)
+    pub fn current_at(&self, t: f64) -> f64 {
+        if t >= self.delay && t < self.delay + self.width {
+            self.amplitude
+        } else {
+            0.0
+        }
+    }
+}
+
+/// One recorded sample of the clamped cell
+#[derive(Debug, Clone, Serialize)]
+pub struct VoltageSample {
+    pub time: f64,
+    pub injected_pa: f64,
+    pub soma_mv: f64,
+    pub site_mv: f64,
+    /// An action potential started since the previous sample
+    pub spiking: bool,
+}
+
+impl Record for VoltageSample {
+    fn header() -> Vec<&'static str> {
+        vec!["time", "injected_pa", "soma_mv", "site_mv", "spiking"]
+    }
+
+    fn fields(&self) -> Vec<String> {
+        vec![
+            self.time.to_string(),
+            self.injected_pa.to_string(),
+            self.soma_mv.to_string(),
+            self.site_mv.to_string(),
+            self.spiking.to_string(),
+        ]
+    }
+}
+
+/// Load a JSON biophysics spec
+pub fn load_biophysics(path: &Path) -> Result<Biophysics> {
+    let text = std::fs::read_to_string(path)?;
+    Ok(serde_json::from_str(&text)?)
+}
+
+/// Run the current clamp and return the recorded trace
+pub fn current_clamp(
+    neuron: &mut MultiCompartmentalNeuron,
+    clamp: &CurrentClamp,
+    t_stop: f64,
+    record_every: usize,
+) -> Result<Vec<VoltageSample>> {
+    let n = neuron.compartments.len();
+    if clamp.site >= n {
+        return Err(CliError::InvalidConfig(format!(
+            "clamp site {} out of range (cell has {} compartments)",
+            clamp.site, n
+        )));
+    }
+    if record_every == 0 {
+        return Err(CliError::InvalidConfig("record_every must be at least 1".to_string()));
+    }
+
+    let dt = neuron.dt;
+    let steps = (t_stop / dt).round() as usize;
+    let mut channel_states = vec![ChannelStates::default(); n];
+    let mut trace = Vec::with_capacity(steps / record_every + 1);
+    let mut spiked = false;
+
+    for step in 0..steps {
+        let t = step as f64 * dt;
+        let injected = clamp.current_at(t);
+        neuron.inject_current(clamp.site, injected);
+        neuron.step(&mut channel_states);
+        spiked |= neuron.is_spiking;
+
+        if (step + 1) % record_every == 0 {
+            trace.push(VoltageSample {
+                time: t + dt,
+                injected_pa: injected,
+                soma_mv: neuron.get_soma_voltage(),
+                site_mv: neuron.compartments[clamp.site].voltage,
+                spiking: std::mem::take(&mut spiked),
+            });
+        }
+    }
+
+    Ok(trace)
+}
+
+pub fn run(args: &NeuronArgs) -> Result<()> {
+    let mut neuron = match &args.swc {
+        Some(path) => {
+            let morphology = SWCMorphology::from_file(path)
+                .map_err(|e| CliError::InvalidConfig(format!("{}: {}", path.display(), e)))?;
+            let biophysics = match &args.biophysics {
+                Some(spec) => load_biophysics(spec)?,
+                None => Biophysics::default(),
+            };
+            let discretization = Discretization {
+                d_lambda: args.d_lambda,
+                ais_length: args.ais_length,
+                ..Discretization::default()
+            };
+            MultiCompartmentalNeuron::from_swc(0, &morphology, args.dt, &discretization, &biophysics)?
+        }
+        None => match args.model {
+            CellModel::Pyramidal => MultiCompartmentalNeuron::new_pyramidal(0, args.dt),
+            CellModel::BallAndStick => MultiCompartmentalNeuron::new(0, args.compartments.max(1), args.dt),
+        },
+    };
+
+    neuron.integrator = args.solver.into();
+
+    let trace = current_clamp(&mut neuron, &args.clamp, args.t_stop, args.record_every)?;
+    args.output.write(&trace)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use neurons::CompartmentType;
+
+    fn tempfile_path(name: &str) -> PathBuf {
+        std::env::temp_dir().join(format!("humanbrain-cli-{}-{}", std::process::id(), name))
+    }
+
+    fn clamp(amplitude: f64) -> CurrentClamp {
+        CurrentClamp { amplitude, delay: 1.0, width: 5.0, site: 0 }
+    }
+
+    #[test]
+    fn test_depolarizing_pulse_raises_soma_voltage() {
+        let mut neuron = MultiCompartmentalNeuron::new(0, 5, 0.025);
+        let trace = current_clamp(&mut neuron, &clamp(5.0), 10.0, 4).unwrap();
+
+        assert_eq!(trace.len(), 100);
+        let rest = trace[5].soma_mv;
+        let peak = trace.iter().map(|s| s.soma_mv).fold(f64::MIN, f64::max);
+        assert!(peak > rest, "pulse should depolarize the soma");
+        assert_eq!(trace.last().unwrap().injected_pa, 0.0);
+    }
+
+    #[test]
+    fn test_clamp_site_is_checked() {
+        let mut neuron = MultiCompartmentalNeuron::new(0, 3, 0.025);
+        let site = CurrentClamp { site: 3, ..clamp(1.0) };
+        assert!(current_clamp(&mut neuron, &site, 1.0, 1).is_err());
+    }
+
+    #[test]
+    fn test_swc_cell_with_biophysics() {
+        let data = Path::new(env!("CARGO_MANIFEST_DIR")).join("../neurons/test_data");
+        let morphology = SWCMorphology::from_file(&data.join("layer5_pyramidal.swc")).unwrap();
+
+        let biophysics = load_biophysics(&data.join("layer5_biophysics.json")).unwrap();
+
+        let mut neuron =
+            MultiCompartmentalNeuron::from_swc(0, &morphology, 0.025, &Discretization::default(), &biophysics).unwrap();
+        assert_eq!(neuron.compartments[0].compartment_type, CompartmentType::Soma);
+        assert_eq!(neuron.compartments[0].mechanisms.len(), 3);
+        assert!(neuron.compartments.iter().skip(1).all(|c| c.parent_idx.is_some()));
+
+        neuron.integrator = Integrator::BackwardEuler;
+        let trace = current_clamp(&mut neuron, &clamp(50.0), 10.0, 4).unwrap();
+        assert!(trace.iter().any(|s| s.spiking));
+    }
+
+    #[test]
+    fn test_unknown_channel_is_rejected() {
+        let spec = tempfile_path("typo.json");
+        std::fs::write(&spec, r#"{ "regions": [{ "region": "soma", "mechanisms": [{ "channel": "Nav9", "g_max": 1.0 }] }] }"#).unwrap();
+        let biophysics = load_biophysics(&spec).unwrap();
+        std::fs::remove_file(&spec).unwrap();
+
+        let data = Path::new(env!("CARGO_MANIFEST_DIR")).join("../neurons/test_data");
+        let morphology = SWCMorphology::from_file(&data.join("layer23_pyramidal.swc")).unwrap();
+        let err = MultiCompartmentalNeuron::from_swc(0, &morphology, 0.025, &Discretization::default(), &biophysics);
+        assert!(matches!(err.map_err(CliError::from), Err(CliError::Neuron(_))));
+    }
+}
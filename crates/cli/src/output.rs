@@ -0,0 +1,127 @@
+//! CSV / JSON result writers shared by all subcommands.
+
+use clap::{Args, ValueEnum};
+use serde::Serialize;
+use std::fs::File;
+use std::io::{self, BufWriter, Write};
+use std::path::{Path, PathBuf};
+
+use crate::Result;
+
+/// Output encoding for result tables
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ValueEnum)]
+pub enum OutputFormat {
+    /// Comma-separated values with a header row
+    Csv,
+    /// Pretty-printed JSON array of records
+    Json,
+}
+
+/// Output options shared by every subcommand
+#[derive(Debug, Clone, Args)]
+pub struct OutputArgs {
+    /// Output format
+    #[arg(long, short, value_enum, default_value_t = OutputFormat::Csv)]
+    pub format: OutputFormat,
+
+    /// Output file (stdout if omitted)
+    #[arg(long, short)]
+    pub output: Option<PathBuf>,
+}
+
+impl OutputArgs {
+    /// Write records according to these options
+    pub fn write<R: Record>(&self, records: &[R]) -> Result<()> {
+        write_records(records, self.format, self.output.as_deref())
+    }
+}
+
+/// A flat result row that can be written as CSV or JSON
+pub trait Record: Serialize {
+    /// Column names, in the order produced by `fields`
+    fn header() -> Vec<&'static str>;
+
+    /// Field values rendered as text
+    fn fields(&self) -> Vec<String>;
+}
+
+/// Quote a CSV field if it contains a delimiter, quote or line break
+fn escape_csv(field: &str) -> String {
+    if field.contains([',', '"', '\n', '\r']) {
+        format!("\"{}\"", field.replace('"', "\"\""))
+    } else {
+        field.to_string()
+    }
+}
+
+/// Write records to any writer in the requested format
+pub fn write_records_to<R: Record, W: Write>(records: &[R], format: OutputFormat, mut out: W) -> Result<()> {
+    match format {
+        OutputFormat::Csv => {
+            writeln!(out, "{}", R::header().join(","))?;
+            for record in records {
+                let row: Vec<String> = record.fields().iter().map(|f| escape_csv(f)).collect();
+                writeln!(out, "{}", row.join(","))?;
+            }
+        }
+        OutputFormat::Json => {
+            serde_json::to_writer_pretty(&mut out, records)?;
+            writeln!(out)?;
+        }
+    }
+    out.flush()?;
+    Ok(())
+}
+
+/// Write records to `path`, or to stdout when no path is given
+pub fn write_records<R: Record>(records: &[R], format: OutputFormat, path: Option<&Path>) -> Result<()> {
+    match path {
+        Some(path) => write_records_to(records, format, BufWriter::new(File::create(path)?)),
+        None => write_records_to(records, format, io::stdout().lock()),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[derive(Serialize)]
+    struct Row {
+        name: String,
+        value: f64,
+    }
+
+    impl Record for Row {
+        fn header() -> Vec<&'static str> {
+            vec!["name", "value"]
+        }
+
+        fn fields(&self) -> Vec<String> {
+            vec![self.name.clone(), self.value.to_string()]
+        }
+    }
+
+    #[test]
+    fn test_csv_quotes_special_fields() {
+        let rows = vec![
+            Row { name: "plain".to_string(), value: 1.5 },
+            Row { name: "a, \"b\"".to_string(), value: -2.0 },
+        ];
+        let mut buf = Vec::new();
+        write_records_to(&rows, OutputFormat::Csv, &mut buf).unwrap();
+
+        let text = String::from_utf8(buf).unwrap();
+        assert_eq!(text, "name,value\nplain,1.5\n\"a, \"\"b\"\"\",-2\n");
+    }
+
+    #[test]
+    fn test_json_is_an_array_of_records() {
+        let rows = vec![Row { name: "x".to_string(), value: 0.25 }];
+        let mut buf = Vec::new();
+        write_records_to(&rows, OutputFormat::Json, &mut buf).unwrap();
+
+        let parsed: serde_json::Value = serde_json::from_slice(&buf).unwrap();
+        assert_eq!(parsed[0]["name"], "x");
+        assert_eq!(parsed[0]["value"], 0.25);
+    }
+}
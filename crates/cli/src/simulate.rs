@@ -0,0 +1,259 @@
+//! `simulate`: run a `WholeBrain` experiment described by a `BrainConfig`.
+
+use clap::Args;
+use serde::Serialize;
+use std::path::PathBuf;
+use whole_brain::{BrainConfig, BrainState, RecordingTarget, WholeBrain};
+
+use crate::output::{OutputArgs, Record};
+use crate::{CliError, Result};
+
+#[derive(Debug, Args)]
+pub struct SimulateArgs {
+    /// Experiment config file (.toml or .json)
+    pub config: PathBuf,
+
+    /// Override the configured duration (ms)
+    #[arg(long)]
+    pub duration: Option<f64>,
+
+    /// Override the configured master seed
+    #[arg(long)]
+    pub seed: Option<u64>,
+
+    /// Write a checkpoint to this file while running
+    #[arg(long, requires = "checkpoint_every")]
+    pub checkpoint: Option<PathBuf>,
+
+    /// Simulated time between checkpoints (ms)
+    #[arg(long, requires = "checkpoint")]
+    pub checkpoint_every: Option<f64>,
+
+    /// Resume from a checkpoint instead of building a fresh brain; only the
+    /// remaining samples are written
+    #[arg(long)]
+    pub resume: Option<PathBuf>,
+
+    #[command(flatten)]
+    pub output: OutputArgs,
+}
+
+/// Population summary of one recorded `BrainState`
+///
+/// Groups that were not among the recording targets are left empty.
+#[derive(Debug, Clone, Serialize)]
+pub struct BrainSample {
+    pub time: f64,
+    pub layer1_mv: Option<f64>,
+    pub layer2_3_mv: Option<f64>,
+    pub layer4_mv: Option<f64>,
+    pub layer5_mv: Option<f64>,
+    pub layer6_mv: Option<f64>,
+    pub dg_active: Option<f64>,
+    pub ca3_active: Option<f64>,
+    pub ca1_active: Option<f64>,
+    pub vpl_active: Option<f64>,
+    pub gpi_mean: Option<f64>,
+    pub dopamine: Option<f64>,
+    pub fear: Option<f64>,
+    pub purkinje_active: Option<f64>,
+    pub cortisol: Option<f64>,
+    pub norepinephrine: Option<f64>,
+}
+
+fn mean(values: &[f64]) -> Option<f64> {
+    if values.is_empty() {
+        None
+    } else {
+        Some(values.iter().sum::<f64>() / values.len() as f64)
+    }
+}
+
+fn active_fraction(flags: &[bool]) -> Option<f64> {
+    if flags.is_empty() {
+        None
+    } else {
+        Some(flags.iter().filter(|&&f| f).count() as f64 / flags.len() as f64)
+    }
+}
+
+impl BrainSample {
+    fn from_state(state: &BrainState, config: &BrainConfig) -> Self {
+        let layers = &state.cortical_layers;
+        Self {
+            time: state.time,
+            layer1_mv: mean(&layers.layer1),
+            layer2_3_mv: mean(&layers.layer2_3),
+            layer4_mv: mean(&layers.layer4),
+            layer5_mv: mean(&layers.layer5),
+            layer6_mv: mean(&layers.layer6),
+            dg_active: active_fraction(&state.dg_activity),
+            ca3_active: active_fraction(&state.ca3_activity),
+            ca1_active: active_fraction(&state.ca1_activity),
+            vpl_active: active_fraction(&state.vpl_activity),
+            gpi_mean: mean(&state.gpi_activity),
+            dopamine: config.recording.records(RecordingTarget::Dopamine).then_some(state.snc_dopamine),
+            fear: config.recording.records(RecordingTarget::Amygdala).then_some(state.fear_response),
+            purkinje_active: active_fraction(&state.purkinje_spikes),
+            cortisol: config.recording.records(RecordingTarget::Hypothalamus).then_some(state.cortisol),
+            norepinephrine: config.recording.records(RecordingTarget::Brainstem).then_some(state.norepinephrine),
+        }
+    }
+}
+
+impl Record for BrainSample {
+    fn header() -> Vec<&'static str> {
+        vec![
+            "time", "layer1_mv", "layer2_3_mv", "layer4_mv", "layer5_mv", "layer6_mv",
+            "dg_active", "ca3_active", "ca1_active", "vpl_active", "gpi_mean", "dopamine",
+            "fear", "purkinje_active", "cortisol", "norepinephrine",
+        ]
+    }
+
+    fn fields(&self) -> Vec<String> {
+        let optional = [
+            self.layer1_mv, self.layer2_3_mv, self.layer4_mv, self.layer5_mv, self.layer6_mv,
+            self.dg_active, self.ca3_active, self.ca1_active, self.vpl_active, self.gpi_mean, self.dopamine,
+            self.fear, self.purkinje_active, self.cortisol, self.norepinephrine,
+        ];
+        std::iter::once(self.time.to_string())
+            .chain(optional.iter().map(|v| v.map(|x| x.to_string()).unwrap_or_default()))
+            .collect()
+    }
+}
+
+fn simulation_err(e: impl std::fmt::Display) -> CliError {
+    CliError::Simulation(e.to_string())
+}
+
+/// Periodic snapshotting of a running brain
+#[derive(Debug, Clone)]
+pub struct CheckpointPlan {
+    pub path: PathBuf,
+    /// Simulated time between snapshots (ms)
+    pub every_ms: f64,
+}
+
+/// Run the configured experiment and summarize the recorded states
+pub fn simulate(config: &BrainConfig) -> Result<Vec<BrainSample>> {
+    let mut brain = WholeBrain::from_config(config).map_err(simulation_err)?;
+    simulate_from(&mut brain, config, None)
+}
+
+/// Continue `brain` until the end of the protocol, snapshotting it according
+/// to `plan` (the final state is always written when a plan is given)
+pub fn simulate_from(
+    brain: &mut WholeBrain,
+    config: &BrainConfig,
+    plan: Option<&CheckpointPlan>,
+) -> Result<Vec<BrainSample>> {
+    let states = match plan {
+        None => brain.run(config).map_err(simulation_err)?,
+        Some(plan) => {
+            if !plan.every_ms.is_finite() || plan.every_ms < config.dt {
+                return Err(CliError::InvalidConfig(format!(
+                    "checkpoint interval must be at least dt ({} ms)",
+                    config.dt
+                )));
+            }
+
+            let end_step = config.num_steps();
+            let mut states = Vec::new();
+            loop {
+                let until = (brain.time + plan.every_ms).min(config.duration_ms);
+                states.extend(brain.run_until(config, until).map_err(simulation_err)?);
+                brain.save_checkpoint_file(&plan.path)?;
+
+                if (brain.time / config.dt).round() as usize >= end_step {
+                    break states;
+                }
+            }
+        }
+    };
+
+    Ok(states.iter().map(|s| BrainSample::from_state(s, config)).collect())
+}
+
+pub fn run(args: &SimulateArgs) -> Result<()> {
+    let mut config = BrainConfig::from_file(&args.config)?;
+    if let Some(duration) = args.duration {
+        config.duration_ms = duration;
+    }
+    if args.seed.is_some() {
+        config.seed = args.seed;
+    }
+
+    let mut brain = match &args.resume {
+        Some(path) => WholeBrain::load_checkpoint_file(path)?,
+        None => WholeBrain::from_config(&config).map_err(simulation_err)?,
+    };
+
+    let plan = args.checkpoint.as_ref().zip(args.checkpoint_every).map(|(path, every_ms)| CheckpointPlan {
+        path: path.clone(),
+        every_ms,
+    });
+
+    let samples = simulate_from(&mut brain, &config, plan.as_ref())?;
+    args.output.write(&samples)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn config() -> BrainConfig {
+        BrainConfig::from_json_str(
+            r#"{
+                "duration_ms": 1.0,
+                "seed": 7,
+                "regions": { "cortical_columns": 2, "thalamic_neurons": 4, "striatal_neurons": 8 },
+                "stimuli": [{ "target": "sensory", "protocol": { "type": "constant", "amplitude": 10.0 } }],
+                "recording": { "targets": ["cortical_layers", "dopamine"], "every": 2 }
+            }"#,
+        )
+        .unwrap()
+    }
+
+    #[test]
+    fn test_seeded_simulation_records_every_nth_step() {
+        let samples = simulate(&config()).unwrap();
+        assert_eq!(samples.len(), 5);
+        assert!((samples[0].time - 0.2).abs() < 1e-9);
+
+        let again = simulate(&config()).unwrap();
+        assert_eq!(samples.last().unwrap().layer5_mv, again.last().unwrap().layer5_mv);
+    }
+
+    #[test]
+    fn test_unrecorded_groups_are_blank() {
+        let samples = simulate(&config()).unwrap();
+        assert!(samples[0].layer4_mv.is_some());
+        assert!(samples[0].dopamine.is_some());
+        assert!(samples[0].vpl_active.is_none());
+
+        let fields = samples[0].fields();
+        assert_eq!(fields.len(), BrainSample::header().len());
+        assert_eq!(fields[9], "");
+    }
+
+    #[test]
+    fn test_checkpointed_run_resumes_seamlessly() {
+        let config = config();
+        let path = std::env::temp_dir().join(format!("humanbrain-cli-{}.ckpt", std::process::id()));
+        let plan = CheckpointPlan { path: path.clone(), every_ms: 0.4 };
+
+        // Stop after the first snapshot, as a preempted job would
+        let mut brain = WholeBrain::from_config(&config).unwrap();
+        let first = brain.run_until(&config, 0.4).unwrap();
+        brain.save_checkpoint_file(&path).unwrap();
+
+        let mut resumed = WholeBrain::load_checkpoint_file(&path).unwrap();
+        let rest = simulate_from(&mut resumed, &config, Some(&plan)).unwrap();
+        std::fs::remove_file(&path).unwrap();
+
+        let full = simulate(&config).unwrap();
+        assert_eq!(first.len() + rest.len(), full.len());
+        assert_eq!(rest.last().unwrap().layer5_mv, full.last().unwrap().layer5_mv);
+        assert_eq!(rest[0].time, full[first.len()].time);
+    }
+}
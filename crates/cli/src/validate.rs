@@ -0,0 +1,75 @@
+//! `validate`: model predictions against PET occupancy and plasma PK literature.
+
+use clap::{Args, ValueEnum};
+use pharmacology::clinical_literature::{validate_pet_occupancy, validate_pk_literature, ValidationResult};
+
+use crate::output::{OutputArgs, Record};
+use crate::Result;
+
+/// Literature validation suites
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ValueEnum)]
+pub enum Suite {
+    /// Receptor occupancy vs PET imaging studies
+    Pet,
+    /// Single-dose plasma Cmax / Tmax vs clinical references
+    Pk,
+    /// Both suites
+    All,
+}
+
+#[derive(Debug, Args)]
+pub struct ValidateArgs {
+    /// Which suite to run
+    #[arg(long, value_enum, default_value_t = Suite::All)]
+    pub suite: Suite,
+
+    /// Relative error tolerance (0.05 = 5%)
+    #[arg(long, default_value_t = 0.05)]
+    pub tolerance: f64,
+
+    #[command(flatten)]
+    pub output: OutputArgs,
+}
+
+impl Record for ValidationResult {
+    fn header() -> Vec<&'static str> {
+        vec!["drug", "metric", "model_value", "literature_value", "percent_error", "within_tolerance"]
+    }
+
+    fn fields(&self) -> Vec<String> {
+        vec![
+            self.drug.clone(),
+            self.metric.clone(),
+            self.model_value.to_string(),
+            self.literature_value.to_string(),
+            self.percent_error.to_string(),
+            self.within_tolerance.to_string(),
+        ]
+    }
+}
+
+/// Run the selected validation suites
+pub fn validate(suite: Suite, tolerance: f64) -> Vec<ValidationResult> {
+    let mut results = Vec::new();
+    if matches!(suite, Suite::Pet | Suite::All) {
+        results.extend(validate_pet_occupancy(tolerance));
+    }
+    if matches!(suite, Suite::Pk | Suite::All) {
+        results.extend(validate_pk_literature(tolerance));
+    }
+    results
+}
+
+pub fn run(args: &ValidateArgs) -> Result<()> {
+    let results = validate(args.suite, args.tolerance);
+
+    let passed = results.iter().filter(|r| r.within_tolerance).count();
+    eprintln!(
+        "{} / {} validations within {:.1}% tolerance",
+        passed,
+        results.len(),
+        args.tolerance * 100.0
+    );
+
+    args.output.write(&results)
+}
@@ -62,7 +62,7 @@ impl CircadianClock {
     }
 }
 
-#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
 pub enum SleepStage { Awake, N1, N2, N3, REM }
 
 pub struct SleepStageController {
@@ -19,6 +19,7 @@ pub enum CorticalLayer {
 }
 
 /// Connection probability based on distance and layers
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct AnatomicalConnectivity {
     /// Layer-to-layer connection matrix (probabilities)
     pub layer_matrix: Array2<f64>,
@@ -111,6 +112,7 @@ impl AnatomicalConnectivity {
 /// Found primarily between:
 /// - Parvalbumin+ interneurons (10% within 100µm)
 /// - Layer 6 pyramidal cells (5% within 50µm)
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct GapJunctionConnectivity {
     /// Connection probability at zero distance
     pub p_zero: f64,
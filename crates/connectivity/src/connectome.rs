@@ -0,0 +1,463 @@
+//! Connectome construction and exchange.
+//!
+//! [`ConnectomeBuilder`] wires a population of neurons with known layers and
+//! 3D soma positions: every ordered pair is connected with the layer- and
+//! distance-dependent probability of [`AnatomicalConnectivity`], with an
+//! axonal delay proportional to the soma distance, and PV interneuron pairs
+//! are additionally coupled by gap junctions following
+//! [`GapJunctionConnectivity`]. The resulting [`Connectome`] instantiates a
+//! [`SynapticNetwork`] and can be written to / read from a plain-text edge
+//! list so the same wiring can be reused across runs.
+//!
+//! ## Edge-list format
+//! ```text
+//! # comment
+//! neurons 4
+//! syn 0 1 AMPA 0.85 0.12      # pre post type weight delay_ms
+//! gap 2 3 0.5                 # neuron neuron conductance_nS
+//! ```
+
+use crate::anatomical::{AnatomicalConnectivity, CorticalLayer, GapJunctionConnectivity};
+use crate::{ConnectivityError, Result};
+use rand::{Rng, SeedableRng};
+use rand_chacha::ChaCha8Rng;
+use serde::{Deserialize, Serialize};
+use std::fs::File;
+use std::io::{BufRead, BufReader, BufWriter, Write};
+use std::path::Path;
+use synapses::{GapJunction, Synapse, SynapseType, SynapticNetwork};
+
+/// What the wiring rules need to know about a neuron
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct NeuronSite {
+    pub layer: CorticalLayer,
+
+    /// Soma position (µm); z is depth below the pia
+    pub position: [f64; 3],
+
+    /// Glutamatergic (true) or GABAergic (false)
+    pub excitatory: bool,
+
+    /// Parvalbumin-positive interneuron, eligible for gap junctions
+    pub parvalbumin: bool,
+}
+
+impl NeuronSite {
+    /// Horizontal and vertical soma separation (µm)
+    fn separation(&self, other: &NeuronSite) -> (f64, f64) {
+        let [dx, dy, dz] = [0, 1, 2].map(|i| self.position[i] - other.position[i]);
+        ((dx * dx + dy * dy).sqrt(), dz.abs())
+    }
+
+    /// Euclidean soma distance (µm)
+    pub fn distance(&self, other: &NeuronSite) -> f64 {
+        let (horizontal, vertical) = self.separation(other);
+        horizontal.hypot(vertical)
+    }
+}
+
+/// A chemical synapse
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct SynapticEdge {
+    pub pre: usize,
+    pub post: usize,
+    pub synapse_type: SynapseType,
+    pub weight: f64,
+    /// Axonal conduction delay (ms)
+    pub delay: f64,
+}
+
+/// An electrical synapse between two somata
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct GapJunctionEdge {
+    pub a: usize,
+    pub b: usize,
+    /// Junctional conductance (nS)
+    pub conductance: f64,
+}
+
+/// Complete wiring of a population
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct Connectome {
+    pub num_neurons: usize,
+    pub synapses: Vec<SynapticEdge>,
+    pub gap_junctions: Vec<GapJunctionEdge>,
+}
+
+impl Connectome {
+    /// Create an empty connectome
+    pub fn new(num_neurons: usize) -> Self {
+        Self { num_neurons, ..Self::default() }
+    }
+
+    /// Instantiate the chemical synapses and somatic gap junctions, with a
+    /// release stream seeded by `seed`
+    pub fn to_network(&self, seed: u64) -> SynapticNetwork {
+        let mut network = SynapticNetwork::with_seed(self.num_neurons, seed);
+        for (id, edge) in self.synapses.iter().enumerate() {
+            let synapse = Synapse::new(id, edge.pre, edge.post, edge.synapse_type, edge.weight)
+                .with_delay(edge.delay);
+            network.add_synapse(synapse);
+        }
+        for edge in &self.gap_junctions {
+            network.add_gap_junction(GapJunction::new(edge.a, edge.b, edge.conductance));
+        }
+        network
+    }
+
+    /// Write the connectome as a text edge list
+    pub fn write_edge_list<W: Write>(&self, mut writer: W) -> Result<()> {
+        writeln!(writer, "# syn pre post type weight delay_ms / gap a b conductance_nS")?;
+        writeln!(writer, "neurons {}", self.num_neurons)?;
+        for e in &self.synapses {
+            writeln!(writer, "syn {} {} {:?} {} {}", e.pre, e.post, e.synapse_type, e.weight, e.delay)?;
+        }
+        for g in &self.gap_junctions {
+            writeln!(writer, "gap {} {} {}", g.a, g.b, g.conductance)?;
+        }
+        writer.flush()?;
+        Ok(())
+    }
+
+    /// Read an edge list written by [`Connectome::write_edge_list`]
+    pub fn read_edge_list<R: BufRead>(reader: R) -> Result<Self> {
+        let mut connectome: Option<Connectome> = None;
+
+        for (index, line) in reader.lines().enumerate() {
+            let line = line?;
+            let line_no = index + 1;
+            let err = |message: String| ConnectivityError::Parse { line: line_no, message };
+
+            let content = line.split('#').next().unwrap_or("").trim();
+            let fields: Vec<&str> = content.split_whitespace().collect();
+            let Some((&kind, args)) = fields.split_first() else {
+                continue;
+            };
+
+            if kind == "neurons" {
+                let [n] = args else {
+                    return Err(err("expected `neurons <count>`".into()));
+                };
+                if connectome.is_some() {
+                    return Err(err("duplicate `neurons` header".into()));
+                }
+                connectome = Some(Connectome::new(parse(n, "neuron count").map_err(err)?));
+                continue;
+            }
+
+            let connectome = connectome
+                .as_mut()
+                .ok_or_else(|| err("edge before the `neurons` header".into()))?;
+            let neuron = |field: &str| -> std::result::Result<usize, String> {
+                let id: usize = parse(field, "neuron id")?;
+                if id < connectome.num_neurons {
+                    Ok(id)
+                } else {
+                    Err(format!("neuron {id} out of range (0..{})", connectome.num_neurons))
+                }
+            };
+
+            match (kind, args) {
+                ("syn", [pre, post, synapse_type, weight, delay]) => {
+                    let edge = SynapticEdge {
+                        pre: neuron(pre).map_err(err)?,
+                        post: neuron(post).map_err(err)?,
+                        synapse_type: parse_synapse_type(synapse_type).map_err(err)?,
+                        weight: parse_finite(weight, "weight").map_err(err)?,
+                        delay: parse_delay(delay).map_err(err)?,
+                    };
+                    connectome.synapses.push(edge);
+                }
+                ("gap", [a, b, conductance]) => {
+                    let edge = GapJunctionEdge {
+                        a: neuron(a).map_err(err)?,
+                        b: neuron(b).map_err(err)?,
+                        conductance: parse_finite(conductance, "conductance").map_err(err)?,
+                    };
+                    if edge.a == edge.b {
+                        return Err(err(format!("gap junction from neuron {} to itself", edge.a)));
+                    }
+                    if edge.conductance < 0.0 {
+                        return Err(err(format!("negative conductance `{conductance}`")));
+                    }
+                    connectome.gap_junctions.push(edge);
+                }
+                _ => return Err(err(format!("malformed `{kind}` record"))),
+            }
+        }
+
+        connectome.ok_or(ConnectivityError::Parse { line: 0, message: "missing `neurons` header".into() })
+    }
+
+    /// Save to an edge-list file
+    pub fn save(&self, path: impl AsRef<Path>) -> Result<()> {
+        self.write_edge_list(BufWriter::new(File::create(path)?))
+    }
+
+    /// Load from an edge-list file
+    pub fn load(path: impl AsRef<Path>) -> Result<Self> {
+        Self::read_edge_list(BufReader::new(File::open(path)?))
+    }
+}
+
+fn parse<T: std::str::FromStr>(field: &str, what: &str) -> std::result::Result<T, String> {
+    field.parse().map_err(|_| format!("invalid {what} `{field}`"))
+}
+
+fn parse_finite(field: &str, what: &str) -> std::result::Result<f64, String> {
+    let value: f64 = parse(field, what)?;
+    if value.is_finite() {
+        Ok(value)
+    } else {
+        Err(format!("non-finite {what} `{field}`"))
+    }
+}
+
+fn parse_delay(field: &str) -> std::result::Result<f64, String> {
+    let delay = parse_finite(field, "delay")?;
+    if delay >= 0.0 {
+        Ok(delay)
+    } else {
+        Err(format!("negative delay `{field}`"))
+    }
+}
+
+fn parse_synapse_type(field: &str) -> std::result::Result<SynapseType, String> {
+    Ok(match field {
+        "AMPA" => SynapseType::AMPA,
+        "NMDA" => SynapseType::NMDA,
+        "GABAA" => SynapseType::GABAA,
+        "GABAB" => SynapseType::GABAB,
+        "Glycine" => SynapseType::Glycine,
+        "Dopamine" => SynapseType::Dopamine,
+        "Serotonin" => SynapseType::Serotonin,
+        other => return Err(format!("unknown synapse type `{other}`")),
+    })
+}
+
+/// Samples a [`Connectome`] from anatomical wiring rules
+#[derive(Debug, Clone)]
+pub struct ConnectomeBuilder {
+    pub anatomy: AnatomicalConnectivity,
+
+    /// Gap junctions between PV interneurons (None = chemical synapses only)
+    pub gap_junctions: Option<GapJunctionConnectivity>,
+
+    /// Fraction of excitatory synapses that are NMDA rather than AMPA
+    pub nmda_fraction: f64,
+
+    /// Initial weights are drawn uniformly from this range
+    pub weight_range: (f64, f64),
+
+    rng: ChaCha8Rng,
+}
+
+impl ConnectomeBuilder {
+    /// Builder with an entropy-seeded sampler
+    pub fn new(anatomy: AnatomicalConnectivity) -> Self {
+        Self::with_seed(anatomy, rand::random())
+    }
+
+    /// Builder whose sampled wiring is fully determined by `seed`
+    pub fn with_seed(anatomy: AnatomicalConnectivity, seed: u64) -> Self {
+        Self {
+            anatomy,
+            gap_junctions: None,
+            nmda_fraction: 0.2,
+            weight_range: (0.5, 1.5),
+            rng: ChaCha8Rng::seed_from_u64(seed),
+        }
+    }
+
+    /// Builder: couple PV interneurons electrically
+    pub fn with_gap_junctions(mut self, gap_junctions: GapJunctionConnectivity) -> Self {
+        self.gap_junctions = Some(gap_junctions);
+        self
+    }
+
+    /// Builder: range of initial synaptic weights (`min == max` for a fixed weight)
+    pub fn with_weight_range(mut self, min: f64, max: f64) -> Result<Self> {
+        if !(min.is_finite() && max.is_finite() && min <= max) {
+            return Err(ConnectivityError::InvalidConfiguration(format!("invalid weight range {min}..{max}")));
+        }
+        self.weight_range = (min, max);
+        Ok(self)
+    }
+
+    /// Sample the wiring of `sites`
+    pub fn build(&mut self, sites: &[NeuronSite]) -> Connectome {
+        let mut connectome = Connectome::new(sites.len());
+
+        for (pre, source) in sites.iter().enumerate() {
+            for (post, target) in sites.iter().enumerate() {
+                if pre == post {
+                    continue;
+                }
+
+                let (horizontal, vertical) = source.separation(target);
+                let p = self.anatomy.connection_probability(source.layer, target.layer, horizontal, vertical);
+                if self.rng.gen::<f64>() >= p {
+                    continue;
+                }
+
+                let synapse_type = if !source.excitatory {
+                    SynapseType::GABAA
+                } else if self.rng.gen::<f64>() < self.nmda_fraction {
+                    SynapseType::NMDA
+                } else {
+                    SynapseType::AMPA
+                };
+                let weight = match self.weight_range {
+                    (w_min, w_max) if w_min < w_max => self.rng.gen_range(w_min..w_max),
+                    (w_min, _) => w_min,
+                };
+                connectome.synapses.push(SynapticEdge {
+                    pre,
+                    post,
+                    synapse_type,
+                    weight,
+                    delay: self.anatomy.axonal_delay(source.distance(target)),
+                });
+            }
+        }
+
+        if let Some(gap) = &self.gap_junctions {
+            for (a, first) in sites.iter().enumerate().filter(|(_, s)| s.parvalbumin) {
+                for (b, second) in sites.iter().enumerate().skip(a + 1).filter(|(_, s)| s.parvalbumin) {
+                    if self.rng.gen::<f64>() < gap.probability(first.distance(second)) {
+                        connectome.gap_junctions.push(GapJunctionEdge { a, b, conductance: gap.conductance() });
+                    }
+                }
+            }
+        }
+
+        connectome
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn site(layer: CorticalLayer, position: [f64; 3], parvalbumin: bool) -> NeuronSite {
+        NeuronSite { layer, position, excitatory: !parvalbumin, parvalbumin }
+    }
+
+    /// A sheet of neurons in one layer, `spacing` µm apart
+    fn sheet(n: usize, spacing: f64, parvalbumin: bool) -> Vec<NeuronSite> {
+        (0..n)
+            .map(|i| site(CorticalLayer::Layer23, [i as f64 * spacing, 0.0, 300.0], parvalbumin))
+            .collect()
+    }
+
+    #[test]
+    fn test_connection_probability_falls_with_distance() {
+        let anatomy = AnatomicalConnectivity::new_cortical();
+        let mut builder = ConnectomeBuilder::with_seed(anatomy.clone(), 1);
+        let sites = sheet(200, 5.0, false);
+        let connectome = builder.build(&sites);
+
+        let (near, far): (Vec<_>, Vec<_>) = connectome
+            .synapses
+            .iter()
+            .map(|e| sites[e.pre].distance(&sites[e.post]))
+            .partition(|&d| d < 100.0);
+        // ~20 neighbours within 100 µm vs ~180 beyond, yet most synapses are local
+        assert!(near.len() > far.len());
+
+        for edge in &connectome.synapses {
+            let distance = sites[edge.pre].distance(&sites[edge.post]);
+            assert_eq!(edge.delay, anatomy.axonal_delay(distance));
+            assert_ne!(edge.synapse_type, SynapseType::GABAA);
+        }
+        assert!(connectome.gap_junctions.is_empty());
+    }
+
+    #[test]
+    fn test_gap_junctions_couple_only_pv_cells() {
+        let mut sites = sheet(40, 10.0, true);
+        sites.extend(sheet(40, 10.0, false));
+        let connectome = ConnectomeBuilder::with_seed(AnatomicalConnectivity::new_cortical(), 2)
+            .with_gap_junctions(GapJunctionConnectivity::parvalbumin_interneurons())
+            .build(&sites);
+
+        assert!(!connectome.gap_junctions.is_empty());
+        for gap in &connectome.gap_junctions {
+            assert!(gap.a < gap.b && sites[gap.a].parvalbumin && sites[gap.b].parvalbumin);
+        }
+
+        // Gap junctions join the network alongside the chemical synapses
+        let network = connectome.to_network(0);
+        assert_eq!(network.synapses.len(), connectome.synapses.len());
+        assert_eq!(network.electrical.len(), connectome.gap_junctions.len());
+        assert!(connectome.synapses.iter().any(|e| e.synapse_type == SynapseType::GABAA));
+    }
+
+    #[test]
+    fn test_edge_list_round_trip() {
+        let mut sites = sheet(30, 20.0, true);
+        sites.extend(sheet(30, 20.0, false));
+        let connectome = ConnectomeBuilder::with_seed(AnatomicalConnectivity::new_cortical(), 3)
+            .with_gap_junctions(GapJunctionConnectivity::parvalbumin_interneurons())
+            .build(&sites);
+
+        let mut text = Vec::new();
+        connectome.write_edge_list(&mut text).unwrap();
+        let restored = Connectome::read_edge_list(text.as_slice()).unwrap();
+        assert_eq!(restored, connectome);
+
+        let path = std::env::temp_dir().join(format!("connectome-{}.txt", std::process::id()));
+        connectome.save(&path).unwrap();
+        assert_eq!(Connectome::load(&path).unwrap(), connectome);
+        std::fs::remove_file(path).ok();
+    }
+
+    #[test]
+    fn test_malformed_edge_lists_are_rejected() {
+        let read = |text: &str| Connectome::read_edge_list(text.as_bytes());
+        assert!(matches!(read("syn 0 1 AMPA 1 0"), Err(ConnectivityError::Parse { line: 1, .. })));
+        assert!(matches!(read("neurons 2\nsyn 0 2 AMPA 1 0"), Err(ConnectivityError::Parse { line: 2, .. })));
+        assert!(matches!(read("neurons 2\nsyn 0 1 Kainate 1 0"), Err(ConnectivityError::Parse { line: 2, .. })));
+        assert!(matches!(read("neurons 2\ngap 0 1"), Err(ConnectivityError::Parse { line: 2, .. })));
+        assert_eq!(read("# empty\nneurons 3\n").unwrap(), Connectome::new(3));
+    }
+
+    #[test]
+    fn test_invalid_edge_values_are_rejected() {
+        let read = |text: &str| Connectome::read_edge_list(text.as_bytes());
+        let rejected_at =
+            |text: &str, line: usize| matches!(read(text), Err(ConnectivityError::Parse { line: l, .. }) if l == line);
+        assert!(rejected_at("neurons 2\nsyn 0 1 AMPA NaN 0", 2));
+        assert!(rejected_at("neurons 2\nsyn 0 1 AMPA inf 0", 2));
+        assert!(rejected_at("neurons 2\nsyn 0 1 AMPA 1 -0.5", 2));
+        assert!(rejected_at("neurons 2\nsyn 0 1 AMPA 1 NaN", 2));
+        assert!(rejected_at("neurons 2\ngap 0 1 inf", 2));
+        assert!(rejected_at("neurons 2\ngap 0 1 -0.5", 2));
+        assert!(rejected_at("neurons 2\ngap 1 1 0.5", 2));
+
+        // A second header would silently drop the edges already read
+        assert!(rejected_at("neurons 2\nsyn 0 1 AMPA 1 0\nneurons 3", 3));
+        assert!(read("neurons 2\nsyn 0 1 AMPA -1 0").is_ok());
+    }
+
+    #[test]
+    fn test_fixed_weight() {
+        let connectome = ConnectomeBuilder::with_seed(AnatomicalConnectivity::new_cortical(), 4)
+            .with_weight_range(0.8, 0.8)
+            .unwrap()
+            .build(&sheet(50, 10.0, false));
+        assert!(!connectome.synapses.is_empty());
+        assert!(connectome.synapses.iter().all(|e| e.weight == 0.8));
+    }
+
+    #[test]
+    fn test_invalid_weight_ranges_are_rejected() {
+        let builder = || ConnectomeBuilder::with_seed(AnatomicalConnectivity::new_cortical(), 4);
+        for (min, max) in [(1.5, 0.5), (f64::NAN, 1.0), (0.5, f64::INFINITY)] {
+            assert!(matches!(
+                builder().with_weight_range(min, max),
+                Err(ConnectivityError::InvalidConfiguration(_))
+            ));
+        }
+    }
+}
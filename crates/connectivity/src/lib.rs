@@ -1,11 +1,31 @@
-//! connectivity module
+//! Anatomical wiring of neural populations.
+//!
+//! [`AnatomicalConnectivity`] and [`GapJunctionConnectivity`] hold the
+//! layer-, distance- and cell-type-dependent wiring statistics;
+//! [`ConnectomeBuilder`] samples them into a concrete [`Connectome`] that
+//! instantiates a `SynapticNetwork` and round-trips through edge-list files.
 
 pub mod anatomical;
+pub mod connectome;
+
 pub use anatomical::{
     AnatomicalConnectivity, GapJunctionConnectivity,
     CorticalLayer,
 };
+pub use connectome::{Connectome, ConnectomeBuilder, GapJunctionEdge, NeuronSite, SynapticEdge};
+
+use thiserror::Error;
+
+#[derive(Debug, Error)]
+pub enum ConnectivityError {
+    #[error("I/O error: {0}")]
+    Io(#[from] std::io::Error),
 
-pub fn placeholder() {
-    println!("connectivity module - to be implemented");
+    #[error("Edge list line {line}: {message}")]
+    Parse { line: usize, message: String },
+
+    #[error("Invalid connectome configuration: {0}")]
+    InvalidConfiguration(String),
 }
+
+pub type Result<T> = std::result::Result<T, ConnectivityError>;
@@ -0,0 +1,164 @@
+//! Global-workspace ignition detection.
+//!
+//! In the global neuronal workspace account, conscious access is marked by
+//! "ignition": a sudden, self-sustained activation recruiting a large share
+//! of the network at once, as opposed to local activity that decays along
+//! the processing hierarchy. An ignition event is a run of at least
+//! `min_duration` samples during which at least `min_fraction` of the
+//! channels exceed their pre-stimulus baseline by `threshold_sd` standard
+//! deviations.
+//!
+//! Reference: Dehaene & Changeux (2011), Neuron 70:200-227
+
+use crate::{ConsciousnessError, Result};
+use ndarray::{s, Array2};
+use serde::{Deserialize, Serialize};
+
+/// One ignition event, in samples after the end of the baseline
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct IgnitionEvent {
+    /// First sample of the run
+    pub onset: usize,
+
+    /// One past the last sample of the run
+    pub offset: usize,
+
+    /// Largest fraction of channels recruited during the run
+    pub peak_fraction: f64,
+}
+
+impl IgnitionEvent {
+    /// Length of the run (samples)
+    pub fn duration(&self) -> usize {
+        self.offset - self.onset
+    }
+}
+
+/// Detector of widespread, sustained activation
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct IgnitionDetector {
+    /// Channel threshold above the baseline mean (baseline SDs)
+    pub threshold_sd: f64,
+
+    /// Floor on the baseline SD, so silent channels need a real change
+    pub min_sd: f64,
+
+    /// Fraction of channels that must be active at once
+    pub min_fraction: f64,
+
+    /// Shortest sustained run counted as ignition (samples)
+    pub min_duration: usize,
+}
+
+impl Default for IgnitionDetector {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl IgnitionDetector {
+    /// Half the channels 3 SD above baseline for 50 samples
+    pub fn new() -> Self {
+        Self { threshold_sd: 3.0, min_sd: 1e-9, min_fraction: 0.5, min_duration: 50 }
+    }
+
+    /// Builder: set the channel threshold (baseline SDs) and SD floor
+    pub fn with_threshold(mut self, threshold_sd: f64, min_sd: f64) -> Self {
+        self.threshold_sd = threshold_sd;
+        self.min_sd = min_sd;
+        self
+    }
+
+    /// Builder: set the recruited fraction required for ignition
+    pub fn with_min_fraction(mut self, min_fraction: f64) -> Self {
+        self.min_fraction = min_fraction;
+        self
+    }
+
+    /// Builder: set the shortest sustained run (samples)
+    pub fn with_min_duration(mut self, min_duration: usize) -> Self {
+        self.min_duration = min_duration;
+        self
+    }
+
+    /// Fraction of channels above threshold at each sample after the
+    /// baseline, for a channel × time `activity` matrix whose first
+    /// `baseline` samples precede the stimulus
+    pub fn recruitment(&self, activity: &Array2<f64>, baseline: usize) -> Result<Vec<f64>> {
+        let (channels, samples) = activity.dim();
+        if baseline < 2 || baseline >= samples {
+            return Err(ConsciousnessError::InvalidProtocol(format!(
+                "baseline of {baseline} samples in a recording of {samples}"
+            )));
+        }
+
+        let thresholds: Vec<f64> = activity
+            .slice(s![.., ..baseline])
+            .rows()
+            .into_iter()
+            .map(|row| {
+                let mean = row.sum() / baseline as f64;
+                let var = row.iter().map(|x| (x - mean).powi(2)).sum::<f64>() / (baseline - 1) as f64;
+                mean + self.threshold_sd * var.sqrt().max(self.min_sd)
+            })
+            .collect();
+
+        Ok(activity
+            .slice(s![.., baseline..])
+            .columns()
+            .into_iter()
+            .map(|sample| {
+                let active = sample.iter().zip(&thresholds).filter(|(&x, &t)| x > t).count();
+                active as f64 / channels.max(1) as f64
+            })
+            .collect())
+    }
+
+    /// Ignition events in a channel × time `activity` matrix whose first
+    /// `baseline` samples precede the stimulus
+    pub fn detect(&self, activity: &Array2<f64>, baseline: usize) -> Result<Vec<IgnitionEvent>> {
+        let recruitment = self.recruitment(activity, baseline)?;
+
+        let mut events = Vec::new();
+        let mut run: Option<IgnitionEvent> = None;
+        for (t, &fraction) in recruitment.iter().chain(std::iter::once(&0.0)).enumerate() {
+            if fraction >= self.min_fraction {
+                let event = run.get_or_insert(IgnitionEvent { onset: t, offset: t, peak_fraction: 0.0 });
+                event.offset = t + 1;
+                event.peak_fraction = event.peak_fraction.max(fraction);
+            } else if let Some(event) = run.take() {
+                if event.duration() >= self.min_duration {
+                    events.push(event);
+                }
+            }
+        }
+        Ok(events)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_detects_sustained_widespread_activation_only() {
+        // 20 channels, 100 baseline samples with a small ripple, then:
+        // a brief global burst (t = 120..130), a sustained local response in
+        // 5 channels (150..300) and a sustained global one in 15 (400..500)
+        let activity = Array2::from_shape_fn((20, 600), |(c, t)| {
+            let ripple = 0.1 * ((c * 31 + t * 17) % 7) as f64;
+            let burst = (120..130).contains(&t);
+            let local = (150..300).contains(&t) && c < 5;
+            let global = (400..500).contains(&t) && c >= 5;
+            ripple + if burst || local || global { 5.0 } else { 0.0 }
+        });
+
+        let detector = IgnitionDetector::new().with_min_duration(20);
+        let events = detector.detect(&activity, 100).unwrap();
+        assert_eq!(events.len(), 1, "{events:?}");
+        assert_eq!((events[0].onset, events[0].offset), (300, 400));
+        assert!((events[0].peak_fraction - 0.75).abs() < 1e-12);
+
+        assert!(detector.detect(&activity, 600).is_err());
+    }
+}
@@ -0,0 +1,146 @@
+//! Lempel-Ziv complexity of binary sequences and channel × time matrices.
+//!
+//! The LZ76 phrase count c(n) is normalized by its asymptotic value for a
+//! random sequence of the same length and symbol entropy,
+//! n · H(p) / log2(n), so that ~1 means maximally diverse and ~0 means
+//! stereotyped or silent activity.
+//!
+//! References:
+//! - Lempel & Ziv (1976), IEEE Trans. Inf. Theory 22:75-81
+//! - Kaspar & Schuster (1987), Phys. Rev. A 36:842-848
+//! - Schartner et al. (2015), PLoS ONE 10:e0133532
+
+use ndarray::Array2;
+
+/// Number of distinct phrases in the LZ76 parsing of `sequence`
+/// (Kaspar-Schuster algorithm)
+pub fn lempel_ziv_complexity(sequence: &[bool]) -> usize {
+    let n = sequence.len();
+    if n < 2 {
+        return n;
+    }
+
+    let (mut complexity, mut prefix, mut i, mut k, mut k_max) = (1, 1, 0, 1, 1);
+    loop {
+        if sequence[i + k - 1] == sequence[prefix + k - 1] {
+            k += 1;
+            if prefix + k > n {
+                complexity += 1;
+                break;
+            }
+        } else {
+            k_max = k_max.max(k);
+            i += 1;
+            if i == prefix {
+                // No earlier copy: the component ends here
+                complexity += 1;
+                prefix += k_max;
+                if prefix + 1 > n {
+                    break;
+                }
+                i = 0;
+                k = 1;
+                k_max = 1;
+            } else {
+                k = 1;
+            }
+        }
+    }
+    complexity
+}
+
+/// Binary entropy (bits) of a sequence with a fraction `p` of ones
+pub fn binary_entropy(p: f64) -> f64 {
+    if p <= 0.0 || p >= 1.0 {
+        0.0
+    } else {
+        -p * p.log2() - (1.0 - p) * (1.0 - p).log2()
+    }
+}
+
+/// LZ76 complexity normalized by that of a random sequence with the same
+/// length and fraction of ones; 0 for constant sequences
+pub fn normalized_lempel_ziv(sequence: &[bool]) -> f64 {
+    let n = sequence.len();
+    let ones = sequence.iter().filter(|&&s| s).count();
+    let entropy = binary_entropy(ones as f64 / n.max(1) as f64);
+    if n < 2 || entropy == 0.0 {
+        return 0.0;
+    }
+    lempel_ziv_complexity(sequence) as f64 * (n as f64).log2() / (n as f64 * entropy)
+}
+
+/// Concatenate a channel × time matrix time sample by time sample, the
+/// order used for PCI and spontaneous LZc
+pub fn flatten_by_time(matrix: &Array2<bool>) -> Vec<bool> {
+    matrix.t().iter().copied().collect()
+}
+
+/// Normalized LZ complexity of a channel × time matrix
+pub fn matrix_lempel_ziv(matrix: &Array2<bool>) -> f64 {
+    normalized_lempel_ziv(&flatten_by_time(matrix))
+}
+
+/// Binarize each channel of a channel × time matrix at its own median
+pub fn binarize_at_median(traces: &Array2<f64>) -> Array2<bool> {
+    let mut binary = Array2::from_elem(traces.dim(), false);
+    for (row, mut out) in traces.rows().into_iter().zip(binary.rows_mut()) {
+        let mut sorted = row.to_vec();
+        sorted.sort_by(|a, b| a.total_cmp(b));
+        let median = sorted.get(sorted.len() / 2).copied().unwrap_or(0.0);
+        for (o, &x) in out.iter_mut().zip(row) {
+            *o = x > median;
+        }
+    }
+    binary
+}
+
+/// Lempel-Ziv complexity of spontaneous activity (LZc): channels binarized
+/// at their medians, concatenated across channels at each time sample
+pub fn spontaneous_complexity(traces: &Array2<f64>) -> f64 {
+    matrix_lempel_ziv(&binarize_at_median(traces))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Uniform deviates in [0, 1) from a linear congruential generator
+    fn uniform(n: usize, seed: u64) -> Vec<f64> {
+        let mut state = seed;
+        (0..n)
+            .map(|_| {
+                state = state.wrapping_mul(6364136223846793005).wrapping_add(1442695040888963407);
+                (state >> 11) as f64 / (1u64 << 53) as f64
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_kaspar_schuster_example() {
+        // 0 | 001 | 10 | 100 | 1000 | 101 (Kaspar & Schuster 1987)
+        let bits: Vec<bool> = "0001101001000101".chars().map(|c| c == '1').collect();
+        assert_eq!(lempel_ziv_complexity(&bits), 6);
+        assert_eq!(lempel_ziv_complexity(&[true; 50]), 2);
+        assert_eq!(lempel_ziv_complexity(&[]), 0);
+    }
+
+    #[test]
+    fn test_normalized_complexity_orders_regularity() {
+        let n = 4096;
+        let constant = vec![false; n];
+        let periodic: Vec<bool> = (0..n).map(|i| i % 8 < 4).collect();
+        let random: Vec<bool> = uniform(n, 7).iter().map(|&u| u < 0.5).collect();
+
+        assert_eq!(normalized_lempel_ziv(&constant), 0.0);
+        let (periodic, random) = (normalized_lempel_ziv(&periodic), normalized_lempel_ziv(&random));
+        assert!(periodic < 0.05, "{periodic}");
+        assert!(random > 0.85 && random < 1.2, "{random}");
+
+        // Median split of noise is random; of a shared slow wave it is not
+        let noise = Array2::from_shape_vec((8, 512), uniform(8 * 512, 11)).unwrap();
+        let wave = Array2::from_shape_fn((8, 512), |(c, t)| ((t as f64 + c as f64) / 40.0).sin());
+        assert!(spontaneous_complexity(&noise) > 0.85);
+        assert!(spontaneous_complexity(&wave) < 0.2);
+    }
+}
@@ -1,5 +1,46 @@
-//! consciousness module
+//! Measurable proxies of consciousness computed from simulated activity.
+//!
+//! - `lempel_ziv`: LZ76 complexity of binary sequences and of spontaneous
+//!   multichannel activity (LZc)
+//! - `pci`: Perturbational Complexity Index of a stimulated `CorticalColumn`
+//! - `phi`: approximate integrated information (Φ) of small binary networks
+//! - `ignition`: global-workspace ignition in multichannel responses
+//! - `states`: wake, sleep stages and GABAergic anesthesia imposed on a
+//!   column, and side-by-side measurement of all of the above
+//!
+//! All measures are relative: they are meant for comparing states of the
+//! same model (e.g. awake vs N3 vs propofol), not as absolute thresholds.
 
-pub fn placeholder() {
-    println!("consciousness module - to be implemented");
+pub mod ignition;
+pub mod lempel_ziv;
+pub mod pci;
+pub mod phi;
+pub mod states;
+
+use thiserror::Error;
+
+#[derive(Debug, Error)]
+pub enum ConsciousnessError {
+    #[error("Network of {0} nodes is too large for exhaustive Φ")]
+    NetworkTooLarge(usize),
+
+    #[error("Dimension mismatch: expected {expected}, got {actual}")]
+    DimensionMismatch { expected: usize, actual: usize },
+
+    #[error("State has no possible cause under the network dynamics")]
+    UnreachableState,
+
+    #[error("Invalid protocol: {0}")]
+    InvalidProtocol(String),
+
+    #[error("Cortex error: {0}")]
+    Cortex(#[from] cortex::CortexError),
 }
+
+pub type Result<T> = std::result::Result<T, ConsciousnessError>;
+
+pub use ignition::{IgnitionDetector, IgnitionEvent};
+pub use lempel_ziv::{lempel_ziv_complexity, matrix_lempel_ziv, normalized_lempel_ziv, spontaneous_complexity};
+pub use pci::{perturbational_complexity, perturbational_complexity_with, PciProtocol, PciResult};
+pub use phi::{BinaryNetwork, Phi, MAX_PHI_NODES};
+pub use states::{BrainState, ConsciousnessReport, StateComparison};
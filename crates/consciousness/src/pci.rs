@@ -0,0 +1,187 @@
+//! Perturbational Complexity Index (PCI).
+//!
+//! PCI "zaps and zips": a brief focal stimulus perturbs the cortex, the
+//! deterministic response is binarized into a matrix of significant
+//! deviations from the pre-stimulus baseline (channels × time), and that
+//! matrix is compressed with Lempel-Ziv. Responses that are both integrated
+//! (widespread) and differentiated (not stereotyped) score high; a local
+//! response and a global but uniform slow wave both score low.
+//!
+//! Here the channels are the soma voltages of a `CorticalColumn`, the
+//! stimulus is a current pulse into the somata nearest the column axis, and
+//! a sample is significant when it departs from the channel's baseline mean
+//! by more than `threshold_sd` baseline SDs (floored at `min_sd` mV).
+//!
+//! Reference: Casali et al. (2013), Sci. Transl. Med. 5:198ra105
+
+use crate::lempel_ziv::matrix_lempel_ziv;
+use crate::{ConsciousnessError, Result};
+use cortex::CorticalColumn;
+use ndarray::{s, Array2};
+use serde::{Deserialize, Serialize};
+
+/// Stimulation and recording protocol for PCI
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct PciProtocol {
+    /// Pre-stimulus recording (steps)
+    pub baseline_steps: usize,
+
+    /// Duration of the current pulse (steps)
+    pub stimulus_steps: usize,
+
+    /// Post-stimulus recording, including the pulse (steps)
+    pub response_steps: usize,
+
+    /// Somatic pulse amplitude (pA)
+    pub stimulus_current: f64,
+
+    /// Fraction of neurons stimulated, those nearest the column axis
+    pub stimulated_fraction: f64,
+
+    /// Significance threshold (baseline SDs)
+    pub threshold_sd: f64,
+
+    /// Floor on the baseline SD (mV)
+    pub min_sd: f64,
+}
+
+impl Default for PciProtocol {
+    fn default() -> Self {
+        Self {
+            baseline_steps: 200,
+            stimulus_steps: 20,
+            response_steps: 600,
+            stimulus_current: 500.0,
+            stimulated_fraction: 0.2,
+            threshold_sd: 3.0,
+            min_sd: 0.5,
+        }
+    }
+}
+
+/// Outcome of one PCI measurement
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct PciResult {
+    /// Normalized Lempel-Ziv complexity of the significant response
+    pub pci: f64,
+
+    /// Significant deviations, neurons × post-stimulus steps
+    pub significant: Array2<bool>,
+
+    /// Soma voltages (mV), neurons × (baseline + response) steps
+    pub voltages: Array2<f64>,
+}
+
+impl PciResult {
+    /// Fraction of neuron-samples significantly perturbed
+    pub fn activation(&self) -> f64 {
+        let total = self.significant.len().max(1);
+        self.significant.iter().filter(|&&s| s).count() as f64 / total as f64
+    }
+}
+
+/// Step `column` for `steps` steps and return the soma voltages, neurons ×
+/// steps. Before each step `input(step, column)` returns the somatic
+/// currents (pA) and may also deliver long-range input to the column.
+pub fn record_soma_voltages(
+    column: &mut CorticalColumn,
+    steps: usize,
+    mut input: impl FnMut(usize, &mut CorticalColumn) -> Vec<f64>,
+) -> Result<Array2<f64>> {
+    let mut voltages = Array2::zeros((column.neurons.len(), steps));
+    for t in 0..steps {
+        let currents = input(t, column);
+        column.step(&currents)?;
+        for (i, neuron) in column.neurons.iter().enumerate() {
+            voltages[[i, t]] = neuron.get_soma_voltage();
+        }
+    }
+    Ok(voltages)
+}
+
+/// Significant deviations of the samples after `baseline` from each
+/// channel's baseline statistics, channels × post-baseline samples
+pub fn binarize_response(traces: &Array2<f64>, baseline: usize, threshold_sd: f64, min_sd: f64) -> Result<Array2<bool>> {
+    let (channels, samples) = traces.dim();
+    if baseline < 2 || baseline >= samples {
+        return Err(ConsciousnessError::InvalidProtocol(format!(
+            "baseline of {baseline} samples in a recording of {samples}"
+        )));
+    }
+
+    let mut significant = Array2::from_elem((channels, samples - baseline), false);
+    for (row, mut out) in traces.rows().into_iter().zip(significant.rows_mut()) {
+        let pre = row.slice(s![..baseline]);
+        let mean = pre.sum() / baseline as f64;
+        let sd = (pre.iter().map(|x| (x - mean).powi(2)).sum::<f64>() / (baseline - 1) as f64).sqrt();
+        let threshold = threshold_sd * sd.max(min_sd);
+        for (o, &x) in out.iter_mut().zip(row.iter().skip(baseline)) {
+            *o = (x - mean).abs() > threshold;
+        }
+    }
+    Ok(significant)
+}
+
+/// Stimulate an otherwise undriven `column` and measure its PCI
+pub fn perturbational_complexity(column: &mut CorticalColumn, protocol: &PciProtocol) -> Result<PciResult> {
+    perturbational_complexity_with(column, protocol, |_| {})
+}
+
+/// Stimulate `column` and measure its PCI while `background` delivers
+/// ongoing afferent input before every step
+pub fn perturbational_complexity_with(
+    column: &mut CorticalColumn,
+    protocol: &PciProtocol,
+    mut background: impl FnMut(&mut CorticalColumn),
+) -> Result<PciResult> {
+    if protocol.response_steps == 0 || protocol.stimulus_steps > protocol.response_steps {
+        return Err(ConsciousnessError::InvalidProtocol(format!(
+            "{}-step stimulus in a {}-step response window",
+            protocol.stimulus_steps, protocol.response_steps
+        )));
+    }
+
+    // Focal stimulus: the somata closest to the column axis
+    let n = column.neurons.len();
+    let radial = |p: &[f64; 3]| p[0].hypot(p[1]);
+    let mut by_distance: Vec<usize> = (0..n).collect();
+    by_distance.sort_by(|&a, &b| radial(&column.neuron_positions[a]).total_cmp(&radial(&column.neuron_positions[b])));
+    let mut pulse = vec![0.0; n];
+    let stimulated = (protocol.stimulated_fraction * n as f64).ceil() as usize;
+    for &i in by_distance.iter().take(stimulated) {
+        pulse[i] = protocol.stimulus_current;
+    }
+
+    let onset = protocol.baseline_steps;
+    let offset = onset + protocol.stimulus_steps;
+    let voltages = record_soma_voltages(column, onset + protocol.response_steps, |t, column| {
+        background(column);
+        if (onset..offset).contains(&t) {
+            pulse.clone()
+        } else {
+            vec![0.0; n]
+        }
+    })?;
+
+    let significant = binarize_response(&voltages, onset, protocol.threshold_sd, protocol.min_sd)?;
+    Ok(PciResult { pci: matrix_lempel_ziv(&significant), significant, voltages })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_binarization_against_baseline() {
+        // Channel 0 is perturbed, channel 1 keeps fluctuating as at baseline
+        let traces = Array2::from_shape_fn((2, 40), |(c, t)| {
+            let noise = if t % 2 == 0 { 0.1 } else { -0.1 };
+            noise + if c == 0 && (25..30).contains(&t) { 5.0 } else { 0.0 }
+        });
+        let significant = binarize_response(&traces, 20, 3.0, 0.0).unwrap();
+        assert_eq!(significant.dim(), (2, 20));
+        assert_eq!(significant.iter().filter(|&&s| s).count(), 5);
+        assert!(significant[[0, 5]] && !significant[[1, 5]]);
+        assert!(binarize_response(&traces, 40, 3.0, 0.0).is_err());
+    }
+}
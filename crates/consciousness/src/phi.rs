@@ -0,0 +1,281 @@
+//! Approximate integrated information (Φ) of small binary networks.
+//!
+//! Follows the state-dependent formulation of Balduzzi & Tononi (2008): given
+//! the present state x1, the network's cause repertoire p(X0 | x1) is the
+//! posterior over past states under a uniform (maximum-entropy) prior. A
+//! bipartition {A, B} is assessed by cutting every connection between the
+//! parts, replacing the severed inputs with uniform noise, and measuring how
+//! much the whole repertoire differs from the product of the parts'
+//! repertoires (KL divergence, bits). Φ is that divergence across the
+//! minimum information bipartition (MIB), the cut minimizing it after
+//! normalization by the smaller part's maximum entropy.
+//!
+//! The search is exhaustive over 2^(n-1) - 1 bipartitions of 2^n states, so
+//! only networks of up to `MAX_PHI_NODES` nodes are accepted. Later IIT
+//! versions (3.0, 4.0) differ in detail; this is a proxy for comparing
+//! network motifs, not an exact Φ.
+//!
+//! Reference: Balduzzi & Tononi (2008), PLoS Comput. Biol. 4:e1000091
+
+use crate::{ConsciousnessError, Result};
+use ndarray::Array2;
+use serde::{Deserialize, Serialize};
+use synapses::{SynapseType, SynapticNetwork};
+
+/// Largest network whose Φ is computed exhaustively
+pub const MAX_PHI_NODES: usize = 12;
+
+/// Network of binary units updated synchronously:
+/// P(x_j = 1 at t+1) = sigmoid((sum_i w_ji x_i - theta_j) / T)
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct BinaryNetwork {
+    /// Connection weights, `weights[[post, pre]]`
+    pub weights: Array2<f64>,
+
+    /// Firing threshold of each unit
+    pub thresholds: Vec<f64>,
+
+    /// Noise temperature; 0 gives deterministic threshold units
+    pub temperature: f64,
+}
+
+/// Integrated information of a network in one state
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Phi {
+    /// Φ across the minimum information bipartition (bits)
+    pub phi: f64,
+
+    /// Information the whole network generates about its past (bits)
+    pub effective_information: f64,
+
+    /// The minimum information bipartition
+    pub partition: (Vec<usize>, Vec<usize>),
+}
+
+impl BinaryNetwork {
+    /// Deterministic network with zero thresholds
+    pub fn new(weights: Array2<f64>) -> Result<Self> {
+        let (rows, cols) = weights.dim();
+        if rows != cols {
+            return Err(ConsciousnessError::DimensionMismatch { expected: rows, actual: cols });
+        }
+        Ok(Self { weights, thresholds: vec![0.0; rows], temperature: 0.0 })
+    }
+
+    /// Network among `nodes` of a synaptic network. Weights are the summed
+    /// efficacy (weight × g_max, scaled by any drug modulation of the
+    /// synapse type) of the synapses between each pair, negative
+    /// for inhibitory ones, scaled so the strongest is 1; each unit fires
+    /// when its input reaches half of its total excitatory weight.
+    pub fn from_synaptic_network(network: &SynapticNetwork, nodes: &[usize]) -> Result<Self> {
+        let n = nodes.len();
+        let mut weights = Array2::<f64>::zeros((n, n));
+        for (post, &post_id) in nodes.iter().enumerate() {
+            for synapse in network.get_incoming_synapses(post_id) {
+                let Some(pre) = nodes.iter().position(|&id| id == synapse.pre_neuron_id) else {
+                    continue;
+                };
+                let sign = match synapse.synapse_type {
+                    SynapseType::GABAA | SynapseType::GABAB | SynapseType::Glycine => -1.0,
+                    _ => 1.0,
+                };
+                let drug = network.drug_modulation_for(synapse.synapse_type);
+                let efficacy = synapse.weight * synapse.g_max * drug.release * drug.conductance * drug.decay;
+                weights[[post, pre]] += sign * efficacy;
+            }
+        }
+
+        let scale = weights.iter().fold(0.0_f64, |m, w| m.max(w.abs()));
+        if scale > 0.0 {
+            weights /= scale;
+        }
+        let thresholds = weights
+            .rows()
+            .into_iter()
+            .map(|row| 0.5 * row.iter().filter(|&&w| w > 0.0).sum::<f64>())
+            .collect();
+
+        Ok(Self::new(weights)?.with_thresholds(thresholds))
+    }
+
+    /// Builder: set the firing thresholds
+    pub fn with_thresholds(mut self, thresholds: Vec<f64>) -> Self {
+        self.thresholds = thresholds;
+        self
+    }
+
+    /// Builder: set the noise temperature
+    pub fn with_temperature(mut self, temperature: f64) -> Self {
+        self.temperature = temperature;
+        self
+    }
+
+    /// Number of units
+    pub fn len(&self) -> usize {
+        self.thresholds.len()
+    }
+
+    /// Whether the network has no units
+    pub fn is_empty(&self) -> bool {
+        self.thresholds.is_empty()
+    }
+
+    /// Probability that `node` fires at the next step given `state`
+    pub fn firing_probability(&self, node: usize, state: &[bool]) -> f64 {
+        let drive: f64 = self
+            .weights
+            .row(node)
+            .iter()
+            .zip(state)
+            .filter(|(_, &on)| on)
+            .map(|(w, _)| w)
+            .sum::<f64>()
+            - self.thresholds[node];
+
+        if self.temperature > 0.0 {
+            1.0 / (1.0 + (-drive / self.temperature).exp())
+        } else if drive > 0.0 {
+            1.0
+        } else {
+            0.0
+        }
+    }
+
+    /// Φ of the network in `state`
+    pub fn phi(&self, state: &[bool]) -> Result<Phi> {
+        let n = self.len();
+        if state.len() != n {
+            return Err(ConsciousnessError::DimensionMismatch { expected: n, actual: state.len() });
+        }
+        if n > MAX_PHI_NODES {
+            return Err(ConsciousnessError::NetworkTooLarge(n));
+        }
+
+        let states = 1usize << n;
+        let bits = |s: usize| (0..n).map(|i| s >> i & 1 == 1).collect::<Vec<_>>();
+
+        // likelihood[j][s]: probability that unit j takes its present value
+        // when the network was in past state s
+        let likelihood: Vec<Vec<f64>> = (0..n)
+            .map(|j| {
+                (0..states)
+                    .map(|s| {
+                        let p = self.firing_probability(j, &bits(s));
+                        if state[j] { p } else { 1.0 - p }
+                    })
+                    .collect()
+            })
+            .collect();
+
+        let whole = normalize((0..states).map(|s| likelihood.iter().map(|l| l[s]).product()).collect())
+            .ok_or(ConsciousnessError::UnreachableState)?;
+        let effective_information = kl_divergence(&whole, &vec![1.0 / states as f64; states]);
+
+        let all = states - 1;
+        let mut best: Option<(f64, f64, usize)> = None;
+        // Unit 0 always sits in part A so each bipartition is visited once
+        for part_a in (1..all).filter(|m| m & 1 == 1) {
+            let part_b = all & !part_a;
+            let mut product = vec![1.0; states];
+            for (j, l) in likelihood.iter().enumerate() {
+                let own = if part_a >> j & 1 == 1 { part_a } else { part_b };
+                let marginal = marginalize(l, own);
+                for (p, s) in product.iter_mut().zip(0..states) {
+                    *p *= marginal[s & own];
+                }
+            }
+            let Some(parts) = normalize(product) else {
+                continue;
+            };
+
+            let phi = kl_divergence(&whole, &parts);
+            let smaller = part_a.count_ones().min(part_b.count_ones()) as f64;
+            if best.is_none_or(|(normalized, _, _)| phi / smaller < normalized) {
+                best = Some((phi / smaller, phi, part_a));
+            }
+        }
+
+        let (phi, part_a) = best.map_or((0.0, all), |(_, phi, part_a)| (phi, part_a));
+        Ok(Phi {
+            phi,
+            effective_information,
+            partition: (0..n).partition(|&i| part_a >> i & 1 == 1),
+        })
+    }
+}
+
+/// Average of `values` over the states outside `mask`, indexed by `s & mask`
+fn marginalize(values: &[f64], mask: usize) -> Vec<f64> {
+    let mut sums = vec![0.0; values.len()];
+    for (s, &v) in values.iter().enumerate() {
+        sums[s & mask] += v;
+    }
+    let outside = (values.len() >> mask.count_ones()) as f64;
+    sums.iter_mut().for_each(|v| *v /= outside);
+    sums
+}
+
+fn normalize(mut distribution: Vec<f64>) -> Option<Vec<f64>> {
+    let total: f64 = distribution.iter().sum();
+    if total <= 0.0 {
+        return None;
+    }
+    distribution.iter_mut().for_each(|p| *p /= total);
+    Some(distribution)
+}
+
+/// KL divergence D(p || q) in bits
+fn kl_divergence(p: &[f64], q: &[f64]) -> f64 {
+    p.iter()
+        .zip(q)
+        .filter(|(&p, _)| p > 0.0)
+        .map(|(&p, &q)| p * (p / q).log2())
+        .sum::<f64>()
+        .max(0.0)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use ndarray::array;
+
+    #[test]
+    fn test_independent_units_have_no_integrated_information() {
+        // Each unit copies itself: four bits of information, none integrated
+        let network = BinaryNetwork::new(Array2::eye(4)).unwrap().with_thresholds(vec![0.5; 4]);
+        let phi = network.phi(&[true, false, true, true]).unwrap();
+        assert!((phi.effective_information - 4.0).abs() < 1e-12);
+        assert!(phi.phi.abs() < 1e-12);
+
+        assert!(matches!(
+            BinaryNetwork::new(Array2::eye(13)).unwrap().phi(&[false; 13]),
+            Err(ConsciousnessError::NetworkTooLarge(13))
+        ));
+    }
+
+    #[test]
+    fn test_coupled_loop_integrates_information() {
+        // Two units swapping states: every bit is generated across the cut
+        let swap = BinaryNetwork::new(array![[0.0, 1.0], [1.0, 0.0]]).unwrap().with_thresholds(vec![0.5; 2]);
+        let phi = swap.phi(&[true, false]).unwrap();
+        assert!((phi.phi - 2.0).abs() < 1e-12, "{phi:?}");
+        assert_eq!(phi.partition, (vec![0], vec![1]));
+
+        // A ring of OR units integrates too; strong noise washes it out
+        let ring = Array2::from_shape_fn((5, 5), |(i, j)| if (i + 1) % 5 == j || (i + 4) % 5 == j { 1.0 } else { 0.0 });
+        let ring = BinaryNetwork::new(ring).unwrap().with_thresholds(vec![0.5; 5]);
+        let state = [false, true, false, false, true];
+        let sharp = ring.phi(&state).unwrap().phi;
+        let noisy = ring.clone().with_temperature(2.0).phi(&state).unwrap().phi;
+        assert!(sharp > 0.0 && noisy < sharp, "{sharp} {noisy}");
+
+        // Motifs can be cut out of a simulated synaptic network
+        let mut network = SynapticNetwork::with_seed(3, 0);
+        network.add_synapse(synapses::Synapse::new(0, 0, 1, SynapseType::AMPA, 1.0));
+        network.add_synapse(synapses::Synapse::new(1, 1, 0, SynapseType::AMPA, 1.0));
+        network.add_synapse(synapses::Synapse::new(2, 2, 0, SynapseType::GABAA, 1.0));
+        let motif = BinaryNetwork::from_synaptic_network(&network, &[0, 1, 2]).unwrap();
+        assert!(motif.weights[[0, 2]] < 0.0 && motif.weights[[1, 0]] > 0.0);
+        assert!(motif.phi(&[true, false, false]).unwrap().phi > 0.0);
+    }
+}
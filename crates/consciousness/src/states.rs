@@ -0,0 +1,348 @@
+//! Brain states and their consciousness signatures.
+//!
+//! A `BrainState` is imposed on a freshly built `CorticalColumn` through the
+//! mechanisms that produce it in vivo:
+//! - **Sleep stages** lower the ascending neuromodulatory tone. Acetylcholine
+//!   and noradrenaline fall from wake through N1 and N2 to N3, reopening the
+//!   leak and K+ conductances they close; in REM acetylcholine returns while
+//!   noradrenaline and serotonin stay silent.
+//! - **GABAergic anesthetics** are bound to a `MultiReceptorSystem`, whose
+//!   synaptic and channel modulations are set on the column as drug
+//!   exposures do in `WholeBrain`: GABA-A IPSCs are potentiated and
+//!   prolonged, and the tonic extrasynaptic GABA-A current adds to the leak.
+//!
+//! `StateComparison` then measures the same column (same seed, same
+//! background drive) in each state: PCI, spontaneous LZ complexity, firing
+//! rate and ignition after the PCI pulse. Its neurons are given
+//! Hodgkin-Huxley excitability, without which the K+ and leak modulations
+//! would act on a passive membrane only.
+
+use crate::ignition::{IgnitionDetector, IgnitionEvent};
+use crate::lempel_ziv::spontaneous_complexity;
+use crate::pci::{perturbational_complexity_with, record_soma_voltages, PciProtocol};
+use crate::Result;
+use cognition::SleepStage;
+use cortex::CorticalColumn;
+use pharmacology::{DrugDatabase, DrugMolecularProfile, IpscModulation, MultiReceptorSystem};
+use rand::{Rng, SeedableRng};
+use rand_chacha::ChaCha8Rng;
+use serde::{Deserialize, Serialize};
+use synapses::{NeuromodulatorState, SynapseType};
+
+/// Global state of the cortex
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub enum BrainState {
+    /// Natural vigilance state, from wakefulness to REM sleep
+    Natural(SleepStage),
+
+    /// Waking neuromodulatory tone with a GABA-A modulator on board
+    Anesthetized {
+        drug: DrugMolecularProfile,
+        /// Effect-site concentration (µM)
+        concentration_um: f64,
+    },
+}
+
+impl From<SleepStage> for BrainState {
+    fn from(stage: SleepStage) -> Self {
+        BrainState::Natural(stage)
+    }
+}
+
+impl BrainState {
+    /// Quiet wakefulness
+    pub fn awake() -> Self {
+        BrainState::Natural(SleepStage::Awake)
+    }
+
+    /// Propofol at `concentration_um` µM, acting at `BindingSite::AnestheticSite`
+    pub fn propofol(concentration_um: f64) -> Self {
+        let drug = DrugDatabase::new().get("propofol").cloned().expect("propofol is in the drug database");
+        BrainState::Anesthetized { drug, concentration_um }
+    }
+
+    /// Short description, e.g. "N3" or "propofol 5 µM"
+    pub fn label(&self) -> String {
+        match self {
+            BrainState::Natural(stage) => format!("{stage:?}"),
+            BrainState::Anesthetized { drug, concentration_um } => format!("{} {concentration_um} µM", drug.name),
+        }
+    }
+
+    /// Extracellular neuromodulator levels in this state
+    pub fn neuromodulators(&self) -> NeuromodulatorState {
+        let stage = match self {
+            BrainState::Natural(stage) => *stage,
+            BrainState::Anesthetized { .. } => SleepStage::Awake,
+        };
+        let (serotonin, norepinephrine, acetylcholine) = match stage {
+            SleepStage::Awake => (0.1, 0.3, 1.0),
+            SleepStage::N1 => (0.06, 0.15, 0.4),
+            SleepStage::N2 => (0.03, 0.08, 0.15),
+            SleepStage::N3 => (0.01, 0.03, 0.05),
+            SleepStage::REM => (0.005, 0.005, 1.2),
+        };
+        NeuromodulatorState { dopamine: 0.1, serotonin, norepinephrine, acetylcholine }
+    }
+
+    /// Receptors of the column in this state: drug-free, or with the
+    /// anesthetic bound at its effect-site concentration
+    pub fn receptors(&self) -> MultiReceptorSystem {
+        let mut receptors = MultiReceptorSystem::new();
+        if let BrainState::Anesthetized { drug, concentration_um } = self {
+            receptors.bind(&drug.clone().into(), *concentration_um);
+        }
+        receptors
+    }
+
+    /// Effect on GABA-A IPSCs (unmodulated without a drug)
+    pub fn ipsc_modulation(&self) -> IpscModulation {
+        self.receptors().gaba_a_modulation()
+    }
+
+    /// Factor on the charge of each GABA-A IPSC (1 without a drug)
+    pub fn gaba_a_modulation(&self) -> f64 {
+        self.ipsc_modulation().charge()
+    }
+
+    /// Impose this state on a column. The synapses' and neurons' own
+    /// parameters are left untouched, so applying a state again (or another
+    /// state) replaces it.
+    pub fn apply(&self, column: &mut CorticalColumn) {
+        column.set_neuromodulators(&self.neuromodulators());
+
+        let receptors = self.receptors();
+        for synapse_type in SynapseType::ALL {
+            column.synaptic_network.set_drug_modulation(synapse_type, receptors.synaptic_modulation(synapse_type));
+        }
+        column.set_drug_channel_modulation(receptors.channel_modulation());
+    }
+}
+
+/// Consciousness proxies of one column in one state
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ConsciousnessReport {
+    /// `BrainState::label` of the measured state
+    pub state: String,
+
+    /// Perturbational Complexity Index
+    pub pci: f64,
+
+    /// Fraction of neuron-samples significantly perturbed by the pulse
+    pub pci_activation: f64,
+
+    /// Lempel-Ziv complexity of spontaneous soma voltages
+    pub spontaneous_lz: f64,
+
+    /// Mean spontaneous firing rate (Hz)
+    pub firing_rate: f64,
+
+    /// Ignition events following the PCI pulse
+    pub ignition: Vec<IgnitionEvent>,
+}
+
+/// Protocol measuring identically built columns across brain states
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct StateComparison {
+    /// Neurons per column
+    pub num_neurons: usize,
+
+    /// Time step (ms)
+    pub dt: f64,
+
+    /// Seed of the column wiring and of the background drive
+    pub seed: u64,
+
+    /// Rate of long-range afferent volleys onto the column (Hz)
+    pub background_rate: f64,
+
+    /// Conductance delivered by each volley (nS)
+    pub background_conductance: f64,
+
+    /// Settling time after a state is imposed (steps)
+    pub settle_steps: usize,
+
+    /// Spontaneous recording for LZ and firing rate (steps)
+    pub spontaneous_steps: usize,
+
+    /// Perturbation protocol
+    pub protocol: PciProtocol,
+
+    /// Ignition criteria applied to the PCI response
+    pub ignition: IgnitionDetector,
+}
+
+impl StateComparison {
+    /// Comparison on columns of `num_neurons` neurons built from `seed`
+    pub fn new(num_neurons: usize, seed: u64) -> Self {
+        Self {
+            num_neurons,
+            dt: 0.1,
+            seed,
+            background_rate: 200.0,
+            background_conductance: 5.0,
+            settle_steps: 200,
+            spontaneous_steps: 500,
+            protocol: PciProtocol::default(),
+            ignition: IgnitionDetector::new().with_threshold(3.0, 0.5).with_min_fraction(0.3),
+        }
+    }
+
+    /// Builder: set the perturbation protocol
+    pub fn with_protocol(mut self, protocol: PciProtocol) -> Self {
+        self.protocol = protocol;
+        self
+    }
+
+    /// Builder: set the background afferent drive (Hz, nS per volley)
+    pub fn with_background(mut self, rate: f64, conductance: f64) -> Self {
+        self.background_rate = rate;
+        self.background_conductance = conductance;
+        self
+    }
+
+    /// Builder: set the settling and spontaneous recording lengths (steps)
+    pub fn with_durations(mut self, settle_steps: usize, spontaneous_steps: usize) -> Self {
+        self.settle_steps = settle_steps;
+        self.spontaneous_steps = spontaneous_steps;
+        self
+    }
+
+    /// Measure a fresh column in `state`
+    pub fn measure(&self, state: &BrainState) -> Result<ConsciousnessReport> {
+        let mut column = CorticalColumn::with_seed(0, self.num_neurons, self.dt, self.seed).with_hodgkin_huxley();
+        state.apply(&mut column);
+
+        // Background drive is drawn per step from its own stream, so every
+        // state receives the same afferent volleys
+        let mut rng = ChaCha8Rng::seed_from_u64(self.seed ^ 0x5eed);
+        let volley_probability = (self.background_rate * self.dt * 1e-3).min(1.0);
+        let mut drive = |column: &mut CorticalColumn| {
+            if rng.gen::<f64>() < volley_probability {
+                column.receive_long_range_input(self.background_conductance);
+            }
+        };
+        let n = column.neurons.len();
+
+        record_soma_voltages(&mut column, self.settle_steps, |_, column| {
+            drive(column);
+            vec![0.0; n]
+        })?;
+
+        let spikes_before = column.get_spike_count();
+        let spontaneous = record_soma_voltages(&mut column, self.spontaneous_steps, |_, column| {
+            drive(column);
+            vec![0.0; n]
+        })?;
+        let spikes = column.get_spike_count() - spikes_before;
+        let duration_s = self.spontaneous_steps as f64 * self.dt * 1e-3;
+
+        let pci = perturbational_complexity_with(&mut column, &self.protocol, &mut drive)?;
+        let ignition = self.ignition.detect(&pci.voltages, self.protocol.baseline_steps)?;
+
+        Ok(ConsciousnessReport {
+            state: state.label(),
+            pci: pci.pci,
+            pci_activation: pci.activation(),
+            spontaneous_lz: spontaneous_complexity(&spontaneous),
+            firing_rate: spikes as f64 / (n.max(1) as f64 * duration_s.max(f64::EPSILON)),
+            ignition,
+        })
+    }
+
+    /// Measure every state in turn
+    pub fn compare(&self, states: &[BrainState]) -> Result<Vec<ConsciousnessReport>> {
+        states.iter().map(|state| self.measure(state)).collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use synapses::SynapticModulation;
+
+    #[test]
+    fn test_states_act_through_their_mechanisms() {
+        let awake = BrainState::awake();
+        let n3 = BrainState::from(SleepStage::N3);
+        let propofol = BrainState::propofol(5.0);
+        assert!(n3.neuromodulators().acetylcholine < awake.neuromodulators().acetylcholine);
+        assert_eq!(propofol.neuromodulators(), awake.neuromodulators());
+
+        let build = |state: &BrainState| {
+            let mut column = CorticalColumn::with_seed(0, 40, 0.1, 1);
+            state.apply(&mut column);
+            column
+        };
+        let (awake_column, n3_column, propofol_column) = (build(&awake), build(&n3), build(&propofol));
+
+        // Without acetylcholine and noradrenaline the K+ channels reopen
+        assert!(n3_column.neurons[0].modulation.potassium > awake_column.neurons[0].modulation.potassium);
+
+        // Propofol at the anesthetic site potentiates and prolongs GABA-A IPSCs
+        let modulation = propofol.gaba_a_modulation();
+        assert!(modulation > 1.5, "{modulation}");
+        let gaba_a = |column: &CorticalColumn| column.synaptic_network.drug_modulation_for(SynapseType::GABAA);
+        let potentiated = gaba_a(&propofol_column);
+        assert!(potentiated.decay > 1.0 && potentiated.conductance >= 1.0);
+        assert!((potentiated.conductance * potentiated.decay - modulation).abs() < 1e-12);
+        assert_eq!(gaba_a(&awake_column), SynapticModulation::default());
+        // ...and the tonic extrasynaptic current by as much
+        let leak = propofol_column.neurons[0].modulation.leak / awake_column.neurons[0].modulation.leak;
+        assert!((leak - modulation).abs() < 1e-9);
+        let ampa = propofol_column.synaptic_network.drug_modulation_for(SynapseType::AMPA);
+        assert_eq!(ampa, SynapticModulation::default());
+
+        // The drug acts through the modulation, not the synapses' own g_max,
+        // so re-applying a state does not compound it
+        let pairs = awake_column.synaptic_network.synapses.iter().zip(&propofol_column.synaptic_network.synapses);
+        assert!(pairs.into_iter().all(|(before, after)| before.g_max == after.g_max));
+        let mut twice = propofol_column.clone();
+        propofol.apply(&mut twice);
+        assert_eq!(gaba_a(&twice), potentiated);
+        awake.apply(&mut twice);
+        assert_eq!(gaba_a(&twice), SynapticModulation::default());
+        assert_eq!(twice.neurons[0].modulation, awake_column.neurons[0].modulation);
+    }
+
+    #[test]
+    fn test_awake_is_more_complex_than_n3_and_propofol() {
+        let protocol = PciProtocol { baseline_steps: 100, response_steps: 300, ..PciProtocol::default() };
+        let comparison = StateComparison::new(60, 5)
+            .with_background(1000.0, 5.0)
+            .with_durations(100, 500)
+            .with_protocol(protocol);
+        let reports = comparison
+            .compare(&[BrainState::awake(), SleepStage::N3.into(), BrainState::propofol(5.0)])
+            .unwrap();
+
+        let awake = &reports[0];
+        for reduced in &reports[1..] {
+            assert!(awake.pci > reduced.pci, "PCI awake {} vs {} {}", awake.pci, reduced.state, reduced.pci);
+            assert!(
+                awake.spontaneous_lz > reduced.spontaneous_lz,
+                "LZ awake {} vs {} {}",
+                awake.spontaneous_lz,
+                reduced.state,
+                reduced.spontaneous_lz
+            );
+            assert!(awake.firing_rate > reduced.firing_rate);
+        }
+    }
+
+    #[test]
+    fn test_state_comparison_is_reproducible() {
+        let protocol = PciProtocol { baseline_steps: 100, response_steps: 200, ..PciProtocol::default() };
+        let comparison = StateComparison::new(40, 5).with_durations(50, 200).with_protocol(protocol);
+        let reports = comparison
+            .compare(&[BrainState::awake(), BrainState::awake(), SleepStage::N3.into()])
+            .unwrap();
+
+        assert_eq!(reports[0], reports[1]);
+        assert_eq!(reports[2].state, "N3");
+        for report in &reports {
+            assert!(report.pci.is_finite() && report.pci > 0.0);
+            assert!(report.pci_activation > 0.0 && report.spontaneous_lz.is_finite());
+        }
+    }
+}
@@ -4,15 +4,20 @@
 //! containing ~100,000 neurons arranged in 6 layers.
 
 use crate::{CorticalNeuronType, layers::*, Result};
-use neurons::{MultiCompartmentalNeuron, compartmental::ChannelStates};
-use synapses::{Synapse, SynapticNetwork, SynapseType};
+use neurons::{ChannelModulation, MultiCompartmentalNeuron, SpikeEvent, compartmental::{ChannelStates, Integrator}};
+use synapses::{NeuromodulatorState, SynapticNetwork};
 use glia::{Astrocyte, Oligodendrocyte, Microglia};
+use connectivity::{AnatomicalConnectivity, ConnectomeBuilder, GapJunctionConnectivity, NeuronSite};
 use metabolism::RegionalMetabolism;
 use serde::{Deserialize, Serialize};
-use rand::Rng;
+use rand::{Rng, SeedableRng};
+use rand_chacha::ChaCha8Rng;
+
+/// Radius of a column (um)
+const COLUMN_RADIUS: f64 = 150.0;
 
 /// A single cortical column
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct CorticalColumn {
     /// Column ID
     pub id: usize,
@@ -29,9 +34,18 @@ pub struct CorticalColumn {
     /// Layer assignments
     pub neuron_layers: Vec<LayerType>,
 
+    /// Soma positions (um): x, y across the column, z depth below the pia
+    #[serde(default)]
+    pub neuron_positions: Vec<[f64; 3]>,
+
     /// Synaptic network
     pub synaptic_network: SynapticNetwork,
 
+    /// Drug action on the neurons' membrane conductances, on top of the
+    /// neuromodulators
+    #[serde(default)]
+    pub drug_channel_modulation: ChannelModulation,
+
     /// Astrocytes
     pub astrocytes: Vec<Astrocyte>,
 
@@ -47,6 +61,13 @@ pub struct CorticalColumn {
     /// Spike history (for analysis)
     pub spike_count: usize,
 
+    /// Long-range (cortico-cortical) AMPA conductance onto L2/3 and L5 pyramids
+    /// and PV basket cells (nS)
+    pub long_range_conductance: f64,
+
+    /// L2/3 and L5 pyramidal spikes emitted in the last step (projection output)
+    pub projection_spikes: usize,
+
     /// Time step (ms)
     pub dt: f64,
 
@@ -55,15 +76,22 @@ pub struct CorticalColumn {
 }
 
 impl CorticalColumn {
-    /// Create a new cortical column
+    /// Create a new cortical column with an entropy-derived seed
     pub fn new(id: usize, num_neurons: usize, dt: f64) -> Self {
-        let mut rng = rand::thread_rng();
+        Self::with_seed(id, num_neurons, dt, rand::random())
+    }
+
+    /// Create a new cortical column whose wiring and stochastic release
+    /// are fully determined by `seed`
+    pub fn with_seed(id: usize, num_neurons: usize, dt: f64, seed: u64) -> Self {
+        let mut rng = ChaCha8Rng::seed_from_u64(seed);
 
         // Create neurons distributed across layers
         let mut neurons = Vec::with_capacity(num_neurons);
         let mut channel_states = Vec::with_capacity(num_neurons);
         let mut neuron_types = Vec::with_capacity(num_neurons);
         let mut neuron_layers = Vec::with_capacity(num_neurons);
+        let mut neuron_positions = Vec::with_capacity(num_neurons);
 
         // Layer distribution (approximating cortical proportions)
         let layer_proportions = [
@@ -117,21 +145,25 @@ impl CorticalColumn {
                 channel_states.push(states);
                 neuron_types.push(neuron_type);
                 neuron_layers.push(layer);
+                neuron_positions.push(Self::sample_position(layer, &mut rng));
 
                 neuron_id += 1;
             }
         }
 
-        // Create synaptic network
-        let mut synaptic_network = SynapticNetwork::new(neurons.len());
-
-        // Add intra-columnar connections
-        Self::create_columnar_connections(
-            &mut synaptic_network,
-            &neuron_types,
-            &neuron_layers,
-            &mut rng,
-        );
+        // Wire the column from the anatomical connection statistics
+        let sites: Vec<NeuronSite> = (0..neurons.len())
+            .map(|i| NeuronSite {
+                layer: neuron_layers[i].into(),
+                position: neuron_positions[i],
+                excitatory: Self::is_excitatory(neuron_types[i]),
+                parvalbumin: neuron_types[i] == CorticalNeuronType::ParvalbuminInterneuron,
+            })
+            .collect();
+        let connectome = ConnectomeBuilder::with_seed(AnatomicalConnectivity::new_cortical(), rng.gen())
+            .with_gap_junctions(GapJunctionConnectivity::parvalbumin_interneurons())
+            .build(&sites);
+        let synaptic_network = connectome.to_network(rng.gen());
 
         // Create glial cells (approximately 1:1 ratio with neurons)
         let num_glial = num_neurons;
@@ -156,106 +188,38 @@ impl CorticalColumn {
             channel_states,
             neuron_types,
             neuron_layers,
+            neuron_positions,
             synaptic_network,
             astrocytes,
             oligodendrocytes,
             microglia,
             metabolism,
             spike_count: 0,
+            long_range_conductance: 0.0,
+            projection_spikes: 0,
+            drug_channel_modulation: ChannelModulation::default(),
             dt,
             time: 0.0,
         }
     }
 
-    /// Create layer-specific connections within the column
-    fn create_columnar_connections(
-        network: &mut SynapticNetwork,
-        neuron_types: &[CorticalNeuronType],
-        neuron_layers: &[LayerType],
-        rng: &mut impl Rng,
-    ) {
-        let num_neurons = neuron_types.len();
-
-        for pre_id in 0..num_neurons {
-            let pre_type = neuron_types[pre_id];
-            let pre_layer = neuron_layers[pre_id];
-
-            // Determine connection probability and targets based on layer
-            let targets = Self::get_connection_targets(pre_layer, neuron_layers);
-
-            for &post_id in &targets {
-                if pre_id == post_id {
-                    continue;
-                }
-
-                // Connection probability
-                let prob = Self::connection_probability(pre_type, neuron_types[post_id]);
-
-                if rng.gen::<f64>() < prob {
-                    // Determine synapse type
-                    let syn_type = if Self::is_excitatory(pre_type) {
-                        if rng.gen::<f64>() < 0.8 {
-                            SynapseType::AMPA
-                        } else {
-                            SynapseType::NMDA
-                        }
-                    } else {
-                        SynapseType::GABAA
-                    };
-
-                    // Random initial weight
-                    let weight = rng.gen_range(0.5..1.5);
-
-                    let synapse = Synapse::new(
-                        network.synapses.len(),
-                        pre_id,
-                        post_id,
-                        syn_type,
-                        weight,
-                    );
-
-                    network.add_synapse(synapse);
-                }
-            }
-        }
-    }
-
-    /// Get potential connection targets based on source layer
-    fn get_connection_targets(source_layer: LayerType, all_layers: &[LayerType]) -> Vec<usize> {
-        all_layers
-            .iter()
-            .enumerate()
-            .filter(|(_, &layer)| Self::layers_connect(source_layer, layer))
-            .map(|(idx, _)| idx)
-            .collect()
-    }
-
-    /// Determine if two layers have connections (simplified connectivity)
-    fn layers_connect(source: LayerType, target: LayerType) -> bool {
-        match (source, target) {
-            // L2/3 -> L5
-            (LayerType::Layer2_3, LayerType::Layer5) => true,
-            // L4 -> L2/3
-            (LayerType::Layer4, LayerType::Layer2_3) => true,
-            // L5 -> L6
-            (LayerType::Layer5, LayerType::Layer6) => true,
-            // L6 -> L4
-            (LayerType::Layer6, LayerType::Layer4) => true,
-            // Within-layer connections
-            _ if source == target => true,
-            _ => false,
-        }
+    /// Builder: give every neuron Hodgkin-Huxley excitability, integrated
+    /// with backward Euler (stable at these conductances for dt = 0.1 ms)
+    pub fn with_hodgkin_huxley(mut self) -> Self {
+        self.neurons = self
+            .neurons
+            .into_iter()
+            .map(|neuron| neuron.with_hodgkin_huxley().with_integrator(Integrator::BackwardEuler))
+            .collect();
+        self
     }
 
-    /// Connection probability between neuron types
-    fn connection_probability(pre: CorticalNeuronType, post: CorticalNeuronType) -> f64 {
-        // Simplified connection probabilities
-        match (Self::is_excitatory(pre), Self::is_excitatory(post)) {
-            (true, true) => 0.1,   // E->E
-            (true, false) => 0.2,  // E->I
-            (false, true) => 0.3,  // I->E
-            (false, false) => 0.1, // I->I
-        }
+    /// Soma position drawn uniformly within the column and the layer's depth band
+    fn sample_position(layer: LayerType, rng: &mut impl Rng) -> [f64; 3] {
+        let radius = COLUMN_RADIUS * rng.gen::<f64>().sqrt();
+        let angle = rng.gen_range(0.0..std::f64::consts::TAU);
+        let (top, bottom) = CorticalLayer::depth_range(layer);
+        [radius * angle.cos(), radius * angle.sin(), rng.gen_range(top..bottom)]
     }
 
     /// Check if neuron type is excitatory
@@ -269,6 +233,68 @@ impl CorticalColumn {
         )
     }
 
+    /// Check if neuron type sends long-range cortico-cortical projections
+    fn is_projection_neuron(neuron_type: CorticalNeuronType) -> bool {
+        matches!(
+            neuron_type,
+            CorticalNeuronType::PyramidalL2_3 | CorticalNeuronType::PyramidalL5
+        )
+    }
+
+    /// Check if neuron type is contacted by long-range afferents: the
+    /// projection neurons and the PV basket cells providing feedforward
+    /// inhibition
+    fn receives_long_range_input(neuron_type: CorticalNeuronType) -> bool {
+        Self::is_projection_neuron(neuron_type) || neuron_type == CorticalNeuronType::ParvalbuminInterneuron
+    }
+
+    /// Deliver long-range input arriving this step (conductance increment, nS)
+    pub fn receive_long_range_input(&mut self, conductance: f64) {
+        self.long_range_conductance += conductance;
+    }
+
+    /// Bathe the column in extracellular neuromodulators: receptor effects
+    /// apply to its synapses and to every neuron's membrane conductances
+    pub fn set_neuromodulators(&mut self, modulators: &NeuromodulatorState) {
+        self.synaptic_network.neuromodulators = Some(modulators.clone());
+        self.update_channel_modulation();
+    }
+
+    /// Set the drug action on every neuron's membrane conductances,
+    /// replacing any it already had
+    pub fn set_drug_channel_modulation(&mut self, modulation: ChannelModulation) {
+        self.drug_channel_modulation = modulation;
+        self.update_channel_modulation();
+    }
+
+    /// Neuromodulator and drug effects on the neurons' conductances
+    fn update_channel_modulation(&mut self) {
+        let channels = self
+            .synaptic_network
+            .neuromodulators
+            .as_ref()
+            .map(NeuromodulatorState::channel_modulation)
+            .unwrap_or_default()
+            .combine(&self.drug_channel_modulation);
+        for neuron in &mut self.neurons {
+            neuron.modulation = channels;
+        }
+    }
+
+    /// Current dipole moment of the column (nA·m, column coordinates with z
+    /// pointing into the cortex): sum of membrane current times position
+    pub fn current_dipole(&self) -> [f64; 3] {
+        let mut moment = [0.0; 3];
+        for source in crate::lfp::column_sources(self) {
+            let midpoint = source.midpoint();
+            for k in 0..3 {
+                // pA·µm -> nA·m
+                moment[k] += source.current * midpoint[k] * 1e-9;
+            }
+        }
+        moment
+    }
+
     /// Step the column simulation
     pub fn step(&mut self, external_input: &[f64]) -> Result<()> {
         // Apply external input
@@ -278,27 +304,45 @@ impl CorticalColumn {
             }
         }
 
+        // Gap junctions see the voltages at the start of the step
+        self.synaptic_network.electrical.couple(&mut self.neurons);
+
         // Update neurons
         let mut spikes = vec![false; self.neurons.len()];
+        self.projection_spikes = 0;
         for (i, neuron) in self.neurons.iter_mut().enumerate() {
             neuron.step(&mut self.channel_states[i]);
             spikes[i] = neuron.is_spiking;
 
             if spikes[i] {
                 self.spike_count += 1;
+                if Self::is_projection_neuron(self.neuron_types[i]) {
+                    self.projection_spikes += 1;
+                }
             }
         }
 
+        // Long-range AMPA conductance decays between deliveries
+        const TAU_LONG_RANGE: f64 = 2.0; // ms
+        self.long_range_conductance *= (-self.dt / TAU_LONG_RANGE).exp();
+
         // Update synapses
         self.synaptic_network.step(self.dt, &spikes, self.time);
 
-        // Calculate synaptic currents and inject into neurons
+        // Calculate synaptic currents and inject into neurons. `Synapse::current`
+        // is outward-positive while the neuron takes inward-positive input.
         for (post_id, neuron) in self.neurons.iter_mut().enumerate() {
             let incoming = self.synaptic_network.get_incoming_synapses(post_id);
-            let total_current: f64 = incoming
+            let mut total_current: f64 = -incoming
                 .iter()
                 .map(|syn| syn.current(neuron.get_soma_voltage()))
-                .sum();
+                .sum::<f64>();
+
+            // Cortico-cortical afferents terminate on L2/3 and L5 pyramids and
+            // on PV basket cells (inward AMPA current, E_rev = 0 mV)
+            if Self::receives_long_range_input(self.neuron_types[post_id]) {
+                total_current += self.long_range_conductance * (0.0 - neuron.get_soma_voltage());
+            }
 
             neuron.synaptic_current[0] = total_current;
         }
@@ -330,6 +374,11 @@ impl CorticalColumn {
         self.spike_count
     }
 
+    /// Action potentials detected during the last step (column-local neuron ids)
+    pub fn spike_events(&self) -> impl Iterator<Item = SpikeEvent> + '_ {
+        self.neurons.iter().filter_map(|neuron| neuron.last_step_spike())
+    }
+
     /// Get average voltage
     pub fn get_average_voltage(&self) -> f64 {
         self.neurons
@@ -343,6 +392,7 @@ impl CorticalColumn {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use synapses::{Synapse, SynapseType};
 
     #[test]
     fn test_column_creation() {
@@ -351,6 +401,112 @@ mod tests {
         assert!(column.synaptic_network.synapses.len() > 0);
     }
 
+    #[test]
+    fn test_seeded_columns_are_identical() {
+        let a = CorticalColumn::with_seed(0, 200, 0.1, 99);
+        let b = CorticalColumn::with_seed(0, 200, 0.1, 99);
+
+        assert_eq!(a.neuron_types, b.neuron_types);
+        assert_eq!(a.synaptic_network.synapses.len(), b.synaptic_network.synapses.len());
+        for (sa, sb) in a.synaptic_network.synapses.iter().zip(&b.synaptic_network.synapses) {
+            assert_eq!(sa.pre_neuron_id, sb.pre_neuron_id);
+            assert_eq!(sa.post_neuron_id, sb.post_neuron_id);
+            assert_eq!(sa.weight.to_bits(), sb.weight.to_bits());
+        }
+    }
+
+    #[test]
+    fn test_neuromodulators_reach_neurons_and_synapses() {
+        let ne = NeuromodulatorState { norepinephrine: 1.0, ..NeuromodulatorState::zero() };
+        let drive = |modulators: Option<&NeuromodulatorState>| {
+            let mut column = CorticalColumn::with_seed(0, 100, 0.1, 5);
+            if let Some(m) = modulators {
+                column.set_neuromodulators(m);
+            }
+            for _ in 0..200 {
+                column.step(&[5.0; 100]).unwrap();
+            }
+            column
+        };
+
+        let control = drive(None);
+        let modulated = drive(Some(&ne));
+        assert_eq!(modulated.synaptic_network.neuromodulators.as_ref(), Some(&ne));
+        assert!(modulated.neurons.iter().all(|n| n.modulation == ne.channel_modulation()));
+
+        // Closing K+ leak raises the input resistance of every cell
+        assert!(modulated.get_average_voltage() > control.get_average_voltage());
+    }
+
+    #[test]
+    fn test_drug_channel_modulation_combines_with_neuromodulators() {
+        let ne = NeuromodulatorState { norepinephrine: 1.0, ..NeuromodulatorState::zero() };
+        let drug = ChannelModulation { leak: 1.5, ..ChannelModulation::default() };
+        let mut column = CorticalColumn::with_seed(0, 50, 0.1, 5);
+        column.set_drug_channel_modulation(drug);
+        assert!(column.neurons.iter().all(|n| n.modulation == drug));
+
+        column.set_neuromodulators(&ne);
+        let expected = ne.channel_modulation().combine(&drug);
+        assert!(column.neurons.iter().all(|n| n.modulation == expected));
+
+        // Re-applying the drug every step does not compound
+        column.set_drug_channel_modulation(drug);
+        assert!(column.neurons.iter().all(|n| n.modulation == expected));
+    }
+
+    #[test]
+    fn test_wiring_follows_soma_positions() {
+        let column = CorticalColumn::with_seed(0, 200, 0.1, 2);
+        let anatomy = AnatomicalConnectivity::new_cortical();
+        let positions = &column.neuron_positions;
+        for synapse in &column.synaptic_network.synapses {
+            let (a, b) = (positions[synapse.pre_neuron_id], positions[synapse.post_neuron_id]);
+            let distance = (0..3).map(|i| (a[i] - b[i]).powi(2)).sum::<f64>().sqrt();
+            assert!((synapse.delay - anatomy.axonal_delay(distance)).abs() < 1e-12);
+
+            let inhibitory = !CorticalColumn::is_excitatory(column.neuron_types[synapse.pre_neuron_id]);
+            assert_eq!(synapse.synapse_type == SynapseType::GABAA, inhibitory);
+        }
+
+        for (position, &layer) in positions.iter().zip(&column.neuron_layers) {
+            let (top, bottom) = CorticalLayer::depth_range(layer);
+            assert!(position[0].hypot(position[1]) <= COLUMN_RADIUS);
+            assert!(position[2] >= top && position[2] < bottom);
+        }
+    }
+
+    #[test]
+    fn test_gap_junctions_pull_pv_cells_together() {
+        let column = CorticalColumn::with_seed(0, 300, 0.1, 23);
+        let junctions = column.synaptic_network.electrical.junctions.clone();
+        assert!(!junctions.is_empty());
+        for gap in &junctions {
+            assert_eq!(column.neuron_types[gap.neuron_a], CorticalNeuronType::ParvalbuminInterneuron);
+            assert_eq!(column.neuron_types[gap.neuron_b], CorticalNeuronType::ParvalbuminInterneuron);
+        }
+
+        // Uneven drive: coupled PV pairs end up closer in voltage than the
+        // same cells with their junctions removed
+        let input: Vec<f64> = (0..300).map(|i| (i % 7) as f64 * 3.0).collect();
+        let mismatch = |mut column: CorticalColumn| {
+            for _ in 0..100 {
+                column.step(&input).unwrap();
+            }
+            junctions
+                .iter()
+                .map(|gap| {
+                    let v = |n: usize| column.neurons[n].get_soma_voltage();
+                    (v(gap.neuron_a) - v(gap.neuron_b)).abs()
+                })
+                .sum::<f64>()
+        };
+
+        let mut uncoupled = column.clone();
+        uncoupled.synaptic_network.electrical.junctions.clear();
+        assert!(mismatch(column) < 0.8 * mismatch(uncoupled));
+    }
+
     #[test]
     fn test_column_simulation() {
         let mut column = CorticalColumn::new(0, 100, 0.1);
@@ -359,4 +515,68 @@ mod tests {
         column.step(&input).unwrap();
         assert!(column.time > 0.0);
     }
+
+    #[test]
+    fn test_spikes_are_counted_per_action_potential() {
+        let mut column = CorticalColumn::with_seed(0, 100, 0.1, 5);
+        let input = vec![50.0; 100];
+
+        let mut events = 0;
+        for _ in 0..200 {
+            column.step(&input).unwrap();
+            for event in column.spike_events() {
+                assert!(event.time > column.time - 2.0 * column.dt && event.time <= column.time);
+                events += 1;
+            }
+        }
+        assert!(events > 0);
+        assert_eq!(column.spike_count, events);
+        assert_eq!(column.spike_count, column.neurons.iter().map(|n| n.spike_events().count()).sum::<usize>());
+    }
+
+    #[test]
+    fn test_long_range_input_drives_pyramids_and_pv_cells() {
+        let mut column = CorticalColumn::with_seed(0, 60, 0.1, 5).with_hodgkin_huxley();
+        let input = vec![0.0; 60];
+
+        let mut spikes = vec![0; 60];
+        for step in 0..1000 {
+            if step % 10 == 0 {
+                column.receive_long_range_input(5.0);
+            }
+            column.step(&input).unwrap();
+            for (count, neuron) in spikes.iter_mut().zip(&column.neurons) {
+                *count += neuron.is_spiking as usize;
+            }
+        }
+
+        let fired = |neuron_type: CorticalNeuronType| {
+            spikes.iter().zip(&column.neuron_types).any(|(&count, &t)| t == neuron_type && count > 0)
+        };
+        assert!(fired(CorticalNeuronType::PyramidalL5));
+        assert!(fired(CorticalNeuronType::ParvalbuminInterneuron));
+        assert!(column.neurons.iter().all(|n| n.get_soma_voltage().abs() < 200.0));
+    }
+
+    #[test]
+    fn test_ampa_synapse_depolarizes_its_target() {
+        let target_voltage = |conductance: f64| {
+            let mut column = CorticalColumn::with_seed(0, 10, 0.1, 0);
+            let mut network = SynapticNetwork::with_seed(10, 0);
+            network.add_synapse(Synapse::new(0, 0, 1, SynapseType::AMPA, 1.0));
+            // The synapse gets no events, so it holds this conductance
+            network.synapses[0].conductance = conductance;
+            column.synaptic_network = network;
+
+            let input = vec![0.0; 10];
+            for _ in 0..50 {
+                column.step(&input).unwrap();
+            }
+            column.neurons[1].get_soma_voltage()
+        };
+
+        let rest = target_voltage(0.0);
+        let driven = target_voltage(0.05);
+        assert!(driven > rest + 1.0, "rest {rest} mV, driven {driven} mV");
+    }
 }
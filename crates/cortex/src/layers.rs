@@ -12,6 +12,18 @@ pub enum LayerType {
     Layer6,    // Multiform layer (cortico-thalamic feedback)
 }
 
+impl From<LayerType> for connectivity::CorticalLayer {
+    fn from(layer: LayerType) -> Self {
+        match layer {
+            LayerType::Layer1 => Self::Layer1,
+            LayerType::Layer2_3 => Self::Layer23,
+            LayerType::Layer4 => Self::Layer4,
+            LayerType::Layer5 => Self::Layer5,
+            LayerType::Layer6 => Self::Layer6,
+        }
+    }
+}
+
 /// Properties of each cortical layer
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct CorticalLayer {
@@ -58,6 +70,29 @@ impl CorticalLayer {
         }
     }
 
+    /// Depth of the layer's upper and lower boundary below the pia (um)
+    pub fn depth_range(layer_type: LayerType) -> (f64, f64) {
+        let order = [
+            LayerType::Layer1,
+            LayerType::Layer2_3,
+            LayerType::Layer4,
+            LayerType::Layer5,
+            LayerType::Layer6,
+        ];
+        let top: f64 = order
+            .iter()
+            .take_while(|&&layer| layer != layer_type)
+            .map(|&layer| Self::properties(layer).thickness)
+            .sum();
+        (top, top + Self::properties(layer_type).thickness)
+    }
+
+    /// Depth of the layer's midpoint below the pia (um)
+    pub fn midpoint_depth(layer_type: LayerType) -> f64 {
+        let (top, bottom) = Self::depth_range(layer_type);
+        (top + bottom) / 2.0
+    }
+
     /// Calculate number of neurons in this layer for a given surface area
     pub fn neuron_count(&self, surface_area_mm2: f64) -> usize {
         let volume_mm3 = surface_area_mm2 * (self.thickness / 1000.0);
@@ -77,6 +112,13 @@ mod tests {
         assert!(layer4.neuron_density > 0.0);
     }
 
+    #[test]
+    fn test_midpoint_depth() {
+        assert_eq!(CorticalLayer::midpoint_depth(LayerType::Layer1), 82.5);
+        // 165 + 450 + 200 um above, half of L5 below
+        assert_eq!(CorticalLayer::midpoint_depth(LayerType::Layer5), 1090.0);
+    }
+
     #[test]
     fn test_neuron_count() {
         let layer = CorticalLayer::properties(LayerType::Layer4);
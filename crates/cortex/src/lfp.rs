@@ -0,0 +1,270 @@
+//! Extracellular potentials computed from compartmental membrane currents.
+//!
+//! - **LFP**: every neuron compartment is a line source carrying its
+//!   transmembrane current spread uniformly along its length, in an infinite
+//!   homogeneous medium (Holt & Koch 1999, the LFPy formulation); somata and
+//!   unplaced compartments are point sources.
+//! - **EEG**: far from the column the same sources reduce to a current dipole
+//!   p = sum(I * r). Each column's dipole is taken radial to the scalp (the
+//!   tangential moments of a column's disc of neurons average out) and its
+//!   potential is evaluated at scalp electrodes in a homogeneous head. Skull
+//!   attenuation is ignored, so absolute amplitudes are an upper bound; the
+//!   model is meant for relative changes such as band power under drugs.
+//!
+//! Positions are in column coordinates (µm): x, y across the column, z depth
+//! below the pia. Potentials are in µV; traces sampled every step can be
+//! passed to `analysis::welch_psd` for band power.
+
+use crate::column::CorticalColumn;
+use serde::{Deserialize, Serialize};
+use std::f64::consts::PI;
+
+/// Conductivity of cortical grey matter (S/m)
+pub const EXTRACELLULAR_CONDUCTIVITY: f64 = 0.3;
+
+/// Effective conductivity of the head for scalp EEG (S/m)
+pub const HEAD_CONDUCTIVITY: f64 = 0.33;
+
+/// Closest approach to a source (µm), keeping potentials finite at the membrane
+const MIN_DISTANCE: f64 = 1.0;
+
+/// Transmembrane current of one compartment and the segment it flows through
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub(crate) struct CurrentSource {
+    /// Outward current (pA)
+    pub current: f64,
+    pub start: [f64; 3],
+    pub end: [f64; 3],
+    pub radius: f64,
+}
+
+impl CurrentSource {
+    pub fn midpoint(&self) -> [f64; 3] {
+        [0, 1, 2].map(|k| 0.5 * (self.start[k] + self.end[k]))
+    }
+
+    /// Potential (µV) at `electrode` in a medium of `conductivity` S/m
+    pub fn potential(&self, electrode: [f64; 3], conductivity: f64) -> f64 {
+        let min_distance = self.radius.max(MIN_DISTANCE);
+        if self.start == self.end {
+            point_source_potential(self.current, self.start, electrode, conductivity, min_distance)
+        } else {
+            line_source_potential(self.current, self.start, self.end, electrode, conductivity, min_distance)
+        }
+    }
+}
+
+/// Potential (µV) at `electrode` of `current` pA leaving a point at `source`
+/// (positions in µm, conductivity in S/m)
+pub fn point_source_potential(
+    current: f64,
+    source: [f64; 3],
+    electrode: [f64; 3],
+    conductivity: f64,
+    min_distance: f64,
+) -> f64 {
+    let distance = norm(sub(electrode, source)).max(min_distance);
+    current / (4.0 * PI * conductivity * distance)
+}
+
+/// Potential (µV) at `electrode` of `current` pA leaving uniformly along the
+/// segment from `start` to `end` (positions in µm, conductivity in S/m):
+/// I / (4 pi sigma L) * [asinh((L - x) / r) + asinh(x / r)], with x the
+/// electrode's coordinate along the segment and r its distance from the axis
+pub fn line_source_potential(
+    current: f64,
+    start: [f64; 3],
+    end: [f64; 3],
+    electrode: [f64; 3],
+    conductivity: f64,
+    min_distance: f64,
+) -> f64 {
+    let axis = sub(end, start);
+    let length = norm(axis);
+    let offset = sub(electrode, start);
+
+    let x = dot(offset, axis) / length;
+    let r = (dot(offset, offset) - x * x).max(0.0).sqrt().max(min_distance);
+    current / (4.0 * PI * conductivity * length) * (((length - x) / r).asinh() + (x / r).asinh())
+}
+
+/// Sources of every compartment in `column`, in column coordinates
+pub(crate) fn column_sources(column: &CorticalColumn) -> Vec<CurrentSource> {
+    let mut sources = Vec::new();
+    for (neuron, soma) in column.neurons.iter().zip(&column.neuron_positions) {
+        // Neuron-local +z points towards the pia, i.e. up in depth
+        let place = |p: [f64; 3]| [soma[0] + p[0], soma[1] + p[1], soma[2] - p[2]];
+
+        for (comp, current) in neuron.compartments.iter().zip(neuron.membrane_currents()) {
+            if current == 0.0 {
+                continue;
+            }
+
+            let center = place(comp.position);
+            let parent = comp.parent_idx.map(|p| place(neuron.compartments[p].position));
+            let (start, end) = match parent.map(|p| sub(center, p)).filter(|d| norm(*d) > 0.0) {
+                Some(direction) => {
+                    let half = comp.length / 2.0 / norm(direction);
+                    (
+                        [0, 1, 2].map(|k| center[k] - half * direction[k]),
+                        [0, 1, 2].map(|k| center[k] + half * direction[k]),
+                    )
+                }
+                None => (center, center),
+            };
+            sources.push(CurrentSource { current, start, end, radius: comp.diameter / 2.0 });
+        }
+    }
+    sources
+}
+
+/// Virtual extracellular electrodes inside a column
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct LfpElectrodes {
+    /// Contact positions in column coordinates (µm)
+    pub positions: Vec<[f64; 3]>,
+
+    /// Extracellular conductivity (S/m)
+    pub conductivity: f64,
+}
+
+impl LfpElectrodes {
+    /// Electrodes at the given positions in grey matter
+    pub fn new(positions: Vec<[f64; 3]>) -> Self {
+        Self { positions, conductivity: EXTRACELLULAR_CONDUCTIVITY }
+    }
+
+    /// Laminar probe down the column axis: `contacts` contacts `spacing` µm
+    /// apart, the first at `spacing` below the pia
+    pub fn laminar(contacts: usize, spacing: f64) -> Self {
+        Self::new((1..=contacts).map(|i| [0.0, 0.0, i as f64 * spacing]).collect())
+    }
+
+    /// Builder: set the extracellular conductivity (S/m)
+    pub fn with_conductivity(mut self, conductivity: f64) -> Self {
+        self.conductivity = conductivity;
+        self
+    }
+
+    /// Potential at every contact from the column's present currents (µV)
+    pub fn record(&self, column: &CorticalColumn) -> Vec<f64> {
+        let sources = column_sources(column);
+        self.positions
+            .iter()
+            .map(|&electrode| sources.iter().map(|s| s.potential(electrode, self.conductivity)).sum())
+            .collect()
+    }
+}
+
+/// Equivalent current dipole of a column placed in the head
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct CurrentDipole {
+    /// Dipole moment (nA·m)
+    pub moment: [f64; 3],
+
+    /// Location in head coordinates (mm)
+    pub location: [f64; 3],
+}
+
+impl CurrentDipole {
+    /// Dipole of `column` at `location` (mm), its axis along the outward unit
+    /// normal `orientation` of the cortical surface
+    pub fn from_column(column: &CorticalColumn, location: [f64; 3], orientation: [f64; 3]) -> Self {
+        // The column's z axis points into the cortex
+        let radial = -column.current_dipole()[2];
+        Self { moment: orientation.map(|n| n * radial), location }
+    }
+}
+
+/// Scalp electrodes over a homogeneous head
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ScalpEeg {
+    /// Electrode positions in head coordinates (mm)
+    pub electrodes: Vec<[f64; 3]>,
+
+    /// Head conductivity (S/m)
+    pub conductivity: f64,
+}
+
+impl ScalpEeg {
+    /// Electrodes at the given head positions
+    pub fn new(electrodes: Vec<[f64; 3]>) -> Self {
+        Self { electrodes, conductivity: HEAD_CONDUCTIVITY }
+    }
+
+    /// Potential at every electrode from a set of dipoles (µV)
+    pub fn record(&self, dipoles: &[CurrentDipole]) -> Vec<f64> {
+        self.electrodes
+            .iter()
+            .map(|&electrode| {
+                dipoles
+                    .iter()
+                    .map(|dipole| {
+                        let offset = sub(electrode, dipole.location);
+                        let distance = norm(offset);
+                        // nA·m / (S/m · mm^2) = 1e3 µV
+                        1e3 * dot(dipole.moment, offset) / (4.0 * PI * self.conductivity * distance.powi(3))
+                    })
+                    .sum()
+            })
+            .collect()
+    }
+}
+
+fn sub(a: [f64; 3], b: [f64; 3]) -> [f64; 3] {
+    [a[0] - b[0], a[1] - b[1], a[2] - b[2]]
+}
+
+fn dot(a: [f64; 3], b: [f64; 3]) -> f64 {
+    a[0] * b[0] + a[1] * b[1] + a[2] * b[2]
+}
+
+fn norm(a: [f64; 3]) -> f64 {
+    dot(a, a).sqrt()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_line_source_matches_point_source_far_away() {
+        let (start, end) = ([0.0, 0.0, 0.0], [0.0, 0.0, 20.0]);
+        for electrode in [[500.0, 0.0, 10.0], [0.0, 0.0, 800.0], [300.0, 300.0, -400.0]] {
+            let line = line_source_potential(100.0, start, end, electrode, 0.3, 1.0);
+            let point = point_source_potential(100.0, [0.0, 0.0, 10.0], electrode, 0.3, 1.0);
+            assert!((line / point - 1.0).abs() < 1e-3, "{line} vs {point}");
+        }
+
+        // Finite on the axis and at the membrane, and symmetric about the midpoint
+        let on_axis = line_source_potential(100.0, start, end, [0.0, 0.0, 5.0], 0.3, 1.0);
+        let mirrored = line_source_potential(100.0, start, end, [0.0, 0.0, 15.0], 0.3, 1.0);
+        assert!(on_axis.is_finite() && (on_axis - mirrored).abs() < 1e-9);
+    }
+
+    #[test]
+    fn test_apical_input_gives_surface_negative_eeg() {
+        // Excitation of the apical tufts is a sink near the pia whose return
+        // current leaves around the soma: the dipole points into the cortex
+        let mut column = CorticalColumn::with_seed(0, 100, 0.1, 3);
+        for neuron in &mut column.neurons {
+            if neuron.compartments.len() > 100 {
+                neuron.inject_current(90, 20.0);
+            }
+        }
+        for _ in 0..20 {
+            column.step(&[]).unwrap();
+        }
+
+        let dipole = column.current_dipole();
+        assert!(dipole[2] > 0.0, "{dipole:?}");
+
+        let lfp = LfpElectrodes::laminar(16, 100.0).record(&column);
+        assert!(lfp.iter().all(|v| v.is_finite()) && lfp.iter().any(|&v| v != 0.0));
+
+        // Negative over the column, fading with distance
+        let source = CurrentDipole::from_column(&column, [0.0, 0.0, 80.0], [0.0, 0.0, 1.0]);
+        let eeg = ScalpEeg::new(vec![[0.0, 0.0, 90.0], [30.0, 0.0, 86.0]]).record(&[source]);
+        assert!(eeg[0] < 0.0 && eeg[0].abs() > eeg[1].abs());
+    }
+}
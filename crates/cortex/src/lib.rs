@@ -9,12 +9,17 @@
 
 pub mod column;
 pub mod layers;
+pub mod lfp;
+pub mod projections;
 
 use ndarray::Array2;
 use neurons::MultiCompartmentalNeuron;
-use synapses::{Synapse, SynapticNetwork, SynapseType};
+use synapses::{NeuromodulatorState, Synapse, SynapticNetwork, SynapseType};
 use glia::{Astrocyte, Oligodendrocyte, Microglia};
 use metabolism::RegionalMetabolism;
+use connectivity::AnatomicalConnectivity;
+use rand::{Rng, SeedableRng};
+use rand_chacha::ChaCha8Rng;
 use serde::{Deserialize, Serialize};
 use thiserror::Error;
 
@@ -31,6 +36,7 @@ pub type Result<T> = std::result::Result<T, CortexError>;
 
 pub use column::CorticalColumn;
 pub use layers::{CorticalLayer, LayerType};
+pub use projections::{LongRangeConnection, SpikeExchange};
 
 /// Cortical neuron types
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
@@ -45,13 +51,22 @@ pub enum CorticalNeuronType {
 }
 
 /// Complete neocortex model
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct Neocortex {
     /// Cortical columns
     pub columns: Vec<CorticalColumn>,
 
     /// Long-range connections between columns
-    pub long_range_connections: Vec<(usize, usize, f64)>, // (source_col, target_col, weight)
+    pub long_range_connections: Vec<LongRangeConnection>,
+
+    /// Delayed spike exchange feeding the long-range connections
+    pub spike_exchange: SpikeExchange,
+
+    /// Anatomical connectivity rules (axonal delays)
+    pub connectivity: AnatomicalConnectivity,
+
+    /// Distance between neighbouring column centres (µm)
+    pub column_spacing: f64,
 
     /// Total number of neurons
     pub total_neurons: usize,
@@ -66,10 +81,18 @@ pub struct Neocortex {
 impl Neocortex {
     /// Create a neocortex with specified number of columns
     pub fn new(num_columns: usize, neurons_per_column: usize, dt: f64) -> Self {
+        Self::with_seed(num_columns, neurons_per_column, dt, rand::random())
+    }
+
+    /// Create a reproducible neocortex
+    ///
+    /// Each column gets its own ChaCha stream derived from `seed` and the
+    /// column index, so results do not depend on rayon's thread scheduling.
+    pub fn with_seed(num_columns: usize, neurons_per_column: usize, dt: f64, seed: u64) -> Self {
         let mut columns = Vec::with_capacity(num_columns);
 
         for i in 0..num_columns {
-            columns.push(CorticalColumn::new(i, neurons_per_column, dt));
+            columns.push(CorticalColumn::with_seed(i, neurons_per_column, dt, Self::column_seed(seed, i)));
         }
 
         let total_neurons = num_columns * neurons_per_column;
@@ -77,37 +100,96 @@ impl Neocortex {
         Self {
             columns,
             long_range_connections: Vec::new(),
+            spike_exchange: SpikeExchange::new(num_columns, 1),
+            connectivity: AnatomicalConnectivity::new_cortical(),
+            column_spacing: 500.0, // ~0.5 mm minicolumn bundle diameter
             total_neurons,
             time: 0.0,
             dt,
         }
     }
 
+    /// Seed for column `idx`, drawn from stream `idx` of the cortex seed
+    pub fn column_seed(seed: u64, idx: usize) -> u64 {
+        let mut rng = ChaCha8Rng::seed_from_u64(seed);
+        rng.set_stream(idx as u64);
+        rng.gen()
+    }
+
+    /// Position of a column centre on the cortical sheet (µm)
+    ///
+    /// Columns are tiled on a square grid with `column_spacing` between centres.
+    pub fn column_position(&self, idx: usize) -> [f64; 2] {
+        let side = (self.columns.len() as f64).sqrt().ceil().max(1.0) as usize;
+        [
+            (idx % side) as f64 * self.column_spacing,
+            (idx / side) as f64 * self.column_spacing,
+        ]
+    }
+
+    /// Distance between two column centres (µm)
+    pub fn column_distance(&self, source: usize, target: usize) -> f64 {
+        let a = self.column_position(source);
+        let b = self.column_position(target);
+        ((a[0] - b[0]).powi(2) + (a[1] - b[1]).powi(2)).sqrt()
+    }
+
     /// Add long-range connection between columns
+    ///
+    /// The conduction delay follows from the distance between the columns
+    /// (`AnatomicalConnectivity::axonal_delay`).
     pub fn connect_columns(&mut self, source: usize, target: usize, weight: f64) {
         if source < self.columns.len() && target < self.columns.len() {
-            self.long_range_connections.push((source, target, weight));
+            let delay = self.connectivity.axonal_delay(self.column_distance(source, target));
+            self.connect_columns_with_delay(source, target, weight, delay);
+        }
+    }
+
+    /// Add long-range connection between columns with an explicit delay (ms)
+    pub fn connect_columns_with_delay(&mut self, source: usize, target: usize, weight: f64, delay: f64) {
+        if source < self.columns.len() && target < self.columns.len() {
+            let connection = LongRangeConnection::new(source, target, weight, delay, self.dt);
+            self.spike_exchange.ensure_capacity(connection.delay_steps);
+            self.long_range_connections.push(connection);
+        }
+    }
+
+    /// Apply the same extracellular neuromodulators to every column
+    pub fn set_neuromodulators(&mut self, modulators: &NeuromodulatorState) {
+        for column in &mut self.columns {
+            column.set_neuromodulators(modulators);
         }
     }
 
     /// Step the simulation forward
     pub fn step(&mut self, external_input: &Array2<f64>) -> Result<()> {
-        // Update each column in parallel
         use rayon::prelude::*;
 
-        self.columns.par_iter_mut().enumerate().for_each(|(i, column)| {
-            // Extract input for this column
-            let col_input = if i < external_input.ncols() {
-                external_input.column(i).to_owned()
-            } else {
-                ndarray::Array1::zeros(column.neurons.len())
-            };
+        // Read side: long-range input due this step, from committed history
+        let long_range_input = self.spike_exchange.gather(&self.long_range_connections);
+
+        // Update each column in parallel; write side is a fresh output buffer
+        let mut emitted = vec![0; self.columns.len()];
+
+        self.columns
+            .par_iter_mut()
+            .zip(emitted.par_iter_mut())
+            .enumerate()
+            .for_each(|(i, (column, out))| {
+                // Extract input for this column
+                let col_input = if i < external_input.ncols() {
+                    external_input.column(i).to_owned()
+                } else {
+                    ndarray::Array1::zeros(column.neurons.len())
+                };
 
-            column.step(col_input.as_slice().unwrap()).ok();
-        });
+                column.receive_long_range_input(long_range_input[i]);
+                column.step(col_input.as_slice().unwrap()).ok();
+                *out = column.projection_spikes;
+            });
 
-        // Process long-range connections
-        // This would pass activity from source columns to target columns
+        // Commit L2/3 and L5 projection spikes once every column has stepped
+        self.spike_exchange.commit(emitted);
 
         self.time += self.dt;
         Ok(())
@@ -142,6 +224,46 @@ mod tests {
         assert_eq!(cortex.total_neurons, 1000);
     }
 
+    #[test]
+    fn test_connect_columns_uses_axonal_delay() {
+        let mut cortex = Neocortex::new(4, 20, 0.1);
+        cortex.connect_columns(0, 3, 1.0);
+
+        let conn = &cortex.long_range_connections[0];
+        let expected = cortex.connectivity.axonal_delay(cortex.column_distance(0, 3));
+        assert!((conn.delay - expected).abs() < 1e-12);
+        assert!(conn.delay_steps >= 1);
+        assert!(cortex.spike_exchange.max_delay_steps() >= conn.delay_steps);
+    }
+
+    #[test]
+    fn test_long_range_input_reaches_target_column() {
+        let dt = 0.1;
+        let mut cortex = Neocortex::new(2, 40, dt);
+        cortex.connect_columns_with_delay(0, 1, 2.0, 1.0);
+
+        // Drive only column 0
+        let mut input = Array2::zeros((40, 2));
+        input.column_mut(0).fill(500.0);
+
+        let mut first_arrival = None;
+        for step in 0..60 {
+            let before = cortex.columns[1].long_range_conductance;
+            cortex.step(&input).unwrap();
+            if first_arrival.is_none() && cortex.columns[1].long_range_conductance > before {
+                first_arrival = Some(step);
+            }
+        }
+
+        let source_fired = cortex.columns[0].get_spike_count() > 0;
+        assert!(source_fired);
+
+        // Nothing can arrive before the 1 ms (10 step) conduction delay
+        let arrival = first_arrival.expect("long-range input never arrived");
+        assert!(arrival >= 10);
+        assert!(cortex.columns[0].long_range_conductance == 0.0);
+    }
+
     #[test]
     fn test_cortex_simulation() {
         let mut cortex = Neocortex::new(5, 50, 0.1);
@@ -153,3 +275,4 @@ mod tests {
 }
 pub mod oscillations;
 pub use oscillations::{OscillationBand, BrainOscillations};
+pub use lfp::{CurrentDipole, LfpElectrodes, ScalpEeg};
@@ -0,0 +1,174 @@
+//! Long-range cortico-cortical projections between columns.
+//!
+//! Supragranular (L2/3) and infragranular (L5) pyramidal neurons send axons to
+//! other cortical columns. Each projection carries the spikes of its source
+//! column to the L2/3 and L5 pyramids of the target column after an axonal
+//! conduction delay.
+//!
+//! Columns are stepped in parallel, so spikes are exchanged through a
+//! double-buffered ring: during a step every column reads its delayed input
+//! from the committed history (read-only) and writes its projection output to a
+//! fresh buffer, which is committed once all columns have finished.
+
+use serde::{Deserialize, Serialize};
+
+/// A long-range projection from one cortical column to another
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct LongRangeConnection {
+    /// Source column index
+    pub source: usize,
+
+    /// Target column index
+    pub target: usize,
+
+    /// Conductance increment per projecting spike (nS)
+    pub weight: f64,
+
+    /// Axonal conduction delay (ms)
+    pub delay: f64,
+
+    /// Delay expressed in simulation steps (>= 1)
+    pub delay_steps: usize,
+}
+
+impl LongRangeConnection {
+    /// Create a projection, converting the delay to whole simulation steps
+    pub fn new(source: usize, target: usize, weight: f64, delay: f64, dt: f64) -> Self {
+        // Spikes emitted in step n are delivered no earlier than step n + 1
+        let delay_steps = ((delay / dt).round() as usize).max(1);
+
+        Self {
+            source,
+            target,
+            weight,
+            delay,
+            delay_steps,
+        }
+    }
+}
+
+/// Double-buffered, delay-aware spike exchange between columns
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SpikeExchange {
+    /// Ring of committed projection spike counts: slot -> per-column counts
+    history: Vec<Vec<usize>>,
+
+    /// Slot holding the most recently committed step
+    head: usize,
+
+    /// Number of columns exchanging spikes
+    num_columns: usize,
+}
+
+impl SpikeExchange {
+    /// Create an exchange for `num_columns` columns able to hold `max_delay_steps`
+    pub fn new(num_columns: usize, max_delay_steps: usize) -> Self {
+        let slots = max_delay_steps.max(1);
+        Self {
+            history: vec![vec![0; num_columns]; slots],
+            head: 0,
+            num_columns,
+        }
+    }
+
+    /// Longest delay (in steps) the ring can currently represent
+    pub fn max_delay_steps(&self) -> usize {
+        self.history.len()
+    }
+
+    /// Grow the ring so that delays of up to `delay_steps` can be delivered
+    pub fn ensure_capacity(&mut self, delay_steps: usize) {
+        let slots = self.history.len();
+        if delay_steps <= slots {
+            return;
+        }
+
+        // Unroll the ring so the oldest committed step comes first, then pad
+        // with silent (older) steps at the front.
+        let mut unrolled = Vec::with_capacity(delay_steps);
+        unrolled.resize(delay_steps - slots, vec![0; self.num_columns]);
+        for k in 1..=slots {
+            unrolled.push(self.history[(self.head + k) % slots].clone());
+        }
+
+        self.history = unrolled;
+        self.head = delay_steps - 1;
+    }
+
+    /// Projection spikes emitted `delay_steps` steps before the upcoming step
+    pub fn delayed(&self, column: usize, delay_steps: usize) -> usize {
+        let slots = self.history.len();
+        debug_assert!(delay_steps >= 1 && delay_steps <= slots);
+
+        // delay 1 = most recently committed step
+        let slot = (self.head + slots + 1 - delay_steps) % slots;
+        self.history[slot][column]
+    }
+
+    /// Sum delayed inputs into a per-column conductance increment (nS)
+    pub fn gather(&self, connections: &[LongRangeConnection]) -> Vec<f64> {
+        let mut input = vec![0.0; self.num_columns];
+        for conn in connections {
+            let spikes = self.delayed(conn.source, conn.delay_steps);
+            if spikes > 0 {
+                input[conn.target] += conn.weight * spikes as f64;
+            }
+        }
+        input
+    }
+
+    /// Commit the projection spikes emitted during the step that just finished
+    pub fn commit(&mut self, emitted: Vec<usize>) {
+        debug_assert_eq!(emitted.len(), self.num_columns);
+        self.head = (self.head + 1) % self.history.len();
+        self.history[self.head] = emitted;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_delay_steps_at_least_one() {
+        let conn = LongRangeConnection::new(0, 1, 1.0, 0.01, 0.1);
+        assert_eq!(conn.delay_steps, 1);
+
+        let conn = LongRangeConnection::new(0, 1, 1.0, 2.0, 0.1);
+        assert_eq!(conn.delay_steps, 20);
+    }
+
+    #[test]
+    fn test_spikes_arrive_after_delay() {
+        let conn = LongRangeConnection::new(0, 1, 0.5, 0.3, 0.1);
+        let mut exchange = SpikeExchange::new(2, conn.delay_steps);
+        let connections = vec![conn];
+
+        // Column 0 emits 4 projection spikes once, then stays silent
+        exchange.commit(vec![4, 0]);
+        assert_eq!(exchange.gather(&connections), vec![0.0, 0.0]);
+
+        exchange.commit(vec![0, 0]);
+        assert_eq!(exchange.gather(&connections), vec![0.0, 0.0]);
+
+        exchange.commit(vec![0, 0]);
+        assert_eq!(exchange.gather(&connections), vec![0.0, 2.0]);
+
+        exchange.commit(vec![0, 0]);
+        assert_eq!(exchange.gather(&connections), vec![0.0, 0.0]);
+    }
+
+    #[test]
+    fn test_growing_ring_preserves_history() {
+        let mut exchange = SpikeExchange::new(1, 2);
+        exchange.commit(vec![1]);
+        exchange.commit(vec![2]);
+        exchange.commit(vec![3]);
+
+        exchange.ensure_capacity(4);
+        assert_eq!(exchange.max_delay_steps(), 4);
+        assert_eq!(exchange.delayed(0, 1), 3);
+        assert_eq!(exchange.delayed(0, 2), 2);
+        assert_eq!(exchange.delayed(0, 4), 0);
+    }
+}
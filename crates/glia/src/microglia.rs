@@ -2,6 +2,7 @@
 //!
 //! Microglia are the brain's immune cells and also prune synapses.
 
+use rand::Rng;
 use serde::{Deserialize, Serialize};
 
 /// Microglia activation state
@@ -86,9 +87,9 @@ impl Microglia {
     }
 
     /// Check if synapse should be pruned
-    pub fn should_prune_synapse(&self, synapse_activity: f64) -> bool {
+    pub fn should_prune_synapse<R: Rng + ?Sized>(&self, synapse_activity: f64, rng: &mut R) -> bool {
         matches!(self.state, MicrogliaState::Resting)
             && synapse_activity < 0.05
-            && rand::random::<f64>() < 0.001
+            && rng.gen::<f64>() < 0.001
     }
 }
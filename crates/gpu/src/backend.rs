@@ -0,0 +1,342 @@
+//! Runtime selection between the wgpu kernels and their CPU ports.
+//!
+//! Both simulators are exposed through [`SimulationBackend`], which works on
+//! the shader's own `#[repr(C)]` state layouts. [`point_neuron_backend`] and
+//! [`cable_backend`] pick the implementation at runtime: with
+//! [`BackendPreference::Auto`] a machine without a usable adapter (headless
+//! CI, laptops) transparently falls back to the CPU.
+//!
+//! ```no_run
+//! use gpu::backend::{cable_backend, BackendPreference};
+//!
+//! let mut sim = cable_backend(10, 0.01, BackendPreference::from_env()).unwrap();
+//! sim.initialize();
+//! let mut currents = vec![0.0; 10 * 152];
+//! currents[0] = 500.0;
+//! sim.set_currents(&currents);
+//! for _ in 0..1000 {
+//!     sim.step();
+//! }
+//! println!("{:?} soma: {} mV", sim.kind(), sim.voltages()[0]);
+//! ```
+
+use crate::cable_simulator::{CableSimulator, CompartmentState};
+use crate::cpu::{CpuCableSimulator, CpuSimulator};
+use crate::{GpuNeuronState, GpuSimulator};
+use anyhow::{anyhow, Result};
+use bytemuck::Pod;
+use std::str::FromStr;
+
+/// Environment variable read by [`BackendPreference::from_env`]
+pub const BACKEND_ENV_VAR: &str = "HUMANBRAIN_BACKEND";
+
+/// Where a simulator actually runs
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum BackendKind {
+    Gpu,
+    Cpu,
+}
+
+/// Requested backend
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum BackendPreference {
+    /// GPU when an adapter is available, otherwise CPU
+    #[default]
+    Auto,
+    /// GPU only; construction fails without an adapter
+    Gpu,
+    /// CPU only
+    Cpu,
+}
+
+impl BackendPreference {
+    /// Read the preference from `HUMANBRAIN_BACKEND` (`auto`, `gpu`, `cpu`),
+    /// defaulting to `Auto` when unset or unrecognised
+    pub fn from_env() -> Self {
+        std::env::var(BACKEND_ENV_VAR)
+            .ok()
+            .and_then(|value| value.parse().ok())
+            .unwrap_or_default()
+    }
+}
+
+impl FromStr for BackendPreference {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> Result<Self> {
+        match s.trim().to_ascii_lowercase().as_str() {
+            "auto" => Ok(Self::Auto),
+            "gpu" => Ok(Self::Gpu),
+            "cpu" => Ok(Self::Cpu),
+            other => Err(anyhow!("Unknown backend '{other}' (expected auto, gpu or cpu)")),
+        }
+    }
+}
+
+/// A simulator stepping a buffer of shader states, on the GPU or the CPU
+pub trait SimulationBackend {
+    /// Per-element state, laid out exactly as in the WGSL kernel
+    type State: Pod;
+
+    fn kind(&self) -> BackendKind;
+
+    /// Run the kernel's initialization pass, if it has one
+    fn initialize(&mut self) {}
+
+    /// Advance all elements by one time step
+    fn step(&mut self);
+
+    /// Set the external current (pA) of every element
+    fn set_currents(&mut self, currents: &[f32]);
+
+    /// Copy of the full state buffer
+    fn read_states(&self) -> Vec<Self::State>;
+
+    /// Membrane voltage of every element (mV)
+    fn voltages(&self) -> Vec<f32>;
+}
+
+impl SimulationBackend for GpuSimulator {
+    type State = GpuNeuronState;
+
+    fn kind(&self) -> BackendKind {
+        BackendKind::Gpu
+    }
+
+    fn step(&mut self) {
+        GpuSimulator::step(self)
+    }
+
+    fn set_currents(&mut self, currents: &[f32]) {
+        GpuSimulator::set_currents(self, currents)
+    }
+
+    fn read_states(&self) -> Vec<GpuNeuronState> {
+        pollster::block_on(GpuSimulator::read_states(self))
+    }
+
+    fn voltages(&self) -> Vec<f32> {
+        pollster::block_on(self.get_voltages())
+    }
+}
+
+impl SimulationBackend for CpuSimulator {
+    type State = GpuNeuronState;
+
+    fn kind(&self) -> BackendKind {
+        BackendKind::Cpu
+    }
+
+    fn step(&mut self) {
+        CpuSimulator::step(self)
+    }
+
+    fn set_currents(&mut self, currents: &[f32]) {
+        CpuSimulator::set_currents(self, currents)
+    }
+
+    fn read_states(&self) -> Vec<GpuNeuronState> {
+        self.states().to_vec()
+    }
+
+    fn voltages(&self) -> Vec<f32> {
+        CpuSimulator::voltages(self)
+    }
+}
+
+impl SimulationBackend for CableSimulator {
+    type State = CompartmentState;
+
+    fn kind(&self) -> BackendKind {
+        BackendKind::Gpu
+    }
+
+    fn initialize(&mut self) {
+        CableSimulator::initialize(self)
+    }
+
+    fn step(&mut self) {
+        CableSimulator::step(self)
+    }
+
+    fn set_currents(&mut self, currents: &[f32]) {
+        CableSimulator::set_currents(self, currents)
+    }
+
+    fn read_states(&self) -> Vec<CompartmentState> {
+        pollster::block_on(self.read_compartments())
+    }
+
+    fn voltages(&self) -> Vec<f32> {
+        self.read_states().iter().map(|c| c.voltage).collect()
+    }
+}
+
+impl SimulationBackend for CpuCableSimulator {
+    type State = CompartmentState;
+
+    fn kind(&self) -> BackendKind {
+        BackendKind::Cpu
+    }
+
+    fn initialize(&mut self) {
+        CpuCableSimulator::initialize(self)
+    }
+
+    fn step(&mut self) {
+        CpuCableSimulator::step(self)
+    }
+
+    fn set_currents(&mut self, currents: &[f32]) {
+        CpuCableSimulator::set_currents(self, currents)
+    }
+
+    fn read_states(&self) -> Vec<CompartmentState> {
+        self.compartments().to_vec()
+    }
+
+    fn voltages(&self) -> Vec<f32> {
+        self.compartments().iter().map(|c| c.voltage).collect()
+    }
+}
+
+/// Point-neuron HH simulator on the preferred backend
+pub type PointNeuronBackend = Box<dyn SimulationBackend<State = GpuNeuronState>>;
+
+/// Cable-equation simulator on the preferred backend
+pub type CableBackend = Box<dyn SimulationBackend<State = CompartmentState>>;
+
+fn select<S>(
+    preference: BackendPreference,
+    gpu: impl FnOnce() -> Result<S>,
+    cpu: impl FnOnce() -> S,
+) -> Result<S> {
+    match preference {
+        BackendPreference::Cpu => Ok(cpu()),
+        BackendPreference::Gpu => gpu(),
+        BackendPreference::Auto => Ok(gpu().unwrap_or_else(|_| cpu())),
+    }
+}
+
+/// Create a point-neuron simulator (`hodgkin_huxley.wgsl`)
+pub fn point_neuron_backend(
+    num_neurons: usize,
+    dt: f32,
+    preference: BackendPreference,
+) -> Result<PointNeuronBackend> {
+    select(
+        preference,
+        || Ok(Box::new(pollster::block_on(GpuSimulator::new(num_neurons, dt))?) as PointNeuronBackend),
+        || Box::new(CpuSimulator::new(num_neurons, dt)) as PointNeuronBackend,
+    )
+}
+
+/// Create a cable-equation simulator (`cable_equation.wgsl`). As with
+/// [`CableSimulator`], call [`SimulationBackend::initialize`] before stepping.
+pub fn cable_backend(
+    num_neurons: usize,
+    dt: f32,
+    preference: BackendPreference,
+) -> Result<CableBackend> {
+    select(
+        preference,
+        || Ok(Box::new(pollster::block_on(CableSimulator::new(num_neurons, dt))?) as CableBackend),
+        || Box::new(CpuCableSimulator::new(num_neurons, dt)) as CableBackend,
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// The GPU half of a parity test, or `None` on machines without an adapter
+    fn gpu_or_skip<S>(backend: Result<S>) -> Option<S> {
+        match backend {
+            Ok(backend) => Some(backend),
+            Err(err) => {
+                eprintln!("skipping CPU/GPU parity check: {err}");
+                None
+            }
+        }
+    }
+
+    fn max_abs_diff(a: &[f32], b: &[f32]) -> f32 {
+        assert_eq!(a.len(), b.len());
+        a.iter().zip(b).map(|(x, y)| (x - y).abs()).fold(0.0, f32::max)
+    }
+
+    #[test]
+    fn test_preference_parsing_and_fallback() {
+        assert_eq!("CPU".parse::<BackendPreference>().unwrap(), BackendPreference::Cpu);
+        assert_eq!(" auto ".parse::<BackendPreference>().unwrap(), BackendPreference::Auto);
+        assert!("cuda".parse::<BackendPreference>().is_err());
+
+        let sim = point_neuron_backend(8, 0.01, BackendPreference::Cpu).unwrap();
+        assert_eq!(sim.kind(), BackendKind::Cpu);
+
+        // Auto always yields a working simulator, whatever the machine has
+        let mut sim = cable_backend(1, 0.01, BackendPreference::Auto).unwrap();
+        sim.initialize();
+        sim.step();
+        assert_eq!(sim.voltages().len(), 152);
+    }
+
+    #[test]
+    fn test_point_neuron_parity() {
+        let Some(mut gpu) = gpu_or_skip(point_neuron_backend(256, 0.01, BackendPreference::Gpu))
+        else {
+            return;
+        };
+        let mut cpu = point_neuron_backend(256, 0.01, BackendPreference::Cpu).unwrap();
+
+        let currents: Vec<f32> = (0..256).map(|i| (i % 4) as f32 * 10.0).collect();
+        gpu.set_currents(&currents);
+        cpu.set_currents(&currents);
+        for _ in 0..50 {
+            gpu.step();
+            cpu.step();
+        }
+
+        let (g, c) = (gpu.read_states(), cpu.read_states());
+        let field = |states: &[GpuNeuronState], f: fn(&GpuNeuronState) -> f32| {
+            states.iter().map(f).collect::<Vec<_>>()
+        };
+        assert!(max_abs_diff(&field(&g, |s| s.voltage), &field(&c, |s| s.voltage)) < 0.1);
+        assert!(max_abs_diff(&field(&g, |s| s.na_m), &field(&c, |s| s.na_m)) < 1e-3);
+        assert!(max_abs_diff(&field(&g, |s| s.k_n), &field(&c, |s| s.k_n)) < 1e-3);
+        assert!(max_abs_diff(&field(&g, |s| s.calcium), &field(&c, |s| s.calcium)) < 1e-5);
+    }
+
+    #[test]
+    fn test_cable_parity() {
+        let Some(mut gpu) = gpu_or_skip(cable_backend(2, 0.01, BackendPreference::Gpu)) else {
+            return;
+        };
+        let mut cpu = cable_backend(2, 0.01, BackendPreference::Cpu).unwrap();
+        gpu.initialize();
+        cpu.initialize();
+
+        // Identical topology; geometry up to WGSL's division accuracy
+        for (g, c) in gpu.read_states().iter().zip(&cpu.read_states()) {
+            assert_eq!(
+                (g.comp_type, g.parent_idx, g.num_children, g.child_idx_0, g.child_idx_1),
+                (c.comp_type, c.parent_idx, c.num_children, c.child_idx_0, c.child_idx_1)
+            );
+            assert!((g.axial_resistance - c.axial_resistance).abs() <= 1e-5 * c.axial_resistance);
+            assert!((g.capacitance - c.capacitance).abs() <= 1e-5 * c.capacitance);
+        }
+
+        let mut currents = vec![0.0; 2 * 152];
+        currents[0] = 500.0;
+        gpu.set_currents(&currents);
+        cpu.set_currents(&currents);
+        for _ in 0..100 {
+            gpu.step();
+            cpu.step();
+        }
+
+        // The GPU kernel reads neighbours without a barrier, so allow for
+        // races and transcendental rounding
+        assert!(max_abs_diff(&gpu.voltages(), &cpu.voltages()) < 0.1);
+    }
+}
@@ -201,6 +201,10 @@ impl CableSimulator {
         let device = Arc::new(device);
         let queue = Arc::new(queue);
 
+        // Shader and pipeline errors are reported as `Err` rather than
+        // panicking, so callers can fall back to the CPU backend
+        device.push_error_scope(wgpu::ErrorFilter::Validation);
+
         // Load shader
         let shader = device.create_shader_module(wgpu::ShaderModuleDescriptor {
             label: Some("Cable Equation Shader"),
@@ -314,6 +318,10 @@ impl CableSimulator {
             entry_point: "initialize_compartments",
         });
 
+        if let Some(err) = device.pop_error_scope().await {
+            return Err(anyhow::anyhow!("Invalid cable equation pipeline: {err}"));
+        }
+
         Ok(Self {
             device,
             queue,
@@ -0,0 +1,547 @@
+//! CPU implementation of the compute shaders.
+//!
+//! Line-by-line ports of `hodgkin_huxley.wgsl` and `cable_equation.wgsl`
+//! operating on the same `#[repr(C)]` buffers (`GpuNeuronState`,
+//! `CompartmentState`) in `f32`, parallelised with rayon. One rayon task
+//! plays the role of one shader invocation.
+//!
+//! ## Differences from the GPU
+//! The cable kernel has no barrier, so on the GPU a compartment may read a
+//! neighbour voltage that was already advanced in the same dispatch. The CPU
+//! solver reads neighbours from a snapshot of the previous step (Jacobi
+//! update), which is what the shader intends and makes runs deterministic.
+//! The difference is below the coupling current times `dt`.
+
+use crate::cable_simulator::{CableConstants, CompartmentState};
+use crate::{GpuNeuronState, HHConstants};
+use rayon::prelude::*;
+
+// ============================================================================
+// HODGKIN-HUXLEY POINT NEURON KERNEL (hodgkin_huxley.wgsl)
+// ============================================================================
+
+mod hh {
+    pub fn alpha_m(v: f32) -> f32 {
+        0.1 * (v + 40.0) / (1.0 - (-0.1 * (v + 40.0)).exp())
+    }
+
+    pub fn beta_m(v: f32) -> f32 {
+        4.0 * (-0.0556 * (v + 65.0)).exp()
+    }
+
+    pub fn alpha_h(v: f32) -> f32 {
+        0.07 * (-0.05 * (v + 65.0)).exp()
+    }
+
+    pub fn beta_h(v: f32) -> f32 {
+        1.0 / (1.0 + (-0.1 * (v + 35.0)).exp())
+    }
+
+    pub fn alpha_n(v: f32) -> f32 {
+        0.01 * (v + 55.0) / (1.0 - (-0.1 * (v + 55.0)).exp())
+    }
+
+    pub fn beta_n(v: f32) -> f32 {
+        0.125 * (-0.0125 * (v + 65.0)).exp()
+    }
+
+    pub fn ca_m_inf(v: f32) -> f32 {
+        1.0 / (1.0 + (-0.15 * (v + 20.0)).exp())
+    }
+
+    pub fn ca_h_inf(v: f32) -> f32 {
+        1.0 / (1.0 + (0.2 * (v + 50.0)).exp())
+    }
+}
+
+/// One `update_neurons` invocation: advance a single point neuron by `dt`
+pub fn update_neuron(neuron: &mut GpuNeuronState, constants: &HHConstants) {
+    let v = neuron.voltage;
+    let dt = constants.dt;
+
+    // 1. Gating variables (forward Euler on the rates at the old voltage)
+    let am = hh::alpha_m(v);
+    let bm = hh::beta_m(v);
+    let ah = hh::alpha_h(v);
+    let bh = hh::beta_h(v);
+
+    neuron.na_m += (am * (1.0 - neuron.na_m) - bm * neuron.na_m) * dt;
+    neuron.na_h += (ah * (1.0 - neuron.na_h) - bh * neuron.na_h) * dt;
+
+    let an = hh::alpha_n(v);
+    let bn = hh::beta_n(v);
+
+    neuron.k_n += (an * (1.0 - neuron.k_n) - bn * neuron.k_n) * dt;
+
+    let tau_ca_m = 0.5;
+    let tau_ca_h = 20.0;
+
+    neuron.ca_m += ((hh::ca_m_inf(v) - neuron.ca_m) / tau_ca_m) * dt;
+    neuron.ca_h += ((hh::ca_h_inf(v) - neuron.ca_h) / tau_ca_h) * dt;
+
+    // 2. Currents with the updated gates
+    let i_na = constants.g_na_bar * neuron.na_m.powi(3) * neuron.na_h * (v - constants.e_na);
+    let i_k = constants.g_k_bar * neuron.k_n.powi(4) * (v - constants.e_k);
+    let i_ca = constants.g_ca_bar * neuron.ca_m.powi(2) * neuron.ca_h * (v - constants.e_ca);
+    let i_leak = constants.g_leak * (v - constants.e_leak);
+    let i_ion = i_na + i_k + i_ca + i_leak;
+
+    // 3. Voltage
+    neuron.voltage += (-i_ion + neuron.external_current) / constants.capacitance * dt;
+
+    // 4. Calcium influx, buffering and clamp
+    neuron.calcium += -i_ca * 0.001 * dt;
+    neuron.calcium *= 0.9;
+    neuron.calcium = neuron.calcium.clamp(0.0001, 0.01);
+}
+
+/// CPU counterpart of [`crate::GpuSimulator`]
+pub struct CpuSimulator {
+    neurons: Vec<GpuNeuronState>,
+    constants: HHConstants,
+}
+
+impl CpuSimulator {
+    pub fn new(num_neurons: usize, dt: f32) -> Self {
+        Self {
+            neurons: vec![GpuNeuronState::default(); num_neurons],
+            constants: HHConstants::new(num_neurons, dt),
+        }
+    }
+
+    /// Update neuron states (single time step)
+    pub fn step(&mut self) {
+        let constants = self.constants;
+        self.neurons
+            .par_iter_mut()
+            .for_each(|neuron| update_neuron(neuron, &constants));
+    }
+
+    /// Set external currents for all neurons
+    pub fn set_currents(&mut self, currents: &[f32]) {
+        assert_eq!(currents.len(), self.neurons.len());
+        for (neuron, &current) in self.neurons.iter_mut().zip(currents) {
+            neuron.external_current = current;
+        }
+    }
+
+    pub fn states(&self) -> &[GpuNeuronState] {
+        &self.neurons
+    }
+
+    pub fn voltages(&self) -> Vec<f32> {
+        self.neurons.iter().map(|n| n.voltage).collect()
+    }
+
+    pub fn num_neurons(&self) -> usize {
+        self.neurons.len()
+    }
+}
+
+// ============================================================================
+// CABLE EQUATION KERNELS (cable_equation.wgsl)
+// ============================================================================
+
+mod cable {
+    pub fn alpha_m(v: f32) -> f32 {
+        let denom = 1.0 - (-(v + 40.0) / 10.0).exp();
+        if denom.abs() < 1e-6 {
+            return 1.0;
+        }
+        0.1 * (v + 40.0) / denom
+    }
+
+    pub fn beta_m(v: f32) -> f32 {
+        4.0 * (-(v + 65.0) / 18.0).exp()
+    }
+
+    pub fn alpha_h(v: f32) -> f32 {
+        0.07 * (-(v + 65.0) / 20.0).exp()
+    }
+
+    pub fn beta_h(v: f32) -> f32 {
+        1.0 / (1.0 + (-(v + 35.0) / 10.0).exp())
+    }
+
+    pub fn alpha_n(v: f32) -> f32 {
+        let denom = 1.0 - (-(v + 55.0) / 10.0).exp();
+        if denom.abs() < 1e-6 {
+            return 0.1;
+        }
+        0.01 * (v + 55.0) / denom
+    }
+
+    pub fn beta_n(v: f32) -> f32 {
+        0.125 * (-(v + 65.0) / 80.0).exp()
+    }
+
+    pub fn alpha_m_ca(v: f32) -> f32 {
+        let denom = 1.0 - (-(v + 27.0) / 3.8).exp();
+        if denom.abs() < 1e-6 {
+            return 0.055 * 3.8;
+        }
+        0.055 * (v + 27.0) / denom
+    }
+
+    pub fn beta_m_ca(v: f32) -> f32 {
+        0.94 * (-(v + 75.0) / 17.0).exp()
+    }
+
+    /// Exponential-Euler relaxation of a gate towards `alpha / (alpha + beta)`
+    pub fn relax(x: f32, alpha: f32, beta: f32, dt: f32) -> f32 {
+        let tau = 1.0 / (alpha + beta);
+        let inf = alpha / (alpha + beta);
+        x + (inf - x) * (1.0 - (-dt / tau).exp())
+    }
+}
+
+fn child_indices(comp: &CompartmentState) -> [i32; 8] {
+    [
+        comp.child_idx_0,
+        comp.child_idx_1,
+        comp.child_idx_2,
+        comp.child_idx_3,
+        comp.child_idx_4,
+        comp.child_idx_5,
+        comp.child_idx_6,
+        comp.child_idx_7,
+    ]
+}
+
+/// One `solve_cable_equation` invocation. Neighbour voltages and axial
+/// resistances are read from `previous`, the state at the start of the step.
+pub fn solve_compartment(
+    comp: &mut CompartmentState,
+    previous: &[CompartmentState],
+    i_ext: f32,
+    constants: &CableConstants,
+) {
+    let dt = constants.dt;
+    let temp_diff = (constants.temperature - constants.ref_temperature) / 10.0;
+    let phi_hh = 2.3_f32.powf(temp_diff);
+    let phi_ca = 3.0_f32.powf(temp_diff);
+
+    // 1. Gating variables at the current voltage
+    let v = comp.voltage;
+    comp.na_m = cable::relax(comp.na_m, cable::alpha_m(v) * phi_hh, cable::beta_m(v) * phi_hh, dt);
+    comp.na_h = cable::relax(comp.na_h, cable::alpha_h(v) * phi_hh, cable::beta_h(v) * phi_hh, dt);
+    comp.k_n = cable::relax(comp.k_n, cable::alpha_n(v) * phi_hh, cable::beta_n(v) * phi_hh, dt);
+    comp.ca_m = cable::relax(
+        comp.ca_m,
+        cable::alpha_m_ca(v) * phi_ca,
+        cable::beta_m_ca(v) * phi_ca,
+        dt,
+    );
+
+    // 2. Ionic currents (densities scaled by area to nS)
+    let area = comp.surface_area;
+    let g_na = constants.g_na_bar * area / 100.0;
+    let g_k = constants.g_k_bar * area / 100.0;
+    let g_ca = constants.g_ca_bar * area / 100.0;
+    let m3 = comp.na_m * comp.na_m * comp.na_m;
+    let n4 = comp.k_n * comp.k_n * comp.k_n * comp.k_n;
+    let i_ion = g_na * m3 * comp.na_h * (v - constants.e_na)
+        + g_k * n4 * (v - constants.e_k)
+        + g_ca * comp.ca_m * (v - constants.e_ca)
+        + comp.g_leak * (v - comp.e_leak);
+
+    // 3. Axial currents from the parent and to every child
+    let mut i_axial = 0.0;
+    if comp.parent_idx >= 0 {
+        let parent = &previous[comp.parent_idx as usize];
+        i_axial += (parent.voltage - v) / comp.axial_resistance;
+    }
+    for &child_idx in child_indices(comp).iter().take(comp.num_children as usize) {
+        if child_idx >= 0 {
+            let child = &previous[child_idx as usize];
+            i_axial += (child.voltage - v) / child.axial_resistance;
+        }
+    }
+
+    // 4-6. Forward Euler voltage update, clamped to the physiological range
+    let dv = (-i_ion + i_axial + i_ext) / comp.capacitance;
+    comp.voltage = (v + dv * dt).clamp(-100.0, 60.0);
+}
+
+/// One `initialize_compartments` invocation: the L5 pyramidal morphology
+/// (soma, apical tree, basal tree, AIS) and resting state of compartment
+/// `comp_idx`
+pub fn initialize_compartment(comp_idx: usize, constants: &CableConstants) -> CompartmentState {
+    let per_neuron = constants.num_compartments_per_neuron;
+    let comp_idx = comp_idx as u32;
+    let neuron_id = comp_idx / per_neuron;
+    let comp_id = comp_idx % per_neuron;
+    let idx = |offset: i64| (comp_idx as i64 + offset) as i32;
+
+    let mut comp = CompartmentState {
+        parent_idx: -1,
+        ..CompartmentState::default()
+    };
+    let mut children: Vec<i32> = Vec::new();
+
+    if comp_id == 0 {
+        // Soma: apical trunk, basal dendrite, AIS
+        comp.comp_type = 0;
+        comp.length = 20.0;
+        comp.diameter = 20.0;
+        children.extend([idx(1), idx(101), idx(151)]);
+    } else if comp_id <= 100 {
+        comp.comp_type = 1;
+        if comp_id == 1 {
+            // Apical trunk bifurcating into two branches
+            comp.length = 100.0;
+            comp.diameter = 3.0;
+            comp.parent_idx = idx(-1);
+            children.extend([idx(1), idx(50)]);
+        } else if comp_id <= 50 {
+            // Oblique branch
+            comp.length = 50.0;
+            comp.diameter = 2.0;
+            comp.parent_idx = idx(-1);
+            if comp_id.is_multiple_of(5) && comp_id < 50 {
+                children.extend([idx(1), idx(2)]);
+            } else if comp_id < 50 {
+                children.push(idx(1));
+            }
+        } else {
+            // Tuft
+            comp.length = 30.0;
+            comp.diameter = 0.5;
+            comp.parent_idx = idx(-50);
+            if comp_id.is_multiple_of(7) && comp_id < 100 {
+                children.extend([idx(1), idx(2)]);
+            } else if comp_id < 100 {
+                children.push(idx(1));
+            }
+        }
+    } else if comp_id <= 150 {
+        comp.comp_type = 2;
+        comp.length = 50.0;
+        comp.diameter = 1.5;
+        if comp_id == 101 {
+            comp.parent_idx = idx(-101);
+            children.push(idx(1));
+        } else {
+            comp.parent_idx = idx(-1);
+            if comp_id.is_multiple_of(8) && comp_id < 150 {
+                children.extend([idx(1), idx(2)]);
+            } else if comp_id < 150 {
+                children.push(idx(1));
+            }
+        }
+    } else if comp_id == 151 {
+        comp.comp_type = 3;
+        comp.length = 30.0;
+        comp.diameter = 1.0;
+        comp.parent_idx = idx(-151);
+    }
+
+    let slots = [
+        &mut comp.child_idx_0,
+        &mut comp.child_idx_1,
+        &mut comp.child_idx_2,
+        &mut comp.child_idx_3,
+        &mut comp.child_idx_4,
+        &mut comp.child_idx_5,
+        &mut comp.child_idx_6,
+        &mut comp.child_idx_7,
+    ];
+    for (slot, child) in slots.into_iter().zip(children.iter().chain(std::iter::repeat(&-1))) {
+        *slot = *child;
+    }
+    comp.num_children = children.len() as u32;
+
+    // Derived geometry: cylinder area, 1 uF/cm^2, rho = 150 Ohm*cm
+    let pi = std::f32::consts::PI;
+    comp.surface_area = pi * comp.diameter * comp.length;
+    comp.capacitance = comp.surface_area / 100.0;
+    let radius_cm = comp.diameter / 2.0 * 1e-4;
+    let length_cm = comp.length * 1e-4;
+    comp.axial_resistance = (150.0 * length_cm) / (pi * radius_cm * radius_cm) / 1e6;
+
+    comp.voltage = -70.0;
+    comp.g_leak = 0.025;
+    comp.e_leak = -70.0;
+    comp.na_m = 0.05;
+    comp.na_h = 0.6;
+    comp.k_n = 0.32;
+    comp.ca_m = 0.01;
+
+    comp.neuron_id = neuron_id;
+    comp.comp_id_in_neuron = comp_id;
+    comp
+}
+
+/// CPU counterpart of [`crate::cable_simulator::CableSimulator`]
+pub struct CpuCableSimulator {
+    compartments: Vec<CompartmentState>,
+    /// State at the start of the current step (neighbour reads)
+    previous: Vec<CompartmentState>,
+    currents: Vec<f32>,
+    constants: CableConstants,
+}
+
+impl CpuCableSimulator {
+    /// Create a simulator with default compartments; call
+    /// [`initialize`](Self::initialize) to lay out the morphology
+    pub fn new(num_neurons: usize, dt: f32) -> Self {
+        let constants = CableConstants::new(num_neurons, dt);
+        let total = constants.total_compartments as usize;
+        Self {
+            compartments: vec![CompartmentState::default(); total],
+            previous: Vec::with_capacity(total),
+            currents: vec![0.0; total],
+            constants,
+        }
+    }
+
+    /// Initialize compartment morphology and electrical state
+    pub fn initialize(&mut self) {
+        let constants = self.constants;
+        self.compartments
+            .par_iter_mut()
+            .enumerate()
+            .for_each(|(idx, comp)| *comp = initialize_compartment(idx, &constants));
+    }
+
+    /// Simulate one time step (cable equation solver)
+    pub fn step(&mut self) {
+        self.previous.clear();
+        self.previous.extend_from_slice(&self.compartments);
+
+        let constants = self.constants;
+        let previous = &self.previous;
+        self.compartments
+            .par_iter_mut()
+            .zip(self.currents.par_iter())
+            .for_each(|(comp, &i_ext)| solve_compartment(comp, previous, i_ext, &constants));
+    }
+
+    /// Set external current for a specific compartment
+    pub fn set_current(&mut self, compartment_idx: usize, current_pa: f32) {
+        self.currents[compartment_idx] = current_pa;
+    }
+
+    /// Set currents for all compartments
+    pub fn set_currents(&mut self, currents: &[f32]) {
+        assert_eq!(currents.len(), self.currents.len());
+        self.currents.copy_from_slice(currents);
+    }
+
+    pub fn compartments(&self) -> &[CompartmentState] {
+        &self.compartments
+    }
+
+    /// Soma voltages for all neurons
+    pub fn soma_voltages(&self) -> Vec<f32> {
+        self.compartments
+            .iter()
+            .step_by(self.compartments_per_neuron())
+            .map(|c| c.voltage)
+            .collect()
+    }
+
+    /// Voltages for a specific neuron (all compartments)
+    pub fn neuron_voltages(&self, neuron_id: usize) -> Vec<f32> {
+        assert!(neuron_id < self.num_neurons());
+        let per_neuron = self.compartments_per_neuron();
+        self.compartments[neuron_id * per_neuron..(neuron_id + 1) * per_neuron]
+            .iter()
+            .map(|c| c.voltage)
+            .collect()
+    }
+
+    pub fn num_neurons(&self) -> usize {
+        self.constants.num_neurons as usize
+    }
+
+    pub fn compartments_per_neuron(&self) -> usize {
+        self.constants.num_compartments_per_neuron as usize
+    }
+
+    pub fn total_compartments(&self) -> usize {
+        self.compartments.len()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_single_neuron_resting() {
+        let mut sim = CpuSimulator::new(1, 0.01);
+        for _ in 0..100 {
+            sim.step();
+        }
+        let v = sim.voltages()[0];
+        assert!(v > -75.0 && v < -65.0, "v = {v}");
+    }
+
+    #[test]
+    fn test_current_injection_depolarizes() {
+        let mut sim = CpuSimulator::new(2, 0.01);
+        sim.set_currents(&[100.0, 0.0]);
+        for _ in 0..100 {
+            sim.step();
+        }
+        let v = sim.voltages();
+        assert!(v[0] > -60.0, "v = {}", v[0]);
+        assert!(v[0] > v[1]);
+        assert!(sim.states().iter().all(|s| (0.0001..=0.01).contains(&s.calcium)));
+    }
+
+    #[test]
+    fn test_initialize_morphology() {
+        let mut sim = CpuCableSimulator::new(2, 0.01);
+        sim.initialize();
+        let comps = sim.compartments();
+        let per_neuron = sim.compartments_per_neuron();
+
+        assert_eq!(comps[0].comp_type, 0);
+        assert_eq!(comps[0].parent_idx, -1);
+        assert_eq!(comps[0].num_children, 3);
+        assert_eq!(
+            [comps[0].child_idx_0, comps[0].child_idx_1, comps[0].child_idx_2, comps[0].child_idx_3],
+            [1, 101, 151, -1]
+        );
+        assert_eq!(comps[151].comp_type, 3);
+        assert_eq!(comps[151].parent_idx, 0);
+
+        // The second neuron's tree is offset by one neuron
+        let soma = &comps[per_neuron];
+        assert_eq!(soma.neuron_id, 1);
+        assert_eq!(soma.child_idx_0, per_neuron as i32 + 1);
+        assert_eq!(comps[per_neuron + 51].parent_idx, per_neuron as i32 + 1);
+
+        // Every referenced neighbour lies inside the same neuron
+        for comp in comps {
+            let range = (comp.neuron_id as usize * per_neuron) as i32
+                ..((comp.neuron_id as usize + 1) * per_neuron) as i32;
+            let children = child_indices(comp);
+            for &child in children.iter().take(comp.num_children as usize) {
+                assert!(range.contains(&child));
+            }
+            assert!(comp.parent_idx == -1 || range.contains(&comp.parent_idx));
+        }
+    }
+
+    #[test]
+    fn test_cable_resting_and_injection() {
+        let mut sim = CpuCableSimulator::new(4, 0.01);
+        sim.initialize();
+        for neuron_id in 0..2 {
+            sim.set_current(neuron_id * sim.compartments_per_neuron(), 500.0);
+        }
+        for _ in 0..200 {
+            sim.step();
+        }
+        let soma = sim.soma_voltages();
+        assert!(soma[0] > -50.0, "driven soma = {}", soma[0]);
+        // Initial gates are not at steady state, so the undriven soma drifts
+        // a few mV below -70
+        assert!((soma[2] - (-70.0)).abs() < 10.0, "resting soma = {}", soma[2]);
+        assert_eq!(soma[0], soma[1]);
+        assert_eq!(sim.neuron_voltages(3).len(), 152);
+    }
+}
@@ -6,10 +6,17 @@
 //! ## Modules
 //! - `compute`: Legacy point neuron HH simulator
 //! - `cable_simulator`: **NEW** Multi-compartmental cable equation with full tree topology
+//! - `cpu`: rayon ports of the shaders for machines without a GPU adapter
+//! - `backend`: `SimulationBackend` trait and runtime GPU/CPU selection
 
 pub mod compute;
 pub mod cable_simulator;
 pub mod feedback_loop;
+pub mod cpu;
+pub mod backend;
+
+pub use backend::{BackendKind, BackendPreference, SimulationBackend};
+pub use cpu::{CpuCableSimulator, CpuSimulator};
 
 use anyhow::Result;
 use bytemuck::{Pod, Zeroable};
@@ -192,32 +192,34 @@ fn q10_correction(rate: f32, q10: f32) -> f32 {
 // ============================================================================
 
 /// Calcula corrientes iónicas para un compartimento
-fn calculate_ion_currents(comp: ptr<storage, CompartmentState, read_write>) -> f32 {
-    let v = (*comp).voltage;
-    let area = (*comp).surface_area;
+fn calculate_ion_currents(comp_idx: u32) -> f32 {
+    let comp = compartments[comp_idx];
+    let v = comp.voltage;
+    let area = comp.surface_area;
 
     // Sodium current (Na⁺) - Action potential upstroke
     let g_na = constants.g_na_bar * area / 100.0; // Convert to nS
-    let m3 = (*comp).na_m * (*comp).na_m * (*comp).na_m;
-    let i_na = g_na * m3 * (*comp).na_h * (v - constants.e_na);
+    let m3 = comp.na_m * comp.na_m * comp.na_m;
+    let i_na = g_na * m3 * comp.na_h * (v - constants.e_na);
 
     // Potassium current (K⁺) - Action potential repolarization
     let g_k = constants.g_k_bar * area / 100.0;
-    let n4 = (*comp).k_n * (*comp).k_n * (*comp).k_n * (*comp).k_n;
+    let n4 = comp.k_n * comp.k_n * comp.k_n * comp.k_n;
     let i_k = g_k * n4 * (v - constants.e_k);
 
     // Calcium current (Ca²⁺) - Synaptic plasticity, dendritic spikes
     let g_ca = constants.g_ca_bar * area / 100.0;
-    let i_ca = g_ca * (*comp).ca_m * (v - constants.e_ca);
+    let i_ca = g_ca * comp.ca_m * (v - constants.e_ca);
 
     // Leak current - Resting potential maintenance
-    let i_leak = (*comp).g_leak * (v - (*comp).e_leak);
+    let i_leak = comp.g_leak * (v - comp.e_leak);
 
     return i_na + i_k + i_ca + i_leak;
 }
 
 /// Actualiza variables de gating (Hodgkin-Huxley)
-fn update_gating_variables(comp: ptr<storage, CompartmentState, read_write>) {
+fn update_gating_variables(comp_idx: u32) {
+    let comp = &compartments[comp_idx];
     let v = (*comp).voltage;
     let dt = constants.dt;
 
@@ -251,15 +253,15 @@ fn update_gating_variables(comp: ptr<storage, CompartmentState, read_write>) {
 }
 
 /// Helper: Get child index by position
-fn get_child_idx(comp: ptr<storage, CompartmentState, read_write>, child_pos: u32) -> i32 {
-    if (child_pos == 0u) { return (*comp).child_idx_0; }
-    if (child_pos == 1u) { return (*comp).child_idx_1; }
-    if (child_pos == 2u) { return (*comp).child_idx_2; }
-    if (child_pos == 3u) { return (*comp).child_idx_3; }
-    if (child_pos == 4u) { return (*comp).child_idx_4; }
-    if (child_pos == 5u) { return (*comp).child_idx_5; }
-    if (child_pos == 6u) { return (*comp).child_idx_6; }
-    if (child_pos == 7u) { return (*comp).child_idx_7; }
+fn get_child_idx(comp: CompartmentState, child_pos: u32) -> i32 {
+    if (child_pos == 0u) { return comp.child_idx_0; }
+    if (child_pos == 1u) { return comp.child_idx_1; }
+    if (child_pos == 2u) { return comp.child_idx_2; }
+    if (child_pos == 3u) { return comp.child_idx_3; }
+    if (child_pos == 4u) { return comp.child_idx_4; }
+    if (child_pos == 5u) { return comp.child_idx_5; }
+    if (child_pos == 6u) { return comp.child_idx_6; }
+    if (child_pos == 7u) { return comp.child_idx_7; }
     return -1;
 }
 
@@ -274,17 +276,17 @@ fn solve_cable_equation(@builtin(global_invocation_id) global_id: vec3<u32>) {
         return;
     }
 
-    var comp = &compartments[comp_idx];
+    let comp = &compartments[comp_idx];
 
     // ========================================================================
     // PASO 1: Actualizar gating variables con voltaje actual
     // ========================================================================
-    update_gating_variables(comp);
+    update_gating_variables(comp_idx);
 
     // ========================================================================
     // PASO 2: Calcular corrientes iónicas
     // ========================================================================
-    let i_ion = calculate_ion_currents(comp);
+    let i_ion = calculate_ion_currents(comp_idx);
 
     // ========================================================================
     // PASO 3: Calcular corrientes axiales (ECUACIÓN DE CABLE CON ÁRBOL)
@@ -304,7 +306,7 @@ fn solve_cable_equation(@builtin(global_invocation_id) global_id: vec3<u32>) {
 
     // Corrientes hacia children (TODOS los hijos, topología arbórea)
     for (var child_pos: u32 = 0u; child_pos < (*comp).num_children; child_pos++) {
-        let child_idx_i32 = get_child_idx(comp, child_pos);
+        let child_idx_i32 = get_child_idx(*comp, child_pos);
 
         if (child_idx_i32 >= 0) {
             let child_idx = u32(child_idx_i32);
@@ -352,7 +354,7 @@ fn initialize_compartments(@builtin(global_invocation_id) global_id: vec3<u32>)
     let neuron_id = comp_idx / constants.num_compartments_per_neuron;
     let comp_id = comp_idx % constants.num_compartments_per_neuron;
 
-    var comp = &compartments[comp_idx];
+    let comp = &compartments[comp_idx];
 
     // Initialize tree topology to empty
     (*comp).parent_idx = -1;
@@ -1,7 +1,8 @@
 //! Complete hippocampus implementation with DG, CA3, CA1, and realistic dynamics.
 
 use serde::{Deserialize, Serialize};
-use rand::Rng;
+use rand::{Rng, SeedableRng};
+use rand_chacha::ChaCha8Rng;
 use std::f64::consts::PI;
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
@@ -49,6 +50,7 @@ impl HippocampalNeuron {
     }
 }
 
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct DentateGyrus {
     pub granule_cells: Vec<HippocampalNeuron>,
 }
@@ -65,13 +67,16 @@ impl DentateGyrus {
     }
 }
 
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct CA3Region {
     pub pyramidal_cells: Vec<HippocampalNeuron>,
     pub recurrent_weights: Vec<Vec<f64>>,
 }
 impl CA3Region {
     pub fn new(n: usize) -> Self {
-        let mut rng = rand::thread_rng();
+        Self::with_rng(n, &mut rand::thread_rng())
+    }
+    pub fn with_rng<R: Rng + ?Sized>(n: usize, rng: &mut R) -> Self {
         let rw = (0..n).map(|i| (0..n).map(|j|
             if i != j && rng.gen::<f64>() < 0.1 { rng.gen::<f64>() * 0.1 } else { 0.0 }
         ).collect()).collect();
@@ -90,13 +95,16 @@ impl CA3Region {
     }
 }
 
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct CA1Region {
     pub pyramidal_cells: Vec<HippocampalNeuron>,
     pub theta_phase: f64, pub theta_frequency: f64,
 }
 impl CA1Region {
     pub fn new(n: usize) -> Self {
-        let mut rng = rand::thread_rng();
+        Self::with_rng(n, &mut rand::thread_rng())
+    }
+    pub fn with_rng<R: Rng + ?Sized>(n: usize, rng: &mut R) -> Self {
         let mut cells = Vec::new();
         for i in 0..n {
             let mut neu = HippocampalNeuron::new(i, HippocampalNeuronType::PyramidalCA1);
@@ -118,6 +126,7 @@ impl CA1Region {
     }
 }
 
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct Hippocampus {
     pub dentate_gyrus: DentateGyrus,
     pub ca3: CA3Region,
@@ -125,10 +134,15 @@ pub struct Hippocampus {
 }
 impl Hippocampus {
     pub fn new(scale: f64) -> Self {
+        Self::with_seed(scale, rand::random())
+    }
+    /// Reproducible hippocampus: CA3 recurrents and CA1 place fields drawn from `seed`
+    pub fn with_seed(scale: f64, seed: u64) -> Self {
+        let mut rng = ChaCha8Rng::seed_from_u64(seed);
         Self {
             dentate_gyrus: DentateGyrus::new((1000.0 * scale) as usize),
-            ca3: CA3Region::new((300.0 * scale) as usize),
-            ca1: CA1Region::new((400.0 * scale) as usize),
+            ca3: CA3Region::with_rng((300.0 * scale) as usize, &mut rng),
+            ca1: CA1Region::with_rng((400.0 * scale) as usize, &mut rng),
         }
     }
     pub fn step(&mut self, dt: f64, input: &[f64], pos: [f64; 2], t: f64) -> Vec<bool> {
@@ -15,6 +15,55 @@ fn q10_correction(rate: f64, q10: f64, temp: f64, ref_temp: f64) -> f64 {
     rate * q10.powf((temp - ref_temp) / 10.0)
 }
 
+/// Time constant at `temp`: Q10 scales the rates, so time constants scale by
+/// its inverse (Hodgkin & Huxley 1952; `tadj` in Mainen et al. 1995)
+fn tau_at_temperature(tau: f64, q10: f64, temp: f64) -> f64 {
+    tau / q10_correction(1.0, q10, temp, REFERENCE_TEMP)
+}
+
+/// Time constant at body temperature
+fn q10_tau(tau: f64, q10: f64) -> f64 {
+    tau_at_temperature(tau, q10, TEMPERATURE)
+}
+
+/// `a * x / (1 - exp(-x / k))`, with its limit `a * k` at x = 0
+fn linoid(a: f64, x: f64, k: f64) -> f64 {
+    if x.abs() < 1e-6 {
+        a * k
+    } else {
+        a * x / (1.0 - (-x / k).exp())
+    }
+}
+
+/// First-order gate kinetics at a fixed voltage
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct GateKinetics {
+    /// Steady-state open probability
+    pub inf: f64,
+    /// Time constant at body temperature (ms)
+    pub tau: f64,
+}
+
+impl GateKinetics {
+    /// Kinetics from opening/closing rates (1/ms)
+    pub fn from_rates(alpha: f64, beta: f64) -> Self {
+        Self {
+            inf: alpha / (alpha + beta),
+            tau: 1.0 / (alpha + beta),
+        }
+    }
+
+    /// Forward Euler step of `x`
+    pub fn euler(self, x: &mut f64, dt: f64) {
+        *x += (self.inf - *x) / self.tau * dt;
+    }
+
+    /// Exponential Euler step of `x`: exact at fixed voltage, stable at any dt
+    pub fn exponential(self, x: &mut f64, dt: f64) {
+        *x = self.inf + (*x - self.inf) * (-dt / self.tau).exp();
+    }
+}
+
 /// Extended channel state for complex gating
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct AdvancedChannelState {
@@ -51,39 +100,53 @@ impl Nav1_1 {
     }
 
     fn alpha_m(&self, v: f64) -> f64 {
-        let v_shift = v + 38.0;
-        if v_shift.abs() < 1e-4 {
-            3.0
-        } else {
-            0.182 * v_shift / (1.0 - (-v_shift / 6.0).exp())
-        }
+        linoid(0.182, v + 38.0, 6.0)
     }
 
     fn beta_m(&self, v: f64) -> f64 {
-        let v_shift = v + 38.0;
-        0.124 * (-v_shift) / (1.0 - (v_shift / 6.0).exp())
+        linoid(0.124, -(v + 38.0), 6.0)
     }
 
     fn alpha_h(&self, v: f64) -> f64 {
-        0.024 * (v + 50.0) / (1.0 - (-(v + 50.0) / 5.0).exp())
+        linoid(0.024, v + 50.0, 5.0)
     }
 
     fn beta_h(&self, v: f64) -> f64 {
-        0.0091 * (-(v + 75.0)) / (1.0 - ((v + 75.0) / 5.0).exp())
-    }
-
-    pub fn conductance(&self, v: f64, state: &AdvancedChannelState) -> f64 {
-        self.g_max * state.m.powi(3) * state.h * (v - self.e_na)
+        linoid(0.0091, -(v + 75.0), 5.0)
     }
 
-    pub fn update(&self, v: f64, state: &mut AdvancedChannelState, dt: f64) {
+    /// Activation (m) kinetics
+    pub fn activation(&self, v: f64) -> GateKinetics {
         let am = q10_correction(self.alpha_m(v), 2.3, TEMPERATURE, REFERENCE_TEMP);
         let bm = q10_correction(self.beta_m(v), 2.3, TEMPERATURE, REFERENCE_TEMP);
+        GateKinetics::from_rates(am, bm)
+    }
+
+    fn h_inf(&self, v: f64) -> f64 {
+        1.0 / (1.0 + ((v + 65.0) / 6.2).exp())
+    }
+
+    /// Inactivation (h) kinetics. As in Mainen et al. (1995) the rates only
+    /// set the time constant; the steady state has its own Boltzmann curve
+    /// (half-inactivation -65 mV, slope 6.2 mV).
+    pub fn inactivation(&self, v: f64) -> GateKinetics {
         let ah = q10_correction(self.alpha_h(v), 2.3, TEMPERATURE, REFERENCE_TEMP);
         let bh = q10_correction(self.beta_h(v), 2.3, TEMPERATURE, REFERENCE_TEMP);
+        GateKinetics { inf: self.h_inf(v), tau: 1.0 / (ah + bh) }
+    }
 
-        state.m += (am * (1.0 - state.m) - bm * state.m) * dt;
-        state.h += (ah * (1.0 - state.h) - bh * state.h) * dt;
+    /// Open conductance g_max * m^3 * h
+    pub fn open_conductance(&self, state: &AdvancedChannelState) -> f64 {
+        self.g_max * state.m.powi(3) * state.h
+    }
+
+    pub fn conductance(&self, v: f64, state: &AdvancedChannelState) -> f64 {
+        self.open_conductance(state) * (v - self.e_na)
+    }
+
+    pub fn update(&self, v: f64, state: &mut AdvancedChannelState, dt: f64) {
+        self.activation(v).euler(&mut state.m, dt);
+        self.inactivation(v).euler(&mut state.h, dt);
     }
 }
 
@@ -116,18 +179,28 @@ impl Nav1_6 {
         1.5 + 1.0 / (1.0 + ((v + 60.0) / 15.0).exp())
     }
 
+    /// Activation (m) kinetics
+    pub fn activation(&self, v: f64) -> GateKinetics {
+        GateKinetics { inf: self.m_inf(v), tau: q10_tau(self.tau_m(v), 2.3) }
+    }
+
+    /// Inactivation (h) kinetics
+    pub fn inactivation(&self, v: f64) -> GateKinetics {
+        GateKinetics { inf: self.h_inf(v), tau: q10_tau(self.tau_h(v), 2.3) }
+    }
+
+    /// Open conductance g_max * m^3 * h
+    pub fn open_conductance(&self, state: &AdvancedChannelState) -> f64 {
+        self.g_max * state.m.powi(3) * state.h
+    }
+
     pub fn conductance(&self, v: f64, state: &AdvancedChannelState) -> f64 {
-        self.g_max * state.m.powi(3) * state.h * (v - self.e_na)
+        self.open_conductance(state) * (v - self.e_na)
     }
 
     pub fn update(&self, v: f64, state: &mut AdvancedChannelState, dt: f64) {
-        let m_inf = self.m_inf(v);
-        let h_inf = self.h_inf(v);
-        let tau_m = q10_correction(self.tau_m(v), 2.3, TEMPERATURE, REFERENCE_TEMP);
-        let tau_h = q10_correction(self.tau_h(v), 2.3, TEMPERATURE, REFERENCE_TEMP);
-
-        state.m += (m_inf - state.m) / tau_m * dt;
-        state.h += (h_inf - state.h) / tau_h * dt;
+        self.activation(v).euler(&mut state.m, dt);
+        self.inactivation(v).euler(&mut state.h, dt);
     }
 }
 
@@ -152,14 +225,22 @@ impl Kv1_1 {
         1.0 + 4.0 / (1.0 + ((v + 30.0) / 20.0).exp())
     }
 
+    /// Activation kinetics
+    pub fn activation(&self, v: f64) -> GateKinetics {
+        GateKinetics { inf: self.n_inf(v), tau: q10_tau(self.tau_n(v), 3.0) }
+    }
+
+    /// Open conductance g_max * m^4
+    pub fn open_conductance(&self, state: &AdvancedChannelState) -> f64 {
+        self.g_max * state.m.powi(4)
+    }
+
     pub fn conductance(&self, v: f64, state: &AdvancedChannelState) -> f64 {
-        self.g_max * state.m.powi(4) * (v - self.e_k)
+        self.open_conductance(state) * (v - self.e_k)
     }
 
     pub fn update(&self, v: f64, state: &mut AdvancedChannelState, dt: f64) {
-        let n_inf = self.n_inf(v);
-        let tau_n = q10_correction(self.tau_n(v), 3.0, TEMPERATURE, REFERENCE_TEMP);
-        state.m += (n_inf - state.m) / tau_n * dt;
+        self.activation(v).euler(&mut state.m, dt);
     }
 }
 
@@ -184,14 +265,22 @@ impl Kv3_1 {
         0.5 + 2.0 / (1.0 + ((v + 40.0) / 15.0).exp())
     }
 
+    /// Activation kinetics
+    pub fn activation(&self, v: f64) -> GateKinetics {
+        GateKinetics { inf: self.n_inf(v), tau: q10_tau(self.tau_n(v), 3.0) }
+    }
+
+    /// Open conductance g_max * m^4
+    pub fn open_conductance(&self, state: &AdvancedChannelState) -> f64 {
+        self.g_max * state.m.powi(4)
+    }
+
     pub fn conductance(&self, v: f64, state: &AdvancedChannelState) -> f64 {
-        self.g_max * state.m.powi(4) * (v - self.e_k)
+        self.open_conductance(state) * (v - self.e_k)
     }
 
     pub fn update(&self, v: f64, state: &mut AdvancedChannelState, dt: f64) {
-        let n_inf = self.n_inf(v);
-        let tau_n = q10_correction(self.tau_n(v), 3.0, TEMPERATURE, REFERENCE_TEMP);
-        state.m += (n_inf - state.m) / tau_n * dt;
+        self.activation(v).euler(&mut state.m, dt);
     }
 }
 
@@ -224,18 +313,28 @@ impl Kv4_2 {
         15.0 + 40.0 / (1.0 + ((v + 70.0) / 15.0).exp())
     }
 
+    /// Activation (m) kinetics
+    pub fn activation(&self, v: f64) -> GateKinetics {
+        GateKinetics { inf: self.m_inf(v), tau: q10_tau(self.tau_m(v), 3.0) }
+    }
+
+    /// Inactivation (h) kinetics
+    pub fn inactivation(&self, v: f64) -> GateKinetics {
+        GateKinetics { inf: self.h_inf(v), tau: q10_tau(self.tau_h(v), 3.0) }
+    }
+
+    /// Open conductance g_max * m^4 * h
+    pub fn open_conductance(&self, state: &AdvancedChannelState) -> f64 {
+        self.g_max * state.m.powi(4) * state.h
+    }
+
     pub fn conductance(&self, v: f64, state: &AdvancedChannelState) -> f64 {
-        self.g_max * state.m.powi(4) * state.h * (v - self.e_k)
+        self.open_conductance(state) * (v - self.e_k)
     }
 
     pub fn update(&self, v: f64, state: &mut AdvancedChannelState, dt: f64) {
-        let m_inf = self.m_inf(v);
-        let h_inf = self.h_inf(v);
-        let tau_m = q10_correction(self.tau_m(v), 3.0, TEMPERATURE, REFERENCE_TEMP);
-        let tau_h = q10_correction(self.tau_h(v), 3.0, TEMPERATURE, REFERENCE_TEMP);
-
-        state.m += (m_inf - state.m) / tau_m * dt;
-        state.h += (h_inf - state.h) / tau_h * dt;
+        self.activation(v).euler(&mut state.m, dt);
+        self.inactivation(v).euler(&mut state.h, dt);
     }
 }
 
@@ -260,14 +359,22 @@ impl Kv7_M {
         100.0 // Very slow activation
     }
 
+    /// Activation kinetics
+    pub fn activation(&self, v: f64) -> GateKinetics {
+        GateKinetics { inf: self.m_inf(v), tau: q10_tau(self.tau_m(v), 2.5) }
+    }
+
+    /// Open conductance g_max * m
+    pub fn open_conductance(&self, state: &AdvancedChannelState) -> f64 {
+        self.g_max * state.m
+    }
+
     pub fn conductance(&self, v: f64, state: &AdvancedChannelState) -> f64 {
-        self.g_max * state.m * (v - self.e_k)
+        self.open_conductance(state) * (v - self.e_k)
     }
 
     pub fn update(&self, v: f64, state: &mut AdvancedChannelState, dt: f64) {
-        let m_inf = self.m_inf(v);
-        let tau_m = q10_correction(self.tau_m(v), 2.5, TEMPERATURE, REFERENCE_TEMP);
-        state.m += (m_inf - state.m) / tau_m * dt;
+        self.activation(v).euler(&mut state.m, dt);
     }
 }
 
@@ -300,18 +407,28 @@ impl Cav1_2 {
         50.0
     }
 
+    /// Activation (m) kinetics
+    pub fn activation(&self, v: f64) -> GateKinetics {
+        GateKinetics { inf: self.m_inf(v), tau: q10_tau(self.tau_m(v), 3.0) }
+    }
+
+    /// Inactivation (h) kinetics
+    pub fn inactivation(&self, v: f64) -> GateKinetics {
+        GateKinetics { inf: self.h_inf(v), tau: q10_tau(self.tau_h(v), 3.0) }
+    }
+
+    /// Open conductance g_max * m^2 * h
+    pub fn open_conductance(&self, state: &AdvancedChannelState) -> f64 {
+        self.g_max * state.m.powi(2) * state.h
+    }
+
     pub fn conductance(&self, v: f64, state: &AdvancedChannelState) -> f64 {
-        self.g_max * state.m.powi(2) * state.h * (v - self.e_ca)
+        self.open_conductance(state) * (v - self.e_ca)
     }
 
     pub fn update(&self, v: f64, state: &mut AdvancedChannelState, dt: f64) {
-        let m_inf = self.m_inf(v);
-        let h_inf = self.h_inf(v);
-        let tau_m = q10_correction(self.tau_m(v), 3.0, TEMPERATURE, REFERENCE_TEMP);
-        let tau_h = q10_correction(self.tau_h(v), 3.0, TEMPERATURE, REFERENCE_TEMP);
-
-        state.m += (m_inf - state.m) / tau_m * dt;
-        state.h += (h_inf - state.h) / tau_h * dt;
+        self.activation(v).euler(&mut state.m, dt);
+        self.inactivation(v).euler(&mut state.h, dt);
     }
 }
 
@@ -344,18 +461,28 @@ impl Cav2_1 {
         25.0
     }
 
+    /// Activation (m) kinetics
+    pub fn activation(&self, v: f64) -> GateKinetics {
+        GateKinetics { inf: self.m_inf(v), tau: q10_tau(self.tau_m(v), 3.0) }
+    }
+
+    /// Inactivation (h) kinetics
+    pub fn inactivation(&self, v: f64) -> GateKinetics {
+        GateKinetics { inf: self.h_inf(v), tau: q10_tau(self.tau_h(v), 3.0) }
+    }
+
+    /// Open conductance g_max * m^2 * h
+    pub fn open_conductance(&self, state: &AdvancedChannelState) -> f64 {
+        self.g_max * state.m.powi(2) * state.h
+    }
+
     pub fn conductance(&self, v: f64, state: &AdvancedChannelState) -> f64 {
-        self.g_max * state.m.powi(2) * state.h * (v - self.e_ca)
+        self.open_conductance(state) * (v - self.e_ca)
     }
 
     pub fn update(&self, v: f64, state: &mut AdvancedChannelState, dt: f64) {
-        let m_inf = self.m_inf(v);
-        let h_inf = self.h_inf(v);
-        let tau_m = q10_correction(self.tau_m(v), 3.0, TEMPERATURE, REFERENCE_TEMP);
-        let tau_h = q10_correction(self.tau_h(v), 3.0, TEMPERATURE, REFERENCE_TEMP);
-
-        state.m += (m_inf - state.m) / tau_m * dt;
-        state.h += (h_inf - state.h) / tau_h * dt;
+        self.activation(v).euler(&mut state.m, dt);
+        self.inactivation(v).euler(&mut state.h, dt);
     }
 }
 
@@ -388,18 +515,28 @@ impl Cav2_2 {
         30.0
     }
 
+    /// Activation (m) kinetics
+    pub fn activation(&self, v: f64) -> GateKinetics {
+        GateKinetics { inf: self.m_inf(v), tau: q10_tau(self.tau_m(v), 3.0) }
+    }
+
+    /// Inactivation (h) kinetics
+    pub fn inactivation(&self, v: f64) -> GateKinetics {
+        GateKinetics { inf: self.h_inf(v), tau: q10_tau(self.tau_h(v), 3.0) }
+    }
+
+    /// Open conductance g_max * m^2 * h
+    pub fn open_conductance(&self, state: &AdvancedChannelState) -> f64 {
+        self.g_max * state.m.powi(2) * state.h
+    }
+
     pub fn conductance(&self, v: f64, state: &AdvancedChannelState) -> f64 {
-        self.g_max * state.m.powi(2) * state.h * (v - self.e_ca)
+        self.open_conductance(state) * (v - self.e_ca)
     }
 
     pub fn update(&self, v: f64, state: &mut AdvancedChannelState, dt: f64) {
-        let m_inf = self.m_inf(v);
-        let h_inf = self.h_inf(v);
-        let tau_m = q10_correction(self.tau_m(v), 3.0, TEMPERATURE, REFERENCE_TEMP);
-        let tau_h = q10_correction(self.tau_h(v), 3.0, TEMPERATURE, REFERENCE_TEMP);
-
-        state.m += (m_inf - state.m) / tau_m * dt;
-        state.h += (h_inf - state.h) / tau_h * dt;
+        self.activation(v).euler(&mut state.m, dt);
+        self.inactivation(v).euler(&mut state.h, dt);
     }
 }
 
@@ -432,18 +569,28 @@ impl Cav3_1 {
         20.0 + 50.0 / (1.0 + ((v + 70.0) / 10.0).exp())
     }
 
+    /// Activation (m) kinetics
+    pub fn activation(&self, v: f64) -> GateKinetics {
+        GateKinetics { inf: self.m_inf(v), tau: q10_tau(self.tau_m(v), 3.0) }
+    }
+
+    /// Inactivation (h) kinetics
+    pub fn inactivation(&self, v: f64) -> GateKinetics {
+        GateKinetics { inf: self.h_inf(v), tau: q10_tau(self.tau_h(v), 3.0) }
+    }
+
+    /// Open conductance g_max * m^2 * h
+    pub fn open_conductance(&self, state: &AdvancedChannelState) -> f64 {
+        self.g_max * state.m.powi(2) * state.h
+    }
+
     pub fn conductance(&self, v: f64, state: &AdvancedChannelState) -> f64 {
-        self.g_max * state.m.powi(2) * state.h * (v - self.e_ca)
+        self.open_conductance(state) * (v - self.e_ca)
     }
 
     pub fn update(&self, v: f64, state: &mut AdvancedChannelState, dt: f64) {
-        let m_inf = self.m_inf(v);
-        let h_inf = self.h_inf(v);
-        let tau_m = q10_correction(self.tau_m(v), 3.0, TEMPERATURE, REFERENCE_TEMP);
-        let tau_h = q10_correction(self.tau_h(v), 3.0, TEMPERATURE, REFERENCE_TEMP);
-
-        state.m += (m_inf - state.m) / tau_m * dt;
-        state.h += (h_inf - state.h) / tau_h * dt;
+        self.activation(v).euler(&mut state.m, dt);
+        self.inactivation(v).euler(&mut state.h, dt);
     }
 }
 
@@ -467,9 +614,13 @@ impl SK_Channel {
         ca_i.powi(4) / (ca_i.powi(4) + k_d.powf(n as f64))
     }
 
+    /// Open conductance: instantaneous Hill function of internal calcium
+    pub fn open_conductance(&self, state: &AdvancedChannelState) -> f64 {
+        self.g_max * self.m_inf(state.ca_i)
+    }
+
     pub fn conductance(&self, v: f64, state: &AdvancedChannelState) -> f64 {
-        let m_inf = self.m_inf(state.ca_i);
-        self.g_max * m_inf * (v - self.e_k)
+        self.open_conductance(state) * (v - self.e_k)
     }
 
     pub fn update(&self, _v: f64, state: &mut AdvancedChannelState, dt: f64) {
@@ -504,14 +655,22 @@ impl BK_Channel {
         1.0 // Fast kinetics
     }
 
+    /// Activation kinetics at internal calcium `ca_i` (uM)
+    pub fn activation(&self, v: f64, ca_i: f64) -> GateKinetics {
+        GateKinetics { inf: self.m_inf(v, ca_i), tau: q10_tau(self.tau_m(v), 3.0) }
+    }
+
+    /// Open conductance g_max * m^2
+    pub fn open_conductance(&self, state: &AdvancedChannelState) -> f64 {
+        self.g_max * state.m.powi(2)
+    }
+
     pub fn conductance(&self, v: f64, state: &AdvancedChannelState) -> f64 {
-        self.g_max * state.m.powi(2) * (v - self.e_k)
+        self.open_conductance(state) * (v - self.e_k)
     }
 
     pub fn update(&self, v: f64, state: &mut AdvancedChannelState, dt: f64) {
-        let m_inf = self.m_inf(v, state.ca_i);
-        let tau_m = q10_correction(self.tau_m(v), 3.0, TEMPERATURE, REFERENCE_TEMP);
-        state.m += (m_inf - state.m) / tau_m * dt;
+        self.activation(v, state.ca_i).euler(&mut state.m, dt);
 
         // Update calcium
         let ca_decay = 0.002;
@@ -542,14 +701,22 @@ impl HCN_Channel {
         100.0 + 500.0 / (1.0 + ((v + 70.0) / 10.0).exp())
     }
 
+    /// Activation kinetics
+    pub fn activation(&self, v: f64) -> GateKinetics {
+        GateKinetics { inf: self.m_inf(v), tau: q10_tau(self.tau_m(v), 2.5) }
+    }
+
+    /// Open conductance g_max * m
+    pub fn open_conductance(&self, state: &AdvancedChannelState) -> f64 {
+        self.g_max * state.m
+    }
+
     pub fn conductance(&self, v: f64, state: &AdvancedChannelState) -> f64 {
-        self.g_max * state.m * (v - self.e_h)
+        self.open_conductance(state) * (v - self.e_h)
     }
 
     pub fn update(&self, v: f64, state: &mut AdvancedChannelState, dt: f64) {
-        let m_inf = self.m_inf(v);
-        let tau_m = q10_correction(self.tau_m(v), 2.5, TEMPERATURE, REFERENCE_TEMP);
-        state.m += (m_inf - state.m) / tau_m * dt;
+        self.activation(v).euler(&mut state.m, dt);
     }
 }
 
@@ -581,16 +748,24 @@ impl NMDA_Advanced {
         glu / (glu + k_d)
     }
 
-    pub fn conductance(&self, v: f64, state: &AdvancedChannelState, glu: f64) -> f64 {
+    /// Gating kinetics at glutamate concentration `glu` (uM)
+    pub fn activation(&self, glu: f64) -> GateKinetics {
+        GateKinetics { inf: self.m_inf(glu), tau: 50.0 } // Slow kinetics
+    }
+
+    /// Open conductance, including the Mg2+ block at voltage `v`
+    pub fn open_conductance(&self, v: f64, state: &AdvancedChannelState, glu: f64) -> f64 {
         let mg_block = self.mg_block(v);
         let glu_binding = self.m_inf(glu);
-        self.g_max * glu_binding * state.m * mg_block * (v - self.e_nmda)
+        self.g_max * glu_binding * state.m * mg_block
+    }
+
+    pub fn conductance(&self, v: f64, state: &AdvancedChannelState, glu: f64) -> f64 {
+        self.open_conductance(v, state, glu) * (v - self.e_nmda)
     }
 
     pub fn update(&self, _v: f64, state: &mut AdvancedChannelState, dt: f64, glu: f64) {
-        let m_inf = self.m_inf(glu);
-        let tau_m = 50.0; // Slow kinetics
-        state.m += (m_inf - state.m) / tau_m * dt;
+        self.activation(glu).euler(&mut state.m, dt);
 
         // NMDA allows calcium influx
         if state.m > 0.1 {
@@ -621,6 +796,37 @@ mod tests {
         assert!(corrected > rate); // Should increase at higher temp
     }
 
+    #[test]
+    fn test_time_constants_shrink_with_temperature() {
+        // Nav1.6 activation, Q10 = 2.3 from the 22 C reference
+        let channel = Nav1_6::new(1.0);
+        let tau = channel.tau_m(-40.0);
+        let body = tau_at_temperature(tau, 2.3, 37.0);
+        let squid = tau_at_temperature(tau, 2.3, 6.3);
+
+        assert!((body - tau / 2.3f64.powf(1.5)).abs() < 1e-12);
+        assert!((squid - tau * 2.3f64.powf(1.57)).abs() < 1e-12);
+        assert!(body < tau && tau < squid);
+        assert_eq!(channel.activation(-40.0).tau, body);
+    }
+
+    #[test]
+    fn test_nav1_1_inactivation_is_boltzmann() {
+        let channel = Nav1_1::new(1.0);
+        let boltzmann = |v: f64| 1.0 / (1.0 + ((v + 65.0) / 6.2).exp());
+
+        assert!((channel.inactivation(-65.0).inf - 0.5).abs() < 1e-12);
+        for v in [-90.0, -75.0, -50.0] {
+            assert!((channel.inactivation(v).inf - boltzmann(v)).abs() < 1e-12);
+        }
+        // The time constant still comes from the alpha/beta rates
+        let rates = GateKinetics::from_rates(
+            q10_correction(channel.alpha_h(-65.0), 2.3, TEMPERATURE, REFERENCE_TEMP),
+            q10_correction(channel.beta_h(-65.0), 2.3, TEMPERATURE, REFERENCE_TEMP),
+        );
+        assert!((channel.inactivation(-65.0).tau - rates.tau).abs() < 1e-12);
+    }
+
     #[test]
     fn test_kv7_m_current() {
         let channel = Kv7_M::new(1.0);
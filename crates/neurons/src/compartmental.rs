@@ -7,6 +7,8 @@ use ndarray::{Array1, Array2};
 use serde::{Deserialize, Serialize};
 use crate::{Result, NeuronError, constants::*};
 use crate::channels::IonChannel;
+use crate::mechanisms::{calcium_pool_step, ChannelMechanism, GateUpdate, InsertedChannel, Ion};
+use crate::spikes::{SpikeDetector, SpikeEvent, SpikeSite};
 
 /// Type of neural compartment
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
@@ -19,6 +21,78 @@ pub enum CompartmentType {
     AxonInitialSegment,
 }
 
+/// Numerical scheme for the cable equation.
+///
+/// The implicit schemes solve the tree-structured linear system with the
+/// Hines algorithm (O(n), no fill-in) and update gating variables with
+/// exponential Euler, so they stay stable at dt = 0.025-0.1 ms on finely
+/// discretized morphologies where forward Euler diverges.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+pub enum Integrator {
+    /// Explicit forward Euler for voltage and gates (first order, conditionally stable)
+    #[default]
+    ForwardEuler,
+    /// Backward Euler (first order, L-stable)
+    BackwardEuler,
+    /// Crank-Nicolson (second order in voltage, A-stable)
+    CrankNicolson,
+}
+
+impl Integrator {
+    /// Implicitness weight of the voltage update (0 = explicit)
+    fn theta(self) -> f64 {
+        match self {
+            Integrator::ForwardEuler => 0.0,
+            Integrator::BackwardEuler => 1.0,
+            Integrator::CrankNicolson => 0.5,
+        }
+    }
+}
+
+/// Multiplicative scaling of membrane conductances by metabotropic
+/// neuromodulator receptors (1 = unmodulated). Applies to the HH channels,
+/// the inserted mechanisms carrying the same ion, and the leak.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct ChannelModulation {
+    pub sodium: f64,
+    pub potassium: f64,
+    pub calcium: f64,
+    pub leak: f64,
+}
+
+impl Default for ChannelModulation {
+    fn default() -> Self {
+        Self {
+            sodium: 1.0,
+            potassium: 1.0,
+            calcium: 1.0,
+            leak: 1.0,
+        }
+    }
+}
+
+impl ChannelModulation {
+    /// Scale factor for channels carrying `ion`
+    pub fn of(&self, ion: Ion) -> f64 {
+        match ion {
+            Ion::Sodium => self.sodium,
+            Ion::Potassium => self.potassium,
+            Ion::Calcium => self.calcium,
+            Ion::NonSpecific => 1.0,
+        }
+    }
+
+    /// Both modulations acting together
+    pub fn combine(&self, other: &ChannelModulation) -> ChannelModulation {
+        ChannelModulation {
+            sodium: self.sodium * other.sodium,
+            potassium: self.potassium * other.potassium,
+            calcium: self.calcium * other.calcium,
+            leak: self.leak * other.leak,
+        }
+    }
+}
+
 /// A single compartment in a multi-compartmental neuron.
 ///
 /// Each compartment has:
@@ -66,6 +140,18 @@ pub struct Compartment {
 
     /// Ion channel densities (channels per um^2)
     pub channel_densities: Vec<(String, f64)>,
+
+    /// Channel mechanisms from `channels_advanced`, each with its own state
+    #[serde(default)]
+    pub mechanisms: Vec<InsertedChannel>,
+
+    /// Extracellular glutamate seen by NMDA mechanisms (uM)
+    #[serde(default)]
+    pub glutamate: f64,
+
+    /// Midpoint in 3D space (um), e.g. from an SWC reconstruction
+    #[serde(default)]
+    pub position: [f64; 3],
 }
 
 impl Compartment {
@@ -96,6 +182,9 @@ impl Compartment {
             parent_idx: None,
             children_idx: Vec::new(),
             channel_densities: Vec::new(),
+            mechanisms: Vec::new(),
+            glutamate: 0.0,
+            position: [0.0; 3],
         }
     }
 
@@ -112,6 +201,41 @@ impl Compartment {
             .map(|(_, density)| density * self.surface_area)
             .unwrap_or(0.0)
     }
+
+    /// Insert a channel mechanism (g_max in mS/cm^2) with its gates at
+    /// steady state for the current voltage
+    pub fn insert_mechanism(&mut self, mechanism: ChannelMechanism) {
+        self.mechanisms.push(InsertedChannel::at_rest(mechanism, self.voltage, self.ca_concentration));
+    }
+
+    /// Total outward current (pA) through the inserted mechanisms
+    pub fn mechanism_current(&self) -> f64 {
+        self.mechanisms
+            .iter()
+            .map(|m| m.current(self.voltage, self.surface_area, self.glutamate))
+            .sum()
+    }
+
+    /// Advance the calcium pool and the mechanism gates by `dt`. The pool is
+    /// only driven when calcium channels are inserted.
+    fn advance_mechanisms(&mut self, dt: f64, update: GateUpdate, modulation: &ChannelModulation) {
+        let (v, area, glutamate) = (self.voltage, self.surface_area, self.glutamate);
+
+        let mut has_calcium = false;
+        let mut i_ca = 0.0;
+        for m in self.mechanisms.iter().filter(|m| m.mechanism.ion() == Ion::Calcium) {
+            has_calcium = true;
+            i_ca += m.current(v, area, glutamate) * modulation.calcium;
+        }
+        if has_calcium {
+            self.ca_concentration = calcium_pool_step(self.ca_concentration, i_ca, area, dt);
+        }
+
+        let ca_i = self.ca_concentration * 1000.0; // mM -> uM
+        for m in &mut self.mechanisms {
+            m.advance(v, dt, ca_i, glutamate, update);
+        }
+    }
 }
 
 /// Multi-compartmental neuron model.
@@ -120,7 +244,7 @@ impl Compartment {
 /// C_m * dV/dt = -I_ion - I_axial + I_ext
 ///
 /// where I_axial couples adjacent compartments.
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct MultiCompartmentalNeuron {
     /// Neuron ID
     pub id: usize,
@@ -143,8 +267,50 @@ pub struct MultiCompartmentalNeuron {
     /// Last spike time (ms)
     pub last_spike_time: f64,
 
-    /// Is neuron currently spiking?
+    /// Did an action potential start during the last step?
     pub is_spiking: bool,
+
+    /// Simulated time (ms)
+    #[serde(default)]
+    pub time: f64,
+
+    /// Threshold-crossing detector and spike log
+    #[serde(default)]
+    pub spike_detector: SpikeDetector,
+
+    /// Compartment monitored by the spike detector, resolved from its site
+    /// when the site is set
+    #[serde(default)]
+    spike_compartment: usize,
+
+    /// Cable equation solver
+    #[serde(default)]
+    pub integrator: Integrator,
+
+    /// Neuromodulatory scaling of the membrane conductances
+    #[serde(default)]
+    pub modulation: ChannelModulation,
+
+    /// Gap-junction coupling to other cells for the next step
+    #[serde(default)]
+    pub gap_inputs: Vec<GapInput>,
+}
+
+/// Electrical coupling of one compartment to a compartment of another cell.
+///
+/// The partner voltage is frozen over a step while the compartment's own
+/// voltage enters the implicit solve, so with the implicit integrators the
+/// coupling current g * (V_partner - V) stays stable for any conductance.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct GapInput {
+    /// Coupled compartment of this neuron
+    pub compartment: usize,
+
+    /// Junction conductance (nS)
+    pub conductance: f64,
+
+    /// Voltage of the partner compartment at the start of the step (mV)
+    pub partner_voltage: f64,
 }
 
 impl MultiCompartmentalNeuron {
@@ -173,6 +339,12 @@ impl MultiCompartmentalNeuron {
             spike_threshold: -40.0,
             last_spike_time: -1000.0,
             is_spiking: false,
+            time: 0.0,
+            spike_detector: SpikeDetector::default(),
+            spike_compartment: 0,
+            integrator: Integrator::default(),
+            modulation: ChannelModulation::default(),
+            gap_inputs: Vec::new(),
         }
     }
 
@@ -181,7 +353,8 @@ impl MultiCompartmentalNeuron {
         let mut neuron = Self::new(id, 1, dt);
         neuron.compartments.clear();
 
-        // Soma
+        // Soma at the origin; the apical dendrite points along +z (towards
+        // the pia), basal dendrites fan out below it and the axon descends
         let soma = Compartment::new(CompartmentType::Soma, 25.0, 25.0);
         neuron.compartments.push(soma);
 
@@ -193,6 +366,7 @@ impl MultiCompartmentalNeuron {
                 2.0 - (i as f64 * 0.015), // Tapering
             );
             comp.parent_idx = Some(i);
+            comp.position = [0.0, 0.0, 12.5 + 10.0 * i as f64 + 5.0];
             if i > 0 {
                 neuron.compartments[i].children_idx.push(i + 1);
             } else {
@@ -209,6 +383,9 @@ impl MultiCompartmentalNeuron {
                 1.5,
             );
             comp.parent_idx = Some(0); // All connect to soma
+            let angle = std::f64::consts::TAU * i as f64 / 50.0;
+            let reach = 12.5 + 4.0;
+            comp.position = [reach * 0.95 * angle.cos(), reach * 0.95 * angle.sin(), -reach * 0.3];
             neuron.compartments[0].children_idx.push(101 + i);
             neuron.compartments.push(comp);
         }
@@ -216,6 +393,7 @@ impl MultiCompartmentalNeuron {
         // Axon initial segment
         let mut ais = Compartment::new(CompartmentType::AxonInitialSegment, 30.0, 1.0);
         ais.parent_idx = Some(0);
+        ais.position = [0.0, 0.0, -(12.5 + 15.0)];
         neuron.compartments[0].children_idx.push(151);
         neuron.compartments.push(ais);
 
@@ -227,8 +405,222 @@ impl MultiCompartmentalNeuron {
         neuron
     }
 
+    /// Pyramidal cell with the channel distribution of neocortical layer 5
+    /// neurons: Nav1.6 concentrated at the axon initial segment, Nav1.1 with
+    /// Kv3.1/Kv1.1 at the soma, and A-type Kv4.2 plus HCN along the apical
+    /// dendrite (densities in mS/cm^2)
+    pub fn new_pyramidal_active(id: usize, dt: f64) -> Self {
+        use crate::channels_advanced::*;
+
+        let mut neuron = Self::new_pyramidal(id, dt);
+        neuron.insert_mechanism(CompartmentType::AxonInitialSegment, ChannelMechanism::Nav1_6(Nav1_6::new(400.0)));
+        neuron.insert_mechanism(CompartmentType::AxonInitialSegment, ChannelMechanism::Kv3_1(Kv3_1::new(100.0)));
+        neuron.insert_mechanism(CompartmentType::Soma, ChannelMechanism::Nav1_1(Nav1_1::new(200.0)));
+        neuron.insert_mechanism(CompartmentType::Soma, ChannelMechanism::Kv3_1(Kv3_1::new(150.0)));
+        neuron.insert_mechanism(CompartmentType::Soma, ChannelMechanism::Kv1_1(Kv1_1::new(5.0)));
+        neuron.insert_mechanism(CompartmentType::ApicalDendrite, ChannelMechanism::Kv4_2(Kv4_2::new(10.0)));
+        neuron.insert_mechanism(CompartmentType::ApicalDendrite, ChannelMechanism::HCN(HCN_Channel::new(0.5)));
+        neuron
+    }
+
+    /// Builder: squid-axon Hodgkin-Huxley excitability, with Na+ (120
+    /// mS/cm^2) and K+ (36 mS/cm^2) at the soma and axon initial segment and
+    /// the 0.3 mS/cm^2 leak over the whole membrane
+    pub fn with_hodgkin_huxley(mut self) -> Self {
+        for comp in &mut self.compartments {
+            comp.g_leak = 0.003 * comp.surface_area;
+            if matches!(comp.compartment_type, CompartmentType::Soma | CompartmentType::AxonInitialSegment) {
+                comp.add_channel("Na".to_string(), 1.2);
+                comp.add_channel("K".to_string(), 0.36);
+            }
+        }
+        self
+    }
+
+    /// Use `integrator` for subsequent steps
+    pub fn with_integrator(mut self, integrator: Integrator) -> Self {
+        self.integrator = integrator;
+        self
+    }
+
+    /// Insert `mechanism` into every compartment of the given type
+    pub fn insert_mechanism(&mut self, compartment_type: CompartmentType, mechanism: ChannelMechanism) {
+        for comp in self.compartments.iter_mut().filter(|c| c.compartment_type == compartment_type) {
+            comp.insert_mechanism(mechanism.clone());
+        }
+    }
+
+    /// Scale the maximal conductance of every mechanism called `name`
+    /// (e.g. `"Nav1.1"`, 0.5 for a heterozygous loss-of-function allele).
+    /// Returns the number of mechanisms changed.
+    pub fn scale_mechanism(&mut self, name: &str, factor: f64) -> usize {
+        let mut scaled = 0;
+        for comp in &mut self.compartments {
+            for m in comp.mechanisms.iter_mut().filter(|m| m.mechanism.name() == name) {
+                m.mechanism.scale(factor);
+                scaled += 1;
+            }
+        }
+        scaled
+    }
+
     /// Step the neuron simulation forward by dt
     pub fn step(&mut self, channel_states: &mut Vec<ChannelStates>) {
+        let site = self.spike_site_index();
+        let v_before = self.compartments[site].voltage;
+        match self.integrator {
+            Integrator::ForwardEuler => self.step_forward_euler(channel_states),
+            implicit => self.step_implicit(channel_states, implicit.theta()),
+        }
+
+        let v_after = self.compartments[site].voltage;
+        let spike = self.spike_detector.detect(v_before, v_after, self.time, self.dt, self.spike_threshold);
+        if let Some(time) = spike {
+            self.last_spike_time = time;
+        }
+        self.is_spiking = spike.is_some();
+        self.time += self.dt;
+    }
+
+    /// Builder: detect spikes at `site`
+    pub fn with_spike_site(mut self, site: SpikeSite) -> Self {
+        self.spike_detector.site = site;
+        self.spike_compartment = match site {
+            SpikeSite::Soma => 0,
+            SpikeSite::AxonInitialSegment => self
+                .compartments
+                .iter()
+                .position(|c| c.compartment_type == CompartmentType::AxonInitialSegment)
+                .unwrap_or(0),
+        };
+        self
+    }
+
+    /// Compartment monitored by the spike detector
+    pub fn spike_site_index(&self) -> usize {
+        self.spike_compartment
+    }
+
+    /// Logged spikes, oldest first
+    pub fn spike_events(&self) -> impl Iterator<Item = SpikeEvent> + '_ {
+        let neuron_id = self.id;
+        self.spike_detector.spike_times().map(move |time| SpikeEvent { neuron_id, time })
+    }
+
+    /// Spike detected during the last step, if any
+    pub fn last_step_spike(&self) -> Option<SpikeEvent> {
+        self.is_spiking.then_some(SpikeEvent { neuron_id: self.id, time: self.last_spike_time })
+    }
+
+    /// Total gap-junction conductance (nS) and drive sum(g * V_partner) (pA)
+    /// onto compartment `i`
+    fn gap_coupling(&self, i: usize) -> (f64, f64) {
+        self.gap_inputs
+            .iter()
+            .filter(|gap| gap.compartment == i)
+            .fold((0.0, 0.0), |(g, drive), gap| (g + gap.conductance, drive + gap.conductance * gap.partner_voltage))
+    }
+
+    /// Net gap-junction current into compartment `i` at its present voltage (pA)
+    pub fn gap_current(&self, i: usize) -> f64 {
+        let (g, drive) = self.gap_coupling(i);
+        drive - g * self.compartments[i].voltage
+    }
+
+    /// Hines ordering of the compartment tree: every compartment appears
+    /// after its parent (breadth-first from each root), so eliminating in
+    /// reverse order never creates fill-in.
+    pub fn hines_order(&self) -> Vec<usize> {
+        let n = self.compartments.len();
+        let mut children = vec![Vec::new(); n];
+        let mut order = Vec::with_capacity(n);
+        for (i, comp) in self.compartments.iter().enumerate() {
+            match comp.parent_idx {
+                Some(parent) => children[parent].push(i),
+                None => order.push(i),
+            }
+        }
+
+        let mut next = 0;
+        while next < order.len() {
+            let i = order[next];
+            order.extend_from_slice(&children[i]);
+            next += 1;
+        }
+
+        debug_assert_eq!(order.len(), n, "compartment topology contains a cycle");
+        order
+    }
+
+    /// Theta-method step of the cable equation (1 = backward Euler,
+    /// 1/2 = Crank-Nicolson) solved with the Hines algorithm, with gating
+    /// variables frozen during the voltage solve and then advanced by
+    /// exponential Euler.
+    fn step_implicit(&mut self, channel_states: &mut [ChannelStates], theta: f64) {
+        let n = self.compartments.len();
+        let dt = self.dt;
+        let order = self.hines_order();
+
+        // Membrane as a conductance g and a source g*E: I_m = g*V - g*E
+        let mut diag = vec![0.0; n];
+        let mut rhs = vec![0.0; n];
+        let mut off = vec![0.0; n];
+
+        for i in 0..n {
+            let comp = &self.compartments[i];
+            let (g, g_e) = self.membrane_conductance(i, &channel_states[i]);
+            let v = comp.voltage;
+            let c = comp.capacitance / dt;
+            let i_ext = self.external_current[i] + self.synaptic_current[i];
+
+            // Gap junctions are backward Euler in the own voltage whatever
+            // theta is: with the partner lagging one step, Crank-Nicolson
+            // would amplify the voltage difference for large conductances
+            let (g_gap, gap_drive) = self.gap_coupling(i);
+
+            diag[i] += c + theta * g + g_gap;
+            rhs[i] += c * v - (1.0 - theta) * g * v + g_e + i_ext + gap_drive;
+
+            if let Some(p) = comp.parent_idx {
+                let a = 1.0 / comp.axial_resistance;
+                let flow = a * (self.compartments[p].voltage - v);
+
+                diag[i] += theta * a;
+                diag[p] += theta * a;
+                off[i] = -theta * a;
+                rhs[i] += (1.0 - theta) * flow;
+                rhs[p] -= (1.0 - theta) * flow;
+            }
+        }
+
+        // Eliminate leaves towards the roots...
+        for &i in order.iter().rev() {
+            if let Some(p) = self.compartments[i].parent_idx {
+                let f = off[i] / diag[i];
+                diag[p] -= f * off[i];
+                rhs[p] -= f * rhs[i];
+            }
+        }
+
+        // ...then back-substitute from the roots outwards
+        for &i in &order {
+            let v = match self.compartments[i].parent_idx {
+                Some(p) => (rhs[i] - off[i] * self.compartments[p].voltage) / diag[i],
+                None => rhs[i] / diag[i],
+            };
+            self.compartments[i].voltage = v;
+        }
+
+        for (i, states) in channel_states.iter_mut().enumerate() {
+            self.update_channel_states_exponential(i, states);
+        }
+        for comp in &mut self.compartments {
+            comp.advance_mechanisms(dt, GateUpdate::Exponential, &self.modulation);
+        }
+    }
+
+    /// Forward Euler step of voltages and gating variables
+    fn step_forward_euler(&mut self, channel_states: &mut [ChannelStates]) {
         let n = self.compartments.len();
         let mut dv = Array1::zeros(n);
 
@@ -237,7 +629,7 @@ impl MultiCompartmentalNeuron {
             let comp = &self.compartments[i];
 
             // Leak current
-            let i_leak = comp.g_leak * (comp.voltage - comp.e_leak);
+            let i_leak = comp.g_leak * self.modulation.leak * (comp.voltage - comp.e_leak);
 
             // Ion channel currents
             let i_ion = self.calculate_ion_currents(i, &channel_states[i]);
@@ -258,8 +650,8 @@ impl MultiCompartmentalNeuron {
                 })
                 .sum();
 
-            // External and synaptic currents
-            let i_ext = self.external_current[i] + self.synaptic_current[i];
+            // External, synaptic and gap-junction currents
+            let i_ext = self.external_current[i] + self.synaptic_current[i] + self.gap_current(i);
 
             // Cable equation: C * dV/dt = -I_leak - I_ion + I_axial + I_ext
             dv[i] = (-i_leak - i_ion + i_axial_parent + i_axial_children + i_ext)
@@ -275,20 +667,22 @@ impl MultiCompartmentalNeuron {
         for i in 0..n {
             self.update_channel_states(i, &mut channel_states[i]);
         }
-
-        // Detect spikes at soma
-        self.is_spiking = self.compartments[0].voltage > self.spike_threshold;
+        let dt = self.dt;
+        for comp in &mut self.compartments {
+            comp.advance_mechanisms(dt, GateUpdate::Euler, &self.modulation);
+        }
     }
 
     /// Calculate ion channel currents for a compartment
     fn calculate_ion_currents(&self, comp_idx: usize, states: &ChannelStates) -> f64 {
         let comp = &self.compartments[comp_idx];
         let v = comp.voltage;
+        let modulation = &self.modulation;
 
         let mut total_current = 0.0;
 
         // Hodgkin-Huxley Na+ current
-        let g_na_bar = comp.get_channel_conductance("Na");
+        let g_na_bar = comp.get_channel_conductance("Na") * modulation.sodium;
         if g_na_bar > 0.0 {
             let e_na = 50.0; // mV
             let g_na = g_na_bar * states.na_m.powi(3) * states.na_h;
@@ -296,7 +690,7 @@ impl MultiCompartmentalNeuron {
         }
 
         // Hodgkin-Huxley K+ current
-        let g_k_bar = comp.get_channel_conductance("K");
+        let g_k_bar = comp.get_channel_conductance("K") * modulation.potassium;
         if g_k_bar > 0.0 {
             let e_k = -90.0; // mV
             let g_k = g_k_bar * states.k_n.powi(4);
@@ -304,43 +698,106 @@ impl MultiCompartmentalNeuron {
         }
 
         // Calcium current
-        let g_ca_bar = comp.get_channel_conductance("Ca");
+        let g_ca_bar = comp.get_channel_conductance("Ca") * modulation.calcium;
         if g_ca_bar > 0.0 {
             let e_ca = 120.0; // mV
             let g_ca = g_ca_bar * states.ca_m.powi(2) * states.ca_h;
             total_current += g_ca * (v - e_ca);
         }
 
+        // Inserted channel mechanisms
+        total_current += comp
+            .mechanisms
+            .iter()
+            .map(|m| m.current(v, comp.surface_area, comp.glutamate) * modulation.of(m.mechanism.ion()))
+            .sum::<f64>();
+
         total_current
     }
 
+    /// Total membrane conductance (nS) and conductance-weighted reversal
+    /// sum (pA) of a compartment, so that I_membrane = g*V - g_e
+    fn membrane_conductance(&self, comp_idx: usize, states: &ChannelStates) -> (f64, f64) {
+        let comp = &self.compartments[comp_idx];
+        let modulation = &self.modulation;
+        let g_leak = comp.g_leak * modulation.leak;
+        let mut g = g_leak;
+        let mut g_e = g_leak * comp.e_leak;
+
+        let g_na = comp.get_channel_conductance("Na") * modulation.sodium * states.na_m.powi(3) * states.na_h;
+        let g_k = comp.get_channel_conductance("K") * modulation.potassium * states.k_n.powi(4);
+        let g_ca = comp.get_channel_conductance("Ca") * modulation.calcium * states.ca_m.powi(2) * states.ca_h;
+
+        for (g_x, e_x) in [(g_na, 50.0), (g_k, -90.0), (g_ca, 120.0)] {
+            g += g_x;
+            g_e += g_x * e_x;
+        }
+
+        for m in &comp.mechanisms {
+            let g_x = m.conductance_ns(comp.voltage, comp.surface_area, comp.glutamate)
+                * modulation.of(m.mechanism.ion());
+            g += g_x;
+            g_e += g_x * m.mechanism.reversal();
+        }
+
+        (g, g_e)
+    }
+
     /// Update ion channel gating variables
     fn update_channel_states(&self, comp_idx: usize, states: &mut ChannelStates) {
         let v = self.compartments[comp_idx].voltage;
+        let r = GateRates::at(v);
 
         // Sodium channel (Hodgkin-Huxley)
-        let alpha_m = 0.1 * (v + 40.0) / (1.0 - (-0.1 * (v + 40.0)).exp());
-        let beta_m = 4.0 * ((-0.0556 * (v + 65.0)).exp());
-        let alpha_h = 0.07 * ((-0.05 * (v + 65.0)).exp());
-        let beta_h = 1.0 / (1.0 + ((-0.1 * (v + 35.0)).exp()));
-
-        states.na_m += (alpha_m * (1.0 - states.na_m) - beta_m * states.na_m) * self.dt;
-        states.na_h += (alpha_h * (1.0 - states.na_h) - beta_h * states.na_h) * self.dt;
+        states.na_m += (r.alpha_m * (1.0 - states.na_m) - r.beta_m * states.na_m) * self.dt;
+        states.na_h += (r.alpha_h * (1.0 - states.na_h) - r.beta_h * states.na_h) * self.dt;
 
         // Potassium channel
-        let alpha_n = 0.01 * (v + 55.0) / (1.0 - (-0.1 * (v + 55.0)).exp());
-        let beta_n = 0.125 * ((-0.0125 * (v + 65.0)).exp());
-
-        states.k_n += (alpha_n * (1.0 - states.k_n) - beta_n * states.k_n) * self.dt;
+        states.k_n += (r.alpha_n * (1.0 - states.k_n) - r.beta_n * states.k_n) * self.dt;
 
         // Calcium channel (simplified)
-        let ca_m_inf = 1.0 / (1.0 + ((-0.15 * (v + 20.0)).exp()));
-        let ca_h_inf = 1.0 / (1.0 + ((0.2 * (v + 50.0)).exp()));
-        let tau_ca_m = 0.5;
-        let tau_ca_h = 20.0;
+        states.ca_m += ((r.ca_m_inf - states.ca_m) / r.tau_ca_m) * self.dt;
+        states.ca_h += ((r.ca_h_inf - states.ca_h) / r.tau_ca_h) * self.dt;
+    }
 
-        states.ca_m += ((ca_m_inf - states.ca_m) / tau_ca_m) * self.dt;
-        states.ca_h += ((ca_h_inf - states.ca_h) / tau_ca_h) * self.dt;
+    /// Exponential Euler update of the gating variables: exact for the
+    /// linear gate ODE at fixed voltage, so gates stay in [0, 1] at any dt
+    fn update_channel_states_exponential(&self, comp_idx: usize, states: &mut ChannelStates) {
+        let v = self.compartments[comp_idx].voltage;
+        let r = GateRates::at(v);
+        let dt = self.dt;
+
+        let relax = |x: &mut f64, x_inf: f64, tau: f64| {
+            *x = x_inf + (*x - x_inf) * (-dt / tau).exp();
+        };
+        let relax_ab = |x: &mut f64, alpha: f64, beta: f64| {
+            let rate = alpha + beta;
+            relax(x, alpha / rate, 1.0 / rate);
+        };
+
+        relax_ab(&mut states.na_m, r.alpha_m, r.beta_m);
+        relax_ab(&mut states.na_h, r.alpha_h, r.beta_h);
+        relax_ab(&mut states.k_n, r.alpha_n, r.beta_n);
+        relax(&mut states.ca_m, r.ca_m_inf, r.tau_ca_m);
+        relax(&mut states.ca_h, r.ca_h_inf, r.tau_ca_h);
+    }
+
+    /// Net transmembrane current of each compartment (pA, outward positive).
+    ///
+    /// By current conservation the capacitive, ionic, synaptic and injected
+    /// currents crossing a compartment's membrane add up to the axial current
+    /// flowing into it from its neighbours, so the currents of one cell sum
+    /// to zero: the sources and sinks seen by extracellular electrodes.
+    pub fn membrane_currents(&self) -> Vec<f64> {
+        let mut currents = vec![0.0; self.compartments.len()];
+        for (i, comp) in self.compartments.iter().enumerate() {
+            if let Some(p) = comp.parent_idx {
+                let axial = (self.compartments[p].voltage - comp.voltage) / comp.axial_resistance;
+                currents[i] += axial;
+                currents[p] -= axial;
+            }
+        }
+        currents
     }
 
     /// Get soma voltage
@@ -356,6 +813,42 @@ impl MultiCompartmentalNeuron {
     }
 }
 
+/// Voltage-dependent gating rates (1/ms) and steady states of `ChannelStates`
+struct GateRates {
+    alpha_m: f64,
+    beta_m: f64,
+    alpha_h: f64,
+    beta_h: f64,
+    alpha_n: f64,
+    beta_n: f64,
+    ca_m_inf: f64,
+    ca_h_inf: f64,
+    tau_ca_m: f64,
+    tau_ca_h: f64,
+}
+
+impl GateRates {
+    fn at(v: f64) -> Self {
+        Self {
+            // Sodium channel (Hodgkin-Huxley)
+            alpha_m: 0.1 * (v + 40.0) / (1.0 - (-0.1 * (v + 40.0)).exp()),
+            beta_m: 4.0 * ((-0.0556 * (v + 65.0)).exp()),
+            alpha_h: 0.07 * ((-0.05 * (v + 65.0)).exp()),
+            beta_h: 1.0 / (1.0 + ((-0.1 * (v + 35.0)).exp())),
+
+            // Potassium channel
+            alpha_n: 0.01 * (v + 55.0) / (1.0 - (-0.1 * (v + 55.0)).exp()),
+            beta_n: 0.125 * ((-0.0125 * (v + 65.0)).exp()),
+
+            // Calcium channel (simplified)
+            ca_m_inf: 1.0 / (1.0 + ((-0.15 * (v + 20.0)).exp())),
+            ca_h_inf: 1.0 / (1.0 + ((0.2 * (v + 50.0)).exp())),
+            tau_ca_m: 0.5,
+            tau_ca_h: 20.0,
+        }
+    }
+}
+
 /// Ion channel gating variables
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct ChannelStates {
@@ -398,6 +891,287 @@ mod tests {
         assert_eq!(neuron.compartments[0].compartment_type, CompartmentType::Soma);
     }
 
+    /// Pyramidal cell with active soma, driven for `t_stop` ms from the soma
+    fn soma_trace(integrator: Integrator, dt: f64, t_stop: f64) -> Vec<f64> {
+        let mut neuron = MultiCompartmentalNeuron::new_pyramidal(0, dt).with_integrator(integrator);
+        neuron.compartments[0].add_channel("Na".to_string(), 0.05);
+        neuron.compartments[0].add_channel("K".to_string(), 0.02);
+        neuron.compartments[0].add_channel("Ca".to_string(), 0.001);
+        neuron.inject_current(0, 40.0);
+        neuron.inject_current(60, 5.0);
+
+        let mut states = vec![ChannelStates::default(); neuron.compartments.len()];
+        let steps = (t_stop / dt).round() as usize;
+        let every = (0.1 / dt).round() as usize;
+        let mut trace = Vec::new();
+        for step in 1..=steps {
+            neuron.step(&mut states);
+            if step % every == 0 {
+                trace.push(neuron.compartments[0].voltage);
+                trace.push(neuron.compartments[60].voltage);
+            }
+        }
+        trace
+    }
+
+    fn max_error(a: &[f64], b: &[f64]) -> f64 {
+        assert_eq!(a.len(), b.len());
+        a.iter().zip(b).map(|(x, y)| (x - y).abs()).fold(0.0, f64::max)
+    }
+
+    #[test]
+    fn test_implicit_solvers_converge_to_forward_euler() {
+        let t_stop = 10.0;
+        let reference = soma_trace(Integrator::ForwardEuler, 0.001, t_stop);
+
+        for integrator in [Integrator::BackwardEuler, Integrator::CrankNicolson] {
+            let errors: Vec<f64> = [0.1, 0.05, 0.025]
+                .iter()
+                .map(|&dt| max_error(&soma_trace(integrator, dt, t_stop), &reference))
+                .collect();
+
+            // Error shrinks with dt (at least first order)
+            for pair in errors.windows(2) {
+                assert!(pair[1] < pair[0] / 1.6, "{integrator:?}: {errors:?}");
+            }
+            assert!(errors[2] < 0.1, "{integrator:?}: {errors:?}");
+        }
+
+        // Crank-Nicolson is markedly more accurate than backward Euler
+        let be = max_error(&soma_trace(Integrator::BackwardEuler, 0.05, t_stop), &reference);
+        let cn = max_error(&soma_trace(Integrator::CrankNicolson, 0.05, t_stop), &reference);
+        assert!(cn < be / 4.0);
+    }
+
+    #[test]
+    fn test_implicit_solvers_stable_on_fine_trees() {
+        // Soma plus a branched tree of 0.1 um segments: far too stiff for
+        // forward Euler at dt = 0.025 ms
+        let run = |integrator: Integrator| {
+            let mut neuron = MultiCompartmentalNeuron::new(0, 1, 0.025).with_integrator(integrator);
+            for i in 1..=40 {
+                let parent = if i == 21 { 10 } else { i - 1 };
+                let mut comp = Compartment::new(CompartmentType::Dendrite, 0.1, 2.0);
+                comp.add_channel("Na".to_string(), 0.05);
+                comp.add_channel("K".to_string(), 0.02);
+                comp.parent_idx = Some(parent);
+                neuron.compartments[parent].children_idx.push(i);
+                neuron.compartments.push(comp);
+            }
+            let n = neuron.compartments.len();
+            neuron.external_current = Array1::zeros(n);
+            neuron.synaptic_current = Array1::zeros(n);
+            neuron.inject_current(40, 2.0);
+
+            let mut states = vec![ChannelStates::default(); n];
+            for _ in 0..400 {
+                neuron.step(&mut states);
+            }
+            (neuron, states)
+        };
+
+        let (explicit, _) = run(Integrator::ForwardEuler);
+        assert!(explicit.compartments.iter().any(|c| !c.voltage.is_finite() || c.voltage.abs() > 1e3));
+
+        for integrator in [Integrator::BackwardEuler, Integrator::CrankNicolson] {
+            let (neuron, states) = run(integrator);
+            assert!(neuron.compartments.iter().all(|c| c.voltage > -100.0 && c.voltage < 60.0));
+            assert!(states.iter().all(|s| {
+                [s.na_m, s.na_h, s.k_n, s.ca_m, s.ca_h].iter().all(|x| (0.0..=1.0).contains(x))
+            }));
+        }
+    }
+
+    #[test]
+    fn test_stiff_gap_junction_relaxes_monotonically() {
+        // 100 nS onto a 20 um soma is stiff at dt = 0.1 ms; the voltage
+        // must approach the clamped partner without overshooting
+        let mut neuron = MultiCompartmentalNeuron::new(0, 1, 0.1).with_integrator(Integrator::CrankNicolson);
+        neuron.gap_inputs.push(GapInput { compartment: 0, conductance: 100.0, partner_voltage: -50.0 });
+        let mut states = vec![ChannelStates::default()];
+
+        let mut previous = neuron.get_soma_voltage();
+        for _ in 0..50 {
+            neuron.step(&mut states);
+            let v = neuron.get_soma_voltage();
+            assert!(v >= previous && v <= -50.0, "{previous} -> {v}");
+            previous = v;
+        }
+        assert!(previous > -51.0);
+    }
+
+    #[test]
+    fn test_synaptic_input_is_a_current_sink() {
+        let mut neuron = MultiCompartmentalNeuron::new_pyramidal(0, 0.025).with_integrator(Integrator::BackwardEuler);
+        neuron.synaptic_current[50] = 20.0;
+        let mut states = vec![ChannelStates::default(); neuron.compartments.len()];
+        for _ in 0..40 {
+            neuron.step(&mut states);
+        }
+
+        // Inward current at the synapse returns through the rest of the cell
+        let currents = neuron.membrane_currents();
+        assert!(currents[50] < 0.0);
+        assert!(currents[49] > 0.0 && currents[51] > 0.0);
+        assert!(currents.iter().sum::<f64>().abs() < 1e-9);
+        assert!(neuron.compartments[50].position[2] > neuron.compartments[49].position[2]);
+    }
+
+    #[test]
+    fn test_hines_order_visits_parents_first() {
+        let neuron = MultiCompartmentalNeuron::new_pyramidal(0, 0.025);
+        let order = neuron.hines_order();
+        assert_eq!(order.len(), neuron.compartments.len());
+
+        let mut position = vec![0; order.len()];
+        for (k, &i) in order.iter().enumerate() {
+            position[i] = k;
+        }
+        for (i, comp) in neuron.compartments.iter().enumerate() {
+            if let Some(p) = comp.parent_idx {
+                assert!(position[p] < position[i]);
+            }
+        }
+    }
+
+    /// Soma spike onsets and peak voltage over `t_stop` ms of `current` pA
+    /// somatic injection
+    fn drive_soma(neuron: &mut MultiCompartmentalNeuron, current: f64, t_stop: f64) -> (usize, f64) {
+        let mut states = vec![ChannelStates::default(); neuron.compartments.len()];
+        neuron.inject_current(0, current);
+
+        let mut spikes = 0;
+        let mut peak = f64::NEG_INFINITY;
+        let mut was_spiking = false;
+        for _ in 0..(t_stop / neuron.dt) as usize {
+            neuron.step(&mut states);
+            peak = peak.max(neuron.get_soma_voltage());
+            if neuron.is_spiking && !was_spiking {
+                spikes += 1;
+            }
+            was_spiking = neuron.is_spiking;
+        }
+        (spikes, peak)
+    }
+
+    #[test]
+    fn test_action_potential_counted_once() {
+        let mut neuron = MultiCompartmentalNeuron::new_pyramidal_active(0, 0.01);
+        assert_eq!(neuron.spike_site_index(), 0);
+        assert_eq!(neuron.clone().with_spike_site(SpikeSite::AxonInitialSegment).spike_site_index(), 151);
+        assert_eq!(MultiCompartmentalNeuron::new(1, 3, 0.01).with_spike_site(SpikeSite::AxonInitialSegment).spike_site_index(), 0);
+        let mut states = vec![ChannelStates::default(); neuron.compartments.len()];
+        neuron.inject_current(0, 10.0);
+
+        let (mut spikes, mut suprathreshold_steps) = (0, 0);
+        for _ in 0..10_000 {
+            neuron.step(&mut states);
+            spikes += neuron.is_spiking as usize;
+            suprathreshold_steps += (neuron.get_soma_voltage() > neuron.spike_threshold) as usize;
+        }
+        assert!(spikes >= 1);
+        assert!(suprathreshold_steps > 10 * spikes);
+        assert!((neuron.time - 100.0).abs() < 1e-9);
+
+        let events: Vec<SpikeEvent> = neuron.spike_events().collect();
+        assert_eq!(events.len(), spikes);
+        assert_eq!(events.last().unwrap().time, neuron.last_spike_time);
+        assert!(events.windows(2).all(|w| w[1].time - w[0].time >= neuron.spike_detector.refractory));
+    }
+
+    #[test]
+    fn test_active_pyramidal_fires() {
+        let neuron = MultiCompartmentalNeuron::new_pyramidal_active(0, 0.01);
+        assert!(neuron.compartments[151].mechanisms.iter().any(|m| m.mechanism.name() == "Nav1.6"));
+        assert!(neuron.compartments[50].mechanisms.iter().any(|m| m.mechanism.name() == "Kv4.2"));
+
+        let (spikes, peak) = drive_soma(&mut neuron.clone(), 0.0, 50.0);
+        assert_eq!(spikes, 0);
+        assert!(peak < -60.0);
+
+        let (spikes, peak) = drive_soma(&mut neuron.clone(), 10.0, 100.0);
+        assert!(spikes >= 1);
+        assert!(peak > 10.0, "overshooting action potential, peak {peak}");
+    }
+
+    #[test]
+    fn test_hodgkin_huxley_neuron_fires() {
+        let neuron = MultiCompartmentalNeuron::new_pyramidal(0, 0.025)
+            .with_hodgkin_huxley()
+            .with_integrator(Integrator::BackwardEuler);
+        let soma = &neuron.compartments[0];
+        assert!((soma.get_channel_conductance("Na") / soma.surface_area - 1.2).abs() < 1e-12);
+        assert_eq!(neuron.compartments[1].get_channel_conductance("Na"), 0.0);
+
+        let (spikes, peak) = drive_soma(&mut neuron.clone(), 0.0, 50.0);
+        assert_eq!(spikes, 0);
+        assert!(peak < -60.0);
+
+        let (spikes, peak) = drive_soma(&mut neuron.clone(), 500.0, 100.0);
+        assert!(spikes >= 1);
+        assert!(peak > 0.0, "overshooting action potential, peak {peak}");
+    }
+
+    #[test]
+    fn test_nav1_1_loss_of_function_raises_rheobase() {
+        let wild_type = MultiCompartmentalNeuron::new_pyramidal_active(0, 0.025)
+            .with_integrator(Integrator::BackwardEuler);
+        let mut dravet = wild_type.clone();
+        assert_eq!(dravet.scale_mechanism("Nav1.1", 0.5), 1);
+
+        // Near threshold only the wild type fires...
+        assert_eq!(drive_soma(&mut wild_type.clone(), 2.0, 100.0).0, 1);
+        assert_eq!(drive_soma(&mut dravet.clone(), 2.0, 100.0).0, 0);
+
+        // ...and above it the haploinsufficient spike is much smaller
+        let (_, wt_peak) = drive_soma(&mut wild_type.clone(), 10.0, 100.0);
+        let (_, dravet_peak) = drive_soma(&mut dravet, 10.0, 100.0);
+        assert!(wt_peak - dravet_peak > 10.0, "WT {wt_peak} vs Dravet {dravet_peak}");
+    }
+
+    #[test]
+    fn test_modulation_scales_leak_and_potassium() {
+        // Passive soma at steady state: halving the leak doubles the depolarization
+        let passive = MultiCompartmentalNeuron::new(0, 1, 1.0).with_integrator(Integrator::BackwardEuler);
+        let mut closed = passive.clone();
+        closed.modulation.leak = 0.5;
+        let rest = passive.compartments[0].e_leak;
+        let (_, open_peak) = drive_soma(&mut passive.clone(), 0.1, 5000.0);
+        let (_, closed_peak) = drive_soma(&mut closed, 0.1, 5000.0);
+        assert!(((closed_peak - rest) / (open_peak - rest) - 2.0).abs() < 0.05);
+
+        // Suppressing K+ conductances lowers the rheobase of an active cell
+        let control = MultiCompartmentalNeuron::new_pyramidal_active(0, 0.025)
+            .with_integrator(Integrator::BackwardEuler);
+        let mut modulated = control.clone();
+        modulated.modulation.potassium = 0.3;
+        assert_eq!(drive_soma(&mut control.clone(), 1.5, 100.0).0, 0);
+        assert!(drive_soma(&mut modulated, 1.5, 100.0).0 >= 1);
+        assert_eq!(control.modulation.of(Ion::NonSpecific), 1.0);
+    }
+
+    #[test]
+    fn test_calcium_mechanisms_drive_pool_and_sk() {
+        use crate::channels_advanced::{Cav2_1, SK_Channel};
+
+        let mut neuron = MultiCompartmentalNeuron::new(0, 1, 0.01);
+        neuron.compartments[0].insert_mechanism(ChannelMechanism::Cav2_1(Cav2_1::new(5.0)));
+        neuron.compartments[0].insert_mechanism(ChannelMechanism::SK(SK_Channel::new(5.0)));
+        let ca_rest = neuron.compartments[0].ca_concentration;
+
+        // Depolarize to open the P/Q-type channels
+        drive_soma(&mut neuron, 500.0, 20.0);
+
+        let soma = &neuron.compartments[0];
+        assert!(soma.ca_concentration > 2.0 * ca_rest);
+        let sk = &soma.mechanisms[1];
+        assert!((sk.state.ca_i - soma.ca_concentration * 1000.0).abs() < 0.01);
+
+        // SK opens and hyperpolarizes the cell below rest despite the injection
+        assert!(sk.mechanism.open_conductance(soma.voltage, &sk.state, 0.0) > 0.0);
+        assert!(soma.voltage < soma.e_leak);
+    }
+
     #[test]
     fn test_pyramidal_neuron() {
         let neuron = MultiCompartmentalNeuron::new_pyramidal(0, 0.01);
@@ -0,0 +1,628 @@
+//! Discretization of SWC reconstructions into compartments.
+//!
+//! The reconstruction is split into unbranched sections, which end at branch
+//! points, terminals and changes of SWC type. Each section is cut into an odd
+//! number of equal-length compartments chosen by the d_lambda rule
+//! (Hines & Carnevale, 2001): no compartment is longer than `d_lambda` times
+//! the AC length constant of the section at `frequency`. Diameters and 3D
+//! positions are interpolated along the traced path, and passive properties
+//! and channel mechanisms are assigned per region from a [`Biophysics`] spec.
+//!
+//! # Example
+//!
+//! ```no_run
+//! use std::path::Path;
+//! use neurons::{Biophysics, Discretization, MultiCompartmentalNeuron, SWCMorphology};
+//! use neurons::discretization::{MechanismSpec, Region, RegionBiophysics};
+//!
+//! let morphology = SWCMorphology::from_file(Path::new("layer5_pyramidal.swc")).unwrap();
+//! let biophysics = Biophysics {
+//!     regions: vec![RegionBiophysics {
+//!         region: Region::AxonInitialSegment,
+//!         g_leak: None,
+//!         e_leak: None,
+//!         mechanisms: vec![MechanismSpec { channel: "Nav1.6".into(), g_max: 400.0 }],
+//!     }],
+//! };
+//! let discretization = Discretization { ais_length: 30.0, ..Discretization::default() };
+//! let neuron = MultiCompartmentalNeuron::from_swc(0, &morphology, 0.025, &discretization, &biophysics).unwrap();
+//! ```
+
+use std::collections::HashMap;
+use std::f64::consts::PI;
+use serde::{Deserialize, Serialize};
+use crate::compartmental::{Compartment, CompartmentType, MultiCompartmentalNeuron};
+use crate::constants::{C_M, R_A};
+use crate::mechanisms::ChannelMechanism;
+use crate::swc_parser::SWCMorphology;
+use crate::{NeuronError, Result};
+
+/// Segments shorter than this (um) are lengthened so that duplicate SWC
+/// samples do not short the axial path
+const MIN_LENGTH: f64 = 0.1;
+
+/// Parameters of the d_lambda rule
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct Discretization {
+    /// Maximum compartment length as a fraction of the AC length constant
+    pub d_lambda: f64,
+
+    /// Frequency at which the length constant is evaluated (Hz)
+    pub frequency: f64,
+
+    /// Path length of axon (um) from the soma labelled as axon initial
+    /// segment; SWC has no type for it
+    pub ais_length: f64,
+}
+
+impl Default for Discretization {
+    fn default() -> Self {
+        Self {
+            d_lambda: 0.1,
+            frequency: 100.0,
+            ais_length: 0.0,
+        }
+    }
+}
+
+impl Discretization {
+    /// AC length constant (um) of a cable of the given diameter (um)
+    pub fn lambda_ac(&self, diameter: f64) -> f64 {
+        // lambda_f = 1e5 * sqrt(d / (4 pi f Ra Cm)), d in um, Ra in ohm*cm, Cm in uF/cm^2
+        1e5 * (diameter / (4.0 * PI * self.frequency * R_A * C_M)).sqrt()
+    }
+
+    /// Number of compartments (always odd) for a section of `length` um and
+    /// mean `diameter` um, following NEURON's d_lambda convention
+    pub fn nseg(&self, length: f64, diameter: f64) -> usize {
+        let electrotonic = length / (self.d_lambda * self.lambda_ac(diameter));
+        ((electrotonic + 0.9) / 2.0) as usize * 2 + 1
+    }
+
+    fn validate(&self) -> Result<()> {
+        let invalid = |msg: &str| Err(NeuronError::MorphologyError(msg.to_string()));
+        if !self.d_lambda.is_finite() || self.d_lambda <= 0.0 {
+            return invalid("d_lambda must be positive");
+        }
+        if !self.frequency.is_finite() || self.frequency <= 0.0 {
+            return invalid("frequency must be positive");
+        }
+        if !self.ais_length.is_finite() || self.ais_length < 0.0 {
+            return invalid("ais_length must be non-negative");
+        }
+        Ok(())
+    }
+}
+
+/// Part of the cell a biophysics entry applies to
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Region {
+    All,
+    Soma,
+    /// Axon including the initial segment
+    Axon,
+    AxonInitialSegment,
+    /// Basal, apical and untyped dendrites
+    Dendrites,
+    Basal,
+    Apical,
+}
+
+impl Region {
+    pub fn contains(self, compartment_type: CompartmentType) -> bool {
+        use CompartmentType::*;
+        match self {
+            Region::All => true,
+            Region::Soma => compartment_type == Soma,
+            Region::Axon => matches!(compartment_type, Axon | AxonInitialSegment),
+            Region::AxonInitialSegment => compartment_type == AxonInitialSegment,
+            Region::Dendrites => matches!(compartment_type, Dendrite | BasalDendrite | ApicalDendrite),
+            Region::Basal => compartment_type == BasalDendrite,
+            Region::Apical => compartment_type == ApicalDendrite,
+        }
+    }
+}
+
+/// A channel to insert, by its [`ChannelMechanism::name`]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct MechanismSpec {
+    pub channel: String,
+    /// Maximal conductance density (mS/cm^2)
+    pub g_max: f64,
+}
+
+/// Passive properties and channels of one region
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct RegionBiophysics {
+    pub region: Region,
+
+    /// Leak conductance density (mS/cm^2); keeps the compartment default if unset
+    #[serde(default)]
+    pub g_leak: Option<f64>,
+
+    /// Leak reversal potential (mV)
+    #[serde(default)]
+    pub e_leak: Option<f64>,
+
+    #[serde(default)]
+    pub mechanisms: Vec<MechanismSpec>,
+}
+
+/// Region-specific channel distribution of a cell. Entries apply in order,
+/// so a later entry overrides the leak of an earlier, broader one while
+/// mechanisms accumulate.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct Biophysics {
+    pub regions: Vec<RegionBiophysics>,
+}
+
+impl Biophysics {
+    /// Check channel names and parameter ranges
+    pub fn validate(&self) -> Result<()> {
+        for entry in &self.regions {
+            for value in [entry.g_leak, entry.e_leak].into_iter().flatten() {
+                if !value.is_finite() {
+                    return Err(NeuronError::ChannelError(format!(
+                        "non-finite leak parameter in {:?} region",
+                        entry.region
+                    )));
+                }
+            }
+            if entry.g_leak.is_some_and(|g| g < 0.0) {
+                return Err(NeuronError::ChannelError(format!(
+                    "negative g_leak in {:?} region",
+                    entry.region
+                )));
+            }
+            for spec in &entry.mechanisms {
+                if !spec.g_max.is_finite() || spec.g_max < 0.0 {
+                    return Err(NeuronError::ChannelError(format!(
+                        "{}: g_max must be finite and non-negative",
+                        spec.channel
+                    )));
+                }
+                if ChannelMechanism::from_name(&spec.channel, spec.g_max).is_none() {
+                    return Err(NeuronError::ChannelError(format!(
+                        "unknown channel mechanism {}",
+                        spec.channel
+                    )));
+                }
+            }
+        }
+        Ok(())
+    }
+
+    /// Apply every matching entry to `comp`
+    pub fn apply(&self, comp: &mut Compartment) {
+        let compartment_type = comp.compartment_type;
+        for entry in self.regions.iter().filter(|e| e.region.contains(compartment_type)) {
+            if let Some(g_leak) = entry.g_leak {
+                comp.g_leak = g_leak * 0.01 * comp.surface_area; // mS/cm^2 -> nS
+            }
+            if let Some(e_leak) = entry.e_leak {
+                comp.e_leak = e_leak;
+                comp.voltage = e_leak;
+            }
+            for spec in &entry.mechanisms {
+                if let Some(mechanism) = ChannelMechanism::from_name(&spec.channel, spec.g_max) {
+                    comp.insert_mechanism(mechanism);
+                }
+            }
+        }
+    }
+}
+
+/// Map SWC point types onto compartment types
+pub fn compartment_type(swc_type: usize) -> CompartmentType {
+    match swc_type {
+        1 => CompartmentType::Soma,
+        2 => CompartmentType::Axon,
+        3 => CompartmentType::BasalDendrite,
+        4 => CompartmentType::ApicalDendrite,
+        _ => CompartmentType::Dendrite,
+    }
+}
+
+/// A traced sample: position (um) and diameter (um)
+#[derive(Debug, Clone, Copy)]
+struct PathPoint {
+    position: [f64; 3],
+    diameter: f64,
+}
+
+/// Unbranched run of samples, starting at the point it attaches to
+struct Section {
+    swc_type: usize,
+    path: Vec<PathPoint>,
+    /// Sample index the section ends at
+    end: usize,
+    /// Compartment the first compartment attaches to
+    parent: usize,
+    /// Path distance from the soma to the start of the section (um)
+    start_distance: f64,
+}
+
+fn distance(a: [f64; 3], b: [f64; 3]) -> f64 {
+    ((a[0] - b[0]).powi(2) + (a[1] - b[1]).powi(2) + (a[2] - b[2]).powi(2)).sqrt()
+}
+
+/// Position and diameter at path distance `s` along `path`, whose
+/// cumulative distances are `arc`
+fn interpolate(path: &[PathPoint], arc: &[f64], s: f64) -> PathPoint {
+    let k = arc.partition_point(|&a| a < s).clamp(1, path.len() - 1);
+    let (a, b) = (path[k - 1], path[k]);
+    let span = arc[k] - arc[k - 1];
+    let f = if span > 0.0 { ((s - arc[k - 1]) / span).clamp(0.0, 1.0) } else { 1.0 };
+    let lerp = |x: f64, y: f64| x + f * (y - x);
+    PathPoint {
+        position: [
+            lerp(a.position[0], b.position[0]),
+            lerp(a.position[1], b.position[1]),
+            lerp(a.position[2], b.position[2]),
+        ],
+        diameter: lerp(a.diameter, b.diameter),
+    }
+}
+
+impl MultiCompartmentalNeuron {
+    /// Discretize an SWC reconstruction with the d_lambda rule and insert
+    /// channels from `biophysics`.
+    ///
+    /// The root sample, together with any type-1 samples attached directly
+    /// to it, becomes the soma (compartment 0, a cylinder as long as it is
+    /// wide). Compartments are numbered so that every parent precedes its
+    /// children, and `axial_resistance` joins compartment centres.
+    pub fn from_swc(
+        id: usize,
+        morphology: &SWCMorphology,
+        dt: f64,
+        discretization: &Discretization,
+        biophysics: &Biophysics,
+    ) -> Result<Self> {
+        discretization.validate()?;
+        biophysics.validate()?;
+
+        let invalid = |msg: String| NeuronError::MorphologyError(format!("{}: {}", morphology.name, msg));
+        let points = &morphology.points;
+
+        let root = points.first().ok_or_else(|| invalid("no samples".to_string()))?;
+        if root.parent_id != -1 {
+            return Err(invalid(format!("first sample {} is not a root", root.id)));
+        }
+
+        // Resolve parents; SWC lists every sample after its parent
+        let mut index_of: HashMap<usize, usize> = HashMap::new();
+        let mut children = vec![Vec::new(); points.len()];
+        for (i, point) in points.iter().enumerate() {
+            if point.parent_id < 0 {
+                if i != 0 {
+                    return Err(invalid(format!("sample {} is a second root", point.id)));
+                }
+            } else {
+                let parent_id = point.parent_id as usize;
+                let &parent = index_of
+                    .get(&parent_id)
+                    .ok_or_else(|| invalid(format!("sample {} precedes its parent {}", point.id, parent_id)))?;
+                children[parent].push(i);
+            }
+            if index_of.insert(point.id, i).is_some() {
+                return Err(invalid(format!("duplicate sample id {}", point.id)));
+            }
+        }
+
+        let trace = |i: usize| PathPoint {
+            position: [points[i].x, points[i].y, points[i].z],
+            diameter: 2.0 * points[i].radius,
+        };
+
+        // Soma: the root plus directly attached soma samples
+        let mut soma_samples = vec![0];
+        let mut k = 0;
+        while k < soma_samples.len() {
+            let i = soma_samples[k];
+            soma_samples.extend(children[i].iter().filter(|&&c| points[c].point_type == 1));
+            k += 1;
+        }
+        let soma_diameters = soma_samples.iter().map(|&i| 2.0 * points[i].radius);
+        let soma_diameter = if soma_diameters.clone().any(f64::is_nan) {
+            f64::NAN
+        } else {
+            soma_diameters.fold(0.0, f64::max)
+        };
+        if !soma_diameter.is_finite() || soma_diameter <= 0.0 {
+            return Err(invalid(format!("soma has diameter {soma_diameter} um")));
+        }
+        let mut soma = Compartment::new(CompartmentType::Soma, soma_diameter, soma_diameter);
+        soma.position = trace(0).position;
+
+        let mut compartments = vec![soma];
+        let mut pending: Vec<Section> = Vec::new();
+        let start_sections = |from: usize, parent: usize, start_distance: f64, pending: &mut Vec<Section>| {
+            for &child in children[from].iter().rev() {
+                if !soma_samples.contains(&child) {
+                    pending.push(Section {
+                        swc_type: points[child].point_type,
+                        path: vec![PathPoint { diameter: 2.0 * points[child].radius, ..trace(from) }, trace(child)],
+                        end: child,
+                        parent,
+                        start_distance,
+                    });
+                }
+            }
+        };
+        for &i in soma_samples.iter().rev() {
+            start_sections(i, 0, 0.0, &mut pending);
+        }
+
+        // Depth-first over sections, so parents are always numbered first
+        while let Some(mut section) = pending.pop() {
+            loop {
+                let next = &children[section.end];
+                if next.len() != 1 || points[next[0]].point_type != section.swc_type {
+                    break;
+                }
+                section.end = next[0];
+                section.path.push(trace(section.end));
+            }
+
+            let mut arc = vec![0.0];
+            for w in section.path.windows(2) {
+                arc.push(arc.last().unwrap() + distance(w[0].position, w[1].position));
+            }
+            let length = *arc.last().unwrap();
+            let mean_diameter = if length > 0.0 {
+                section
+                    .path
+                    .windows(2)
+                    .zip(arc.windows(2))
+                    .map(|(p, s)| 0.5 * (p[0].diameter + p[1].diameter) * (s[1] - s[0]))
+                    .sum::<f64>()
+                    / length
+            } else {
+                section.path[1].diameter
+            };
+            if !length.is_finite() || !mean_diameter.is_finite() || mean_diameter <= 0.0 {
+                return Err(invalid(format!(
+                    "section ending at sample {} has length {length} um and mean diameter {mean_diameter} um",
+                    points[section.end].id
+                )));
+            }
+
+            let nseg = discretization.nseg(length, mean_diameter);
+            let seg_length = length / nseg as f64;
+            let swc_type = compartment_type(section.swc_type);
+            let mut parent = section.parent;
+
+            for k in 0..nseg {
+                let mid = interpolate(&section.path, &arc, (k as f64 + 0.5) * seg_length);
+                let from_soma = section.start_distance + (k as f64 + 0.5) * seg_length;
+                let compartment_type = if swc_type == CompartmentType::Axon && from_soma < discretization.ais_length {
+                    CompartmentType::AxonInitialSegment
+                } else {
+                    swc_type
+                };
+
+                let mut comp = Compartment::new(compartment_type, seg_length.max(MIN_LENGTH), mid.diameter);
+                comp.position = mid.position;
+                comp.axial_resistance = 0.5 * (comp.axial_resistance + compartments[parent].axial_resistance);
+                comp.parent_idx = Some(parent);
+
+                let idx = compartments.len();
+                compartments[parent].children_idx.push(idx);
+                compartments.push(comp);
+                parent = idx;
+            }
+
+            start_sections(section.end, parent, section.start_distance + length, &mut pending);
+        }
+
+        for comp in &mut compartments {
+            biophysics.apply(comp);
+        }
+
+        let n = compartments.len();
+        let mut neuron = Self::new(id, 1, dt);
+        neuron.compartments = compartments;
+        neuron.external_current = ndarray::Array1::zeros(n);
+        neuron.synaptic_current = ndarray::Array1::zeros(n);
+        Ok(neuron)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::compartmental::{ChannelStates, Integrator};
+    use std::path::Path;
+
+    fn morphology(name: &str) -> SWCMorphology {
+        let path = Path::new(env!("CARGO_MANIFEST_DIR")).join("test_data").join(name);
+        SWCMorphology::from_file(&path).unwrap()
+    }
+
+    fn active_biophysics() -> Biophysics {
+        let entry = |region, mechanisms: &[(&str, f64)]| RegionBiophysics {
+            region,
+            g_leak: None,
+            e_leak: None,
+            mechanisms: mechanisms
+                .iter()
+                .map(|&(channel, g_max)| MechanismSpec { channel: channel.to_string(), g_max })
+                .collect(),
+        };
+        Biophysics {
+            regions: vec![
+                entry(Region::Soma, &[("Nav1.1", 200.0), ("Kv3.1", 150.0), ("Kv1.1", 5.0)]),
+                entry(Region::AxonInitialSegment, &[("Nav1.6", 400.0), ("Kv3.1", 100.0)]),
+                entry(Region::Apical, &[("Kv4.2", 10.0), ("HCN", 0.5)]),
+            ],
+        }
+    }
+
+    #[test]
+    fn test_d_lambda_rule() {
+        let rule = Discretization::default();
+        assert_eq!(rule.nseg(1.0, 2.0), 1);
+        assert!(rule.nseg(1000.0, 1.0) > rule.nseg(1000.0, 4.0));
+        for length in [10.0, 100.0, 400.0, 2000.0] {
+            let n = rule.nseg(length, 1.0);
+            assert_eq!(n % 2, 1);
+            assert!(length / n as f64 <= rule.d_lambda * rule.lambda_ac(1.0) * 1.1);
+        }
+    }
+
+    #[test]
+    fn test_preserves_topology_and_geometry() {
+        let morph = morphology("layer5_pyramidal.swc");
+        let coarse = MultiCompartmentalNeuron::from_swc(0, &morph, 0.025, &Discretization::default(), &Biophysics::default()).unwrap();
+        let fine_rule = Discretization { d_lambda: 0.01, ..Discretization::default() };
+        let fine = MultiCompartmentalNeuron::from_swc(0, &morph, 0.025, &fine_rule, &Biophysics::default()).unwrap();
+        assert!(fine.compartments.len() > coarse.compartments.len());
+
+        for neuron in [&coarse, &fine] {
+            assert_eq!(neuron.compartments[0].compartment_type, CompartmentType::Soma);
+            assert_eq!(neuron.external_current.len(), neuron.compartments.len());
+            for (i, comp) in neuron.compartments.iter().enumerate().skip(1) {
+                let parent = comp.parent_idx.unwrap();
+                assert!(parent < i);
+                assert!(neuron.compartments[parent].children_idx.contains(&i));
+            }
+
+            // Dendritic path length is conserved by the discretization
+            let dendritic: f64 = neuron
+                .compartments
+                .iter()
+                .filter(|c| Region::Dendrites.contains(c.compartment_type))
+                .map(|c| c.length)
+                .sum();
+            assert!((dendritic - morph.total_dendritic_length()).abs() < 1e-6 * dendritic);
+        }
+
+        // Both dendritic arbors survive, and branch points become compartments
+        // with several children
+        let has = |t| coarse.compartments.iter().any(|c| c.compartment_type == t);
+        assert!(has(CompartmentType::ApicalDendrite) && has(CompartmentType::BasalDendrite));
+        let branching = coarse.compartments.iter().skip(1).filter(|c| c.children_idx.len() > 1).count();
+        let soma_children = morph.points.iter().filter(|p| p.parent_id == 1).count();
+        assert_eq!(branching, morph.count_branch_points() - 1);
+        assert_eq!(coarse.compartments[0].children_idx.len(), soma_children);
+
+        // Terminal compartments end near their terminal samples
+        for comp in fine.compartments.iter().filter(|c| c.children_idx.is_empty()) {
+            let nearest = morph
+                .points
+                .iter()
+                .map(|p| distance(comp.position, [p.x, p.y, p.z]))
+                .fold(f64::INFINITY, f64::min);
+            assert!(nearest < comp.length, "terminal at {:?} is {nearest} um from any sample", comp.position);
+        }
+    }
+
+    #[test]
+    fn test_region_biophysics() {
+        let morph = morphology("layer5_pyramidal.swc");
+        let rule = Discretization { ais_length: 30.0, ..Discretization::default() };
+        let mut biophysics = active_biophysics();
+        biophysics.regions.insert(0, RegionBiophysics {
+            region: Region::All,
+            g_leak: Some(0.03),
+            e_leak: Some(-75.0),
+            mechanisms: Vec::new(),
+        });
+        let neuron = MultiCompartmentalNeuron::from_swc(0, &morph, 0.025, &rule, &biophysics).unwrap();
+
+        let names = |c: &Compartment| c.mechanisms.iter().map(|m| m.mechanism.name()).collect::<Vec<_>>();
+        assert_eq!(names(&neuron.compartments[0]), ["Nav1.1", "Kv3.1", "Kv1.1"]);
+        for comp in &neuron.compartments {
+            assert_eq!(comp.e_leak, -75.0);
+            assert!((comp.g_leak - 0.03 * 0.01 * comp.surface_area).abs() < 1e-12);
+            match comp.compartment_type {
+                CompartmentType::AxonInitialSegment => assert_eq!(names(comp), ["Nav1.6", "Kv3.1"]),
+                CompartmentType::ApicalDendrite => assert_eq!(names(comp), ["Kv4.2", "HCN"]),
+                CompartmentType::Soma => {}
+                _ => assert!(comp.mechanisms.is_empty()),
+            }
+        }
+        assert!(neuron.compartments.iter().any(|c| c.compartment_type == CompartmentType::AxonInitialSegment));
+        assert!(neuron.compartments.iter().any(|c| c.compartment_type == CompartmentType::Axon));
+
+        let typo = Biophysics {
+            regions: vec![RegionBiophysics {
+                region: Region::Soma,
+                g_leak: None,
+                e_leak: None,
+                mechanisms: vec![MechanismSpec { channel: "Nav1.7".to_string(), g_max: 1.0 }],
+            }],
+        };
+        assert!(MultiCompartmentalNeuron::from_swc(0, &morph, 0.025, &rule, &typo).is_err());
+    }
+
+    #[test]
+    fn test_reconstructed_cells_simulate() {
+        for name in ["layer23_pyramidal.swc", "layer5_pyramidal.swc", "parvalbumin_interneuron.swc"] {
+            let mut neuron = MultiCompartmentalNeuron::from_swc(
+                0,
+                &morphology(name),
+                0.025,
+                &Discretization { ais_length: 30.0, ..Discretization::default() },
+                &active_biophysics(),
+            )
+            .unwrap()
+            .with_integrator(Integrator::BackwardEuler);
+
+            let mut states = vec![ChannelStates::default(); neuron.compartments.len()];
+            neuron.inject_current(0, 10.0);
+            let mut peak = f64::NEG_INFINITY;
+            for _ in 0..4000 {
+                neuron.step(&mut states);
+                peak = peak.max(neuron.get_soma_voltage());
+            }
+            assert!(peak > 0.0, "{name}: soma peaked at {peak} mV");
+        }
+    }
+
+    #[test]
+    fn test_rejects_malformed_morphologies() {
+        let rule = Discretization::default();
+        let none = Biophysics::default();
+        let point = |id, parent_id| crate::SWCPoint { id, point_type: 3, x: id as f64, y: 0.0, z: 0.0, radius: 1.0, parent_id };
+
+        let orphan = SWCMorphology { points: vec![point(1, -1), point(2, 5)], name: "orphan".to_string() };
+        assert!(MultiCompartmentalNeuron::from_swc(0, &orphan, 0.025, &rule, &none).is_err());
+
+        let forest = SWCMorphology { points: vec![point(1, -1), point(2, -1)], name: "forest".to_string() };
+        assert!(MultiCompartmentalNeuron::from_swc(0, &forest, 0.025, &rule, &none).is_err());
+
+        let bad_rule = Discretization { d_lambda: 0.0, ..rule };
+        let single = SWCMorphology { points: vec![point(1, -1)], name: "single".to_string() };
+        assert!(MultiCompartmentalNeuron::from_swc(0, &single, 0.025, &bad_rule, &none).is_err());
+        assert_eq!(MultiCompartmentalNeuron::from_swc(0, &single, 0.025, &rule, &none).unwrap().compartments.len(), 1);
+
+        // Zero or NaN diameters would give an infinite compartment count
+        for radius in [0.0, f64::NAN] {
+            let thin = SWCMorphology {
+                points: vec![point(1, -1), crate::SWCPoint { radius, ..point(2, 1) }],
+                name: "thin".to_string(),
+            };
+            assert!(MultiCompartmentalNeuron::from_swc(0, &thin, 0.025, &rule, &none).is_err());
+        }
+
+        // A soma without area would divide its currents by zero
+        for radius in [0.0, -1.0, f64::NAN, f64::INFINITY] {
+            let pinhead = SWCMorphology {
+                points: vec![crate::SWCPoint { radius, ..point(1, -1) }, point(2, 1)],
+                name: "pinhead".to_string(),
+            };
+            let result = MultiCompartmentalNeuron::from_swc(0, &pinhead, 0.025, &rule, &none);
+            assert!(
+                matches!(result, Err(NeuronError::MorphologyError(msg)) if msg.contains("soma")),
+                "radius {radius}"
+            );
+        }
+    }
+}
@@ -18,10 +18,12 @@ pub mod channels;
 pub mod morphology;
 pub mod signaling;
 pub mod swc_parser;
+pub mod spikes;
 
-pub use compartmental::{Compartment, MultiCompartmentalNeuron, CompartmentType};
+pub use compartmental::{ChannelModulation, Compartment, GapInput, MultiCompartmentalNeuron, CompartmentType, Integrator};
 pub use channels::{IonChannel, HodgkinHuxleyNa, HodgkinHuxleyK, CalciumChannel, NMDAChannel};
 pub use signaling::IntracellularSignaling;
+pub use spikes::{SpikeDetector, SpikeEvent, SpikeSite};
 pub use swc_parser::{SWCPoint, SWCMorphology};
 pub use morphology::{NeuronMorphology, DendriticTree};
 
@@ -61,3 +63,9 @@ pub mod constants {
 
 pub mod channels_advanced;
 pub use channels_advanced::{AdvancedChannelState, Nav1_1, Nav1_6, Kv1_1, Kv3_1, Kv4_2, Kv7_M, Cav1_2, Cav2_1, Cav2_2, Cav3_1, SK_Channel, BK_Channel, HCN_Channel, NMDA_Advanced};
+
+pub mod mechanisms;
+pub use mechanisms::{ChannelMechanism, InsertedChannel};
+
+pub mod discretization;
+pub use discretization::{Biophysics, Discretization};
@@ -0,0 +1,335 @@
+//! Channel mechanisms inserted into compartments.
+//!
+//! Wraps the `channels_advanced` library in a serializable enum so that every
+//! compartment can carry its own set of channels, each with its own gating
+//! state (e.g. Nav1.6 at the axon initial segment, Kv4.2 along the apical
+//! dendrite). Mechanism `g_max` values are densities in mS/cm^2, the units
+//! used throughout `channels_advanced`.
+
+use serde::{Deserialize, Serialize};
+use crate::channels_advanced::*;
+
+/// mS/cm^2 * um^2 -> nS
+const DENSITY_TO_NS: f64 = 0.01;
+
+/// Calcium pool: shell depth (um) and clearance time constant (ms)
+const CA_SHELL_DEPTH: f64 = 0.1;
+const TAU_CA: f64 = 20.0;
+
+/// Resting intracellular calcium (mM)
+pub const CA_REST: f64 = 0.0001;
+
+/// How gating variables are advanced in time
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum GateUpdate {
+    Euler,
+    Exponential,
+}
+
+impl GateUpdate {
+    fn apply(self, kinetics: GateKinetics, x: &mut f64, dt: f64) {
+        match self {
+            GateUpdate::Euler => kinetics.euler(x, dt),
+            GateUpdate::Exponential => kinetics.exponential(x, dt),
+        }
+    }
+}
+
+/// Ionic species carried by a mechanism
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Ion {
+    Sodium,
+    Potassium,
+    Calcium,
+    /// Mixed cation (HCN, NMDA)
+    NonSpecific,
+}
+
+/// Any channel from `channels_advanced`
+#[allow(non_camel_case_types)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub enum ChannelMechanism {
+    Nav1_1(Nav1_1),
+    Nav1_6(Nav1_6),
+    Kv1_1(Kv1_1),
+    Kv3_1(Kv3_1),
+    Kv4_2(Kv4_2),
+    Kv7_M(Kv7_M),
+    Cav1_2(Cav1_2),
+    Cav2_1(Cav2_1),
+    Cav2_2(Cav2_2),
+    Cav3_1(Cav3_1),
+    SK(SK_Channel),
+    BK(BK_Channel),
+    HCN(HCN_Channel),
+    NMDA(NMDA_Advanced),
+}
+
+impl ChannelMechanism {
+    /// Build a mechanism from its conventional name (see [`Self::name`])
+    /// with maximal conductance `g_max` (mS/cm^2)
+    pub fn from_name(name: &str, g_max: f64) -> Option<Self> {
+        let mechanism = match name {
+            "Nav1.1" => ChannelMechanism::Nav1_1(Nav1_1::new(g_max)),
+            "Nav1.6" => ChannelMechanism::Nav1_6(Nav1_6::new(g_max)),
+            "Kv1.1" => ChannelMechanism::Kv1_1(Kv1_1::new(g_max)),
+            "Kv3.1" => ChannelMechanism::Kv3_1(Kv3_1::new(g_max)),
+            "Kv4.2" => ChannelMechanism::Kv4_2(Kv4_2::new(g_max)),
+            "Kv7" => ChannelMechanism::Kv7_M(Kv7_M::new(g_max)),
+            "Cav1.2" => ChannelMechanism::Cav1_2(Cav1_2::new(g_max)),
+            "Cav2.1" => ChannelMechanism::Cav2_1(Cav2_1::new(g_max)),
+            "Cav2.2" => ChannelMechanism::Cav2_2(Cav2_2::new(g_max)),
+            "Cav3.1" => ChannelMechanism::Cav3_1(Cav3_1::new(g_max)),
+            "SK" => ChannelMechanism::SK(SK_Channel::new(g_max)),
+            "BK" => ChannelMechanism::BK(BK_Channel::new(g_max)),
+            "HCN" => ChannelMechanism::HCN(HCN_Channel::new(g_max)),
+            "NMDA" => ChannelMechanism::NMDA(NMDA_Advanced::new(g_max)),
+            _ => return None,
+        };
+        Some(mechanism)
+    }
+
+    /// Conventional channel name, used to look mechanisms up
+    pub fn name(&self) -> &'static str {
+        match self {
+            ChannelMechanism::Nav1_1(_) => "Nav1.1",
+            ChannelMechanism::Nav1_6(_) => "Nav1.6",
+            ChannelMechanism::Kv1_1(_) => "Kv1.1",
+            ChannelMechanism::Kv3_1(_) => "Kv3.1",
+            ChannelMechanism::Kv4_2(_) => "Kv4.2",
+            ChannelMechanism::Kv7_M(_) => "Kv7",
+            ChannelMechanism::Cav1_2(_) => "Cav1.2",
+            ChannelMechanism::Cav2_1(_) => "Cav2.1",
+            ChannelMechanism::Cav2_2(_) => "Cav2.2",
+            ChannelMechanism::Cav3_1(_) => "Cav3.1",
+            ChannelMechanism::SK(_) => "SK",
+            ChannelMechanism::BK(_) => "BK",
+            ChannelMechanism::HCN(_) => "HCN",
+            ChannelMechanism::NMDA(_) => "NMDA",
+        }
+    }
+
+    pub fn ion(&self) -> Ion {
+        match self {
+            ChannelMechanism::Nav1_1(_) | ChannelMechanism::Nav1_6(_) => Ion::Sodium,
+            ChannelMechanism::Kv1_1(_)
+            | ChannelMechanism::Kv3_1(_)
+            | ChannelMechanism::Kv4_2(_)
+            | ChannelMechanism::Kv7_M(_)
+            | ChannelMechanism::SK(_)
+            | ChannelMechanism::BK(_) => Ion::Potassium,
+            ChannelMechanism::Cav1_2(_)
+            | ChannelMechanism::Cav2_1(_)
+            | ChannelMechanism::Cav2_2(_)
+            | ChannelMechanism::Cav3_1(_) => Ion::Calcium,
+            ChannelMechanism::HCN(_) | ChannelMechanism::NMDA(_) => Ion::NonSpecific,
+        }
+    }
+
+    /// Maximal conductance density (mS/cm^2)
+    pub fn g_max(&self) -> f64 {
+        match self {
+            ChannelMechanism::Nav1_1(c) => c.g_max,
+            ChannelMechanism::Nav1_6(c) => c.g_max,
+            ChannelMechanism::Kv1_1(c) => c.g_max,
+            ChannelMechanism::Kv3_1(c) => c.g_max,
+            ChannelMechanism::Kv4_2(c) => c.g_max,
+            ChannelMechanism::Kv7_M(c) => c.g_max,
+            ChannelMechanism::Cav1_2(c) => c.g_max,
+            ChannelMechanism::Cav2_1(c) => c.g_max,
+            ChannelMechanism::Cav2_2(c) => c.g_max,
+            ChannelMechanism::Cav3_1(c) => c.g_max,
+            ChannelMechanism::SK(c) => c.g_max,
+            ChannelMechanism::BK(c) => c.g_max,
+            ChannelMechanism::HCN(c) => c.g_max,
+            ChannelMechanism::NMDA(c) => c.g_max,
+        }
+    }
+
+    fn g_max_mut(&mut self) -> &mut f64 {
+        match self {
+            ChannelMechanism::Nav1_1(c) => &mut c.g_max,
+            ChannelMechanism::Nav1_6(c) => &mut c.g_max,
+            ChannelMechanism::Kv1_1(c) => &mut c.g_max,
+            ChannelMechanism::Kv3_1(c) => &mut c.g_max,
+            ChannelMechanism::Kv4_2(c) => &mut c.g_max,
+            ChannelMechanism::Kv7_M(c) => &mut c.g_max,
+            ChannelMechanism::Cav1_2(c) => &mut c.g_max,
+            ChannelMechanism::Cav2_1(c) => &mut c.g_max,
+            ChannelMechanism::Cav2_2(c) => &mut c.g_max,
+            ChannelMechanism::Cav3_1(c) => &mut c.g_max,
+            ChannelMechanism::SK(c) => &mut c.g_max,
+            ChannelMechanism::BK(c) => &mut c.g_max,
+            ChannelMechanism::HCN(c) => &mut c.g_max,
+            ChannelMechanism::NMDA(c) => &mut c.g_max,
+        }
+    }
+
+    /// Reversal potential (mV)
+    pub fn reversal(&self) -> f64 {
+        match self {
+            ChannelMechanism::Nav1_1(c) => c.e_na,
+            ChannelMechanism::Nav1_6(c) => c.e_na,
+            ChannelMechanism::Kv1_1(c) => c.e_k,
+            ChannelMechanism::Kv3_1(c) => c.e_k,
+            ChannelMechanism::Kv4_2(c) => c.e_k,
+            ChannelMechanism::Kv7_M(c) => c.e_k,
+            ChannelMechanism::Cav1_2(c) => c.e_ca,
+            ChannelMechanism::Cav2_1(c) => c.e_ca,
+            ChannelMechanism::Cav2_2(c) => c.e_ca,
+            ChannelMechanism::Cav3_1(c) => c.e_ca,
+            ChannelMechanism::SK(c) => c.e_k,
+            ChannelMechanism::BK(c) => c.e_k,
+            ChannelMechanism::HCN(c) => c.e_h,
+            ChannelMechanism::NMDA(c) => c.e_nmda,
+        }
+    }
+
+    /// Open conductance density (mS/cm^2) in the given state
+    pub fn open_conductance(&self, v: f64, state: &AdvancedChannelState, glutamate: f64) -> f64 {
+        match self {
+            ChannelMechanism::Nav1_1(c) => c.open_conductance(state),
+            ChannelMechanism::Nav1_6(c) => c.open_conductance(state),
+            ChannelMechanism::Kv1_1(c) => c.open_conductance(state),
+            ChannelMechanism::Kv3_1(c) => c.open_conductance(state),
+            ChannelMechanism::Kv4_2(c) => c.open_conductance(state),
+            ChannelMechanism::Kv7_M(c) => c.open_conductance(state),
+            ChannelMechanism::Cav1_2(c) => c.open_conductance(state),
+            ChannelMechanism::Cav2_1(c) => c.open_conductance(state),
+            ChannelMechanism::Cav2_2(c) => c.open_conductance(state),
+            ChannelMechanism::Cav3_1(c) => c.open_conductance(state),
+            ChannelMechanism::SK(c) => c.open_conductance(state),
+            ChannelMechanism::BK(c) => c.open_conductance(state),
+            ChannelMechanism::HCN(c) => c.open_conductance(state),
+            ChannelMechanism::NMDA(c) => c.open_conductance(v, state, glutamate),
+        }
+    }
+
+    /// Activation and (if any) inactivation kinetics
+    fn kinetics(&self, v: f64, state: &AdvancedChannelState, glutamate: f64) -> (Option<GateKinetics>, Option<GateKinetics>) {
+        match self {
+            ChannelMechanism::Nav1_1(c) => (Some(c.activation(v)), Some(c.inactivation(v))),
+            ChannelMechanism::Nav1_6(c) => (Some(c.activation(v)), Some(c.inactivation(v))),
+            ChannelMechanism::Kv1_1(c) => (Some(c.activation(v)), None),
+            ChannelMechanism::Kv3_1(c) => (Some(c.activation(v)), None),
+            ChannelMechanism::Kv4_2(c) => (Some(c.activation(v)), Some(c.inactivation(v))),
+            ChannelMechanism::Kv7_M(c) => (Some(c.activation(v)), None),
+            ChannelMechanism::Cav1_2(c) => (Some(c.activation(v)), Some(c.inactivation(v))),
+            ChannelMechanism::Cav2_1(c) => (Some(c.activation(v)), Some(c.inactivation(v))),
+            ChannelMechanism::Cav2_2(c) => (Some(c.activation(v)), Some(c.inactivation(v))),
+            ChannelMechanism::Cav3_1(c) => (Some(c.activation(v)), Some(c.inactivation(v))),
+            // Instantaneous in calcium
+            ChannelMechanism::SK(_) => (None, None),
+            ChannelMechanism::BK(c) => (Some(c.activation(v, state.ca_i)), None),
+            ChannelMechanism::HCN(c) => (Some(c.activation(v)), None),
+            ChannelMechanism::NMDA(c) => (Some(c.activation(glutamate)), None),
+        }
+    }
+
+    /// Scale the maximal conductance, e.g. `0.5` for a heterozygous
+    /// loss-of-function allele
+    pub fn scale(&mut self, factor: f64) {
+        *self.g_max_mut() *= factor;
+    }
+}
+
+/// A mechanism inserted into a compartment, with its own gating state
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct InsertedChannel {
+    pub mechanism: ChannelMechanism,
+    pub state: AdvancedChannelState,
+}
+
+impl InsertedChannel {
+    /// Insert `mechanism` with its gates at steady state for voltage `v`
+    pub fn at_rest(mechanism: ChannelMechanism, v: f64, ca_concentration: f64) -> Self {
+        let mut state = AdvancedChannelState {
+            ca_i: ca_concentration * 1000.0,
+            ..AdvancedChannelState::default()
+        };
+        let (m, h) = mechanism.kinetics(v, &state, 0.0);
+        if let Some(m) = m {
+            state.m = m.inf;
+        }
+        if let Some(h) = h {
+            state.h = h.inf;
+        }
+        Self { mechanism, state }
+    }
+
+    /// Membrane conductance (nS) of this channel on `area` um^2
+    pub fn conductance_ns(&self, v: f64, area: f64, glutamate: f64) -> f64 {
+        self.mechanism.open_conductance(v, &self.state, glutamate) * area * DENSITY_TO_NS
+    }
+
+    /// Outward current (pA) on `area` um^2
+    pub fn current(&self, v: f64, area: f64, glutamate: f64) -> f64 {
+        self.conductance_ns(v, area, glutamate) * (v - self.mechanism.reversal())
+    }
+
+    /// Advance the gates by `dt` at voltage `v`; `ca_i` is the compartment
+    /// calcium (uM) seen by calcium-activated channels
+    pub fn advance(&mut self, v: f64, dt: f64, ca_i: f64, glutamate: f64, update: GateUpdate) {
+        self.state.ca_i = ca_i;
+        let (m, h) = self.mechanism.kinetics(v, &self.state, glutamate);
+        if let Some(m) = m {
+            update.apply(m, &mut self.state.m, dt);
+        }
+        if let Some(h) = h {
+            update.apply(h, &mut self.state.h, dt);
+        }
+    }
+}
+
+/// Advance a compartment's submembrane calcium pool (mM) given the total
+/// calcium current (pA, inward negative) through its `area` um^2
+pub fn calcium_pool_step(ca: f64, i_ca: f64, area: f64, dt: f64) -> f64 {
+    use crate::constants::FARADAY;
+
+    // pA over a shell of area * depth um^3 -> mM/ms
+    let influx = -i_ca.min(0.0) * 1e3 / (2.0 * FARADAY * area * CA_SHELL_DEPTH);
+    let next = ca + (influx - (ca - CA_REST) / TAU_CA) * dt;
+    next.max(0.0)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_inserted_channel_starts_at_steady_state() {
+        let channel = InsertedChannel::at_rest(ChannelMechanism::Kv4_2(Kv4_2::new(10.0)), -70.0, CA_REST);
+        let before = channel.state.clone();
+
+        let mut stepped = channel.clone();
+        stepped.advance(-70.0, 1.0, CA_REST * 1000.0, 0.0, GateUpdate::Exponential);
+        assert!((stepped.state.m - before.m).abs() < 1e-12);
+        assert!((stepped.state.h - before.h).abs() < 1e-12);
+    }
+
+    #[test]
+    fn test_current_scales_with_area_and_density() {
+        let mut channel = InsertedChannel::at_rest(ChannelMechanism::Nav1_6(Nav1_6::new(100.0)), -40.0, CA_REST);
+        let i_small = channel.current(-20.0, 100.0, 0.0);
+        let i_large = channel.current(-20.0, 200.0, 0.0);
+        assert!(i_small < 0.0); // Inward sodium current
+        assert!((i_large - 2.0 * i_small).abs() < 1e-9);
+
+        channel.mechanism.scale(0.5);
+        assert!((channel.current(-20.0, 100.0, 0.0) - 0.5 * i_small).abs() < 1e-9);
+    }
+
+    #[test]
+    fn test_calcium_pool_rises_and_clears() {
+        let mut ca = CA_REST;
+        for _ in 0..100 {
+            ca = calcium_pool_step(ca, -5.0, 500.0, 0.1);
+        }
+        assert!(ca > 10.0 * CA_REST);
+
+        for _ in 0..2000 {
+            ca = calcium_pool_step(ca, 0.0, 500.0, 0.1);
+        }
+        assert!((ca - CA_REST).abs() < 1e-6);
+    }
+}
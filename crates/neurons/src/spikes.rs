@@ -0,0 +1,164 @@
+//! Action potential detection.
+//!
+//! A spike is an upward crossing of the threshold at the detection site (soma
+//! or axon initial segment), so one action potential counts once however many
+//! steps it stays depolarized. The crossing time is interpolated linearly
+//! within the step, crossings inside the refractory window are ignored, and
+//! recent spike times are kept in a bounded log (none with capacity 0).
+
+use serde::{Deserialize, Serialize};
+use std::collections::VecDeque;
+
+/// Compartment whose voltage is monitored for spikes
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+pub enum SpikeSite {
+    #[default]
+    Soma,
+    /// Axon initial segment, where action potentials start; falls back to
+    /// the soma in neurons without one
+    AxonInitialSegment,
+}
+
+/// One detected action potential
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct SpikeEvent {
+    pub neuron_id: usize,
+    /// Interpolated threshold-crossing time (ms)
+    pub time: f64,
+}
+
+/// Threshold-crossing detector with refractory bookkeeping
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct SpikeDetector {
+    pub site: SpikeSite,
+
+    /// Minimum interval between detected spikes (ms)
+    pub refractory: f64,
+
+    /// Number of most recent spike times kept in the log (0 keeps none)
+    pub log_capacity: usize,
+
+    /// Recent spike times, oldest first (ms)
+    log: VecDeque<f64>,
+
+    /// Most recent spike time, kept for the refractory window even when
+    /// the log is disabled (ms)
+    #[serde(default)]
+    last: Option<f64>,
+}
+
+impl Default for SpikeDetector {
+    fn default() -> Self {
+        Self {
+            site: SpikeSite::Soma,
+            refractory: 2.0,
+            log_capacity: 1000,
+            log: VecDeque::new(),
+            last: None,
+        }
+    }
+}
+
+impl SpikeDetector {
+    /// Create a detector monitoring `site`
+    pub fn new(site: SpikeSite) -> Self {
+        Self { site, ..Self::default() }
+    }
+
+    /// Check a step from `t` to `t + dt` during which the site went from
+    /// `v_before` to `v_after`, and log the spike if the threshold was crossed
+    /// upwards outside the refractory window. Returns the spike time.
+    pub fn detect(&mut self, v_before: f64, v_after: f64, t: f64, dt: f64, threshold: f64) -> Option<f64> {
+        if !(v_before < threshold && v_after >= threshold) {
+            return None;
+        }
+
+        let time = t + dt * (threshold - v_before) / (v_after - v_before);
+        if self.last_spike().is_some_and(|last| time - last < self.refractory) {
+            return None;
+        }
+
+        self.last = Some(time);
+        if self.log_capacity > 0 {
+            while self.log.len() >= self.log_capacity {
+                self.log.pop_front();
+            }
+            self.log.push_back(time);
+        }
+        Some(time)
+    }
+
+    /// Time of the most recent spike (ms)
+    pub fn last_spike(&self) -> Option<f64> {
+        self.last
+    }
+
+    /// Logged spike times, oldest first (ms)
+    pub fn spike_times(&self) -> impl Iterator<Item = f64> + '_ {
+        self.log.iter().copied()
+    }
+
+    /// Logged spike times strictly after `t` (ms)
+    pub fn spike_times_since(&self, t: f64) -> impl Iterator<Item = f64> + '_ {
+        let start = self.log.partition_point(|&time| time <= t);
+        self.log.range(start..).copied()
+    }
+
+    /// Forget all logged spikes
+    pub fn clear(&mut self) {
+        self.log.clear();
+        self.last = None;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_upward_crossings_are_interpolated() {
+        let mut detector = SpikeDetector::default();
+
+        // Only the step that crosses -40 mV counts, not the ones above it
+        assert_eq!(detector.detect(-60.0, -50.0, 0.0, 0.1, -40.0), None);
+        let time = detector.detect(-50.0, -30.0, 0.1, 0.1, -40.0).unwrap();
+        assert!((time - 0.15).abs() < 1e-12);
+        assert_eq!(detector.detect(-30.0, 20.0, 0.2, 0.1, -40.0), None);
+        assert_eq!(detector.detect(20.0, -45.0, 0.3, 0.1, -40.0), None);
+        assert_eq!(detector.last_spike(), Some(time));
+    }
+
+    #[test]
+    fn test_refractory_window_and_bounded_log() {
+        let mut detector = SpikeDetector { log_capacity: 2, ..SpikeDetector::default() };
+        assert!(detector.detect(-50.0, -30.0, 0.0, 0.1, -40.0).is_some());
+
+        // A re-crossing 1 ms later falls inside the 2 ms refractory window
+        assert!(detector.detect(-50.0, -30.0, 1.0, 0.1, -40.0).is_none());
+        assert!(detector.detect(-50.0, -30.0, 3.0, 0.1, -40.0).is_some());
+        assert!(detector.detect(-50.0, -30.0, 6.0, 0.1, -40.0).is_some());
+
+        let times: Vec<f64> = detector.spike_times().collect();
+        assert_eq!(times.len(), 2);
+        assert!((times[0] - 3.05).abs() < 1e-12);
+        assert_eq!(detector.spike_times_since(3.05).count(), 1);
+
+        // Shrinking the capacity drops the oldest spikes on the next one
+        detector.log_capacity = 1;
+        assert!(detector.detect(-50.0, -30.0, 9.0, 0.1, -40.0).is_some());
+        assert_eq!(detector.spike_times().count(), 1);
+    }
+
+    #[test]
+    fn test_zero_capacity_keeps_no_log() {
+        let mut detector = SpikeDetector { log_capacity: 0, ..SpikeDetector::default() };
+        let time = detector.detect(-50.0, -30.0, 0.0, 0.1, -40.0).unwrap();
+        assert_eq!(detector.spike_times().count(), 0);
+        assert_eq!(detector.last_spike(), Some(time));
+
+        // The refractory window still applies without a log
+        assert!(detector.detect(-50.0, -30.0, 1.0, 0.1, -40.0).is_none());
+        assert!(detector.detect(-50.0, -30.0, 3.0, 0.1, -40.0).is_some());
+        assert_eq!(detector.spike_times().count(), 0);
+    }
+}
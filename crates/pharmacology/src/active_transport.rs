@@ -37,6 +37,7 @@
 
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
+use crate::library::DrugLibrary;
 
 /// Types of membrane transporters
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
@@ -296,96 +297,9 @@ pub struct TransporterDatabase {
 }
 
 impl TransporterDatabase {
+    /// Create database with the built-in drug library's profiles
     pub fn new() -> Self {
-        let mut db = Self::default();
-
-        // P-gp substrates (effluxed from brain)
-        let mut loperamide = DrugTransporterProfile::new("loperamide");
-        loperamide.substrate_of.insert(TransporterType::Pgp, 1.0);
-        db.profiles.insert("loperamide".to_string(), loperamide);
-
-        let mut digoxin = DrugTransporterProfile::new("digoxin");
-        digoxin.substrate_of.insert(TransporterType::Pgp, 1.2);
-        db.profiles.insert("digoxin".to_string(), digoxin);
-
-        let mut fexofenadine = DrugTransporterProfile::new("fexofenadine");
-        fexofenadine.substrate_of.insert(TransporterType::Pgp, 0.8);
-        db.profiles.insert("fexofenadine".to_string(), fexofenadine);
-
-        // P-gp inhibitors (increase brain penetration)
-        let mut ketoconazole = DrugTransporterProfile::new("ketoconazole");
-        ketoconazole.inhibitor_of.insert(TransporterType::Pgp, 0.5);
-        db.profiles.insert("ketoconazole".to_string(), ketoconazole);
-
-        let mut verapamil = DrugTransporterProfile::new("verapamil");
-        verapamil.inhibitor_of.insert(TransporterType::Pgp, 1.5);
-        verapamil.substrate_of.insert(TransporterType::Pgp, 0.5);
-        db.profiles.insert("verapamil".to_string(), verapamil);
-
-        let mut cyclosporine = DrugTransporterProfile::new("cyclosporine");
-        cyclosporine.inhibitor_of.insert(TransporterType::Pgp, 0.2);
-        db.profiles.insert("cyclosporine".to_string(), cyclosporine);
-
-        let mut quinidine = DrugTransporterProfile::new("quinidine");
-        quinidine.inhibitor_of.insert(TransporterType::Pgp, 0.8);
-        db.profiles.insert("quinidine".to_string(), quinidine);
-
-        // BCRP substrates
-        let mut rosuvastatin = DrugTransporterProfile::new("rosuvastatin");
-        rosuvastatin.substrate_of.insert(TransporterType::Bcrp, 1.0);
-        rosuvastatin.substrate_of.insert(TransporterType::Oat3, 0.5);
-        db.profiles.insert("rosuvastatin".to_string(), rosuvastatin);
-
-        // Neurotransmitter transporter substrates
-        let mut serotonin = DrugTransporterProfile::new("serotonin");
-        serotonin.substrate_of.insert(TransporterType::Sert, 1.0);
-        db.profiles.insert("serotonin".to_string(), serotonin);
-
-        let mut dopamine = DrugTransporterProfile::new("dopamine");
-        dopamine.substrate_of.insert(TransporterType::Dat, 1.0);
-        db.profiles.insert("dopamine".to_string(), dopamine);
-
-        let mut norepinephrine = DrugTransporterProfile::new("norepinephrine");
-        norepinephrine.substrate_of.insert(TransporterType::Net, 1.0);
-        db.profiles.insert("norepinephrine".to_string(), norepinephrine);
-
-        let mut gaba = DrugTransporterProfile::new("gaba");
-        gaba.substrate_of.insert(TransporterType::Gat1, 1.0);
-        gaba.substrate_of.insert(TransporterType::Gat3, 0.7);
-        db.profiles.insert("gaba".to_string(), gaba);
-
-        // SERT inhibitors (SSRIs)
-        let mut fluoxetine = DrugTransporterProfile::new("fluoxetine");
-        fluoxetine.inhibitor_of.insert(TransporterType::Sert, 0.001);  // Very potent
-        db.profiles.insert("fluoxetine".to_string(), fluoxetine);
-
-        let mut sertraline = DrugTransporterProfile::new("sertraline");
-        sertraline.inhibitor_of.insert(TransporterType::Sert, 0.0003);
-        sertraline.inhibitor_of.insert(TransporterType::Dat, 0.025);
-        db.profiles.insert("sertraline".to_string(), sertraline);
-
-        let mut paroxetine = DrugTransporterProfile::new("paroxetine");
-        paroxetine.inhibitor_of.insert(TransporterType::Sert, 0.0001);  // Most potent SSRI
-        db.profiles.insert("paroxetine".to_string(), paroxetine);
-
-        // DAT inhibitors
-        let mut methylphenidate = DrugTransporterProfile::new("methylphenidate");
-        methylphenidate.inhibitor_of.insert(TransporterType::Dat, 0.02);
-        methylphenidate.inhibitor_of.insert(TransporterType::Net, 0.1);
-        db.profiles.insert("methylphenidate".to_string(), methylphenidate);
-
-        let mut cocaine = DrugTransporterProfile::new("cocaine");
-        cocaine.inhibitor_of.insert(TransporterType::Dat, 0.2);
-        cocaine.inhibitor_of.insert(TransporterType::Net, 0.3);
-        cocaine.inhibitor_of.insert(TransporterType::Sert, 0.4);
-        db.profiles.insert("cocaine".to_string(), cocaine);
-
-        // GAT inhibitors
-        let mut tiagabine = DrugTransporterProfile::new("tiagabine");
-        tiagabine.inhibitor_of.insert(TransporterType::Gat1, 0.07);
-        db.profiles.insert("tiagabine".to_string(), tiagabine);
-
-        db
+        DrugLibrary::builtin().transporter_database()
     }
 
     pub fn get(&self, drug: &str) -> Option<&DrugTransporterProfile> {
@@ -17,6 +17,8 @@
 
 use std::collections::HashMap;
 use serde::{Deserialize, Serialize};
+use crate::pharmacokinetics::{calculate_brain_concentration, PkDatabase, PkParameters, RouteOfAdministration};
+use crate::library::DrugLibrary;
 
 /// PET occupancy data from clinical studies
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -65,7 +67,7 @@ pub struct ClinicalPkReference {
 }
 
 /// Validation result comparing model to literature
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct ValidationResult {
     pub drug: String,
     pub metric: String,
@@ -107,500 +109,27 @@ impl Default for ClinicalLiteratureDb {
 }
 
 impl ClinicalLiteratureDb {
+    /// Create database with the built-in drug library's references
     pub fn new() -> Self {
-        let mut db = Self {
-            pet_data: HashMap::new(),
-            pk_data: HashMap::new(),
-        };
-        db.init_pet_data();
-        db.init_pk_data();
-        db
-    }
-
-    fn init_pet_data(&mut self) {
-        // ============================================================
-        // BENZODIAZEPINES - [11C]Flumazenil PET
-        // ============================================================
-        // Lingford-Hughes A et al. (2002) PMID: 12499952
-
-        self.add_pet(PetOccupancyData {
-            drug: "diazepam".to_string(),
-            receptor: "GABA-A".to_string(),
-            dose_mg: 10.0,
-            route: "oral".to_string(),
-            time_h: 1.5,
-            occupancy_percent: 15.0, // Low occupancy at therapeutic doses
-            sd: Some(5.0),
-            pmid: Some(12499952),
-            region: "cortex".to_string(),
-        });
-
-        self.add_pet(PetOccupancyData {
-            drug: "diazepam".to_string(),
-            receptor: "GABA-A".to_string(),
-            dose_mg: 20.0,
-            route: "oral".to_string(),
-            time_h: 1.5,
-            occupancy_percent: 28.0,
-            sd: Some(8.0),
-            pmid: Some(12499952),
-            region: "cortex".to_string(),
-        });
-
-        self.add_pet(PetOccupancyData {
-            drug: "lorazepam".to_string(),
-            receptor: "GABA-A".to_string(),
-            dose_mg: 2.0,
-            route: "oral".to_string(),
-            time_h: 2.0,
-            occupancy_percent: 20.0,
-            sd: Some(6.0),
-            pmid: Some(12499952),
-            region: "cortex".to_string(),
-        });
-
-        self.add_pet(PetOccupancyData {
-            drug: "midazolam".to_string(),
-            receptor: "GABA-A".to_string(),
-            dose_mg: 7.5,
-            route: "IV".to_string(),
-            time_h: 0.25,
-            occupancy_percent: 35.0,
-            sd: Some(10.0),
-            pmid: Some(12499952),
-            region: "cortex".to_string(),
-        });
-
-        self.add_pet(PetOccupancyData {
-            drug: "alprazolam".to_string(),
-            receptor: "GABA-A".to_string(),
-            dose_mg: 1.0,
-            route: "oral".to_string(),
-            time_h: 1.5,
-            occupancy_percent: 22.0,
-            sd: Some(7.0),
-            pmid: Some(12499952),
-            region: "cortex".to_string(),
-        });
-
-        // ============================================================
-        // ANTIPSYCHOTICS - [11C]Raclopride PET (D2)
-        // ============================================================
-        // Farde L et al. (1992) PMID: 1616206
-        // Kapur S et al. (2000) PMID: 10686270
-
-        self.add_pet(PetOccupancyData {
-            drug: "haloperidol".to_string(),
-            receptor: "D2".to_string(),
-            dose_mg: 5.0,
-            route: "oral".to_string(),
-            time_h: 4.0,
-            occupancy_percent: 70.0, // Therapeutic range 65-80%
-            sd: Some(8.0),
-            pmid: Some(1616206),
-            region: "striatum".to_string(),
-        });
-
-        self.add_pet(PetOccupancyData {
-            drug: "haloperidol".to_string(),
-            receptor: "D2".to_string(),
-            dose_mg: 10.0,
-            route: "oral".to_string(),
-            time_h: 4.0,
-            occupancy_percent: 80.0,
-            sd: Some(5.0),
-            pmid: Some(1616206),
-            region: "striatum".to_string(),
-        });
-
-        self.add_pet(PetOccupancyData {
-            drug: "risperidone".to_string(),
-            receptor: "D2".to_string(),
-            dose_mg: 2.0,
-            route: "oral".to_string(),
-            time_h: 4.0,
-            occupancy_percent: 66.0,
-            sd: Some(10.0),
-            pmid: Some(10686270),
-            region: "striatum".to_string(),
-        });
-
-        self.add_pet(PetOccupancyData {
-            drug: "risperidone".to_string(),
-            receptor: "D2".to_string(),
-            dose_mg: 4.0,
-            route: "oral".to_string(),
-            time_h: 4.0,
-            occupancy_percent: 75.0,
-            sd: Some(8.0),
-            pmid: Some(10686270),
-            region: "striatum".to_string(),
-        });
-
-        self.add_pet(PetOccupancyData {
-            drug: "olanzapine".to_string(),
-            receptor: "D2".to_string(),
-            dose_mg: 10.0,
-            route: "oral".to_string(),
-            time_h: 4.0,
-            occupancy_percent: 60.0,
-            sd: Some(12.0),
-            pmid: Some(10686270),
-            region: "striatum".to_string(),
-        });
-
-        self.add_pet(PetOccupancyData {
-            drug: "clozapine".to_string(),
-            receptor: "D2".to_string(),
-            dose_mg: 400.0,
-            route: "oral".to_string(),
-            time_h: 4.0,
-            occupancy_percent: 52.0, // Lower D2 occupancy is characteristic
-            sd: Some(15.0),
-            pmid: Some(10686270),
-            region: "striatum".to_string(),
-        });
-
-        // ============================================================
-        // SSRIs - [11C]DASB PET (SERT)
-        // ============================================================
-        // Meyer JH et al. (2004) PMID: 15121618
-
-        self.add_pet(PetOccupancyData {
-            drug: "fluoxetine".to_string(),
-            receptor: "SERT".to_string(),
-            dose_mg: 20.0,
-            route: "oral".to_string(),
-            time_h: 336.0, // Steady state (2 weeks)
-            occupancy_percent: 80.0,
-            sd: Some(5.0),
-            pmid: Some(15121618),
-            region: "striatum".to_string(),
-        });
-
-        self.add_pet(PetOccupancyData {
-            drug: "paroxetine".to_string(),
-            receptor: "SERT".to_string(),
-            dose_mg: 20.0,
-            route: "oral".to_string(),
-            time_h: 336.0,
-            occupancy_percent: 83.0,
-            sd: Some(6.0),
-            pmid: Some(15121618),
-            region: "striatum".to_string(),
-        });
-
-        self.add_pet(PetOccupancyData {
-            drug: "sertraline".to_string(),
-            receptor: "SERT".to_string(),
-            dose_mg: 50.0,
-            route: "oral".to_string(),
-            time_h: 336.0,
-            occupancy_percent: 77.0,
-            sd: Some(8.0),
-            pmid: Some(15121618),
-            region: "striatum".to_string(),
-        });
-
-        self.add_pet(PetOccupancyData {
-            drug: "citalopram".to_string(),
-            receptor: "SERT".to_string(),
-            dose_mg: 20.0,
-            route: "oral".to_string(),
-            time_h: 336.0,
-            occupancy_percent: 72.0,
-            sd: Some(7.0),
-            pmid: Some(15121618),
-            region: "striatum".to_string(),
-        });
-
-        self.add_pet(PetOccupancyData {
-            drug: "escitalopram".to_string(),
-            receptor: "SERT".to_string(),
-            dose_mg: 10.0,
-            route: "oral".to_string(),
-            time_h: 336.0,
-            occupancy_percent: 80.0,
-            sd: Some(5.0),
-            pmid: Some(15121618),
-            region: "striatum".to_string(),
-        });
-
-        self.add_pet(PetOccupancyData {
-            drug: "venlafaxine".to_string(),
-            receptor: "SERT".to_string(),
-            dose_mg: 75.0,
-            route: "oral".to_string(),
-            time_h: 336.0,
-            occupancy_percent: 45.0, // Lower at low doses
-            sd: Some(12.0),
-            pmid: Some(15121618),
-            region: "striatum".to_string(),
-        });
-
-        self.add_pet(PetOccupancyData {
-            drug: "venlafaxine".to_string(),
-            receptor: "SERT".to_string(),
-            dose_mg: 150.0,
-            route: "oral".to_string(),
-            time_h: 336.0,
-            occupancy_percent: 70.0,
-            sd: Some(10.0),
-            pmid: Some(15121618),
-            region: "striatum".to_string(),
-        });
-
-        // ============================================================
-        // OPIOIDS - [11C]Carfentanil PET (mu-opioid)
-        // ============================================================
-        // Melichar JK et al. (2005) PMID: 15483561
-
-        self.add_pet(PetOccupancyData {
-            drug: "morphine".to_string(),
-            receptor: "OPRM1".to_string(),
-            dose_mg: 10.0,
-            route: "IV".to_string(),
-            time_h: 0.5,
-            occupancy_percent: 42.0,
-            sd: Some(12.0),
-            pmid: Some(15483561),
-            region: "thalamus".to_string(),
-        });
-
-        self.add_pet(PetOccupancyData {
-            drug: "fentanyl".to_string(),
-            receptor: "OPRM1".to_string(),
-            dose_mg: 0.1, // 100 mcg
-            route: "IV".to_string(),
-            time_h: 0.25,
-            occupancy_percent: 35.0,
-            sd: Some(10.0),
-            pmid: Some(15483561),
-            region: "thalamus".to_string(),
-        });
-
-        self.add_pet(PetOccupancyData {
-            drug: "buprenorphine".to_string(),
-            receptor: "OPRM1".to_string(),
-            dose_mg: 2.0,
-            route: "sublingual".to_string(),
-            time_h: 2.0,
-            occupancy_percent: 75.0, // High occupancy even at low doses
-            sd: Some(8.0),
-            pmid: Some(15483561),
-            region: "thalamus".to_string(),
-        });
-
-        // ============================================================
-        // ANESTHETICS - Propofol effect-site concentration
-        // ============================================================
-        // Schuttler J & Ihmsen H (2000) Anesthesiology
-
-        self.add_pet(PetOccupancyData {
-            drug: "propofol".to_string(),
-            receptor: "GABA-A".to_string(),
-            dose_mg: 140.0, // 2 mg/kg for 70 kg
-            route: "IV".to_string(),
-            time_h: 0.05, // 3 min
-            occupancy_percent: 50.0, // At LOC
-            sd: Some(15.0),
-            pmid: Some(10754634),
-            region: "cortex".to_string(),
-        });
-
-        // ============================================================
-        // NMDA ANTAGONISTS - Ketamine
-        // ============================================================
-
-        self.add_pet(PetOccupancyData {
-            drug: "ketamine".to_string(),
-            receptor: "NMDA".to_string(),
-            dose_mg: 35.0, // 0.5 mg/kg for 70 kg
-            route: "IV".to_string(),
-            time_h: 0.25,
-            occupancy_percent: 30.0, // Subanesthetic
-            sd: Some(10.0),
-            pmid: Some(11283682),
-            region: "cortex".to_string(),
-        });
+        DrugLibrary::builtin().clinical_literature()
     }
 
-    fn init_pk_data(&mut self) {
-        // ============================================================
-        // BENZODIAZEPINES
-        // ============================================================
-
-        self.add_pk(ClinicalPkReference {
-            drug: "diazepam".to_string(),
-            dose_mg: 10.0,
-            route: "oral".to_string(),
-            cmax_plasma_ng_ml: 300.0,
-            tmax_h: 1.0,
-            t_half_h: 43.0,
-            brain_plasma_ratio: Some(0.9),
-            csf_ng_ml: Some(15.0),
-            source: "FDA Label / Greenblatt DJ (1980)".to_string(),
-        });
-
-        self.add_pk(ClinicalPkReference {
-            drug: "midazolam".to_string(),
-            dose_mg: 7.5,
-            route: "IV".to_string(),
-            cmax_plasma_ng_ml: 100.0, // Immediate
-            tmax_h: 0.0,
-            t_half_h: 2.5,
-            brain_plasma_ratio: Some(0.8),
-            csf_ng_ml: Some(5.0),
-            source: "FDA Label".to_string(),
-        });
-
-        self.add_pk(ClinicalPkReference {
-            drug: "lorazepam".to_string(),
-            dose_mg: 2.0,
-            route: "oral".to_string(),
-            cmax_plasma_ng_ml: 25.0,
-            tmax_h: 2.0,
-            t_half_h: 12.0,
-            brain_plasma_ratio: Some(0.8),
-            csf_ng_ml: Some(4.0),
-            source: "FDA Label".to_string(),
-        });
-
-        // ============================================================
-        // ANESTHETICS
-        // ============================================================
-
-        self.add_pk(ClinicalPkReference {
-            drug: "propofol".to_string(),
-            dose_mg: 140.0, // 2 mg/kg
-            route: "IV".to_string(),
-            cmax_plasma_ng_ml: 4000.0, // 4 µg/mL at induction
-            tmax_h: 0.0,
-            t_half_h: 0.5, // Distribution half-life
-            brain_plasma_ratio: Some(1.2), // Rapid equilibration
-            csf_ng_ml: Some(800.0), // Effect-site ~1 µg/mL
-            source: "Schuttler J & Ihmsen H (2000)".to_string(),
-        });
-
-        self.add_pk(ClinicalPkReference {
-            drug: "ketamine".to_string(),
-            dose_mg: 35.0, // 0.5 mg/kg
-            route: "IV".to_string(),
-            cmax_plasma_ng_ml: 500.0,
-            tmax_h: 0.0,
-            t_half_h: 2.5,
-            brain_plasma_ratio: Some(4.0), // High brain penetration
-            csf_ng_ml: Some(250.0),
-            source: "Clements JA (1982)".to_string(),
-        });
-
-        // ============================================================
-        // OPIOIDS
-        // ============================================================
-
-        self.add_pk(ClinicalPkReference {
-            drug: "morphine".to_string(),
-            dose_mg: 10.0,
-            route: "IV".to_string(),
-            cmax_plasma_ng_ml: 100.0,
-            tmax_h: 0.0,
-            t_half_h: 3.0,
-            brain_plasma_ratio: Some(0.3), // Poor BBB penetration
-            csf_ng_ml: Some(10.0),
-            source: "FDA Label".to_string(),
-        });
-
-        self.add_pk(ClinicalPkReference {
-            drug: "fentanyl".to_string(),
-            dose_mg: 0.1, // 100 mcg
-            route: "IV".to_string(),
-            cmax_plasma_ng_ml: 1.5, // 1.5 ng/mL
-            tmax_h: 0.0,
-            t_half_h: 4.0,
-            brain_plasma_ratio: Some(2.5), // High lipophilicity
-            csf_ng_ml: Some(0.5),
-            source: "FDA Label".to_string(),
-        });
-
-        // ============================================================
-        // ANTIPSYCHOTICS
-        // ============================================================
-
-        self.add_pk(ClinicalPkReference {
-            drug: "haloperidol".to_string(),
-            dose_mg: 5.0,
-            route: "oral".to_string(),
-            cmax_plasma_ng_ml: 5.0,
-            tmax_h: 4.0,
-            t_half_h: 18.0,
-            brain_plasma_ratio: Some(20.0), // Very high brain accumulation
-            csf_ng_ml: Some(1.5),
-            source: "Farde L (1992)".to_string(),
-        });
-
-        self.add_pk(ClinicalPkReference {
-            drug: "risperidone".to_string(),
-            dose_mg: 2.0,
-            route: "oral".to_string(),
-            cmax_plasma_ng_ml: 10.0,
-            tmax_h: 1.0,
-            t_half_h: 3.0, // Parent compound
-            brain_plasma_ratio: Some(3.0),
-            csf_ng_ml: Some(2.0),
-            source: "FDA Label".to_string(),
-        });
-
-        // ============================================================
-        // SSRIs
-        // ============================================================
-
-        self.add_pk(ClinicalPkReference {
-            drug: "fluoxetine".to_string(),
-            dose_mg: 20.0,
-            route: "oral".to_string(),
-            cmax_plasma_ng_ml: 20.0,
-            tmax_h: 6.0,
-            t_half_h: 72.0, // Long half-life
-            brain_plasma_ratio: Some(3.0),
-            csf_ng_ml: Some(10.0),
-            source: "FDA Label".to_string(),
-        });
-
-        self.add_pk(ClinicalPkReference {
-            drug: "sertraline".to_string(),
-            dose_mg: 50.0,
-            route: "oral".to_string(),
-            cmax_plasma_ng_ml: 40.0,
-            tmax_h: 6.0,
-            t_half_h: 26.0,
-            brain_plasma_ratio: Some(5.0),
-            csf_ng_ml: Some(8.0),
-            source: "FDA Label".to_string(),
-        });
-
-        // ============================================================
-        // ANTI-PARKINSON
-        // ============================================================
-
-        self.add_pk(ClinicalPkReference {
-            drug: "levodopa".to_string(),
-            dose_mg: 100.0,
-            route: "oral".to_string(),
-            cmax_plasma_ng_ml: 1500.0, // With carbidopa
-            tmax_h: 1.0,
-            t_half_h: 1.5,
-            brain_plasma_ratio: Some(0.1), // Requires active transport
-            csf_ng_ml: Some(50.0),
-            source: "Nutt JG (2008)".to_string(),
-        });
+    /// Create an empty database
+    pub fn empty() -> Self {
+        Self {
+            pet_data: HashMap::new(),
+            pk_data: HashMap::new(),
+        }
     }
 
-    fn add_pet(&mut self, data: PetOccupancyData) {
+    /// Add a PET occupancy study
+    pub fn add_pet(&mut self, data: PetOccupancyData) {
         let key = data.drug.to_lowercase();
         self.pet_data.entry(key).or_insert_with(Vec::new).push(data);
     }
 
-    fn add_pk(&mut self, data: ClinicalPkReference) {
+    /// Add a clinical PK reference
+    pub fn add_pk(&mut self, data: ClinicalPkReference) {
         let key = data.drug.to_lowercase();
         self.pk_data.entry(key).or_insert_with(Vec::new).push(data);
     }
@@ -737,6 +266,124 @@ pub fn validate_drug(
     results
 }
 
+/// Free brain concentration (µM) right after an IV bolus (instant distribution)
+fn iv_bolus_brain_um(pk: &PkParameters, dose_mg: f64, weight_kg: f64) -> f64 {
+    let cmax_plasma_mg_l = dose_mg / pk.vd_l(weight_kg);
+    let cmax_plasma_um = (cmax_plasma_mg_l * 1000.0) / pk.molecular_weight;
+    cmax_plasma_um * pk.brain_partition * pk.free_fraction()
+}
+
+/// PET occupancy study: label, drug, dose (mg), route, Ki (nM), steady-state
+/// accumulation factor, metric and literature occupancy (%)
+#[derive(Clone, Copy)]
+struct OccupancyCase<'a>(&'a str, &'a str, f64, RouteOfAdministration, f64, f64, &'a str, f64);
+
+/// Validate predicted receptor occupancy against the PET literature
+///
+/// Covers the benzodiazepine ([11C]flumazenil), antipsychotic
+/// ([11C]raclopride), SSRI ([11C]DASB), opioid ([11C]carfentanil) and
+/// anesthetic studies. A 70 kg subject is assumed throughout.
+pub fn validate_pet_occupancy(tolerance: f64) -> Vec<ValidationResult> {
+    let pk_db = PkDatabase::new();
+    let weight_kg = 70.0;
+    let mut results = Vec::new();
+
+    // Free brain concentration right after a single dose
+    let brain_um = |drug: &str, dose_mg: f64, route: RouteOfAdministration| match route {
+        RouteOfAdministration::IvBolus => pk_db.get(drug).map(|pk| iv_bolus_brain_um(pk, dose_mg, weight_kg)),
+        _ => calculate_brain_concentration(drug, dose_mg, weight_kg, route, &pk_db),
+    };
+    let occupancy_cases = |cases: &[OccupancyCase]| {
+        cases.iter()
+            .filter_map(|&OccupancyCase(label, drug, dose_mg, route, ki_nm, ss_factor, metric, lit)| {
+                let um = brain_um(drug, dose_mg, route)?;
+                let model = calculate_occupancy_from_ki(um * ss_factor, ki_nm);
+                Some(ValidationResult::new(label, metric, model, lit, tolerance))
+            })
+            .collect::<Vec<_>>()
+    };
+
+    use RouteOfAdministration::{IvBolus, Oral};
+
+    results.extend(occupancy_cases(&[
+        // Lingford-Hughes 2002; Ki for the BZ site ~3 nM (flumazenil displacement)
+        OccupancyCase("diazepam", "diazepam", 10.0, Oral, 3.0, 1.0, "GABA-A occupancy", 15.0),
+        OccupancyCase("midazolam", "midazolam", 7.5, IvBolus, 2.5, 1.0, "GABA-A occupancy", 35.0),
+        OccupancyCase("alprazolam", "alprazolam", 1.0, Oral, 2.0, 1.0, "GABA-A occupancy", 22.0),
+        // Farde 1992; Ki = 0.5 nM (ChEMBL)
+        OccupancyCase("haloperidol", "haloperidol", 5.0, Oral, 0.5, 1.0, "D2 occupancy", 70.0),
+        OccupancyCase("haloperidol 10mg", "haloperidol", 10.0, Oral, 0.5, 1.0, "D2 occupancy", 80.0),
+        // Meyer 2004; Ki = 1.1 nM, ~5x accumulation at steady state (t1/2 = 72h)
+        OccupancyCase("fluoxetine (SS)", "fluoxetine", 20.0, Oral, 1.1, 5.0, "SERT occupancy", 80.0),
+    ]));
+
+    // Sertraline 50mg oral (steady state); Ki = 0.1 nM (very high affinity)
+    if let Some(pk) = pk_db.get("sertraline") {
+        let vd = pk.vd_l(weight_kg);
+        let cmax_plasma_mg_l = (50.0 * pk.bioavailability_oral) / vd;
+        let cmax_plasma_um = (cmax_plasma_mg_l * 1000.0) / 306.2; // MW sertraline
+        let brain_um = cmax_plasma_um * 5.0 * (1.0 - 0.98); // High protein binding
+        let steady_state_brain_um = brain_um * 3.0; // t1/2 ~26h
+
+        let model = calculate_occupancy_from_ki(steady_state_brain_um, 0.1);
+        results.push(ValidationResult::new("sertraline (SS)", "SERT occupancy", model, 77.0, tolerance));
+    }
+
+    results.extend(occupancy_cases(&[
+        // Melichar 2005; Ki = 0.3 nM
+        OccupancyCase("morphine", "morphine", 10.0, IvBolus, 0.3, 1.0, "μ-OR occupancy", 42.0),
+        OccupancyCase("fentanyl", "fentanyl", 0.1, IvBolus, 1.0, 1.0, "μ-OR occupancy", 35.0),
+    ]));
+
+    // Propofol 2 mg/kg IV: ~50% GABA-A effect at loss of consciousness,
+    // EC50 for potentiation ~1-3 µM
+    if let Some(pk) = pk_db.get("propofol") {
+        let brain_um = iv_bolus_brain_um(pk, 140.0, weight_kg);
+        let ec50_um = 2.0;
+        let model = 100.0 * brain_um / (ec50_um + brain_um);
+        results.push(ValidationResult::new("propofol", "GABA-A effect", model, 50.0, tolerance));
+    }
+
+    results
+}
+
+/// Validate single-dose plasma PK (Cmax, Tmax) against clinical references
+///
+/// Every drug with both a literature PK entry and `PkDatabase` parameters is
+/// dosed as in the reference study (70 kg subject).
+pub fn validate_pk_literature(tolerance: f64) -> Vec<ValidationResult> {
+    let pk_db = PkDatabase::new();
+    let lit_db = ClinicalLiteratureDb::new();
+    let weight_kg = 70.0;
+
+    let mut drugs = lit_db.drugs_with_pk_data();
+    drugs.sort();
+
+    let mut results = Vec::new();
+    for drug in drugs {
+        let Some(pk) = pk_db.get(&drug) else { continue };
+        for reference in lit_db.get_pk_data(&drug).into_iter().flatten() {
+            let oral = reference.route.eq_ignore_ascii_case("oral");
+            let bioavailability = if oral { pk.bioavailability_oral } else { 1.0 };
+            let cmax_mg_l = reference.dose_mg * bioavailability / pk.vd_l(weight_kg);
+            let tmax_h = if oral { pk.tmax_oral_h } else { 0.0 };
+
+            results.push(ValidationResult::new(
+                &drug,
+                "Cmax plasma (ng/mL)",
+                cmax_mg_l * 1000.0,
+                reference.cmax_plasma_ng_ml,
+                tolerance,
+            ));
+            if reference.tmax_h > 0.0 && tmax_h > 0.0 {
+                results.push(ValidationResult::new(&drug, "Tmax (h)", tmax_h, reference.tmax_h, tolerance));
+            }
+        }
+    }
+
+    results
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -798,4 +445,14 @@ mod tests {
         assert!(pet.is_some());
         assert_eq!(pet.unwrap().occupancy_percent, 70.0);
     }
+
+    #[test]
+    fn test_literature_validation_suites() {
+        let pet = validate_pet_occupancy(0.05);
+        assert!(pet.iter().any(|r| r.drug == "diazepam" && r.metric == "GABA-A occupancy"));
+        assert!(pet.iter().all(|r| r.model_value >= 0.0 && r.model_value <= 100.0));
+
+        let pk = validate_pk_literature(0.05);
+        assert!(pk.iter().any(|r| r.drug == "diazepam" && r.metric.starts_with("Cmax")));
+    }
 }
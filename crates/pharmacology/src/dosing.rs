@@ -0,0 +1,550 @@
+//! Dosing Regimens
+//! ===============
+//!
+//! Multiple-dose pharmacokinetics by superposition. Every dose, by any
+//! `RouteOfAdministration`, is an input into a linear disposition model, so
+//! the plasma concentration of a regimen is the sum of the single-dose
+//! curves, each shifted to its dosing time and absorption lag.
+//!
+//! # Inputs
+//! - **Bolus** (IV bolus): the whole dose enters the central compartment
+//! - **First-order** (oral, IM, SC, sublingual, inhalation, intranasal):
+//!   the bioavailable dose is absorbed at rate `ka` after a lag time
+//! - **Zero-order** (IV infusion, transdermal patch): constant input over
+//!   the dose's duration
+//!
+//! # Disposition
+//! The unit impulse response of the central compartment is a sum of
+//! exponentials, C(t) = D * sum(c_i * exp(-lambda_i t)): one phase for the
+//! one-compartment model, two (alpha, beta) for the two-compartment model.
+//! Elimination uses `PkParameters::k_el`, as in `OneCompartmentModel` and
+//! `TwoCompartmentModel`.
+//!
+//! # Steady State
+//! For a dose repeated every tau hours, C_ss(t) = sum_n C_1(t + n tau),
+//! summed until the terms vanish; Cmax, Cmin, AUC_tau and the accumulation
+//! ratio AUC_tau,ss / AUC_0-tau,1 follow from that curve.
+//!
+//! # References
+//! - Rowland M & Tozer TN (2010) Clinical Pharmacokinetics, ch. 11
+//! - Gibaldi M & Perrier D (1982) Pharmacokinetics, 2nd ed, ch. 3
+
+use crate::pharmacokinetics::{bateman, PkParameters, RouteOfAdministration};
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+
+/// Points per dosing interval when sampling steady-state curves
+const STEADY_STATE_SAMPLES: usize = 1000;
+
+/// How a dose enters the central compartment
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub enum InputKind {
+    /// Instantaneous
+    Bolus,
+    /// First-order absorption at `ka` (1/h)
+    FirstOrder { ka: f64 },
+    /// Constant rate over the dose's duration
+    ZeroOrder,
+}
+
+/// Absorption model of one route
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct AbsorptionModel {
+    /// Fraction of the dose reaching the systemic circulation (0-1)
+    pub bioavailability: f64,
+    /// Input kinetics
+    pub kind: InputKind,
+    /// Delay between administration and the start of absorption (hours)
+    pub lag_h: f64,
+}
+
+impl AbsorptionModel {
+    /// Default absorption of `route` for a drug: oral doses use the drug's
+    /// own bioavailability and `ka`; other extravascular routes use typical
+    /// route bioavailability and the `ka` that peaks at the route's typical
+    /// tmax
+    pub fn for_route(route: RouteOfAdministration, pk: &PkParameters) -> Self {
+        let (bioavailability, kind) = match route {
+            RouteOfAdministration::IvBolus => (1.0, InputKind::Bolus),
+            RouteOfAdministration::IvInfusion => (1.0, InputKind::ZeroOrder),
+            RouteOfAdministration::Transdermal => (route.typical_bioavailability(), InputKind::ZeroOrder),
+            RouteOfAdministration::Oral => (pk.bioavailability_oral, InputKind::FirstOrder { ka: pk.ka }),
+            _ => (
+                route.typical_bioavailability(),
+                InputKind::FirstOrder { ka: ka_for_tmax(route.typical_tmax_h(), pk.k_el()) },
+            ),
+        };
+        Self { bioavailability, kind, lag_h: route.typical_lag_h() }
+    }
+
+    /// Builder: set the lag time (hours)
+    pub fn with_lag(mut self, lag_h: f64) -> Self {
+        self.lag_h = lag_h;
+        self
+    }
+}
+
+/// Absorption rate constant (1/h) giving a peak at `tmax_h` when elimination
+/// is `k_el`, solving tmax = ln(ka / k) / (ka - k) for ka > k
+pub fn ka_for_tmax(tmax_h: f64, k_el: f64) -> f64 {
+    if tmax_h <= 0.0 {
+        return f64::INFINITY;
+    }
+    let tmax = |ka: f64| (ka / k_el).ln() / (ka - k_el);
+    // tmax falls monotonically from 1/k (ka -> k) to 0 (ka -> infinity)
+    if tmax_h >= 1.0 / k_el {
+        return k_el * (1.0 + 1e-6);
+    }
+    let (mut lo, mut hi) = (k_el * (1.0 + 1e-9), k_el * 2.0);
+    while tmax(hi) > tmax_h {
+        hi *= 2.0;
+    }
+    for _ in 0..100 {
+        let mid = 0.5 * (lo + hi);
+        if tmax(mid) > tmax_h {
+            lo = mid;
+        } else {
+            hi = mid;
+        }
+    }
+    0.5 * (lo + hi)
+}
+
+/// One administration
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct Dose {
+    /// Administration time (hours from the start of the regimen)
+    pub time_h: f64,
+    /// Amount administered (mg)
+    pub amount_mg: f64,
+    pub route: RouteOfAdministration,
+    /// Input duration for infusions and patches (hours)
+    pub duration_h: f64,
+}
+
+impl Dose {
+    /// Dose of `amount_mg` by `route` at `time_h`. Infusions default to one
+    /// hour and transdermal patches to 24 hours of wear.
+    pub fn new(time_h: f64, amount_mg: f64, route: RouteOfAdministration) -> Self {
+        let duration_h = match route {
+            RouteOfAdministration::IvInfusion => 1.0,
+            RouteOfAdministration::Transdermal => 24.0,
+            _ => 0.0,
+        };
+        Self { time_h, amount_mg, route, duration_h }
+    }
+
+    /// Constant-rate IV infusion of `rate_mg_h` for `duration_h` from `time_h`
+    pub fn infusion(time_h: f64, rate_mg_h: f64, duration_h: f64) -> Self {
+        Self::new(time_h, rate_mg_h * duration_h, RouteOfAdministration::IvInfusion).with_duration(duration_h)
+    }
+
+    /// Builder: set the input duration (hours)
+    pub fn with_duration(mut self, duration_h: f64) -> Self {
+        self.duration_h = duration_h;
+        self
+    }
+}
+
+/// A schedule of doses
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct DosingRegimen {
+    /// Doses, in order of administration when built with `with_dose`
+    pub doses: Vec<Dose>,
+}
+
+impl DosingRegimen {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// `count` doses of `amount_mg` every `interval_h` hours from time 0,
+    /// e.g. three times daily for two weeks: `repeated(0.5, Oral, 8.0, 42)`
+    pub fn repeated(amount_mg: f64, route: RouteOfAdministration, interval_h: f64, count: usize) -> Self {
+        Self {
+            doses: (0..count).map(|i| Dose::new(i as f64 * interval_h, amount_mg, route)).collect(),
+        }
+    }
+
+    /// Builder: add a dose, keeping the schedule in time order
+    pub fn with_dose(mut self, dose: Dose) -> Self {
+        let at = self.doses.partition_point(|d| d.time_h <= dose.time_h);
+        self.doses.insert(at, dose);
+        self
+    }
+
+    /// Total amount administered (mg)
+    pub fn total_amount_mg(&self) -> f64 {
+        self.doses.iter().map(|d| d.amount_mg).sum()
+    }
+}
+
+/// Central-compartment disposition as a sum of exponential phases
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Disposition {
+    /// (coefficient 1/L, rate 1/h) of each phase: a unit dose gives
+    /// C(t) = sum(c * exp(-lambda t)) mg/L
+    pub phases: Vec<(f64, f64)>,
+}
+
+impl Disposition {
+    /// Single well-stirred compartment of volume Vd
+    pub fn one_compartment(pk: &PkParameters, weight_kg: f64) -> Self {
+        Self { phases: vec![(1.0 / pk.vd_l(weight_kg), pk.k_el())] }
+    }
+
+    /// Central compartment (30% of Vd, as in `TwoCompartmentModel`)
+    /// exchanging with a peripheral one at rates `k12`, `k21` (1/h)
+    pub fn two_compartment(pk: &PkParameters, weight_kg: f64, k12: f64, k21: f64) -> Self {
+        let k10 = pk.k_el();
+        let vc = pk.vd_l(weight_kg) * 0.3;
+        let sum = k10 + k12 + k21;
+        let discriminant = (sum * sum - 4.0 * k10 * k21).sqrt();
+        let (alpha, beta) = ((sum + discriminant) / 2.0, (sum - discriminant) / 2.0);
+        Self {
+            phases: vec![
+                ((alpha - k21) / ((alpha - beta) * vc), alpha),
+                ((k21 - beta) / ((alpha - beta) * vc), beta),
+            ],
+        }
+    }
+
+    /// Total clearance relative to the central volume: dose / AUC (L/h)
+    pub fn clearance_l_h(&self) -> f64 {
+        1.0 / self.phases.iter().map(|(c, lambda)| c / lambda).sum::<f64>()
+    }
+
+    /// Slowest (terminal) rate constant (1/h)
+    pub fn terminal_rate(&self) -> f64 {
+        self.phases.iter().map(|&(_, lambda)| lambda).fold(f64::INFINITY, f64::min)
+    }
+
+    /// Time to reach `fraction` (0-1) of steady state under repeated
+    /// dosing, governed by the terminal phase (hours)
+    pub fn time_to_steady_state_h(&self, fraction: f64) -> f64 {
+        -(1.0 - fraction.clamp(0.0, 1.0 - 1e-12)).ln() / self.terminal_rate()
+    }
+
+    /// Concentration per mg for each input kind, `time_h` after input starts;
+    /// zero-order inputs deliver 1 mg over `duration_h`
+    fn unit_response(&self, kind: InputKind, duration_h: f64, time_h: f64) -> f64 {
+        if time_h <= 0.0 {
+            return 0.0;
+        }
+        match kind {
+            InputKind::Bolus => self.phases.iter().map(|(c, lambda)| c * (-lambda * time_h).exp()).sum(),
+            InputKind::FirstOrder { ka } if ka.is_infinite() => self.unit_response(InputKind::Bolus, 0.0, time_h),
+            InputKind::FirstOrder { ka } => self.phases.iter().map(|&(c, lambda)| c * bateman(ka, lambda, time_h)).sum(),
+            InputKind::ZeroOrder if duration_h <= 0.0 => self.unit_response(InputKind::Bolus, 0.0, time_h),
+            InputKind::ZeroOrder => {
+                // Unit-rate step response, switched off after the duration
+                let step = |t: f64| -> f64 {
+                    if t <= 0.0 {
+                        return 0.0;
+                    }
+                    self.phases.iter().map(|(c, lambda)| c / lambda * (1.0 - (-lambda * t).exp())).sum()
+                };
+                (step(time_h) - step(time_h - duration_h)) / duration_h
+            }
+        }
+    }
+}
+
+/// Plasma and brain concentration time course
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct ConcentrationProfile {
+    /// Sample times (hours)
+    pub times_h: Vec<f64>,
+    /// Total plasma concentration (mg/L)
+    pub plasma_mg_l: Vec<f64>,
+    /// Free brain concentration (uM)
+    pub brain_um: Vec<f64>,
+}
+
+impl ConcentrationProfile {
+    /// Peak plasma concentration (mg/L) and its time (hours)
+    pub fn cmax(&self) -> (f64, f64) {
+        self.times_h
+            .iter()
+            .zip(&self.plasma_mg_l)
+            .fold((0.0, 0.0), |best, (&t, &c)| if c > best.0 { (c, t) } else { best })
+    }
+
+    /// Area under the plasma curve by the trapezoidal rule (mg·h/L)
+    pub fn auc(&self) -> f64 {
+        self.times_h
+            .windows(2)
+            .zip(self.plasma_mg_l.windows(2))
+            .map(|(t, c)| 0.5 * (c[0] + c[1]) * (t[1] - t[0]))
+            .sum()
+    }
+
+    /// Receptor occupancy (0-1) at each sample for a drug binding with
+    /// affinity `ki_um` at its brain target
+    pub fn occupancy(&self, ki_um: f64) -> Vec<f64> {
+        self.brain_um.iter().map(|&c| c / (c + ki_um)).collect()
+    }
+}
+
+/// Steady-state exposure under a repeated dose
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct SteadyState {
+    /// Dosing interval tau (hours)
+    pub interval_h: f64,
+    /// Peak concentration (mg/L)
+    pub cmax_mg_l: f64,
+    /// Time of the peak after each dose (hours)
+    pub tmax_h: f64,
+    /// Trough concentration (mg/L)
+    pub cmin_mg_l: f64,
+    /// Area under the curve over one interval (mg·h/L)
+    pub auc_tau: f64,
+    /// Average concentration, AUC_tau / tau (mg/L)
+    pub cavg_mg_l: f64,
+    /// AUC_tau at steady state over AUC_0-tau of the first dose
+    pub accumulation_ratio: f64,
+    /// (Cmax - Cmin) / Cavg
+    pub fluctuation: f64,
+}
+
+/// Multiple-dose pharmacokinetic model of one drug in one patient
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RegimenModel {
+    pk: PkParameters,
+    disposition: Disposition,
+    absorption: HashMap<RouteOfAdministration, AbsorptionModel>,
+}
+
+impl RegimenModel {
+    /// One-compartment model for a patient of `weight_kg`
+    pub fn new(pk: PkParameters, weight_kg: f64) -> Self {
+        let disposition = Disposition::one_compartment(&pk, weight_kg);
+        Self { pk, disposition, absorption: HashMap::new() }
+    }
+
+    /// Two-compartment model with distribution rates `k12`, `k21` (1/h)
+    pub fn two_compartment(pk: PkParameters, weight_kg: f64, k12: f64, k21: f64) -> Self {
+        let disposition = Disposition::two_compartment(&pk, weight_kg, k12, k21);
+        Self { pk, disposition, absorption: HashMap::new() }
+    }
+
+    /// Builder: override the absorption model of a route
+    pub fn with_absorption(mut self, route: RouteOfAdministration, model: AbsorptionModel) -> Self {
+        self.absorption.insert(route, model);
+        self
+    }
+
+    pub fn disposition(&self) -> &Disposition {
+        &self.disposition
+    }
+
+    /// Absorption model used for `route`
+    pub fn absorption(&self, route: RouteOfAdministration) -> AbsorptionModel {
+        self.absorption
+            .get(&route)
+            .copied()
+            .unwrap_or_else(|| AbsorptionModel::for_route(route, &self.pk))
+    }
+
+    /// Plasma concentration (mg/L) contributed by `dose` at `time_h`
+    pub fn dose_concentration(&self, dose: &Dose, time_h: f64) -> f64 {
+        let absorption = self.absorption(dose.route);
+        let elapsed = time_h - dose.time_h - absorption.lag_h;
+        absorption.bioavailability
+            * dose.amount_mg
+            * self.disposition.unit_response(absorption.kind, dose.duration_h, elapsed)
+    }
+
+    /// Plasma concentration (mg/L) of a regimen at `time_h`
+    pub fn concentration_at(&self, regimen: &DosingRegimen, time_h: f64) -> f64 {
+        regimen
+            .doses
+            .iter()
+            .filter(|d| d.time_h < time_h)
+            .map(|d| self.dose_concentration(d, time_h))
+            .sum()
+    }
+
+    /// Free brain concentration (uM) for a plasma concentration (mg/L)
+    pub fn brain_um(&self, plasma_mg_l: f64) -> f64 {
+        plasma_mg_l * 1000.0 / self.pk.molecular_weight * self.pk.brain_partition * self.pk.free_fraction()
+    }
+
+    /// Sample a regimen every `dt_h` hours from 0 to `duration_h`
+    pub fn simulate(&self, regimen: &DosingRegimen, duration_h: f64, dt_h: f64) -> ConcentrationProfile {
+        let samples = (duration_h / dt_h).round() as usize;
+        let mut profile = ConcentrationProfile::default();
+        for i in 0..=samples {
+            let t = i as f64 * dt_h;
+            let plasma = self.concentration_at(regimen, t);
+            profile.times_h.push(t);
+            profile.plasma_mg_l.push(plasma);
+            profile.brain_um.push(self.brain_um(plasma));
+        }
+        profile
+    }
+
+    /// Steady state of `amount_mg` by `route` every `interval_h` hours
+    pub fn steady_state(&self, amount_mg: f64, route: RouteOfAdministration, interval_h: f64) -> SteadyState {
+        let dose = Dose::new(0.0, amount_mg, route);
+        let absorption = self.absorption(route);
+
+        // Enough past doses for the oldest contributions to vanish
+        let slowest = match absorption.kind {
+            InputKind::FirstOrder { ka } => self.disposition.terminal_rate().min(ka),
+            _ => self.disposition.terminal_rate(),
+        };
+        let horizon = absorption.lag_h + dose.duration_h + 40.0 / slowest;
+        let past_doses = (horizon / interval_h).ceil() as usize + 1;
+
+        let dt = interval_h / STEADY_STATE_SAMPLES as f64;
+        let mut first = Vec::with_capacity(STEADY_STATE_SAMPLES + 1);
+        let mut steady = Vec::with_capacity(STEADY_STATE_SAMPLES + 1);
+        for i in 0..=STEADY_STATE_SAMPLES {
+            let t = i as f64 * dt;
+            // Right limit at t = 0 so the dose given at tau is not counted twice
+            let t_eval = if i == 0 { 1e-12 } else { t };
+            first.push(self.dose_concentration(&dose, t_eval));
+            steady.push((0..past_doses).map(|n| self.dose_concentration(&dose, t_eval + n as f64 * interval_h)).sum::<f64>());
+        }
+
+        let trapezoid = |c: &[f64]| c.windows(2).map(|w| 0.5 * (w[0] + w[1]) * dt).sum::<f64>();
+        let (auc_first, auc_tau) = (trapezoid(&first), trapezoid(&steady));
+        let (cmax, tmax) = steady
+            .iter()
+            .enumerate()
+            .fold((0.0, 0.0), |best, (i, &c)| if c > best.0 { (c, i as f64 * dt) } else { best });
+        let cmin = steady.iter().copied().fold(f64::INFINITY, f64::min);
+        let cavg = auc_tau / interval_h;
+
+        SteadyState {
+            interval_h,
+            cmax_mg_l: cmax,
+            tmax_h: tmax,
+            cmin_mg_l: cmin,
+            auc_tau,
+            cavg_mg_l: cavg,
+            accumulation_ratio: if auc_first > 0.0 { auc_tau / auc_first } else { 1.0 },
+            fluctuation: if cavg > 0.0 { (cmax - cmin) / cavg } else { 0.0 },
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::pharmacokinetics::{OneCompartmentModel, PkDatabase};
+    use crate::receptor_trafficking::ReceptorDynamics;
+    use crate::DrugDatabase;
+
+    fn alprazolam() -> PkParameters {
+        PkDatabase::new().get("alprazolam").unwrap().clone()
+    }
+
+    #[test]
+    fn test_single_doses_match_closed_forms() {
+        let pk = alprazolam();
+        let model = RegimenModel::new(pk.clone(), 70.0)
+            .with_absorption(RouteOfAdministration::Oral, AbsorptionModel::for_route(RouteOfAdministration::Oral, &pk).with_lag(0.0));
+        let vd = pk.vd_l(70.0);
+
+        // Oral dose follows the one-compartment depot model
+        let oral = DosingRegimen::new().with_dose(Dose::new(0.0, 1.0, RouteOfAdministration::Oral));
+        let mut reference = OneCompartmentModel::new(pk.clone(), 70.0);
+        reference.give_oral(1.0);
+        for t in [0.5, 2.0, 12.0] {
+            assert!((model.concentration_at(&oral, t) - reference.concentration_at(t)).abs() < 1e-12);
+        }
+
+        // AUC = F * D / CL for every route
+        let cl = model.disposition().clearance_l_h();
+        assert!((cl - pk.k_el() * vd).abs() < 1e-9);
+        for route in [
+            RouteOfAdministration::IvBolus,
+            RouteOfAdministration::IvInfusion,
+            RouteOfAdministration::Oral,
+            RouteOfAdministration::Intramuscular,
+            RouteOfAdministration::Subcutaneous,
+            RouteOfAdministration::Sublingual,
+            RouteOfAdministration::Transdermal,
+            RouteOfAdministration::Inhalation,
+            RouteOfAdministration::Intranasal,
+        ] {
+            let regimen = DosingRegimen::new().with_dose(Dose::new(0.0, 1.0, route));
+            let auc = model.simulate(&regimen, 400.0, 0.01).auc();
+            let expected = model.absorption(route).bioavailability / cl;
+            assert!((auc / expected - 1.0).abs() < 0.01, "{route:?}: {auc} vs {expected}");
+        }
+
+        // Routes with a typical tmax peak there
+        let sc = DosingRegimen::new().with_dose(Dose::new(0.0, 1.0, RouteOfAdministration::Subcutaneous));
+        let (_, tmax) = model.simulate(&sc, 10.0, 0.01).cmax();
+        assert!((tmax - RouteOfAdministration::Subcutaneous.typical_tmax_h()).abs() < 0.02);
+
+        // Infusion plateaus at rate / CL
+        let infusion = DosingRegimen::new().with_dose(Dose::infusion(0.0, 2.0, 200.0));
+        assert!((model.concentration_at(&infusion, 199.0) / (2.0 / cl) - 1.0).abs() < 1e-3);
+    }
+
+    #[test]
+    fn test_repeated_dosing_accumulates_to_steady_state() {
+        // Alprazolam 0.5 mg TID for two weeks
+        let pk = alprazolam();
+        let model = RegimenModel::new(pk.clone(), 70.0);
+        let regimen = DosingRegimen::repeated(0.5, RouteOfAdministration::Oral, 8.0, 42);
+        assert!((regimen.total_amount_mg() - 21.0).abs() < 1e-12);
+
+        // Bolus accumulation follows 1 / (1 - exp(-k tau)); slow absorption
+        // lowers the first interval's AUC and so raises the ratio
+        let expected = 1.0 / (1.0 - (-pk.k_el() * 8.0).exp());
+        let bolus = model.steady_state(0.5, RouteOfAdministration::IvBolus, 8.0);
+        assert!((bolus.accumulation_ratio / expected - 1.0).abs() < 1e-3, "{bolus:?}");
+        let ss = model.steady_state(0.5, RouteOfAdministration::Oral, 8.0);
+        assert!(ss.accumulation_ratio > expected, "{ss:?}");
+        assert!((ss.auc_tau - 0.5 * pk.bioavailability_oral / model.disposition().clearance_l_h()).abs() < 1e-3 * ss.auc_tau);
+        assert!(ss.cmin_mg_l < ss.cavg_mg_l && ss.cavg_mg_l < ss.cmax_mg_l);
+
+        // The last interval of the two weeks reproduces the steady state
+        let last = 41.0 * 8.0;
+        let trough = (0..800).map(|i| model.concentration_at(&regimen, last + i as f64 * 0.01)).fold(f64::INFINITY, f64::min);
+        assert!((trough / ss.cmin_mg_l - 1.0).abs() < 0.01, "{trough} vs {}", ss.cmin_mg_l);
+        let peak = model.concentration_at(&regimen, last + ss.tmax_h);
+        assert!((peak / ss.cmax_mg_l - 1.0).abs() < 0.01);
+        assert!(model.disposition().time_to_steady_state_h(0.9) < last);
+
+        // Two-compartment disposition conserves AUC
+        let two = RegimenModel::two_compartment(pk.clone(), 70.0, 0.5, 0.2);
+        let ss2 = two.steady_state(0.5, RouteOfAdministration::IvBolus, 8.0);
+        assert!((ss2.auc_tau * two.disposition().clearance_l_h() / 0.5 - 1.0).abs() < 1e-3);
+    }
+
+    #[test]
+    fn test_dose_order_does_not_matter() {
+        let model = RegimenModel::new(alprazolam(), 70.0);
+        let sorted = DosingRegimen::repeated(0.5, RouteOfAdministration::Oral, 8.0, 3);
+        let mut unsorted = sorted.clone();
+        unsorted.doses.reverse();
+
+        for t in [1.0, 9.0, 20.0] {
+            let expected = model.concentration_at(&sorted, t);
+            assert!(expected > 0.0);
+            assert!((model.concentration_at(&unsorted, t) / expected - 1.0).abs() < 1e-12);
+        }
+    }
+
+    #[test]
+    fn test_regimen_drives_receptor_tolerance() {
+        // High-dose alprazolam (2 mg TID) keeps BZ-site occupancy above 50%
+        let pk = alprazolam();
+        let ki_um = DrugDatabase::new().get("alprazolam").unwrap().ki_nm / 1000.0;
+        let model = RegimenModel::new(pk, 70.0);
+        let regimen = DosingRegimen::repeated(2.0, RouteOfAdministration::Oral, 8.0, 42);
+        let profile = model.simulate(&regimen, 14.0 * 24.0, 0.5);
+        let occupancy = profile.occupancy(ki_um);
+
+        let mut dynamics = ReceptorDynamics::new_gaba_a();
+        dynamics.simulate_occupancy_profile(&occupancy, 0.5);
+        assert!((dynamics.time_h - 14.0 * 24.0).abs() < 0.6);
+        assert!(dynamics.plasticity.tolerance_factor() < 1.0);
+    }
+}
@@ -29,6 +29,7 @@
 
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
+use crate::library::DrugLibrary;
 
 /// Physical constants for enzyme kinetics
 pub mod constants {
@@ -374,6 +375,8 @@ pub struct Cyp450Enzyme {
     pub substrate_specificity: HashMap<String, f64>,
     /// Inducers: drug -> fold induction
     pub inducers: HashMap<String, f64>,
+    /// Inhibitors: drug -> Ki (µM)
+    pub inhibitors: HashMap<String, f64>,
     /// Current expression level relative to baseline
     pub expression_level: f64,
 }
@@ -387,6 +390,7 @@ impl Cyp450Enzyme {
             kinetics: EnzymeKinetics::new(params),
             substrate_specificity: HashMap::new(),
             inducers: HashMap::new(),
+            inhibitors: HashMap::new(),
             expression_level: 1.0,
         }
     }
@@ -396,6 +400,11 @@ impl Cyp450Enzyme {
         self.substrate_specificity.insert(drug.to_string(), relative_km);
     }
 
+    /// Add inhibitor with its inhibition constant
+    pub fn add_inhibitor(&mut self, drug: &str, ki_um: f64) {
+        self.inhibitors.insert(drug.to_string(), ki_um);
+    }
+
     /// Calculate metabolism rate for a specific drug
     pub fn metabolize(&self, drug: &str, concentration_um: f64) -> f64 {
         let specificity = self.substrate_specificity.get(drug).unwrap_or(&1.0);
@@ -550,64 +559,9 @@ impl Default for Cyp450Database {
 }
 
 impl Cyp450Database {
+    /// Create database with the built-in drug library's isoforms
     pub fn new() -> Self {
-        let mut enzymes = HashMap::new();
-
-        // CYP3A4 - Most abundant, metabolizes ~50% of drugs
-        let mut cyp3a4 = Cyp450Enzyme::new("CYP3A4", 10.0, 5.0);
-        cyp3a4.add_substrate("midazolam", 1.0);
-        cyp3a4.add_substrate("diazepam", 1.2);
-        cyp3a4.add_substrate("alprazolam", 0.8);
-        cyp3a4.add_substrate("triazolam", 0.9);
-        cyp3a4.add_substrate("fentanyl", 1.5);
-        cyp3a4.add_substrate("carbamazepine", 2.0);
-        enzymes.insert("CYP3A4".to_string(), cyp3a4);
-
-        // CYP2D6 - Highly polymorphic
-        let mut cyp2d6 = Cyp450Enzyme::new("CYP2D6", 5.0, 10.0);
-        cyp2d6.add_substrate("codeine", 1.0);
-        cyp2d6.add_substrate("tramadol", 1.2);
-        cyp2d6.add_substrate("haloperidol", 0.8);
-        cyp2d6.add_substrate("fluoxetine", 2.0);
-        cyp2d6.add_substrate("paroxetine", 1.5);
-        enzymes.insert("CYP2D6".to_string(), cyp2d6);
-
-        // CYP2C19 - Important for PPIs and some benzos
-        let mut cyp2c19 = Cyp450Enzyme::new("CYP2C19", 8.0, 15.0);
-        cyp2c19.add_substrate("diazepam", 0.8);
-        cyp2c19.add_substrate("clobazam", 1.0);
-        cyp2c19.add_substrate("clopidogrel", 1.2);
-        enzymes.insert("CYP2C19".to_string(), cyp2c19);
-
-        // CYP2C9 - Warfarin, phenytoin
-        let mut cyp2c9 = Cyp450Enzyme::new("CYP2C9", 6.0, 8.0);
-        cyp2c9.add_substrate("phenytoin", 1.0);
-        cyp2c9.add_substrate("valproate", 1.5);
-        cyp2c9.add_substrate("ibuprofen", 0.7);
-        enzymes.insert("CYP2C9".to_string(), cyp2c9);
-
-        // CYP1A2 - Caffeine, theophylline
-        let mut cyp1a2 = Cyp450Enzyme::new("CYP1A2", 4.0, 20.0);
-        cyp1a2.add_substrate("caffeine", 1.0);
-        cyp1a2.add_substrate("theophylline", 1.2);
-        cyp1a2.add_substrate("clozapine", 0.8);
-        enzymes.insert("CYP1A2".to_string(), cyp1a2);
-
-        // CYP2B6 - Bupropion, methadone
-        let mut cyp2b6 = Cyp450Enzyme::new("CYP2B6", 3.0, 25.0);
-        cyp2b6.add_substrate("bupropion", 1.0);
-        cyp2b6.add_substrate("ketamine", 1.5);
-        cyp2b6.add_substrate("propofol", 0.6);
-        enzymes.insert("CYP2B6".to_string(), cyp2b6);
-
-        // CYP2E1 - Ethanol, acetaminophen
-        let mut cyp2e1 = Cyp450Enzyme::new("CYP2E1", 2.0, 50.0);
-        cyp2e1.add_substrate("ethanol", 1.0);
-        cyp2e1.add_substrate("acetaminophen", 2.0);
-        cyp2e1.add_substrate("isoflurane", 0.5);
-        enzymes.insert("CYP2E1".to_string(), cyp2e1);
-
-        Self { enzymes }
+        DrugLibrary::builtin().cyp450_database()
     }
 
     /// Get enzyme by isoform name
@@ -8,7 +8,9 @@
 //!
 //! ## Core Pharmacology
 //! - : GABA_A receptor binding and modulation
+//! - : NMDA, AMPA, D2, 5-HT2A, opioid, alpha2, nicotinic and H1 targets
 //! - : ADME modeling (absorption, distribution, metabolism, elimination)
+//! - : Multiple-dose regimens and steady state
 //! - : Nernst-Planck equations for ion channels
 //!
 //! ## Advanced Kinetics
@@ -22,6 +24,7 @@
 //! ## Individual Variation
 //! - : CYP450 polymorphisms and phenotypes
 //! - : Chaotic threshold dynamics, rare events
+//! - : Population PK/PD virtual trials
 //!
 //! ## Safety
 //! - : GSH balance and hepatotoxicity
@@ -30,14 +33,19 @@
 //! ## Validation
 //! - : PET imaging and PK data from clinical studies
 //!
+//! ## Data
+//! - : Versioned drug library populating all databases
+//!
 //! # Author
 //! Francisco Molina Burgos (Yatrogenesis)
 //! ORCID: 0009-0008-6093-8267
 
 // Core modules
 pub mod receptor_mechanisms;
+pub mod receptor_targets;
 pub mod ion_dynamics;
 pub mod pharmacokinetics;
+pub mod dosing;
 
 // Advanced kinetics
 pub mod enzyme_kinetics;
@@ -50,6 +58,7 @@ pub mod receptor_trafficking;
 // Individual variation
 pub mod pharmacogenomics;
 pub mod stochastic_resonance;
+pub mod population;
 
 // Safety
 pub mod reactive_metabolites;
@@ -58,11 +67,18 @@ pub mod adverse_events;
 // Validation against clinical literature
 pub mod clinical_literature;
 
+// Drug library
+pub mod library;
+
 // Re-exports for convenience
 pub use receptor_mechanisms::*;
+pub use receptor_targets::{MultiReceptorProfile, MultiReceptorSystem, ReceptorTarget, TargetBinding, TargetDatabase, TargetResponse, TargetSite};
 pub use enzyme_kinetics::{EnzymeKinetics, SaturationRegime, Cyp450Database};
 pub use compartments::{MultiCompartmentModel, CompartmentType};
+pub use dosing::{Dose, DosingRegimen, RegimenModel, SteadyState};
 pub use pharmacogenomics::{PharmacogenomicProfile, MetabolizerPhenotype, CypIsoform};
 pub use stochastic_resonance::OntologicalOscillator;
+pub use population::{AttainmentTarget, CohortSpec, TrialResult, Variability, VirtualTrial};
 pub use adverse_events::AdverseEventPredictor;
-pub use clinical_literature::{ClinicalLiteratureDb, ValidationResult, calculate_occupancy_from_ki};
+pub use clinical_literature::{ClinicalLiteratureDb, ValidationResult, calculate_occupancy_from_ki, validate_pet_occupancy, validate_pk_literature};
+pub use library::{DrugLibrary, Inconsistency, LibraryError};
@@ -0,0 +1,955 @@
+//! Drug Library
+//! ============
+//!
+//! One versioned on-disk description of every drug the crate knows, from
+//! which the per-topic databases are populated:
+//!
+//! - receptor affinities and effect profile -> [`DrugDatabase`]
+//! - bindings to the other receptor targets -> [`TargetDatabase`]
+//! - pharmacokinetic parameters -> [`PkDatabase`]
+//! - CYP450 substrates and inhibitors -> [`Cyp450Database`]
+//! - transporter substrates and inhibitors -> [`TransporterDatabase`]
+//! - reactive metabolites -> [`ReactiveMetaboliteDatabase`]
+//! - pharmacogenomic guidance -> [`DrugGeneInteractions`]
+//! - PET occupancy and clinical PK references -> [`ClinicalLiteratureDb`]
+//!
+//! The library is TOML (or the equivalent JSON): a `schema_version`, the
+//! CYP450 isoforms with their kinetics, and one `[[drugs]]` table per drug
+//! whose optional sections feed the databases above, so adding a drug is a
+//! single entry. Unknown keys, unknown enum values, out-of-range parameters
+//! and references to undeclared isoforms are rejected when the library is
+//! loaded; gaps between the tables (affinities without PK, literature that
+//! cannot be reproduced, pathways naming isoforms that do not metabolize the
+//! drug, ...) are listed by [`DrugLibrary::consistency_report`].
+//!
+//! The built-in library (`data/drug_library.toml`) is compiled into the
+//! crate and backs the `new()` constructors of all the databases.
+
+use std::collections::{HashMap, HashSet};
+use std::fmt;
+use std::path::Path;
+use std::sync::OnceLock;
+
+use serde::{Deserialize, Serialize};
+use thiserror::Error;
+
+use crate::active_transport::{DrugTransporterProfile, TransporterDatabase, TransporterType};
+use crate::clinical_literature::{ClinicalLiteratureDb, ClinicalPkReference, PetOccupancyData};
+use crate::enzyme_kinetics::{Cyp450Database, Cyp450Enzyme};
+use crate::pharmacogenomics::{CypIsoform, DrugGeneInteraction, DrugGeneInteractions};
+use crate::pharmacokinetics::{PkDatabase, PkParameters};
+use crate::reactive_metabolites::{DrugReactiveProfile, ReactiveMetabolite, ReactiveMetaboliteDatabase};
+use crate::receptor_mechanisms::{BindingSite, DrugDatabase, DrugMolecularProfile, EffectType};
+use crate::receptor_targets::{MultiReceptorProfile, ReceptorTarget, TargetBinding, TargetDatabase, TargetSite};
+
+/// Library schema version understood by this crate
+pub const LIBRARY_SCHEMA_VERSION: u32 = 1;
+
+/// The built-in library
+const BUILTIN_LIBRARY: &str = include_str!("../data/drug_library.toml");
+
+/// Half-lives differing by more than this factor are reported
+const HALF_LIFE_TOLERANCE: f64 = 2.0;
+
+/// Error types for drug libraries
+#[derive(Debug, Error)]
+pub enum LibraryError {
+    #[error("I/O error: {0}")]
+    Io(#[from] std::io::Error),
+    #[error("Parse error: {0}")]
+    Parse(String),
+    #[error("Unsupported schema version {found} (expected {expected})")]
+    SchemaVersion { found: u32, expected: u32 },
+    #[error("Duplicate entry: {0}")]
+    Duplicate(String),
+    #[error("Invalid entry for {entry}: {message}")]
+    Invalid { entry: String, message: String },
+}
+
+pub type Result<T> = std::result::Result<T, LibraryError>;
+
+/// A drug library
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct DrugLibrary {
+    pub schema_version: u32,
+    /// CYP450 isoforms
+    #[serde(default)]
+    pub enzymes: Vec<EnzymeEntry>,
+    #[serde(default)]
+    pub drugs: Vec<DrugEntry>,
+}
+
+/// CYP450 isoform kinetics
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct EnzymeEntry {
+    /// Isoform name (e.g. "CYP3A4")
+    pub isoform: String,
+    /// Maximum velocity (µM/s)
+    pub vmax: f64,
+    /// Michaelis constant (µM)
+    pub km: f64,
+}
+
+/// Everything known about one drug
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct DrugEntry {
+    /// Drug name (case-insensitive)
+    pub name: String,
+    /// Molecular weight (g/mol); required with `receptor` or `pk`
+    pub molecular_weight: Option<f64>,
+    /// Lipophilicity (log octanol/water partition)
+    pub log_p: Option<f64>,
+    /// GABA_A receptor binding
+    pub receptor: Option<ReceptorEntry>,
+    /// Bindings to the other receptor targets
+    #[serde(default)]
+    pub targets: Vec<TargetEntry>,
+    /// Pharmacokinetic parameters
+    pub pk: Option<PkEntry>,
+    /// CYP450 metabolism
+    pub metabolism: Option<MetabolismEntry>,
+    /// Membrane transporters
+    pub transport: Option<TransportEntry>,
+    /// Reactive metabolite formation
+    pub bioactivation: Option<BioactivationEntry>,
+    /// Pharmacogenomic guidance
+    #[serde(default)]
+    pub gene_interactions: Vec<GeneInteractionEntry>,
+    /// PET receptor occupancy studies
+    #[serde(default)]
+    pub pet: Vec<PetEntry>,
+    /// Clinical PK reference values
+    #[serde(default)]
+    pub pk_references: Vec<PkReferenceEntry>,
+}
+
+/// Binding to a receptor target, see [`TargetBinding`]
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct TargetEntry {
+    pub target: ReceptorTarget,
+    /// Binding site (default orthosteric)
+    pub site: Option<TargetSite>,
+    /// Binding affinity (nM)
+    pub ki_nm: f64,
+    /// Intrinsic efficacy (0-1, default 0)
+    pub efficacy: Option<f64>,
+    /// Hill coefficient (default 1)
+    pub hill: Option<f64>,
+    /// Allosteric cooperativity (default 1)
+    pub cooperativity: Option<f64>,
+}
+
+/// GABA_A receptor binding, see [`DrugMolecularProfile`]
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct ReceptorEntry {
+    pub site: BindingSite,
+    /// Binding affinity (nM)
+    pub ki_nm: f64,
+    /// Intrinsic efficacy (0-1)
+    pub efficacy: f64,
+    /// Hill coefficient (default 1)
+    pub hill: Option<f64>,
+    /// Allosteric factor (default: the site's)
+    pub allosteric: Option<f64>,
+    /// Effect type -> strength (0-1)
+    #[serde(default)]
+    pub effects: HashMap<EffectType, f64>,
+}
+
+/// Pharmacokinetic parameters, see [`PkParameters`]
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct PkEntry {
+    pub bioavailability_oral: f64,
+    pub vd_l_kg: f64,
+    pub clearance_l_h_kg: f64,
+    pub half_life_h: f64,
+    pub protein_binding: f64,
+    pub brain_partition: f64,
+    pub tmax_oral_h: f64,
+    pub ka: f64,
+}
+
+/// CYP450 metabolism, keyed by isoform name
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct MetabolismEntry {
+    /// Isoform -> Km relative to the isoform's reference
+    #[serde(default)]
+    pub substrate_of: HashMap<String, f64>,
+    /// Isoform -> Ki (µM)
+    #[serde(default)]
+    pub inhibitor_of: HashMap<String, f64>,
+}
+
+/// Membrane transporters, see [`DrugTransporterProfile`]
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct TransportEntry {
+    /// Transporter -> relative affinity (1.0 = reference)
+    #[serde(default)]
+    pub substrate_of: HashMap<TransporterType, f64>,
+    /// Transporter -> Ki (µM)
+    #[serde(default)]
+    pub inhibitor_of: HashMap<TransporterType, f64>,
+}
+
+/// Reactive metabolite formation, see [`DrugReactiveProfile`]
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct BioactivationEntry {
+    pub fraction_bioactivated: f64,
+    /// Enzymes forming the metabolites (e.g. "CYP2E1, CYP3A4")
+    pub cyp_pathway: String,
+    pub risk_category: String,
+    pub metabolites: Vec<MetaboliteEntry>,
+}
+
+/// One reactive metabolite, see [`ReactiveMetabolite`]
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct MetaboliteEntry {
+    pub name: String,
+    pub half_life_s: f64,
+    pub gsh_reactivity: f64,
+    pub protein_reactivity: f64,
+    #[serde(default)]
+    pub is_epoxide: bool,
+    #[serde(default)]
+    pub is_quinone: bool,
+}
+
+/// Pharmacogenomic guidance, see [`DrugGeneInteraction`]
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct GeneInteractionEntry {
+    /// Isoform name (e.g. "CYP2D6")
+    pub gene: String,
+    #[serde(default)]
+    pub is_prodrug: bool,
+    pub pm_recommendation: String,
+    pub um_recommendation: String,
+}
+
+/// PET occupancy study, see [`PetOccupancyData`]
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct PetEntry {
+    pub receptor: String,
+    pub dose_mg: f64,
+    pub route: String,
+    pub time_h: f64,
+    pub occupancy_percent: f64,
+    pub sd: Option<f64>,
+    pub pmid: Option<u32>,
+    pub region: String,
+}
+
+/// Clinical PK reference, see [`ClinicalPkReference`]
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct PkReferenceEntry {
+    pub dose_mg: f64,
+    pub route: String,
+    pub cmax_plasma_ng_ml: f64,
+    pub tmax_h: f64,
+    pub t_half_h: f64,
+    pub brain_plasma_ratio: Option<f64>,
+    pub csf_ng_ml: Option<f64>,
+    pub source: String,
+}
+
+/// Gap or disagreement between the tables of a library
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub enum Inconsistency {
+    /// Receptor affinities but no PK: brain concentrations cannot be predicted
+    ReceptorWithoutPk { drug: String },
+    /// Literature values that cannot be reproduced without PK parameters
+    ReferenceWithoutPk { drug: String },
+    /// Half-life of the PK parameters far from a clinical reference
+    HalfLifeMismatch { drug: String, model_h: f64, reference_h: f64 },
+    /// Bioactivation pathway naming an isoform without the drug as substrate
+    PathwayNotSubstrate { drug: String, isoform: String },
+    /// Pharmacogenomic guidance for an isoform without the drug as substrate
+    GeneNotSubstrate { drug: String, isoform: String },
+    /// PET occupancy of a receptor the drug has no binding data for
+    PetWithoutBinding { drug: String, receptor: String },
+}
+
+impl fmt::Display for Inconsistency {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Inconsistency::ReceptorWithoutPk { drug } => {
+                write!(f, "{drug}: receptor affinities but no PK parameters")
+            }
+            Inconsistency::ReferenceWithoutPk { drug } => {
+                write!(f, "{drug}: literature references but no PK parameters")
+            }
+            Inconsistency::HalfLifeMismatch { drug, model_h, reference_h } => {
+                write!(f, "{drug}: half-life {model_h} h vs {reference_h} h in the literature")
+            }
+            Inconsistency::PathwayNotSubstrate { drug, isoform } => {
+                write!(f, "{drug}: bioactivated by {isoform}, which has no substrate entry for it")
+            }
+            Inconsistency::GeneNotSubstrate { drug, isoform } => {
+                write!(f, "{drug}: {isoform} guidance, but no {isoform} substrate entry")
+            }
+            Inconsistency::PetWithoutBinding { drug, receptor } => {
+                write!(f, "{drug}: {receptor} PET occupancy, but no {receptor} binding data")
+            }
+        }
+    }
+}
+
+impl DrugLibrary {
+    /// Parse and validate a TOML library
+    pub fn from_toml_str(source: &str) -> Result<Self> {
+        let library: Self = toml::from_str(source).map_err(|e| LibraryError::Parse(e.to_string()))?;
+        library.validate()?;
+        Ok(library)
+    }
+
+    /// Parse and validate a JSON library
+    pub fn from_json_str(source: &str) -> Result<Self> {
+        let library: Self = serde_json::from_str(source).map_err(|e| LibraryError::Parse(e.to_string()))?;
+        library.validate()?;
+        Ok(library)
+    }
+
+    /// Load a library file; `.json` files are read as JSON, anything else
+    /// as TOML
+    pub fn from_file(path: impl AsRef<Path>) -> Result<Self> {
+        let path = path.as_ref();
+        let source = std::fs::read_to_string(path)?;
+        if path.extension().is_some_and(|ext| ext.eq_ignore_ascii_case("json")) {
+            Self::from_json_str(&source)
+        } else {
+            Self::from_toml_str(&source)
+        }
+    }
+
+    /// The library compiled into the crate
+    pub fn builtin() -> &'static Self {
+        static BUILTIN: OnceLock<DrugLibrary> = OnceLock::new();
+        BUILTIN.get_or_init(|| Self::from_toml_str(BUILTIN_LIBRARY).expect("built-in drug library is invalid"))
+    }
+
+    /// Serialize as TOML
+    pub fn to_toml_string(&self) -> Result<String> {
+        toml::to_string(self).map_err(|e| LibraryError::Parse(e.to_string()))
+    }
+
+    /// Entry of a drug
+    pub fn drug(&self, name: &str) -> Option<&DrugEntry> {
+        self.drugs.iter().find(|d| d.name.eq_ignore_ascii_case(name))
+    }
+
+    /// Names of all drugs (lowercase)
+    pub fn drug_names(&self) -> Vec<String> {
+        self.drugs.iter().map(DrugEntry::key).collect()
+    }
+
+    /// Check the schema version, ranges and cross-references
+    pub fn validate(&self) -> Result<()> {
+        if self.schema_version != LIBRARY_SCHEMA_VERSION {
+            return Err(LibraryError::SchemaVersion { found: self.schema_version, expected: LIBRARY_SCHEMA_VERSION });
+        }
+
+        let mut isoforms = HashSet::new();
+        for enzyme in &self.enzymes {
+            if !isoforms.insert(enzyme.isoform.as_str()) {
+                return Err(LibraryError::Duplicate(enzyme.isoform.clone()));
+            }
+            let check = Checker::new(&enzyme.isoform);
+            check.positive("vmax", enzyme.vmax)?;
+            check.positive("km", enzyme.km)?;
+        }
+
+        let mut names = HashSet::new();
+        for drug in &self.drugs {
+            if !names.insert(drug.key()) {
+                return Err(LibraryError::Duplicate(drug.name.clone()));
+            }
+            drug.validate(&isoforms)?;
+        }
+        Ok(())
+    }
+
+    /// Receptor binding profiles
+    pub fn drug_database(&self) -> DrugDatabase {
+        let mut db = DrugDatabase::empty();
+        for drug in &self.drugs {
+            if let Some(profile) = drug.molecular_profile() {
+                db.add(profile);
+            }
+        }
+        db
+    }
+
+    /// Multi-receptor profiles of the drugs with GABA_A or other targets
+    pub fn target_database(&self) -> TargetDatabase {
+        let mut db = TargetDatabase::empty();
+        for drug in &self.drugs {
+            if let Some(profile) = drug.receptor_profile() {
+                db.add(profile);
+            }
+        }
+        db
+    }
+
+    /// Pharmacokinetic parameters
+    pub fn pk_database(&self) -> PkDatabase {
+        let mut db = PkDatabase::empty();
+        for drug in &self.drugs {
+            if let Some(pk) = drug.pk_parameters() {
+                db.add(pk);
+            }
+        }
+        db
+    }
+
+    /// CYP450 isoforms with their substrates and inhibitors
+    pub fn cyp450_database(&self) -> Cyp450Database {
+        let mut enzymes: HashMap<String, Cyp450Enzyme> = self
+            .enzymes
+            .iter()
+            .map(|e| (e.isoform.clone(), Cyp450Enzyme::new(&e.isoform, e.vmax, e.km)))
+            .collect();
+
+        for drug in &self.drugs {
+            let Some(metabolism) = &drug.metabolism else { continue };
+            for (isoform, &relative_km) in &metabolism.substrate_of {
+                if let Some(enzyme) = enzymes.get_mut(isoform) {
+                    enzyme.add_substrate(&drug.key(), relative_km);
+                }
+            }
+            for (isoform, &ki_um) in &metabolism.inhibitor_of {
+                if let Some(enzyme) = enzymes.get_mut(isoform) {
+                    enzyme.add_inhibitor(&drug.key(), ki_um);
+                }
+            }
+        }
+        Cyp450Database { enzymes }
+    }
+
+    /// Transporter substrates and inhibitors
+    pub fn transporter_database(&self) -> TransporterDatabase {
+        let mut db = TransporterDatabase::default();
+        for drug in &self.drugs {
+            let Some(transport) = &drug.transport else { continue };
+            let mut profile = DrugTransporterProfile::new(&drug.key());
+            profile.substrate_of = transport.substrate_of.clone();
+            profile.inhibitor_of = transport.inhibitor_of.clone();
+            db.profiles.insert(drug.key(), profile);
+        }
+        db
+    }
+
+    /// Reactive metabolite profiles
+    pub fn reactive_metabolite_database(&self) -> ReactiveMetaboliteDatabase {
+        let mut db = ReactiveMetaboliteDatabase::default();
+        for drug in &self.drugs {
+            let Some(bioactivation) = &drug.bioactivation else { continue };
+            let metabolites = bioactivation
+                .metabolites
+                .iter()
+                .map(|m| ReactiveMetabolite {
+                    parent: drug.key(),
+                    name: m.name.clone(),
+                    half_life_s: m.half_life_s,
+                    gsh_reactivity: m.gsh_reactivity,
+                    protein_reactivity: m.protein_reactivity,
+                    is_epoxide: m.is_epoxide,
+                    is_quinone: m.is_quinone,
+                })
+                .collect();
+            db.drugs.insert(
+                drug.key(),
+                DrugReactiveProfile {
+                    drug: drug.key(),
+                    metabolites,
+                    fraction_bioactivated: bioactivation.fraction_bioactivated,
+                    cyp_pathway: bioactivation.cyp_pathway.clone(),
+                    risk_category: bioactivation.risk_category.clone(),
+                },
+            );
+        }
+        db
+    }
+
+    /// Pharmacogenomic guidance
+    pub fn drug_gene_interactions(&self) -> DrugGeneInteractions {
+        let mut db = DrugGeneInteractions::default();
+        for drug in self.drugs.iter().filter(|d| !d.gene_interactions.is_empty()) {
+            let interactions = drug
+                .gene_interactions
+                .iter()
+                .filter_map(|g| {
+                    Some(DrugGeneInteraction {
+                        drug: drug.key(),
+                        gene: CypIsoform::from_name(&g.gene)?,
+                        is_prodrug: g.is_prodrug,
+                        pm_recommendation: g.pm_recommendation.clone(),
+                        um_recommendation: g.um_recommendation.clone(),
+                    })
+                })
+                .collect();
+            db.interactions.insert(drug.key(), interactions);
+        }
+        db
+    }
+
+    /// PET occupancy and clinical PK references
+    pub fn clinical_literature(&self) -> ClinicalLiteratureDb {
+        let mut db = ClinicalLiteratureDb::empty();
+        for drug in &self.drugs {
+            for pet in &drug.pet {
+                db.add_pet(PetOccupancyData {
+                    drug: drug.key(),
+                    receptor: pet.receptor.clone(),
+                    dose_mg: pet.dose_mg,
+                    route: pet.route.clone(),
+                    time_h: pet.time_h,
+                    occupancy_percent: pet.occupancy_percent,
+                    sd: pet.sd,
+                    pmid: pet.pmid,
+                    region: pet.region.clone(),
+                });
+            }
+            for reference in &drug.pk_references {
+                db.add_pk(ClinicalPkReference {
+                    drug: drug.key(),
+                    dose_mg: reference.dose_mg,
+                    route: reference.route.clone(),
+                    cmax_plasma_ng_ml: reference.cmax_plasma_ng_ml,
+                    tmax_h: reference.tmax_h,
+                    t_half_h: reference.t_half_h,
+                    brain_plasma_ratio: reference.brain_plasma_ratio,
+                    csf_ng_ml: reference.csf_ng_ml,
+                    source: reference.source.clone(),
+                });
+            }
+        }
+        db
+    }
+
+    /// Gaps and disagreements between the tables, in library order
+    pub fn consistency_report(&self) -> Vec<Inconsistency> {
+        let mut issues = Vec::new();
+        for drug in &self.drugs {
+            let name = drug.key();
+            let substrate_of =
+                |isoform: &str| drug.metabolism.as_ref().is_some_and(|m| m.substrate_of.contains_key(isoform));
+
+            match &drug.pk {
+                None if drug.receptor.is_some() || !drug.targets.is_empty() => {
+                    issues.push(Inconsistency::ReceptorWithoutPk { drug: name.clone() });
+                }
+                None if !drug.pet.is_empty() || !drug.pk_references.is_empty() => {
+                    issues.push(Inconsistency::ReferenceWithoutPk { drug: name.clone() });
+                }
+                Some(pk) => {
+                    for reference in &drug.pk_references {
+                        let ratio = pk.half_life_h / reference.t_half_h;
+                        if !(1.0 / HALF_LIFE_TOLERANCE..=HALF_LIFE_TOLERANCE).contains(&ratio) {
+                            issues.push(Inconsistency::HalfLifeMismatch {
+                                drug: name.clone(),
+                                model_h: pk.half_life_h,
+                                reference_h: reference.t_half_h,
+                            });
+                        }
+                    }
+                }
+                None => {}
+            }
+
+            if let Some(bioactivation) = &drug.bioactivation {
+                for isoform in cyp_names(&bioactivation.cyp_pathway) {
+                    if !substrate_of(isoform) {
+                        issues.push(Inconsistency::PathwayNotSubstrate { drug: name.clone(), isoform: isoform.to_string() });
+                    }
+                }
+            }
+
+            for interaction in &drug.gene_interactions {
+                let isoform = CypIsoform::from_name(&interaction.gene).map_or(interaction.gene.as_str(), |i| i.name());
+                if !substrate_of(isoform) {
+                    issues.push(Inconsistency::GeneNotSubstrate { drug: name.clone(), isoform: isoform.to_string() });
+                }
+            }
+
+            let mut receptors: Vec<&str> = Vec::new();
+            for pet in &drug.pet {
+                let receptor = pet.receptor.as_str();
+                if !receptors.contains(&receptor) && drug.binds(receptor) == Some(false) {
+                    receptors.push(receptor);
+                    issues.push(Inconsistency::PetWithoutBinding { drug: name.clone(), receptor: receptor.to_string() });
+                }
+            }
+        }
+        issues
+    }
+}
+
+impl DrugEntry {
+    /// Database key (lowercase name)
+    pub fn key(&self) -> String {
+        self.name.to_lowercase()
+    }
+
+    /// Receptor binding profile, if the drug has one
+    pub fn molecular_profile(&self) -> Option<DrugMolecularProfile> {
+        let receptor = self.receptor.as_ref()?;
+        let mut profile = DrugMolecularProfile::new(&self.name, receptor.site, receptor.ki_nm, receptor.efficacy);
+        if let Some(hill) = receptor.hill {
+            profile = profile.with_hill(hill);
+        }
+        if let Some(allosteric) = receptor.allosteric {
+            profile = profile.with_allosteric(allosteric);
+        }
+        for (&effect, &strength) in &receptor.effects {
+            profile = profile.with_effect(effect, strength);
+        }
+        if let Some(mw) = self.molecular_weight {
+            profile = profile.with_mw(mw);
+        }
+        if let Some(log_p) = self.log_p {
+            profile = profile.with_log_p(log_p);
+        }
+        Some(profile)
+    }
+
+    /// GABA_A and other receptor targets, if the drug has any
+    pub fn receptor_profile(&self) -> Option<MultiReceptorProfile> {
+        if self.receptor.is_none() && self.targets.is_empty() {
+            return None;
+        }
+        let mut profile = MultiReceptorProfile::new(&self.name);
+        profile.gaba_a = self.molecular_profile();
+        for entry in &self.targets {
+            let mut binding = TargetBinding::new(entry.target, entry.ki_nm, entry.efficacy.unwrap_or(0.0));
+            binding.site = entry.site.unwrap_or(TargetSite::Orthosteric);
+            if let Some(hill) = entry.hill {
+                binding = binding.with_hill(hill);
+            }
+            if let Some(cooperativity) = entry.cooperativity {
+                binding.cooperativity = cooperativity;
+            }
+            profile.bindings.push(binding);
+        }
+        Some(profile)
+    }
+
+    /// Whether the drug has binding data for a receptor named as in PET
+    /// studies (`None` if the name is not a modelled receptor)
+    fn binds(&self, receptor: &str) -> Option<bool> {
+        if receptor.eq_ignore_ascii_case("GABA-A") {
+            return Some(self.receptor.is_some());
+        }
+        let transporter = match receptor.to_ascii_uppercase().as_str() {
+            "SERT" => Some(TransporterType::Sert),
+            "DAT" => Some(TransporterType::Dat),
+            "NET" => Some(TransporterType::Net),
+            _ => None,
+        };
+        if let Some(transporter) = transporter {
+            return Some(self.transport.as_ref().is_some_and(|t| t.inhibitor_of.contains_key(&transporter)));
+        }
+        let target = ReceptorTarget::from_name(receptor)?;
+        Some(self.targets.iter().any(|t| t.target == target))
+    }
+
+    /// Pharmacokinetic parameters, if the drug has them
+    pub fn pk_parameters(&self) -> Option<PkParameters> {
+        let pk = self.pk.as_ref()?;
+        Some(PkParameters {
+            name: self.key(),
+            molecular_weight: self.molecular_weight.unwrap_or(PkParameters::new("").molecular_weight),
+            bioavailability_oral: pk.bioavailability_oral,
+            vd_l_kg: pk.vd_l_kg,
+            clearance_l_h_kg: pk.clearance_l_h_kg,
+            half_life_h: pk.half_life_h,
+            protein_binding: pk.protein_binding,
+            brain_partition: pk.brain_partition,
+            tmax_oral_h: pk.tmax_oral_h,
+            ka: pk.ka,
+        })
+    }
+
+    fn validate(&self, isoforms: &HashSet<&str>) -> Result<()> {
+        let check = Checker::new(&self.name);
+        if self.name.trim().is_empty() {
+            return Err(check.fail("empty drug name"));
+        }
+        if let Some(mw) = self.molecular_weight {
+            check.positive("molecular_weight", mw)?;
+        } else if self.receptor.is_some() || self.pk.is_some() {
+            return Err(check.fail("molecular_weight is required with receptor or pk data"));
+        }
+
+        if let Some(receptor) = &self.receptor {
+            check.positive("receptor.ki_nm", receptor.ki_nm)?;
+            check.fraction("receptor.efficacy", receptor.efficacy)?;
+            if let Some(hill) = receptor.hill {
+                check.positive("receptor.hill", hill)?;
+            }
+            if let Some(allosteric) = receptor.allosteric {
+                check.non_negative("receptor.allosteric", allosteric)?;
+            }
+            for (effect, &strength) in &receptor.effects {
+                check.fraction(&format!("receptor.effects.{effect:?}"), strength)?;
+            }
+        }
+
+        for entry in &self.targets {
+            let field = |name: &str| format!("targets.{:?}.{name}", entry.target);
+            check.positive(&field("ki_nm"), entry.ki_nm)?;
+            if let Some(efficacy) = entry.efficacy {
+                check.fraction(&field("efficacy"), efficacy)?;
+            }
+            if let Some(hill) = entry.hill {
+                check.positive(&field("hill"), hill)?;
+            }
+            if let Some(cooperativity) = entry.cooperativity {
+                if entry.site != Some(TargetSite::Allosteric) {
+                    return Err(check.fail(&format!("{} is only valid at an allosteric site", field("cooperativity"))));
+                }
+                check.non_negative(&field("cooperativity"), cooperativity)?;
+            }
+        }
+
+        if let Some(pk) = &self.pk {
+            check.fraction("pk.bioavailability_oral", pk.bioavailability_oral)?;
+            check.positive("pk.vd_l_kg", pk.vd_l_kg)?;
+            check.positive("pk.clearance_l_h_kg", pk.clearance_l_h_kg)?;
+            check.positive("pk.half_life_h", pk.half_life_h)?;
+            check.fraction("pk.protein_binding", pk.protein_binding)?;
+            if pk.protein_binding >= 1.0 {
+                return Err(check.fail("pk.protein_binding must leave a free fraction"));
+            }
+            check.non_negative("pk.brain_partition", pk.brain_partition)?;
+            check.non_negative("pk.tmax_oral_h", pk.tmax_oral_h)?;
+            check.non_negative("pk.ka", pk.ka)?;
+        }
+
+        if let Some(metabolism) = &self.metabolism {
+            for (role, table) in [("substrate_of", &metabolism.substrate_of), ("inhibitor_of", &metabolism.inhibitor_of)] {
+                for (isoform, &value) in table {
+                    if !isoforms.contains(isoform.as_str()) {
+                        return Err(check.fail(&format!("metabolism.{role} names undeclared isoform {isoform}")));
+                    }
+                    check.positive(&format!("metabolism.{role}.{isoform}"), value)?;
+                }
+            }
+        }
+
+        if let Some(transport) = &self.transport {
+            for (role, table) in [("substrate_of", &transport.substrate_of), ("inhibitor_of", &transport.inhibitor_of)] {
+                for (transporter, &value) in table {
+                    check.positive(&format!("transport.{role}.{transporter:?}"), value)?;
+                }
+            }
+        }
+
+        if let Some(bioactivation) = &self.bioactivation {
+            check.fraction("bioactivation.fraction_bioactivated", bioactivation.fraction_bioactivated)?;
+            if bioactivation.metabolites.is_empty() {
+                return Err(check.fail("bioactivation without metabolites"));
+            }
+            for metabolite in &bioactivation.metabolites {
+                check.positive("bioactivation.metabolites.half_life_s", metabolite.half_life_s)?;
+                check.non_negative("bioactivation.metabolites.gsh_reactivity", metabolite.gsh_reactivity)?;
+                check.non_negative("bioactivation.metabolites.protein_reactivity", metabolite.protein_reactivity)?;
+            }
+        }
+
+        for interaction in &self.gene_interactions {
+            if CypIsoform::from_name(&interaction.gene).is_none() {
+                return Err(check.fail(&format!("unknown gene {}", interaction.gene)));
+            }
+        }
+
+        for pet in &self.pet {
+            check.positive("pet.dose_mg", pet.dose_mg)?;
+            check.non_negative("pet.time_h", pet.time_h)?;
+            if !(0.0..=100.0).contains(&pet.occupancy_percent) {
+                return Err(check.fail(&format!("pet.occupancy_percent = {} outside 0-100", pet.occupancy_percent)));
+            }
+        }
+
+        for reference in &self.pk_references {
+            check.positive("pk_references.dose_mg", reference.dose_mg)?;
+            check.non_negative("pk_references.cmax_plasma_ng_ml", reference.cmax_plasma_ng_ml)?;
+            check.non_negative("pk_references.tmax_h", reference.tmax_h)?;
+            check.positive("pk_references.t_half_h", reference.t_half_h)?;
+        }
+        Ok(())
+    }
+}
+
+/// Range checks reporting against one library entry
+struct Checker<'a> {
+    entry: &'a str,
+}
+
+impl<'a> Checker<'a> {
+    fn new(entry: &'a str) -> Self {
+        Self { entry }
+    }
+
+    fn fail(&self, message: &str) -> LibraryError {
+        LibraryError::Invalid { entry: self.entry.to_string(), message: message.to_string() }
+    }
+
+    fn positive(&self, field: &str, value: f64) -> Result<()> {
+        if value.is_finite() && value > 0.0 {
+            Ok(())
+        } else {
+            Err(self.fail(&format!("{field} = {value} must be positive")))
+        }
+    }
+
+    fn non_negative(&self, field: &str, value: f64) -> Result<()> {
+        if value.is_finite() && value >= 0.0 {
+            Ok(())
+        } else {
+            Err(self.fail(&format!("{field} = {value} must be non-negative")))
+        }
+    }
+
+    fn fraction(&self, field: &str, value: f64) -> Result<()> {
+        if (0.0..=1.0).contains(&value) {
+            Ok(())
+        } else {
+            Err(self.fail(&format!("{field} = {value} outside 0-1")))
+        }
+    }
+}
+
+/// CYP isoform names mentioned in a pathway description
+fn cyp_names(pathway: &str) -> impl Iterator<Item = &str> {
+    pathway
+        .split(|c: char| !c.is_ascii_alphanumeric())
+        .filter(|word| word.starts_with("CYP"))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const MINIMAL: &str = r#"
+        schema_version = 1
+
+        [[enzymes]]
+        isoform = "CYP3A4"
+        vmax = 10.0
+        km = 5.0
+
+        [[drugs]]
+        name = "Testazepam"
+        molecular_weight = 300.0
+
+        [drugs.receptor]
+        site = "BzSite"
+        ki_nm = 10.0
+        efficacy = 0.5
+        effects = { Sedation = 0.7 }
+
+        [[drugs.targets]]
+        target = "Nmda"
+        site = "Allosteric"
+        ki_nm = 100.0
+        cooperativity = 0.5
+
+        [drugs.metabolism]
+        substrate_of = { CYP3A4 = 1.0 }
+
+        [[drugs.gene_interactions]]
+        gene = "CYP2D6"
+        pm_recommendation = "Reduce dose"
+        um_recommendation = "Standard dosing"
+    "#;
+
+    #[test]
+    fn test_builtin_library_populates_all_databases() {
+        let library = DrugLibrary::builtin();
+        let diazepam = library.drug("Diazepam").unwrap();
+
+        // One entry feeds every table, sharing the molecular weight
+        let profile = DrugDatabase::new().get("diazepam").unwrap().clone();
+        let pk = PkDatabase::new().get("diazepam").unwrap().clone();
+        assert_eq!(profile.molecular_weight, diazepam.molecular_weight.unwrap());
+        assert_eq!(pk.molecular_weight, profile.molecular_weight);
+        assert_eq!(Cyp450Database::new().get("CYP2C19").unwrap().substrate_specificity["diazepam"], 0.8);
+        assert!(ClinicalLiteratureDb::new().find_matching_pet("diazepam", "GABA-A", 10.0, "oral").is_some());
+
+        assert!(TransporterDatabase::new().get("loperamide").unwrap().is_substrate_of(TransporterType::Pgp));
+        assert!(ReactiveMetaboliteDatabase::new().get("acetaminophen").unwrap().metabolites[0].is_quinone);
+        let codeine = &DrugGeneInteractions::new().interactions["codeine"][0];
+        assert!(codeine.is_prodrug && codeine.gene == CypIsoform::Cyp2d6);
+        assert!(Cyp450Database::new().get("CYP3A4").unwrap().inhibitors.contains_key("ketoconazole"));
+
+        // JSON is an equivalent format
+        let json = serde_json::to_string(library).unwrap();
+        let reloaded = DrugLibrary::from_json_str(&json).unwrap();
+        assert_eq!(reloaded.drug_names(), library.drug_names());
+        let toml = library.to_toml_string().unwrap();
+        assert_eq!(DrugLibrary::from_toml_str(&toml).unwrap().drugs.len(), library.drugs.len());
+    }
+
+    #[test]
+    fn test_library_validation() {
+        let library = DrugLibrary::from_toml_str(MINIMAL).unwrap();
+        let profile = library.drug_database().get("testazepam").unwrap().clone();
+        assert_eq!(profile.effect_profile[&EffectType::Sedation], 0.7);
+        assert_eq!(profile.allosteric_factor, BindingSite::BzSite.default_allosteric_factor());
+        let targets = library.target_database().get("testazepam").unwrap().clone();
+        assert!(targets.gaba_a.is_some());
+        assert_eq!(targets.bindings, vec![TargetBinding::allosteric(ReceptorTarget::Nmda, 100.0, 0.5)]);
+
+        let invalid = |from: &str, to: &str| DrugLibrary::from_toml_str(&MINIMAL.replace(from, to)).unwrap_err();
+        assert!(matches!(invalid("schema_version = 1", "schema_version = 2"), LibraryError::SchemaVersion { found: 2, .. }));
+        assert!(matches!(invalid("ki_nm = 10.0", "ki_mn = 10.0"), LibraryError::Parse(_)));
+        assert!(matches!(invalid("\"BzSite\"", "\"Cerebellum\""), LibraryError::Parse(_)));
+        assert!(matches!(invalid("efficacy = 0.5", "efficacy = 1.5"), LibraryError::Invalid { .. }));
+        assert!(matches!(invalid("molecular_weight = 300.0", ""), LibraryError::Invalid { .. }));
+        assert!(matches!(invalid("{ CYP3A4 = 1.0 }", "{ CYP2C9 = 1.0 }"), LibraryError::Invalid { .. }));
+        assert!(matches!(invalid("gene = \"CYP2D6\"", "gene = \"NAT2\""), LibraryError::Invalid { .. }));
+        assert!(matches!(invalid("\"Nmda\"", "\"Kainate\""), LibraryError::Parse(_)));
+        assert!(matches!(invalid("site = \"Allosteric\"", ""), LibraryError::Invalid { .. }));
+
+        let duplicate = format!("{MINIMAL}\n[[drugs]]\nname = \"testazepam\"\n");
+        assert!(matches!(DrugLibrary::from_toml_str(&duplicate), Err(LibraryError::Duplicate(_))));
+    }
+
+    #[test]
+    fn test_consistency_report() {
+        let issues = DrugLibrary::from_toml_str(MINIMAL).unwrap().consistency_report();
+        assert_eq!(
+            issues,
+            vec![
+                Inconsistency::ReceptorWithoutPk { drug: "testazepam".into() },
+                Inconsistency::GeneNotSubstrate { drug: "testazepam".into(), isoform: "CYP2D6".into() },
+            ]
+        );
+
+        // The built-in library has known gaps, e.g. literature for drugs
+        // without PK, SERT occupancy without transporter data and a CYP3A4
+        // bioactivation route for acetaminophen
+        let issues = DrugLibrary::builtin().consistency_report();
+        assert!(issues.contains(&Inconsistency::ReferenceWithoutPk { drug: "sertraline".into() }));
+        assert!(issues.contains(&Inconsistency::ReceptorWithoutPk { drug: "risperidone".into() }));
+        assert!(issues.contains(&Inconsistency::PetWithoutBinding { drug: "citalopram".into(), receptor: "SERT".into() }));
+        assert!(!issues.iter().any(|i| matches!(i, Inconsistency::PetWithoutBinding { drug, .. } if drug == "haloperidol")));
+        assert!(issues.contains(&Inconsistency::PathwayNotSubstrate {
+            drug: "acetaminophen".into(),
+            isoform: "CYP3A4".into()
+        }));
+        assert!(!issues.iter().any(|i| matches!(i, Inconsistency::ReceptorWithoutPk { drug } if drug == "diazepam")));
+    }
+}
@@ -29,8 +29,10 @@
 //! | 1.25-2.25      | Normal (NM)            |
 //! | >2.25          | Ultrarapid (UM)        |
 
+use rand::Rng;
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
+use crate::library::DrugLibrary;
 
 /// CYP450 enzyme isoform
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
@@ -54,6 +56,37 @@ pub enum CypIsoform {
 }
 
 impl CypIsoform {
+    /// All isoforms
+    pub const ALL: [CypIsoform; 8] = [
+        CypIsoform::Cyp2d6,
+        CypIsoform::Cyp2c19,
+        CypIsoform::Cyp2c9,
+        CypIsoform::Cyp3a4,
+        CypIsoform::Cyp3a5,
+        CypIsoform::Cyp1a2,
+        CypIsoform::Cyp2b6,
+        CypIsoform::Cyp2e1,
+    ];
+
+    /// Conventional name (e.g. "CYP2D6")
+    pub fn name(&self) -> &'static str {
+        match self {
+            CypIsoform::Cyp2d6 => "CYP2D6",
+            CypIsoform::Cyp2c19 => "CYP2C19",
+            CypIsoform::Cyp2c9 => "CYP2C9",
+            CypIsoform::Cyp3a4 => "CYP3A4",
+            CypIsoform::Cyp3a5 => "CYP3A5",
+            CypIsoform::Cyp1a2 => "CYP1A2",
+            CypIsoform::Cyp2b6 => "CYP2B6",
+            CypIsoform::Cyp2e1 => "CYP2E1",
+        }
+    }
+
+    /// Isoform from its name, case-insensitively
+    pub fn from_name(name: &str) -> Option<Self> {
+        Self::ALL.into_iter().find(|isoform| isoform.name().eq_ignore_ascii_case(name.trim()))
+    }
+
     /// Typical hepatic content (pmol/mg microsomal protein)
     pub fn hepatic_content(&self) -> f64 {
         match self {
@@ -550,87 +583,9 @@ pub struct DrugGeneInteraction {
 }
 
 impl DrugGeneInteractions {
+    /// Create database with the built-in drug library's interactions
     pub fn new() -> Self {
-        let mut interactions: HashMap<String, Vec<DrugGeneInteraction>> = HashMap::new();
-
-        // Codeine - CYP2D6 (classic example)
-        interactions.insert("codeine".to_string(), vec![
-            DrugGeneInteraction {
-                drug: "codeine".to_string(),
-                gene: CypIsoform::Cyp2d6,
-                is_prodrug: true,
-                pm_recommendation: "AVOID - codeine ineffective, no morphine formed".to_string(),
-                um_recommendation: "AVOID - risk of morphine toxicity, respiratory depression".to_string(),
-            }
-        ]);
-
-        // Tramadol - CYP2D6
-        interactions.insert("tramadol".to_string(), vec![
-            DrugGeneInteraction {
-                drug: "tramadol".to_string(),
-                gene: CypIsoform::Cyp2d6,
-                is_prodrug: true,
-                pm_recommendation: "Reduced efficacy, consider alternative".to_string(),
-                um_recommendation: "Risk of toxicity, consider dose reduction or alternative".to_string(),
-            }
-        ]);
-
-        // Fluoxetine - CYP2D6 (inhibitor and substrate)
-        interactions.insert("fluoxetine".to_string(), vec![
-            DrugGeneInteraction {
-                drug: "fluoxetine".to_string(),
-                gene: CypIsoform::Cyp2d6,
-                is_prodrug: false,
-                pm_recommendation: "Consider 50% dose reduction".to_string(),
-                um_recommendation: "Standard dosing, may need higher dose for efficacy".to_string(),
-            }
-        ]);
-
-        // Clopidogrel - CYP2C19
-        interactions.insert("clopidogrel".to_string(), vec![
-            DrugGeneInteraction {
-                drug: "clopidogrel".to_string(),
-                gene: CypIsoform::Cyp2c19,
-                is_prodrug: true,
-                pm_recommendation: "AVOID - use alternative (prasugrel, ticagrelor)".to_string(),
-                um_recommendation: "Standard therapy appropriate".to_string(),
-            }
-        ]);
-
-        // Omeprazole - CYP2C19
-        interactions.insert("omeprazole".to_string(), vec![
-            DrugGeneInteraction {
-                drug: "omeprazole".to_string(),
-                gene: CypIsoform::Cyp2c19,
-                is_prodrug: false,
-                pm_recommendation: "Increased efficacy, consider dose reduction for chronic use".to_string(),
-                um_recommendation: "May need increased dose for H. pylori eradication".to_string(),
-            }
-        ]);
-
-        // Warfarin - CYP2C9 and VKORC1
-        interactions.insert("warfarin".to_string(), vec![
-            DrugGeneInteraction {
-                drug: "warfarin".to_string(),
-                gene: CypIsoform::Cyp2c9,
-                is_prodrug: false,
-                pm_recommendation: "Reduce starting dose by 50-75%, high bleeding risk".to_string(),
-                um_recommendation: "Standard dosing".to_string(),
-            }
-        ]);
-
-        // Phenytoin - CYP2C9
-        interactions.insert("phenytoin".to_string(), vec![
-            DrugGeneInteraction {
-                drug: "phenytoin".to_string(),
-                gene: CypIsoform::Cyp2c9,
-                is_prodrug: false,
-                pm_recommendation: "Reduce starting dose by 50%, monitor levels".to_string(),
-                um_recommendation: "Standard dosing".to_string(),
-            }
-        ]);
-
-        Self { interactions }
+        DrugLibrary::builtin().drug_gene_interactions()
     }
 
     /// Get recommendations for a drug based on phenotype
@@ -701,6 +656,15 @@ impl PopulationSimulator {
 
     /// Generate a random profile based on ancestry
     pub fn generate_profile(&self, ancestry: Ancestry) -> PharmacogenomicProfile {
+        self.generate_profile_with_rng(ancestry, &mut rand::thread_rng())
+    }
+
+    /// Generate a profile drawing genotypes from `rng` (seed it for reproducible cohorts)
+    pub fn generate_profile_with_rng<R: Rng + ?Sized>(
+        &self,
+        ancestry: Ancestry,
+        rng: &mut R,
+    ) -> PharmacogenomicProfile {
         let mut profile = PharmacogenomicProfile::new();
         profile.ancestry = Some(ancestry);
 
@@ -709,7 +673,7 @@ impl PopulationSimulator {
         let um_freq = ancestry.cyp2d6_um_frequency();
 
         // Simple random assignment (would use proper Hardy-Weinberg in full implementation)
-        let rand_val = rand::random::<f64>();
+        let rand_val = rng.gen::<f64>();
         if rand_val < pm_freq {
             profile = profile.with_pm(CypIsoform::Cyp2d6);
         } else if rand_val < pm_freq + um_freq {
@@ -718,7 +682,7 @@ impl PopulationSimulator {
 
         // Simulate CYP2C19
         let pm_freq = ancestry.cyp2c19_pm_frequency();
-        if rand::random::<f64>() < pm_freq {
+        if rng.gen::<f64>() < pm_freq {
             profile = profile.with_pm(CypIsoform::Cyp2c19);
         }
 
@@ -19,6 +19,7 @@
 
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
+use crate::library::DrugLibrary;
 
 /// Routes of drug administration
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
@@ -73,6 +74,29 @@ impl RouteOfAdministration {
             RouteOfAdministration::Intranasal => 0.25,
         }
     }
+
+    /// Typical absorption lag time (hours)
+    pub fn typical_lag_h(&self) -> f64 {
+        match self {
+            RouteOfAdministration::Oral => 0.25, // Gastric emptying
+            RouteOfAdministration::Transdermal => 2.0, // Filling the skin depot
+            _ => 0.0,
+        }
+    }
+}
+
+/// Bateman function: concentration-time shape of a unit first-order input
+/// absorbed at rate `ka` into a compartment eliminated at rate `k` (1/h),
+/// i.e. ka / (ka - k) * (exp(-k t) - exp(-ka t)); tends to k t exp(-k t)
+/// as ka approaches k. Multiply by F * dose / V for a concentration.
+pub fn bateman(ka: f64, k: f64, time_h: f64) -> f64 {
+    if time_h <= 0.0 {
+        return 0.0;
+    }
+    if (ka - k).abs() < 1e-9 * ka.max(k) {
+        return ka * time_h * (-k * time_h).exp();
+    }
+    ka / (ka - k) * ((-k * time_h).exp() - (-ka * time_h).exp())
 }
 
 /// Pharmacokinetic parameters for a drug
@@ -150,217 +174,16 @@ impl Default for PkDatabase {
 }
 
 impl PkDatabase {
+    /// Create database with the built-in drug library's profiles
     pub fn new() -> Self {
-        let mut db = Self {
-            profiles: HashMap::new(),
-        };
-        db.init_profiles();
-        db
-    }
-
-    fn init_profiles(&mut self) {
-        // Benzodiazepines
-        self.add(PkParameters {
-            name: "diazepam".to_string(),
-            molecular_weight: 284.74,
-            bioavailability_oral: 0.93,
-            vd_l_kg: 1.1,
-            clearance_l_h_kg: 0.025,
-            half_life_h: 43.0,
-            protein_binding: 0.98,
-            brain_partition: 0.9,
-            tmax_oral_h: 1.0,
-            ka: 1.5,
-        });
-
-        self.add(PkParameters {
-            name: "alprazolam".to_string(),
-            molecular_weight: 308.77,
-            bioavailability_oral: 0.88,
-            vd_l_kg: 0.9,
-            clearance_l_h_kg: 0.05,
-            half_life_h: 11.0,
-            protein_binding: 0.80,
-            brain_partition: 0.85,
-            tmax_oral_h: 1.5,
-            ka: 1.2,
-        });
+        DrugLibrary::builtin().pk_database()
+    }
 
-        self.add(PkParameters {
-            name: "midazolam".to_string(),
-            molecular_weight: 325.77,
-            bioavailability_oral: 0.44,
-            vd_l_kg: 1.0,
-            clearance_l_h_kg: 0.35,
-            half_life_h: 2.5,
-            protein_binding: 0.97,
-            brain_partition: 0.75,
-            tmax_oral_h: 0.5,
-            ka: 2.5,
-        });
-
-        self.add(PkParameters {
-            name: "lorazepam".to_string(),
-            molecular_weight: 321.16,
-            bioavailability_oral: 0.90,
-            vd_l_kg: 1.3,
-            clearance_l_h_kg: 0.08,
-            half_life_h: 12.0,
-            protein_binding: 0.85,
-            brain_partition: 0.8,
-            tmax_oral_h: 2.0,
-            ka: 1.0,
-        });
-
-        self.add(PkParameters {
-            name: "clonazepam".to_string(),
-            molecular_weight: 315.71,
-            bioavailability_oral: 0.90,
-            vd_l_kg: 3.0,
-            clearance_l_h_kg: 0.06,
-            half_life_h: 34.0,
-            protein_binding: 0.86,
-            brain_partition: 0.85,
-            tmax_oral_h: 3.0,
-            ka: 0.8,
-        });
-
-        // Z-drugs
-        self.add(PkParameters {
-            name: "zolpidem".to_string(),
-            molecular_weight: 307.39,
-            bioavailability_oral: 0.70,
-            vd_l_kg: 0.54,
-            clearance_l_h_kg: 0.25,
-            half_life_h: 2.5,
-            protein_binding: 0.92,
-            brain_partition: 0.80,
-            tmax_oral_h: 1.5,
-            ka: 2.0,
-        });
-
-        // Anesthetics
-        self.add(PkParameters {
-            name: "propofol".to_string(),
-            molecular_weight: 178.27,
-            bioavailability_oral: 0.0, // IV only
-            vd_l_kg: 4.0,
-            clearance_l_h_kg: 1.5,
-            half_life_h: 0.5, // Distribution half-life
-            protein_binding: 0.98,
-            brain_partition: 1.2,
-            tmax_oral_h: 0.0,
-            ka: 0.0,
-        });
-
-        self.add(PkParameters {
-            name: "ketamine".to_string(),
-            molecular_weight: 237.73,
-            bioavailability_oral: 0.20,
-            vd_l_kg: 2.5,
-            clearance_l_h_kg: 1.0,
-            half_life_h: 2.5,
-            protein_binding: 0.47,
-            brain_partition: 4.0,
-            tmax_oral_h: 0.5,
-            ka: 3.0,
-        });
-
-        // Opioids
-        self.add(PkParameters {
-            name: "morphine".to_string(),
-            molecular_weight: 285.34,
-            bioavailability_oral: 0.30,
-            vd_l_kg: 3.5,
-            clearance_l_h_kg: 0.9,
-            half_life_h: 3.0,
-            protein_binding: 0.35,
-            brain_partition: 0.3,
-            tmax_oral_h: 1.0,
-            ka: 2.0,
-        });
-
-        self.add(PkParameters {
-            name: "fentanyl".to_string(),
-            molecular_weight: 336.47,
-            bioavailability_oral: 0.30,
-            vd_l_kg: 4.0,
-            clearance_l_h_kg: 0.7,
-            half_life_h: 4.0,
-            protein_binding: 0.84,
-            brain_partition: 2.5,
-            tmax_oral_h: 0.0, // Usually IV/transdermal
-            ka: 0.0,
-        });
-
-        // Antipsychotics
-        self.add(PkParameters {
-            name: "haloperidol".to_string(),
-            molecular_weight: 375.86,
-            bioavailability_oral: 0.60,
-            vd_l_kg: 18.0,
-            clearance_l_h_kg: 0.7,
-            half_life_h: 18.0,
-            protein_binding: 0.92,
-            brain_partition: 20.0,
-            tmax_oral_h: 4.0,
-            ka: 0.5,
-        });
-
-        // Antidepressants
-        self.add(PkParameters {
-            name: "fluoxetine".to_string(),
-            molecular_weight: 309.33,
-            bioavailability_oral: 0.72,
-            vd_l_kg: 25.0,
-            clearance_l_h_kg: 0.4,
-            half_life_h: 72.0,
-            protein_binding: 0.95,
-            brain_partition: 3.0,
-            tmax_oral_h: 6.0,
-            ka: 0.3,
-        });
-
-        // Anti-Parkinson
-        self.add(PkParameters {
-            name: "levodopa".to_string(),
-            molecular_weight: 197.19,
-            bioavailability_oral: 0.30,
-            vd_l_kg: 0.5,
-            clearance_l_h_kg: 0.5,
-            half_life_h: 1.5,
-            protein_binding: 0.10,
-            brain_partition: 0.1,
-            tmax_oral_h: 1.0,
-            ka: 2.0,
-        });
-
-        // Barbiturates
-        self.add(PkParameters {
-            name: "thiopental".to_string(),
-            molecular_weight: 242.34,
-            bioavailability_oral: 0.0, // IV only
-            vd_l_kg: 2.5,
-            clearance_l_h_kg: 0.2,
-            half_life_h: 11.0,
-            protein_binding: 0.85,
-            brain_partition: 1.5,
-            tmax_oral_h: 0.0,
-            ka: 0.0,
-        });
-
-        self.add(PkParameters {
-            name: "phenobarbital".to_string(),
-            molecular_weight: 232.24,
-            bioavailability_oral: 0.95,
-            vd_l_kg: 0.6,
-            clearance_l_h_kg: 0.004,
-            half_life_h: 100.0,
-            protein_binding: 0.50,
-            brain_partition: 0.8,
-            tmax_oral_h: 6.0,
-            ka: 0.3,
-        });
+    /// Create an empty database
+    pub fn empty() -> Self {
+        Self {
+            profiles: HashMap::new(),
+        }
     }
 
     /// Add a drug profile
@@ -379,12 +202,14 @@ impl PkDatabase {
     }
 }
 
-/// One-compartment pharmacokinetic model
+/// One-compartment pharmacokinetic model with a first-order gut depot
 #[derive(Debug, Clone)]
 pub struct OneCompartmentModel {
     pk: PkParameters,
     weight_kg: f64,
     current_concentration_mg_l: f64,
+    /// Bioavailable drug still to be absorbed (mg)
+    depot_mg: f64,
     current_time_h: f64,
 }
 
@@ -395,6 +220,7 @@ impl OneCompartmentModel {
             pk,
             weight_kg,
             current_concentration_mg_l: 0.0,
+            depot_mg: 0.0,
             current_time_h: 0.0,
         }
     }
@@ -405,18 +231,19 @@ impl OneCompartmentModel {
         self.current_concentration_mg_l += dose_mg / vd;
     }
 
-    /// Administer oral dose
+    /// Administer oral dose: the bioavailable fraction enters the gut depot
+    /// and is absorbed at rate `ka`
     pub fn give_oral(&mut self, dose_mg: f64) {
-        let absorbed = dose_mg * self.pk.bioavailability_oral;
-        let vd = self.pk.vd_l(self.weight_kg);
-        // Simplified: assumes instant absorption to peak
-        self.current_concentration_mg_l += absorbed / vd;
+        self.depot_mg += dose_mg * self.pk.bioavailability_oral;
     }
 
-    /// Calculate concentration at time t after last dose (hours)
+    /// Calculate concentration at time t from now (hours), including drug
+    /// still being absorbed
     pub fn concentration_at(&self, time_h: f64) -> f64 {
         let k_el = self.pk.k_el();
+        let vd = self.pk.vd_l(self.weight_kg);
         self.current_concentration_mg_l * (-k_el * time_h).exp()
+            + self.depot_mg / vd * bateman(self.pk.ka, k_el, time_h)
     }
 
     /// Calculate brain concentration (uM) at time t
@@ -429,6 +256,7 @@ impl OneCompartmentModel {
     /// Advance time and update concentration
     pub fn advance_time(&mut self, delta_h: f64) {
         self.current_concentration_mg_l = self.concentration_at(delta_h);
+        self.depot_mg *= (-self.pk.ka * delta_h).exp();
         self.current_time_h += delta_h;
     }
 
@@ -443,7 +271,8 @@ impl OneCompartmentModel {
         plasma_um * self.pk.brain_partition * self.pk.free_fraction()
     }
 
-    /// Calculate time to reach a target concentration
+    /// Calculate time for the present concentration to decay to a target
+    /// (ignores drug still in the gut depot)
     pub fn time_to_concentration(&self, target_mg_l: f64) -> Option<f64> {
         if target_mg_l >= self.current_concentration_mg_l || target_mg_l <= 0.0 {
             return None;
@@ -452,11 +281,18 @@ impl OneCompartmentModel {
         Some((self.current_concentration_mg_l / target_mg_l).ln() / k_el)
     }
 
-    /// Calculate Cmax after oral dose
+    /// Calculate Cmax after a single oral dose, reached at
+    /// tmax = ln(ka / k) / (ka - k)
     pub fn calculate_cmax_oral(&self, dose_mg: f64) -> f64 {
         let absorbed = dose_mg * self.pk.bioavailability_oral;
         let vd = self.pk.vd_l(self.weight_kg);
-        absorbed / vd
+        let (ka, k_el) = (self.pk.ka, self.pk.k_el());
+        let tmax = if (ka - k_el).abs() < 1e-9 * ka.max(k_el) {
+            1.0 / k_el
+        } else {
+            (ka / k_el).ln() / (ka - k_el)
+        };
+        absorbed / vd * bateman(ka, k_el, tmax)
     }
 
     /// Calculate AUC (area under curve) for a single dose
@@ -667,6 +503,27 @@ mod tests {
         assert!((c_half / c0 - 0.5).abs() < 0.1);
     }
 
+    #[test]
+    fn test_oral_dose_is_absorbed_gradually() {
+        let pk = PkDatabase::new().get("alprazolam").unwrap().clone();
+        let mut model = OneCompartmentModel::new(pk.clone(), 70.0);
+        model.give_oral(1.0);
+        assert_eq!(model.current_plasma_mg_l(), 0.0);
+
+        // Peak at ln(ka/k) / (ka - k), below the instant-absorption bound
+        let tmax = (pk.ka / pk.k_el()).ln() / (pk.ka - pk.k_el());
+        let cmax = model.calculate_cmax_oral(1.0);
+        assert!((model.concentration_at(tmax) - cmax).abs() < 1e-12);
+        assert!(model.concentration_at(tmax - 0.5) < cmax && model.concentration_at(tmax + 0.5) < cmax);
+        assert!(cmax < pk.bioavailability_oral / pk.vd_l(70.0));
+
+        // Stepping through time follows the same curve
+        for _ in 0..10 {
+            model.advance_time(tmax / 10.0);
+        }
+        assert!((model.current_plasma_mg_l() - cmax).abs() < 1e-9);
+    }
+
     #[test]
     fn test_brain_concentration() {
         let db = PkDatabase::new();
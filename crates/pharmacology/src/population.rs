@@ -0,0 +1,708 @@
+//! Population Pharmacokinetics
+//! ===========================
+//!
+//! Virtual trials: a regimen given to a cohort of simulated patients whose
+//! PK parameters vary with their covariates and with unexplained
+//! inter-individual variability (IIV).
+//!
+//! # Individual Parameters
+//! For a patient of weight WT with random effects eta ~ N(0, omega^2):
+//!
+//! ```text
+//! CL_i = CL_pop * (WT/70)^0.75 * [fe * R + (1 - fe) * H * A * M] * exp(eta_CL)
+//! V_i  = V_pop  * (WT/70)      * exp(eta_V)
+//! ka_i = ka_pop * exp(eta_ka)
+//! ```
+//!
+//! - fe: fraction of clearance excreted renally; R = CrCL relative to the
+//!   typical patient, with CrCL from Cockcroft-Gault (age, weight, sex,
+//!   serum creatinine)
+//! - H: hepatic function by Child-Pugh class (A 1.0, B 0.6, C 0.3)
+//! - A: hepatic ageing, -0.8% per year after 40 (at least 0.5)
+//! - M: metabolic capacity, `1 - sum f_j + sum f_j * a_j`, where f_j is
+//!   the share of hepatic clearance by CYP isoform j and a_j its activity:
+//!   the genotype's activity score relative to normal times
+//!   `1 / (1 + [I]/Ki)` for each co-medicated inhibitor
+//!
+//! The half-life follows as t1/2_pop * (V_i/V_pop) / (CL_i/CL_pop) per kg,
+//! so the typical 70 kg adult reproduces the population parameters.
+//!
+//! # Outputs
+//! [`TrialResult`] gives percentiles of the concentration-time curves,
+//! the probability of target attainment (e.g. peak receptor occupancy) and
+//! adverse-event rates from [`AdverseEventPredictor`], which is fed each
+//! patient's risk factors and an exposure-equivalent dose (dose scaled by
+//! the patient's AUC relative to the typical patient).
+//!
+//! # References
+//! - Mould DR & Upton RN (2013) CPT Pharmacometrics Syst Pharmacol 2:e38
+//! - Anderson BJ & Holford NHG (2008) Annu Rev Pharmacol Toxicol 48:303
+//! - Cockcroft DW & Gault MH (1976) Nephron 16:31
+
+use std::collections::HashMap;
+
+use rand::{Rng, SeedableRng};
+use rand_chacha::ChaCha8Rng;
+use rand_distr::{Distribution, Normal};
+use rayon::prelude::*;
+use serde::{Deserialize, Serialize};
+
+use crate::adverse_events::{AdverseEventPrediction, AdverseEventPredictor, PatientRiskFactors};
+use crate::dosing::{ConcentrationProfile, DosingRegimen, RegimenModel};
+use crate::enzyme_kinetics::Cyp450Database;
+use crate::library::DrugLibrary;
+use crate::pharmacogenomics::{Ancestry, CypIsoform, MetabolizerPhenotype, PopulationSimulator};
+use crate::pharmacokinetics::PkParameters;
+
+/// Reference weight of the population parameters (kg)
+const REFERENCE_WEIGHT_KG: f64 = 70.0;
+
+/// Share of hepatic clearance attributed to the CYP isoforms a drug is a
+/// substrate of, when derived from the drug library
+const CYP_SHARE: f64 = 0.9;
+
+/// Log-normal inter-individual variability (omega, the SD of eta; about
+/// the coefficient of variation for small omega)
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct Variability {
+    pub clearance: f64,
+    pub volume: f64,
+    pub ka: f64,
+}
+
+impl Default for Variability {
+    fn default() -> Self {
+        Self { clearance: 0.3, volume: 0.25, ka: 0.5 }
+    }
+}
+
+impl Variability {
+    /// No unexplained variability: covariates only
+    pub fn none() -> Self {
+        Self { clearance: 0.0, volume: 0.0, ka: 0.0 }
+    }
+}
+
+/// Elimination routes of a drug
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct ClearancePathways {
+    /// Fraction of clearance excreted unchanged by the kidney (0-1)
+    pub renal_fraction: f64,
+    /// Share of hepatic clearance by each CYP isoform (summing to at most 1)
+    pub cyp: Vec<(CypIsoform, f64)>,
+}
+
+impl ClearancePathways {
+    /// Hepatic clearance split among the drug's CYP isoforms in the
+    /// built-in library, in proportion to their affinity (1 / relative Km)
+    pub fn for_drug(name: &str) -> Self {
+        let substrate_of = DrugLibrary::builtin()
+            .drug(name)
+            .and_then(|d| d.metabolism.as_ref())
+            .map(|m| &m.substrate_of);
+        let affinities: Vec<(CypIsoform, f64)> = substrate_of
+            .into_iter()
+            .flatten()
+            .filter_map(|(isoform, &km)| CypIsoform::from_name(isoform).map(|i| (i, 1.0 / km)))
+            .collect();
+        let total: f64 = affinities.iter().map(|(_, a)| a).sum();
+        let mut cyp: Vec<(CypIsoform, f64)> =
+            affinities.into_iter().map(|(isoform, a)| (isoform, CYP_SHARE * a / total)).collect();
+        cyp.sort_by_key(|(isoform, _)| isoform.name());
+        Self { renal_fraction: 0.0, cyp }
+    }
+
+    /// Builder: set the renally excreted fraction
+    pub fn with_renal_fraction(mut self, fraction: f64) -> Self {
+        self.renal_fraction = fraction.clamp(0.0, 1.0);
+        self
+    }
+
+    /// Builder: set the share of hepatic clearance by one isoform
+    pub fn with_cyp(mut self, isoform: CypIsoform, share: f64) -> Self {
+        self.cyp.retain(|(i, _)| *i != isoform);
+        self.cyp.push((isoform, share.clamp(0.0, 1.0)));
+        self
+    }
+
+    /// Hepatic capacity relative to normal for the given isoform activities
+    pub fn metabolic_capacity(&self, activity: &HashMap<CypIsoform, f64>) -> f64 {
+        let cyp_share: f64 = self.cyp.iter().map(|(_, f)| f).sum();
+        (1.0 - cyp_share).max(0.0)
+            + self.cyp.iter().map(|(isoform, f)| f * activity.get(isoform).copied().unwrap_or(1.0)).sum::<f64>()
+    }
+}
+
+/// Covariates of one patient
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Covariates {
+    pub weight_kg: f64,
+    /// Age in years
+    pub age: f64,
+    pub female: bool,
+    pub serum_creatinine_mg_dl: f64,
+    /// Child-Pugh score (5-15)
+    pub child_pugh: u8,
+    /// Metabolizer phenotype of the polymorphic isoforms
+    pub phenotypes: HashMap<CypIsoform, MetabolizerPhenotype>,
+    /// CYP activity relative to normal (genotype and inhibition)
+    pub cyp_activity: HashMap<CypIsoform, f64>,
+    /// Co-medications
+    pub medications: Vec<String>,
+}
+
+impl Covariates {
+    /// 70 kg, 40-year-old male with normal organ function and metabolism
+    pub fn typical() -> Self {
+        Self {
+            weight_kg: REFERENCE_WEIGHT_KG,
+            age: 40.0,
+            female: false,
+            serum_creatinine_mg_dl: 1.0,
+            child_pugh: 5,
+            phenotypes: HashMap::new(),
+            cyp_activity: HashMap::new(),
+            medications: Vec::new(),
+        }
+    }
+
+    /// Creatinine clearance by Cockcroft-Gault (mL/min)
+    pub fn crcl_ml_min(&self) -> f64 {
+        let sex = if self.female { 0.85 } else { 1.0 };
+        ((140.0 - self.age) * self.weight_kg / (72.0 * self.serum_creatinine_mg_dl) * sex).max(0.0)
+    }
+
+    /// Hepatic clearance relative to normal for the Child-Pugh class
+    pub fn hepatic_function(&self) -> f64 {
+        match self.child_pugh {
+            0..=6 => 1.0,
+            7..=9 => 0.6,
+            _ => 0.3,
+        }
+    }
+
+    /// Decline of hepatic clearance with age
+    pub fn hepatic_ageing(&self) -> f64 {
+        (1.0 - 0.008 * (self.age - 40.0).max(0.0)).max(0.5)
+    }
+
+    /// Activity of `isoform` relative to normal
+    pub fn activity(&self, isoform: CypIsoform) -> f64 {
+        self.cyp_activity.get(&isoform).copied().unwrap_or(1.0)
+    }
+
+    /// Risk factors for [`AdverseEventPredictor`]
+    pub fn risk_factors(&self) -> PatientRiskFactors {
+        let mut patient = PatientRiskFactors::new(self.age, self.crcl_ml_min());
+        patient.child_pugh = self.child_pugh;
+        patient.medications = self.medications.clone();
+        for (isoform, phenotype) in &self.phenotypes {
+            if *phenotype != MetabolizerPhenotype::NormalMetabolizer {
+                patient.genetic_risks.insert(format!("{} {:?}", isoform.name(), phenotype), 1.0);
+            }
+        }
+        patient
+    }
+}
+
+/// Population from which patients are drawn
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CohortSpec {
+    /// Age range, uniform (years)
+    pub age_range: (f64, f64),
+    /// Median weight (kg) and coefficient of variation, log-normal
+    pub weight_kg: (f64, f64),
+    pub female_fraction: f64,
+    /// Median serum creatinine (mg/dL) and coefficient of variation
+    pub serum_creatinine_mg_dl: (f64, f64),
+    /// Fraction with hepatic impairment (Child-Pugh 7-12)
+    pub hepatic_impairment_fraction: f64,
+    /// Ancestry for CYP genotype frequencies
+    pub ancestry: Ancestry,
+    /// Co-medicated CYP inhibitors with their free concentrations (uM)
+    pub inhibitors: Vec<(String, f64)>,
+}
+
+impl Default for CohortSpec {
+    fn default() -> Self {
+        Self::adults()
+    }
+}
+
+impl CohortSpec {
+    /// Adults aged 18-65
+    pub fn adults() -> Self {
+        Self {
+            age_range: (18.0, 65.0),
+            weight_kg: (75.0, 0.2),
+            female_fraction: 0.5,
+            serum_creatinine_mg_dl: (0.9, 0.2),
+            hepatic_impairment_fraction: 0.0,
+            ancestry: Ancestry::European,
+            inhibitors: Vec::new(),
+        }
+    }
+
+    /// Patients aged 65-90
+    pub fn elderly() -> Self {
+        Self {
+            age_range: (65.0, 90.0),
+            weight_kg: (70.0, 0.2),
+            female_fraction: 0.55,
+            serum_creatinine_mg_dl: (1.0, 0.25),
+            ..Self::adults()
+        }
+    }
+
+    /// Builder: set the age range
+    pub fn with_age_range(mut self, min: f64, max: f64) -> Self {
+        self.age_range = (min, max);
+        self
+    }
+
+    /// Builder: set the ancestry
+    pub fn with_ancestry(mut self, ancestry: Ancestry) -> Self {
+        self.ancestry = ancestry;
+        self
+    }
+
+    /// Builder: set the fraction with hepatic impairment
+    pub fn with_hepatic_impairment(mut self, fraction: f64) -> Self {
+        self.hepatic_impairment_fraction = fraction.clamp(0.0, 1.0);
+        self
+    }
+
+    /// Builder: co-medicate every patient with a CYP inhibitor at a free
+    /// concentration (uM); its Ki per isoform comes from [`Cyp450Database`]
+    pub fn with_inhibitor(mut self, name: &str, concentration_um: f64) -> Self {
+        self.inhibitors.push((name.to_lowercase(), concentration_um));
+        self
+    }
+
+    /// Activity left to each isoform by the co-medicated inhibitors, with
+    /// their Ki from `cyp450`
+    pub fn inhibition(&self, cyp450: &Cyp450Database) -> HashMap<CypIsoform, f64> {
+        let mut activity = HashMap::new();
+        for (name, concentration) in &self.inhibitors {
+            for (isoform, enzyme) in &cyp450.enzymes {
+                let (Some(isoform), Some(ki)) = (CypIsoform::from_name(isoform), enzyme.inhibitors.get(name)) else {
+                    continue;
+                };
+                *activity.entry(isoform).or_insert(1.0) /= 1.0 + concentration / ki;
+            }
+        }
+        activity
+    }
+
+    /// Draw the covariates of one patient
+    pub fn sample<R: Rng + ?Sized>(&self, rng: &mut R) -> Covariates {
+        self.sample_inhibited(rng, &self.inhibition(&Cyp450Database::new()))
+    }
+
+    /// Draw the covariates of one patient, with the isoform activities left
+    /// by the inhibitors already computed by [`CohortSpec::inhibition`]
+    pub fn sample_inhibited<R: Rng + ?Sized>(&self, rng: &mut R, inhibition: &HashMap<CypIsoform, f64>) -> Covariates {
+        let log_normal = |rng: &mut R, (median, cv): (f64, f64)| {
+            let sigma = (1.0 + cv * cv).ln().sqrt();
+            median * (sigma * standard_normal(rng)).exp()
+        };
+        let (min_age, max_age) = self.age_range;
+        let age = if max_age > min_age { rng.gen_range(min_age..max_age) } else { min_age };
+        let weight_kg = log_normal(rng, self.weight_kg);
+        let female = rng.gen::<f64>() < self.female_fraction;
+        let serum_creatinine_mg_dl = log_normal(rng, self.serum_creatinine_mg_dl);
+        let child_pugh = if rng.gen::<f64>() < self.hepatic_impairment_fraction { rng.gen_range(7..=12) } else { 5 };
+
+        let genotype = PopulationSimulator::new().generate_profile_with_rng(self.ancestry, rng);
+        let mut phenotypes = HashMap::new();
+        let mut cyp_activity = HashMap::new();
+        for isoform in CypIsoform::ALL {
+            let phenotype = genotype.phenotype(isoform);
+            if isoform.is_highly_polymorphic() {
+                phenotypes.insert(isoform, phenotype);
+            }
+            let activity = genotype.metabolism_rate(isoform) * inhibition.get(&isoform).copied().unwrap_or(1.0);
+            if activity != 1.0 {
+                cyp_activity.insert(isoform, activity);
+            }
+        }
+
+        Covariates {
+            weight_kg,
+            age,
+            female,
+            serum_creatinine_mg_dl,
+            child_pugh,
+            phenotypes,
+            cyp_activity,
+            medications: self.inhibitors.iter().map(|(name, _)| name.clone()).collect(),
+        }
+    }
+}
+
+/// One simulated patient
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct VirtualPatient {
+    pub id: usize,
+    pub covariates: Covariates,
+    /// Random effects on clearance, volume and ka
+    pub eta: [f64; 3],
+    /// Individual PK parameters
+    pub pk: PkParameters,
+}
+
+/// Exposure and predicted adverse events of one patient
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PatientOutcome {
+    pub patient: VirtualPatient,
+    pub profile: ConcentrationProfile,
+    /// AUC relative to the typical patient
+    pub relative_exposure: f64,
+    pub adverse_events: Vec<AdverseEventPrediction>,
+}
+
+impl PatientOutcome {
+    /// Highest receptor occupancy (0-1) for a target of affinity `ki_um`
+    pub fn peak_occupancy(&self, ki_um: f64) -> f64 {
+        self.profile.occupancy(ki_um).into_iter().fold(0.0, f64::max)
+    }
+}
+
+/// Exposure goal of a trial
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub enum AttainmentTarget {
+    /// Peak occupancy of a receptor of affinity `ki_um` at least `threshold`
+    PeakOccupancy { ki_um: f64, threshold: f64 },
+    /// Peak plasma concentration at least `mg_l`
+    PeakPlasma { mg_l: f64 },
+    /// Plasma AUC over the simulation at least `mg_h_l`
+    Auc { mg_h_l: f64 },
+}
+
+impl AttainmentTarget {
+    /// Whether a patient reaches the target
+    pub fn is_met(&self, outcome: &PatientOutcome) -> bool {
+        match *self {
+            AttainmentTarget::PeakOccupancy { ki_um, threshold } => outcome.peak_occupancy(ki_um) >= threshold,
+            AttainmentTarget::PeakPlasma { mg_l } => outcome.profile.cmax().0 >= mg_l,
+            AttainmentTarget::Auc { mg_h_l } => outcome.profile.auc() >= mg_h_l,
+        }
+    }
+}
+
+/// Percentiles of the concentration-time curves across patients
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ConcentrationPercentiles {
+    pub times_h: Vec<f64>,
+    /// Percentiles (0-100)
+    pub percentiles: Vec<f64>,
+    /// Plasma concentration (mg/L) per percentile, per sample
+    pub plasma_mg_l: Vec<Vec<f64>>,
+    /// Free brain concentration (uM) per percentile, per sample
+    pub brain_um: Vec<Vec<f64>>,
+}
+
+/// Outcomes of a virtual trial
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TrialResult {
+    pub drug: String,
+    pub outcomes: Vec<PatientOutcome>,
+}
+
+impl TrialResult {
+    /// Number of patients
+    pub fn len(&self) -> usize {
+        self.outcomes.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.outcomes.is_empty()
+    }
+
+    /// Patients matching `predicate`, e.g. a covariate subgroup
+    pub fn subgroup(&self, predicate: impl Fn(&PatientOutcome) -> bool) -> TrialResult {
+        TrialResult {
+            drug: self.drug.clone(),
+            outcomes: self.outcomes.iter().filter(|o| predicate(o)).cloned().collect(),
+        }
+    }
+
+    /// Fraction of patients matching `predicate`
+    pub fn fraction(&self, predicate: impl Fn(&PatientOutcome) -> bool) -> f64 {
+        if self.outcomes.is_empty() {
+            return 0.0;
+        }
+        self.outcomes.iter().filter(|o| predicate(o)).count() as f64 / self.outcomes.len() as f64
+    }
+
+    /// Probability of target attainment
+    pub fn probability_of_target_attainment(&self, target: &AttainmentTarget) -> f64 {
+        self.fraction(|o| target.is_met(o))
+    }
+
+    /// Percentiles (0-100) of plasma and brain concentration at each sample
+    pub fn percentiles(&self, percentiles: &[f64]) -> ConcentrationPercentiles {
+        let times_h = self.outcomes.first().map(|o| o.profile.times_h.clone()).unwrap_or_default();
+        let across = |series: fn(&ConcentrationProfile) -> &Vec<f64>| -> Vec<Vec<f64>> {
+            let columns: Vec<Vec<f64>> = (0..times_h.len())
+                .map(|i| {
+                    let mut values: Vec<f64> = self.outcomes.iter().map(|o| series(&o.profile)[i]).collect();
+                    values.sort_by(f64::total_cmp);
+                    values
+                })
+                .collect();
+            percentiles.iter().map(|&p| columns.iter().map(|values| percentile(values, p)).collect()).collect()
+        };
+        ConcentrationPercentiles {
+            plasma_mg_l: across(|profile| &profile.plasma_mg_l),
+            brain_um: across(|profile| &profile.brain_um),
+            times_h,
+            percentiles: percentiles.to_vec(),
+        }
+    }
+
+    /// Expected incidence of each adverse event across the cohort
+    pub fn adverse_event_rates(&self) -> HashMap<String, f64> {
+        let mut rates: HashMap<String, f64> = HashMap::new();
+        for outcome in &self.outcomes {
+            for event in &outcome.adverse_events {
+                *rates.entry(event.event.clone()).or_insert(0.0) += event.probability;
+            }
+        }
+        let n = self.outcomes.len().max(1) as f64;
+        rates.values_mut().for_each(|rate| *rate /= n);
+        rates
+    }
+}
+
+/// A regimen of one drug given to a virtual cohort
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct VirtualTrial {
+    /// Population PK parameters
+    pub pk: PkParameters,
+    pub pathways: ClearancePathways,
+    pub variability: Variability,
+    pub cohort: CohortSpec,
+    pub regimen: DosingRegimen,
+    /// Simulated time (hours)
+    pub duration_h: f64,
+    /// Sampling interval (hours)
+    pub dt_h: f64,
+    pub seed: u64,
+}
+
+impl VirtualTrial {
+    /// Trial of `regimen` in adults, with the drug's clearance pathways from
+    /// the built-in library and an entropy-derived seed
+    pub fn new(pk: PkParameters, regimen: DosingRegimen) -> Self {
+        Self::with_seed(pk, regimen, rand::random())
+    }
+
+    /// Trial whose cohort and random effects are fully determined by `seed`
+    pub fn with_seed(pk: PkParameters, regimen: DosingRegimen, seed: u64) -> Self {
+        let last_dose = regimen.doses.iter().map(|d| d.time_h + d.duration_h).fold(0.0, f64::max);
+        Self {
+            pathways: ClearancePathways::for_drug(&pk.name),
+            variability: Variability::default(),
+            cohort: CohortSpec::adults(),
+            duration_h: last_dose + 24.0,
+            dt_h: 0.25,
+            pk,
+            regimen,
+            seed,
+        }
+    }
+
+    /// Builder: set the cohort
+    pub fn with_cohort(mut self, cohort: CohortSpec) -> Self {
+        self.cohort = cohort;
+        self
+    }
+
+    /// Builder: set the inter-individual variability
+    pub fn with_variability(mut self, variability: Variability) -> Self {
+        self.variability = variability;
+        self
+    }
+
+    /// Builder: set the clearance pathways
+    pub fn with_pathways(mut self, pathways: ClearancePathways) -> Self {
+        self.pathways = pathways;
+        self
+    }
+
+    /// Builder: simulate `duration_h` hours sampled every `dt_h`
+    pub fn with_sampling(mut self, duration_h: f64, dt_h: f64) -> Self {
+        self.duration_h = duration_h;
+        self.dt_h = dt_h;
+        self
+    }
+
+    /// Individual PK parameters for covariates and random effects
+    pub fn individual_pk(&self, covariates: &Covariates, eta: [f64; 3]) -> PkParameters {
+        let pk = &self.pk;
+        let size = covariates.weight_kg / REFERENCE_WEIGHT_KG;
+        let renal = covariates.crcl_ml_min() / Covariates::typical().crcl_ml_min();
+        let hepatic = covariates.hepatic_function()
+            * covariates.hepatic_ageing()
+            * self.pathways.metabolic_capacity(&covariates.cyp_activity);
+        let fe = self.pathways.renal_fraction;
+        let organ = (fe * renal + (1.0 - fe) * hepatic).max(1e-3);
+
+        // Per-kg parameters: allometric clearance falls with size as WT^-0.25
+        let clearance_ratio = size.powf(-0.25) * organ * eta[0].exp();
+        let volume_ratio = eta[1].exp();
+        PkParameters {
+            clearance_l_h_kg: pk.clearance_l_h_kg * clearance_ratio,
+            vd_l_kg: pk.vd_l_kg * volume_ratio,
+            half_life_h: pk.half_life_h * volume_ratio / clearance_ratio,
+            ka: pk.ka * eta[2].exp(),
+            ..pk.clone()
+        }
+    }
+
+    /// Draw patient `id` (independent of the other patients)
+    pub fn patient(&self, id: usize) -> VirtualPatient {
+        self.draw_patient(id, &self.cohort.inhibition(&Cyp450Database::new()))
+    }
+
+    /// Draw patient `id` given the cohort's CYP inhibition
+    fn draw_patient(&self, id: usize, inhibition: &HashMap<CypIsoform, f64>) -> VirtualPatient {
+        let mut rng = ChaCha8Rng::seed_from_u64(self.seed);
+        rng.set_stream(id as u64);
+        let covariates = self.cohort.sample_inhibited(&mut rng, inhibition);
+        let omega = self.variability;
+        let eta = [omega.clearance, omega.volume, omega.ka].map(|sd| sd * standard_normal(&mut rng));
+        let pk = self.individual_pk(&covariates, eta);
+        VirtualPatient { id, covariates, eta, pk }
+    }
+
+    /// Concentration time course of a patient
+    pub fn simulate_patient(&self, patient: &VirtualPatient) -> ConcentrationProfile {
+        RegimenModel::new(patient.pk.clone(), patient.covariates.weight_kg).simulate(
+            &self.regimen,
+            self.duration_h,
+            self.dt_h,
+        )
+    }
+
+    /// Simulate `n` patients in parallel
+    pub fn run(&self, n: usize) -> TrialResult {
+        let typical = self.individual_pk(&Covariates::typical(), [0.0; 3]);
+        let typical_auc = RegimenModel::new(typical, REFERENCE_WEIGHT_KG)
+            .simulate(&self.regimen, self.duration_h, self.dt_h)
+            .auc();
+        let largest_dose = self.regimen.doses.iter().map(|d| d.amount_mg).fold(0.0, f64::max);
+        let predictor = AdverseEventPredictor::new();
+        let inhibition = self.cohort.inhibition(&Cyp450Database::new());
+
+        let outcomes = (0..n)
+            .into_par_iter()
+            .map(|id| {
+                let patient = self.draw_patient(id, &inhibition);
+                let profile = self.simulate_patient(&patient);
+                let relative_exposure = if typical_auc > 0.0 { profile.auc() / typical_auc } else { 1.0 };
+
+                let mut predictor = predictor.clone();
+                predictor.set_patient(patient.covariates.risk_factors());
+                let adverse_events = predictor.predict(&self.pk.name, largest_dose * relative_exposure);
+                PatientOutcome { patient, profile, relative_exposure, adverse_events }
+            })
+            .collect();
+
+        TrialResult { drug: self.pk.name.clone(), outcomes }
+    }
+}
+
+/// Draw from N(0, 1)
+fn standard_normal<R: Rng + ?Sized>(rng: &mut R) -> f64 {
+    Normal::new(0.0, 1.0).expect("unit normal is valid").sample(rng)
+}
+
+/// Percentile `p` (0-100) of sorted values, interpolating linearly
+fn percentile(sorted: &[f64], p: f64) -> f64 {
+    match sorted.len() {
+        0 => 0.0,
+        1 => sorted[0],
+        n => {
+            let rank = (p / 100.0).clamp(0.0, 1.0) * (n - 1) as f64;
+            let (lo, hi) = (rank.floor() as usize, rank.ceil() as usize);
+            sorted[lo] + (sorted[hi] - sorted[lo]) * (rank - lo as f64)
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::pharmacokinetics::{PkDatabase, RouteOfAdministration};
+    use crate::DrugDatabase;
+
+    fn trial(drug: &str, regimen: DosingRegimen) -> VirtualTrial {
+        VirtualTrial::with_seed(PkDatabase::new().get(drug).unwrap().clone(), regimen, 7)
+    }
+
+    #[test]
+    fn test_covariates_scale_individual_parameters() {
+        let pk = PkDatabase::new().get("alprazolam").unwrap().clone();
+        let trial = trial("alprazolam", DosingRegimen::repeated(1.0, RouteOfAdministration::Oral, 8.0, 3));
+        assert_eq!(trial.pathways.cyp, vec![(CypIsoform::Cyp3a4, CYP_SHARE)]);
+
+        // The typical patient reproduces the population parameters
+        let typical = Covariates::typical();
+        assert!((typical.crcl_ml_min() - 97.2).abs() < 0.1);
+        let individual = trial.individual_pk(&typical, [0.0; 3]);
+        assert!((individual.half_life_h - pk.half_life_h).abs() < 1e-12);
+
+        // Age, liver disease and CYP3A4 inhibition each slow elimination
+        let slower = |covariates: Covariates| trial.individual_pk(&covariates, [0.0; 3]).half_life_h > pk.half_life_h;
+        assert!(slower(Covariates { age: 80.0, ..Covariates::typical() }));
+        assert!(slower(Covariates { child_pugh: 9, ..Covariates::typical() }));
+        let inhibited = CohortSpec::adults().with_inhibitor("ketoconazole", 0.5).sample(&mut ChaCha8Rng::seed_from_u64(1));
+        assert!(inhibited.activity(CypIsoform::Cyp3a4) < 0.05);
+        assert!(slower(inhibited));
+
+        // Renal function only matters for renally excreted drugs
+        let renal = trial.clone().with_pathways(ClearancePathways::default().with_renal_fraction(0.8));
+        let impaired = Covariates { serum_creatinine_mg_dl: 3.0, ..Covariates::typical() };
+        assert!(renal.individual_pk(&impaired, [0.0; 3]).half_life_h > 2.0 * pk.half_life_h);
+
+        // Patients are reproducible from the seed, independently of each other
+        assert_eq!(trial.patient(3).covariates, trial.patient(3).covariates);
+        assert_eq!(trial.patient(3).pk.clearance_l_h_kg, trial.patient(3).pk.clearance_l_h_kg);
+        assert_ne!(trial.patient(3).eta, trial.patient(4).eta);
+    }
+
+    #[test]
+    fn test_virtual_trial_summaries() {
+        let alprazolam = trial("alprazolam", DosingRegimen::repeated(1.0, RouteOfAdministration::Oral, 8.0, 6));
+        let result = alprazolam.run(200);
+        assert_eq!(result.len(), 200);
+
+        // Percentile bands are ordered and widen with variability
+        let bands = result.percentiles(&[5.0, 50.0, 95.0]);
+        let peak = bands.plasma_mg_l.iter().map(|curve| curve.iter().copied().fold(0.0, f64::max)).collect::<Vec<_>>();
+        assert!(peak[0] < peak[1] && peak[1] < peak[2]);
+        let narrow = alprazolam.clone().with_variability(Variability::none()).run(200).percentiles(&[5.0, 95.0]);
+        let spread = |b: &ConcentrationPercentiles| b.plasma_mg_l[1].last().unwrap() / b.plasma_mg_l[0].last().unwrap();
+        assert!(spread(&bands) > spread(&narrow));
+
+        // What fraction of elderly, CYP3A4-inhibited patients exceed 80%
+        // GABA_A occupancy, compared with adults on alprazolam alone?
+        let ki_um = DrugDatabase::new().get("alprazolam").unwrap().ki_nm / 1000.0;
+        let target = AttainmentTarget::PeakOccupancy { ki_um, threshold: 0.8 };
+        let elderly = alprazolam.clone().with_cohort(CohortSpec::elderly().with_inhibitor("ketoconazole", 0.1));
+        let inhibited = elderly.run(200);
+        // The cohort's inhibition is computed once per run, as for a single patient
+        assert_eq!(inhibited.outcomes[7].patient.covariates, elderly.patient(7).covariates);
+        let at_risk = inhibited.probability_of_target_attainment(&target);
+        assert!(at_risk > result.probability_of_target_attainment(&target) + 0.3);
+        assert!(inhibited.subgroup(|o| o.patient.covariates.age >= 80.0).len() < inhibited.len());
+
+        // Adverse events follow exposure and risk factors
+        let diazepam = trial("diazepam", DosingRegimen::repeated(10.0, RouteOfAdministration::Oral, 12.0, 4));
+        let adults = diazepam.run(100).adverse_event_rates();
+        let elderly = diazepam.with_cohort(CohortSpec::elderly().with_inhibitor("ketoconazole", 0.1)).run(100);
+        assert!(elderly.adverse_event_rates()["Somnolence"] > adults["Somnolence"]);
+    }
+}
@@ -37,6 +37,7 @@
 
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
+use crate::library::DrugLibrary;
 
 /// Glutathione state in hepatocyte
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -419,95 +420,9 @@ pub struct DrugReactiveProfile {
 }
 
 impl ReactiveMetaboliteDatabase {
+    /// Create database with the built-in drug library's profiles
     pub fn new() -> Self {
-        let mut db = Self::default();
-
-        // Acetaminophen - NAPQI
-        db.drugs.insert("acetaminophen".to_string(), DrugReactiveProfile {
-            drug: "acetaminophen".to_string(),
-            metabolites: vec![ReactiveMetabolite {
-                parent: "acetaminophen".to_string(),
-                name: "NAPQI".to_string(),
-                half_life_s: 0.1,
-                gsh_reactivity: 100.0,
-                protein_reactivity: 10.0,
-                is_epoxide: false,
-                is_quinone: true,
-            }],
-            fraction_bioactivated: 0.05,
-            cyp_pathway: "CYP2E1, CYP3A4".to_string(),
-            risk_category: "High (dose-dependent hepatotoxicity)".to_string(),
-        });
-
-        // Carbamazepine - epoxide
-        db.drugs.insert("carbamazepine".to_string(), DrugReactiveProfile {
-            drug: "carbamazepine".to_string(),
-            metabolites: vec![ReactiveMetabolite {
-                parent: "carbamazepine".to_string(),
-                name: "carbamazepine-10,11-epoxide".to_string(),
-                half_life_s: 60.0,  // Longer-lived
-                gsh_reactivity: 10.0,
-                protein_reactivity: 1.0,
-                is_epoxide: true,
-                is_quinone: false,
-            }],
-            fraction_bioactivated: 0.30,
-            cyp_pathway: "CYP3A4".to_string(),
-            risk_category: "Moderate (idiosyncratic reactions)".to_string(),
-        });
-
-        // Valproic acid - reactive metabolites
-        db.drugs.insert("valproate".to_string(), DrugReactiveProfile {
-            drug: "valproate".to_string(),
-            metabolites: vec![ReactiveMetabolite {
-                parent: "valproate".to_string(),
-                name: "4-ene-valproic acid".to_string(),
-                half_life_s: 120.0,
-                gsh_reactivity: 5.0,
-                protein_reactivity: 2.0,
-                is_epoxide: false,
-                is_quinone: false,
-            }],
-            fraction_bioactivated: 0.02,
-            cyp_pathway: "CYP2C9, CYP2A6".to_string(),
-            risk_category: "Low-moderate (rare hepatotoxicity)".to_string(),
-        });
-
-        // Phenytoin - arene oxide
-        db.drugs.insert("phenytoin".to_string(), DrugReactiveProfile {
-            drug: "phenytoin".to_string(),
-            metabolites: vec![ReactiveMetabolite {
-                parent: "phenytoin".to_string(),
-                name: "phenytoin arene oxide".to_string(),
-                half_life_s: 0.5,
-                gsh_reactivity: 50.0,
-                protein_reactivity: 5.0,
-                is_epoxide: true,
-                is_quinone: false,
-            }],
-            fraction_bioactivated: 0.05,
-            cyp_pathway: "CYP2C9, CYP2C19".to_string(),
-            risk_category: "Moderate (hypersensitivity)".to_string(),
-        });
-
-        // Isoniazid - hydrazine
-        db.drugs.insert("isoniazid".to_string(), DrugReactiveProfile {
-            drug: "isoniazid".to_string(),
-            metabolites: vec![ReactiveMetabolite {
-                parent: "isoniazid".to_string(),
-                name: "hydrazine".to_string(),
-                half_life_s: 300.0,
-                gsh_reactivity: 20.0,
-                protein_reactivity: 3.0,
-                is_epoxide: false,
-                is_quinone: false,
-            }],
-            fraction_bioactivated: 0.10,
-            cyp_pathway: "NAT2 acetylation".to_string(),
-            risk_category: "Moderate (slow acetylators at risk)".to_string(),
-        });
-
-        db
+        DrugLibrary::builtin().reactive_metabolite_database()
     }
 
     pub fn get(&self, drug: &str) -> Option<&DrugReactiveProfile> {
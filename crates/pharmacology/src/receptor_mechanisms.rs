@@ -18,6 +18,7 @@
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
 use thiserror::Error;
+use crate::library::DrugLibrary;
 
 /// Error types for receptor mechanisms
 #[derive(Debug, Error)]
@@ -59,6 +60,56 @@ impl BindingSite {
             BindingSite::PicrotoxinSite => 0.0, // Antagonist
         }
     }
+
+    /// Share of the modulation expressed as a slower IPSC decay rather than
+    /// a larger amplitude. Benzodiazepines mainly raise opening frequency;
+    /// barbiturates, anesthetics and neurosteroids prolong channel openings.
+    pub fn decay_share(&self) -> f64 {
+        match self {
+            BindingSite::BzSite => 0.3,
+            BindingSite::AnestheticSite => 0.8,
+            BindingSite::BarbituraSite => 0.9,
+            BindingSite::NeurosteroidSite => 0.7,
+            BindingSite::GabaSite => 0.0,
+            BindingSite::PicrotoxinSite => 0.0,
+        }
+    }
+}
+
+/// Drug effect on synaptic GABA_A currents
+///
+/// The product `amplitude * decay` is the receptor modulation factor, i.e.
+/// the scaling of the charge carried by each IPSC.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct IpscModulation {
+    /// Scaling of the peak conductance
+    pub amplitude: f64,
+    /// Scaling of the decay time constant
+    pub decay: f64,
+}
+
+impl Default for IpscModulation {
+    fn default() -> Self {
+        Self { amplitude: 1.0, decay: 1.0 }
+    }
+}
+
+impl IpscModulation {
+    /// Split a modulation factor between amplitude and decay as the binding
+    /// site dictates
+    pub fn from_modulation(modulation: f64, site: BindingSite) -> Self {
+        let modulation = modulation.max(1e-3);
+        let share = site.decay_share();
+        Self {
+            amplitude: modulation.powf(1.0 - share),
+            decay: modulation.powf(share),
+        }
+    }
+
+    /// Charge carried per IPSC relative to the drug-free receptor
+    pub fn charge(&self) -> f64 {
+        self.amplitude * self.decay
+    }
 }
 
 /// Types of pharmacological effects
@@ -70,6 +121,13 @@ pub enum EffectType {
     MuscleRelaxation,
     Anticonvulsant,
     Anesthesia,
+    Analgesia,
+    RespiratoryDepression,
+    Dissociation,
+    Antipsychotic,
+    ExtrapyramidalSymptoms,
+    Psychedelic,
+    Arousal,
 }
 
 /// Molecular and pharmacological properties of a drug
@@ -156,263 +214,14 @@ impl Default for DrugDatabase {
 impl DrugDatabase {
     /// Create database with all validated drug profiles
     pub fn new() -> Self {
-        let mut db = Self {
-            profiles: HashMap::new(),
-        };
-        db.init_benzodiazepines();
-        db.init_anesthetics();
-        db.init_barbiturates();
-        db.init_z_drugs();
-        db
+        DrugLibrary::builtin().drug_database()
     }
 
-    fn init_benzodiazepines(&mut self) {
-        // Diazepam - classic anxiolytic
-        self.add(
-            DrugMolecularProfile::new("diazepam", BindingSite::BzSite, 15.0, 0.55)
-                .with_allosteric(1.2)
-                .with_effect(EffectType::Anxiolysis, 0.8)
-                .with_effect(EffectType::Sedation, 0.5)
-                .with_effect(EffectType::MuscleRelaxation, 0.6)
-                .with_effect(EffectType::Anticonvulsant, 0.7)
-                .with_effect(EffectType::Amnesia, 0.4)
-                .with_effect(EffectType::Anesthesia, 0.1)
-                .with_mw(284.74)
-                .with_log_p(2.82),
-        );
-
-        // Alprazolam - high-potency anxiolytic
-        self.add(
-            DrugMolecularProfile::new("alprazolam", BindingSite::BzSite, 5.0, 0.55)
-                .with_allosteric(1.2)
-                .with_effect(EffectType::Anxiolysis, 0.9)
-                .with_effect(EffectType::Sedation, 0.4)
-                .with_effect(EffectType::MuscleRelaxation, 0.3)
-                .with_effect(EffectType::Anticonvulsant, 0.5)
-                .with_effect(EffectType::Amnesia, 0.5)
-                .with_effect(EffectType::Anesthesia, 0.1)
-                .with_mw(308.77)
-                .with_log_p(2.12),
-        );
-
-        // Clonazepam - anticonvulsant
-        self.add(
-            DrugMolecularProfile::new("clonazepam", BindingSite::BzSite, 2.0, 0.55)
-                .with_allosteric(1.2)
-                .with_effect(EffectType::Anticonvulsant, 0.95)
-                .with_effect(EffectType::Anxiolysis, 0.7)
-                .with_effect(EffectType::Sedation, 0.6)
-                .with_effect(EffectType::MuscleRelaxation, 0.5)
-                .with_effect(EffectType::Amnesia, 0.3)
-                .with_effect(EffectType::Anesthesia, 0.1)
-                .with_mw(315.71)
-                .with_log_p(2.41),
-        );
-
-        // Midazolam - procedural sedation
-        self.add(
-            DrugMolecularProfile::new("midazolam", BindingSite::BzSite, 6.0, 0.65)
-                .with_allosteric(1.3)
-                .with_effect(EffectType::Amnesia, 0.95)
-                .with_effect(EffectType::Sedation, 0.8)
-                .with_effect(EffectType::Anxiolysis, 0.7)
-                .with_effect(EffectType::Anticonvulsant, 0.6)
-                .with_effect(EffectType::MuscleRelaxation, 0.3)
-                .with_effect(EffectType::Anesthesia, 0.2)
-                .with_mw(325.77)
-                .with_log_p(3.89),
-        );
-
-        // Lorazepam - status epilepticus
-        self.add(
-            DrugMolecularProfile::new("lorazepam", BindingSite::BzSite, 3.0, 0.60)
-                .with_allosteric(1.2)
-                .with_effect(EffectType::Anxiolysis, 0.85)
-                .with_effect(EffectType::Sedation, 0.7)
-                .with_effect(EffectType::Amnesia, 0.8)
-                .with_effect(EffectType::Anticonvulsant, 0.9)
-                .with_effect(EffectType::MuscleRelaxation, 0.5)
-                .with_effect(EffectType::Anesthesia, 0.15)
-                .with_mw(321.16)
-                .with_log_p(2.39),
-        );
-
-        // Bromazepam
-        self.add(
-            DrugMolecularProfile::new("bromazepam", BindingSite::BzSite, 20.0, 0.55)
-                .with_allosteric(1.2)
-                .with_effect(EffectType::Anxiolysis, 0.85)
-                .with_effect(EffectType::Sedation, 0.5)
-                .with_effect(EffectType::MuscleRelaxation, 0.5)
-                .with_effect(EffectType::Anticonvulsant, 0.4)
-                .with_effect(EffectType::Amnesia, 0.3)
-                .with_effect(EffectType::Anesthesia, 0.1)
-                .with_mw(316.15)
-                .with_log_p(2.05),
-        );
-
-        // Triazolam - hypnotic
-        self.add(
-            DrugMolecularProfile::new("triazolam", BindingSite::BzSite, 4.0, 0.60)
-                .with_allosteric(1.25)
-                .with_effect(EffectType::Sedation, 0.9)
-                .with_effect(EffectType::Amnesia, 0.85)
-                .with_effect(EffectType::Anxiolysis, 0.6)
-                .with_effect(EffectType::Anticonvulsant, 0.4)
-                .with_effect(EffectType::MuscleRelaxation, 0.3)
-                .with_effect(EffectType::Anesthesia, 0.1)
-                .with_mw(343.22)
-                .with_log_p(2.42),
-        );
-
-        // Temazepam - hypnotic
-        self.add(
-            DrugMolecularProfile::new("temazepam", BindingSite::BzSite, 8.0, 0.55)
-                .with_allosteric(1.2)
-                .with_effect(EffectType::Sedation, 0.85)
-                .with_effect(EffectType::Anxiolysis, 0.6)
-                .with_effect(EffectType::Amnesia, 0.5)
-                .with_effect(EffectType::Anticonvulsant, 0.3)
-                .with_effect(EffectType::MuscleRelaxation, 0.4)
-                .with_effect(EffectType::Anesthesia, 0.1)
-                .with_mw(300.75)
-                .with_log_p(2.19),
-        );
-    }
-
-    fn init_anesthetics(&mut self) {
-        // Propofol - IV anesthetic
-        self.add(
-            DrugMolecularProfile::new("propofol", BindingSite::AnestheticSite, 3500.0, 0.95)
-                .with_hill(1.2)
-                .with_allosteric(2.0)
-                .with_effect(EffectType::Anesthesia, 0.95)
-                .with_effect(EffectType::Sedation, 0.9)
-                .with_effect(EffectType::Amnesia, 0.85)
-                .with_effect(EffectType::Anticonvulsant, 0.6)
-                .with_effect(EffectType::Anxiolysis, 0.3)
-                .with_effect(EffectType::MuscleRelaxation, 0.2)
-                .with_mw(178.27)
-                .with_log_p(3.79),
-        );
-
-        // Etomidate - hemodynamically stable anesthetic
-        self.add(
-            DrugMolecularProfile::new("etomidate", BindingSite::AnestheticSite, 2000.0, 0.90)
-                .with_hill(1.1)
-                .with_allosteric(1.8)
-                .with_effect(EffectType::Anesthesia, 0.90)
-                .with_effect(EffectType::Sedation, 0.85)
-                .with_effect(EffectType::Amnesia, 0.80)
-                .with_effect(EffectType::Anticonvulsant, 0.5)
-                .with_effect(EffectType::Anxiolysis, 0.2)
-                .with_effect(EffectType::MuscleRelaxation, 0.1)
-                .with_mw(244.29)
-                .with_log_p(2.49),
-        );
-
-        // Sevoflurane - volatile anesthetic
-        self.add(
-            DrugMolecularProfile::new("sevoflurane", BindingSite::AnestheticSite, 260000.0, 0.90)
-                .with_allosteric(2.0)
-                .with_effect(EffectType::Anesthesia, 0.95)
-                .with_effect(EffectType::Sedation, 0.95)
-                .with_effect(EffectType::Amnesia, 0.9)
-                .with_effect(EffectType::Anticonvulsant, 0.5)
-                .with_effect(EffectType::MuscleRelaxation, 0.3)
-                .with_effect(EffectType::Anxiolysis, 0.2)
-                .with_mw(200.05)
-                .with_log_p(2.42),
-        );
-
-        // Isoflurane - volatile anesthetic
-        self.add(
-            DrugMolecularProfile::new("isoflurane", BindingSite::AnestheticSite, 270000.0, 0.90)
-                .with_allosteric(2.0)
-                .with_effect(EffectType::Anesthesia, 0.95)
-                .with_effect(EffectType::Sedation, 0.95)
-                .with_effect(EffectType::Amnesia, 0.85)
-                .with_effect(EffectType::Anticonvulsant, 0.4)
-                .with_effect(EffectType::MuscleRelaxation, 0.3)
-                .with_effect(EffectType::Anxiolysis, 0.2)
-                .with_mw(184.49)
-                .with_log_p(2.35),
-        );
-
-        // Desflurane - volatile anesthetic
-        self.add(
-            DrugMolecularProfile::new("desflurane", BindingSite::AnestheticSite, 380000.0, 0.85)
-                .with_allosteric(1.9)
-                .with_effect(EffectType::Anesthesia, 0.90)
-                .with_effect(EffectType::Sedation, 0.90)
-                .with_effect(EffectType::Amnesia, 0.8)
-                .with_effect(EffectType::Anticonvulsant, 0.35)
-                .with_effect(EffectType::MuscleRelaxation, 0.25)
-                .with_effect(EffectType::Anxiolysis, 0.15)
-                .with_mw(168.04)
-                .with_log_p(2.08),
-        );
-    }
-
-    fn init_barbiturates(&mut self) {
-        // Thiopental - IV anesthetic (rapid induction)
-        self.add(
-            DrugMolecularProfile::new("thiopental", BindingSite::BarbituraSite, 5000.0, 0.95)
-                .with_hill(1.1)
-                .with_allosteric(2.2)
-                .with_effect(EffectType::Anesthesia, 0.95)
-                .with_effect(EffectType::Sedation, 0.95)
-                .with_effect(EffectType::Amnesia, 0.85)
-                .with_effect(EffectType::Anticonvulsant, 0.8)
-                .with_effect(EffectType::MuscleRelaxation, 0.2)
-                .with_effect(EffectType::Anxiolysis, 0.3)
-                .with_mw(242.34)
-                .with_log_p(2.85),
-        );
-
-        // Phenobarbital - anticonvulsant
-        self.add(
-            DrugMolecularProfile::new("phenobarbital", BindingSite::BarbituraSite, 15000.0, 0.70)
-                .with_allosteric(1.8)
-                .with_effect(EffectType::Anticonvulsant, 0.95)
-                .with_effect(EffectType::Sedation, 0.7)
-                .with_effect(EffectType::Anesthesia, 0.4)
-                .with_effect(EffectType::Amnesia, 0.4)
-                .with_effect(EffectType::Anxiolysis, 0.5)
-                .with_effect(EffectType::MuscleRelaxation, 0.2)
-                .with_mw(232.24)
-                .with_log_p(1.47),
-        );
-    }
-
-    fn init_z_drugs(&mut self) {
-        // Zolpidem - alpha1-selective hypnotic
-        self.add(
-            DrugMolecularProfile::new("zolpidem", BindingSite::BzSite, 10.0, 0.60)
-                .with_allosteric(1.3)
-                .with_effect(EffectType::Sedation, 0.95)
-                .with_effect(EffectType::Amnesia, 0.7)
-                .with_effect(EffectType::Anxiolysis, 0.3)
-                .with_effect(EffectType::Anticonvulsant, 0.2)
-                .with_effect(EffectType::MuscleRelaxation, 0.2)
-                .with_effect(EffectType::Anesthesia, 0.1)
-                .with_mw(307.39)
-                .with_log_p(3.0),
-        );
-
-        // Zaleplon - ultra-short-acting hypnotic
-        self.add(
-            DrugMolecularProfile::new("zaleplon", BindingSite::BzSite, 12.0, 0.55)
-                .with_allosteric(1.2)
-                .with_effect(EffectType::Sedation, 0.9)
-                .with_effect(EffectType::Amnesia, 0.6)
-                .with_effect(EffectType::Anxiolysis, 0.25)
-                .with_effect(EffectType::Anticonvulsant, 0.15)
-                .with_effect(EffectType::MuscleRelaxation, 0.15)
-                .with_effect(EffectType::Anesthesia, 0.05)
-                .with_mw(305.34)
-                .with_log_p(1.23),
-        );
+    /// Create an empty database
+    pub fn empty() -> Self {
+        Self {
+            profiles: HashMap::new(),
+        }
     }
 
     /// Add a drug profile to the database
@@ -714,6 +523,15 @@ impl MechanisticGabaAReceptor {
     pub fn site_occupancy(&self, site: BindingSite) -> f64 {
         *self.site_occupancy.get(&site).unwrap_or(&0.0)
     }
+
+    /// Effect on synaptic currents of `profile` at its site's present
+    /// occupancy (bind it first with `bind_drug`)
+    pub fn ipsc_modulation(&self, profile: &DrugMolecularProfile) -> IpscModulation {
+        let site = profile.binding_site;
+        let occupancy = self.site_occupancy(site);
+        let modulation = 1.0 + profile.intrinsic_efficacy * occupancy * profile.allosteric_factor;
+        IpscModulation::from_modulation(modulation, site)
+    }
 }
 
 /// Dual-mode GABA_A receptor model
@@ -1087,6 +905,26 @@ mod tests {
         assert!(receptor.get_sedation_percentage() > 30.0);
     }
 
+    #[test]
+    fn test_ipsc_modulation_by_site() {
+        let db = DrugDatabase::new();
+        let mut receptor = MechanisticGabaAReceptor::new();
+        assert_eq!(receptor.ipsc_modulation(db.get("propofol").unwrap()), IpscModulation::default());
+
+        // Same charge as the receptor modulation, carried mostly by a slower
+        // decay for propofol and by a larger amplitude for alprazolam
+        let propofol = db.get("propofol").unwrap();
+        let modulation = receptor.bind_drug(propofol, 5.0);
+        let ipsc = receptor.ipsc_modulation(propofol);
+        assert!((ipsc.charge() - modulation).abs() < 1e-12);
+        assert!(ipsc.decay > ipsc.amplitude && ipsc.amplitude > 1.0);
+
+        let alprazolam = db.get("alprazolam").unwrap();
+        receptor.bind_drug(alprazolam, 0.05);
+        let ipsc = receptor.ipsc_modulation(alprazolam);
+        assert!(ipsc.amplitude > ipsc.decay && ipsc.decay > 1.0);
+    }
+
     #[test]
     fn test_unified_model_database_mode() {
         let mut model = UnifiedGabaAModel::database_mode();
@@ -0,0 +1,732 @@
+//! Multi-Receptor Pharmacodynamics
+//! ===============================
+//!
+//! Drug action on the receptors beyond GABA_A: ionotropic glutamate (NMDA,
+//! AMPA), dopamine D2, serotonin 5-HT2A, mu-opioid, alpha2-adrenergic,
+//! nicotinic acetylcholine and histamine H1 receptors.
+//!
+//! Each [`TargetBinding`] binds one [`ReceptorTarget`] at one [`TargetSite`]:
+//!
+//! - Orthosteric ligands compete with each other and with the endogenous
+//!   agonist, whose tone `A/K_A` is the target's resting level of activation.
+//!   With `x_i = (C_i/K_i)^n_i` the activation is
+//!   `(tone + sum x_i e_i) / (1 + tone + sum x_i)`, so full agonists (`e = 1`)
+//!   raise it, antagonists (`e = 0`) lower it and partial agonists pull it
+//!   towards their efficacy.
+//! - Allosteric modulators bind independently (`phi = x/(1+x)`) and scale the
+//!   orthosteric activation by `1 + phi(alpha - 1)`, plus any direct
+//!   efficacy of their own.
+//! - Pore blockers (ketamine, memantine) occlude the open channel, scaling
+//!   the activation by `1 - phi`.
+//!
+//! GABA_A drugs follow
+//! [`MechanisticGabaAReceptor`](crate::receptor_mechanisms::MechanisticGabaAReceptor).
+//! Drugs at the same [`BindingSite`] compete for it, each taking the share
+//! `x_i / (1 + sum x)`, and the site's modulation is `1 + sum share_i e_i a_i`.
+//! Drugs at different sites multiply.
+//!
+//! [`MultiReceptorSystem`] holds the drugs on board and translates the
+//! activation of every target, relative to its drug-free baseline, into the
+//! [`SynapticModulation`] of each [`SynapseType`], the [`ChannelModulation`]
+//! of the neurons' membrane conductances, and an effect profile.
+
+use std::collections::HashMap;
+
+use neurons::ChannelModulation;
+use serde::{Deserialize, Serialize};
+use synapses::{SynapseType, SynapticModulation};
+
+use crate::library::DrugLibrary;
+use crate::receptor_mechanisms::{BindingSite, DrugMolecularProfile, EffectType, IpscModulation};
+
+/// Receptors targeted besides GABA_A
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum ReceptorTarget {
+    /// NMDA glutamate receptor (ionotropic)
+    Nmda,
+    /// AMPA glutamate receptor (ionotropic)
+    Ampa,
+    /// Gi-coupled dopamine receptor
+    D2,
+    /// Gq-coupled serotonin receptor
+    Ht2a,
+    /// Gi-coupled mu-opioid receptor (OPRM1)
+    MuOpioid,
+    /// Gi-coupled presynaptic adrenoceptor
+    Alpha2,
+    /// Nicotinic acetylcholine receptor (ionotropic, mostly presynaptic)
+    Nicotinic,
+    /// Gq-coupled histamine receptor
+    H1,
+}
+
+impl ReceptorTarget {
+    /// All targets
+    pub const ALL: [Self; 8] = [
+        Self::Nmda,
+        Self::Ampa,
+        Self::D2,
+        Self::Ht2a,
+        Self::MuOpioid,
+        Self::Alpha2,
+        Self::Nicotinic,
+        Self::H1,
+    ];
+
+    /// Conventional receptor name
+    pub fn name(&self) -> &'static str {
+        match self {
+            Self::Nmda => "NMDA",
+            Self::Ampa => "AMPA",
+            Self::D2 => "D2",
+            Self::Ht2a => "5-HT2A",
+            Self::MuOpioid => "OPRM1",
+            Self::Alpha2 => "alpha2",
+            Self::Nicotinic => "nAChR",
+            Self::H1 => "H1",
+        }
+    }
+
+    /// Parse a receptor name as used in the clinical literature
+    pub fn from_name(name: &str) -> Option<Self> {
+        let name = name.to_ascii_lowercase();
+        Self::ALL.into_iter().find(|t| t.name().eq_ignore_ascii_case(&name)).or(match name.as_str() {
+            "5ht2a" | "ht2a" => Some(Self::Ht2a),
+            "mu" | "mu-opioid" | "mor" => Some(Self::MuOpioid),
+            "alpha2a" | "adra2a" => Some(Self::Alpha2),
+            "nicotinic" | "alpha4beta2" => Some(Self::Nicotinic),
+            "hrh1" => Some(Self::H1),
+            _ => None,
+        })
+    }
+
+    /// Endogenous agonist tone `A/K_A` in the resting brain. D2, 5-HT2A and
+    /// alpha2 match the resting occupancy of `NeuromodulatorState`; the
+    /// ionotropic receptors sit at half activation during synaptic
+    /// transmission.
+    pub fn endogenous_tone(&self) -> f64 {
+        match self {
+            Self::Nmda | Self::Ampa => 1.0,
+            Self::D2 => 5.0,
+            Self::Ht2a => 2.0,
+            Self::MuOpioid => 0.1,
+            Self::Alpha2 => 2.0,
+            Self::Nicotinic => 0.2,
+            Self::H1 => 1.0,
+        }
+    }
+
+    /// Synapse type whose postsynaptic receptor this is
+    pub fn synapse_type(&self) -> Option<SynapseType> {
+        match self {
+            Self::Nmda => Some(SynapseType::NMDA),
+            Self::Ampa => Some(SynapseType::AMPA),
+            Self::D2 => Some(SynapseType::Dopamine),
+            Self::Ht2a => Some(SynapseType::Serotonin),
+            _ => None,
+        }
+    }
+
+    /// Effects of activation above baseline (effect, weight at full agonism)
+    fn agonist_effects(&self) -> &'static [(EffectType, f64)] {
+        use EffectType::*;
+        match self {
+            Self::Nmda => &[],
+            Self::Ampa => &[(Arousal, 0.4)],
+            Self::D2 => &[(Arousal, 0.3)],
+            Self::Ht2a => &[(Psychedelic, 0.9)],
+            Self::MuOpioid => &[(Analgesia, 0.9), (RespiratoryDepression, 0.7), (Sedation, 0.5)],
+            Self::Alpha2 => &[(Sedation, 0.7), (Anxiolysis, 0.3), (Analgesia, 0.3)],
+            Self::Nicotinic => &[(Arousal, 0.5)],
+            Self::H1 => &[(Arousal, 0.4)],
+        }
+    }
+
+    /// Effects of activation below baseline (effect, weight at full block)
+    fn antagonist_effects(&self) -> &'static [(EffectType, f64)] {
+        use EffectType::*;
+        match self {
+            Self::Nmda => &[(Dissociation, 0.9), (Anesthesia, 0.6), (Amnesia, 0.6), (Analgesia, 0.5)],
+            Self::Ampa => &[(Anticonvulsant, 0.6), (Sedation, 0.5), (MuscleRelaxation, 0.3)],
+            Self::D2 => &[(Antipsychotic, 0.9), (ExtrapyramidalSymptoms, 0.7), (Sedation, 0.2)],
+            Self::Ht2a => &[(Sedation, 0.2)],
+            Self::MuOpioid => &[],
+            Self::Alpha2 => &[(Arousal, 0.3)],
+            Self::Nicotinic => &[(Amnesia, 0.2)],
+            Self::H1 => &[(Sedation, 0.6)],
+        }
+    }
+}
+
+/// Where on the receptor a drug binds
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum TargetSite {
+    /// Agonist site, competing with the endogenous ligand
+    Orthosteric,
+    /// Separate modulatory site
+    Allosteric,
+    /// Channel pore (uncompetitive open-channel block)
+    Pore,
+}
+
+/// Binding of a drug to one receptor target
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct TargetBinding {
+    pub target: ReceptorTarget,
+    pub site: TargetSite,
+    /// Binding affinity in nM
+    pub ki_nm: f64,
+    /// Intrinsic efficacy (0 = antagonist, 1 = full agonist)
+    pub efficacy: f64,
+    /// Hill coefficient
+    pub hill: f64,
+    /// Allosteric cooperativity alpha (>1 = PAM, <1 = NAM; allosteric only)
+    pub cooperativity: f64,
+}
+
+impl TargetBinding {
+    /// Orthosteric ligand of `target`
+    pub fn new(target: ReceptorTarget, ki_nm: f64, efficacy: f64) -> Self {
+        Self { target, site: TargetSite::Orthosteric, ki_nm, efficacy, hill: 1.0, cooperativity: 1.0 }
+    }
+
+    /// Allosteric modulator of `target` with cooperativity `alpha`
+    pub fn allosteric(target: ReceptorTarget, ki_nm: f64, cooperativity: f64) -> Self {
+        Self { site: TargetSite::Allosteric, cooperativity, ..Self::new(target, ki_nm, 0.0) }
+    }
+
+    /// Open-channel blocker of `target`
+    pub fn pore_blocker(target: ReceptorTarget, ki_nm: f64) -> Self {
+        Self { site: TargetSite::Pore, ..Self::new(target, ki_nm, 0.0) }
+    }
+
+    /// Builder pattern: set Hill coefficient
+    pub fn with_hill(mut self, hill: f64) -> Self {
+        self.hill = hill;
+        self
+    }
+
+    /// Builder pattern: set intrinsic efficacy
+    pub fn with_efficacy(mut self, efficacy: f64) -> Self {
+        self.efficacy = efficacy;
+        self
+    }
+
+    /// Normalized occupancy term `(C/Ki)^n` at `concentration_um`
+    pub fn binding_ratio(&self, concentration_um: f64) -> f64 {
+        (concentration_um.max(0.0) / (self.ki_nm / 1000.0)).powf(self.hill)
+    }
+}
+
+/// Receptor targets of one drug: its GABA_A profile, if any, and its
+/// bindings to the other receptors
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct MultiReceptorProfile {
+    /// Drug name (lowercase)
+    pub name: String,
+    /// GABA_A binding
+    pub gaba_a: Option<DrugMolecularProfile>,
+    /// Bindings to the other receptors
+    pub bindings: Vec<TargetBinding>,
+}
+
+impl MultiReceptorProfile {
+    /// A drug without targets yet
+    pub fn new(name: &str) -> Self {
+        Self { name: name.to_lowercase(), gaba_a: None, bindings: Vec::new() }
+    }
+
+    /// Builder pattern: add a binding
+    pub fn with_binding(mut self, binding: TargetBinding) -> Self {
+        self.bindings.push(binding);
+        self
+    }
+
+    /// Builder pattern: set the GABA_A profile
+    pub fn with_gaba_a(mut self, profile: DrugMolecularProfile) -> Self {
+        self.gaba_a = Some(profile);
+        self
+    }
+
+    /// Bindings to `target`
+    pub fn bindings_to(&self, target: ReceptorTarget) -> impl Iterator<Item = &TargetBinding> {
+        self.bindings.iter().filter(move |b| b.target == target)
+    }
+
+    /// Whether the drug binds `target`
+    pub fn binds(&self, target: ReceptorTarget) -> bool {
+        self.bindings_to(target).next().is_some()
+    }
+}
+
+impl From<DrugMolecularProfile> for MultiReceptorProfile {
+    fn from(profile: DrugMolecularProfile) -> Self {
+        Self::new(&profile.name).with_gaba_a(profile)
+    }
+}
+
+/// Database of multi-receptor profiles
+pub struct TargetDatabase {
+    profiles: HashMap<String, MultiReceptorProfile>,
+}
+
+impl Default for TargetDatabase {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl TargetDatabase {
+    /// Database of every drug in the built-in library with receptor targets
+    pub fn new() -> Self {
+        DrugLibrary::builtin().target_database()
+    }
+
+    /// Database without profiles
+    pub fn empty() -> Self {
+        Self { profiles: HashMap::new() }
+    }
+
+    /// Add a profile
+    pub fn add(&mut self, profile: MultiReceptorProfile) {
+        self.profiles.insert(profile.name.clone(), profile);
+    }
+
+    /// Get profile by name
+    pub fn get(&self, name: &str) -> Option<&MultiReceptorProfile> {
+        self.profiles.get(&name.to_lowercase())
+    }
+
+    /// Check if drug exists
+    pub fn contains(&self, name: &str) -> bool {
+        self.profiles.contains_key(&name.to_lowercase())
+    }
+
+    /// Names of drugs binding `target`
+    pub fn ligands_of(&self, target: ReceptorTarget) -> Vec<&str> {
+        let mut names: Vec<&str> =
+            self.profiles.values().filter(|p| p.binds(target)).map(|p| p.name.as_str()).collect();
+        names.sort_unstable();
+        names
+    }
+}
+
+/// State of one receptor target
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct TargetResponse {
+    pub target: ReceptorTarget,
+    /// Fraction of receptors with a drug bound at any site
+    pub occupancy: f64,
+    /// Fraction of receptors active (0-1)
+    pub activation: f64,
+    /// Drug-free activation
+    pub baseline: f64,
+}
+
+impl TargetResponse {
+    /// Activation relative to baseline
+    pub fn relative(&self) -> f64 {
+        self.scaled(|f| f)
+    }
+
+    /// Ratio of a downstream quantity `g(activation)` to its drug-free value
+    fn scaled(&self, g: impl Fn(f64) -> f64) -> f64 {
+        g(self.activation) / g(self.baseline)
+    }
+
+    /// Activation gained above baseline, as a fraction of the headroom
+    pub fn agonism(&self) -> f64 {
+        ((self.activation - self.baseline) / (1.0 - self.baseline)).clamp(0.0, 1.0)
+    }
+
+    /// Activation lost below baseline, as a fraction of the baseline
+    pub fn antagonism(&self) -> f64 {
+        ((self.baseline - self.activation) / self.baseline).clamp(0.0, 1.0)
+    }
+}
+
+/// Receptors of a brain region exposed to any number of drugs
+///
+/// Drugs binding the same target compete for it (orthosteric) or act on it
+/// together (allosteric, pore), so polypharmacy is resolved per receptor
+/// rather than by multiplying single-drug effects.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct MultiReceptorSystem {
+    /// Endogenous tone overriding the targets' defaults
+    tones: HashMap<ReceptorTarget, f64>,
+    /// Drugs on board with their concentrations (uM)
+    drugs: Vec<(MultiReceptorProfile, f64)>,
+}
+
+impl MultiReceptorSystem {
+    /// Drug-free receptors at their default endogenous tone
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Builder pattern: set the endogenous tone `A/K_A` of `target`
+    pub fn with_tone(mut self, target: ReceptorTarget, tone: f64) -> Self {
+        self.tones.insert(target, tone.max(0.0));
+        self
+    }
+
+    /// Endogenous tone of `target`
+    pub fn tone(&self, target: ReceptorTarget) -> f64 {
+        self.tones.get(&target).copied().unwrap_or_else(|| target.endogenous_tone())
+    }
+
+    /// Add a drug at `concentration_um`
+    pub fn bind(&mut self, profile: &MultiReceptorProfile, concentration_um: f64) {
+        self.drugs.push((profile.clone(), concentration_um.max(0.0)));
+    }
+
+    /// Remove all drugs
+    pub fn reset(&mut self) {
+        self.drugs.clear();
+    }
+
+    /// Bindings to `target` with their drugs' concentrations
+    fn ligands(&self, target: ReceptorTarget) -> impl Iterator<Item = (&TargetBinding, f64)> {
+        self.drugs
+            .iter()
+            .flat_map(move |(profile, concentration)| profile.bindings_to(target).map(move |b| (b, *concentration)))
+    }
+
+    /// Denominator of the orthosteric competition at `target`
+    fn orthosteric_partition(&self, target: ReceptorTarget) -> f64 {
+        1.0 + self.tone(target)
+            + self
+                .ligands(target)
+                .filter(|(b, _)| b.site == TargetSite::Orthosteric)
+                .map(|(b, c)| b.binding_ratio(c))
+                .sum::<f64>()
+    }
+
+    /// Occupancy of `target` by one drug
+    pub fn occupancy(&self, drug: &str, target: ReceptorTarget) -> f64 {
+        let partition = self.orthosteric_partition(target);
+        let drug = drug.to_lowercase();
+        let free = self
+            .drugs
+            .iter()
+            .filter(|(profile, _)| profile.name == drug)
+            .flat_map(|(profile, concentration)| profile.bindings_to(target).map(move |b| (b, *concentration)))
+            .map(|(b, c)| {
+                let x = b.binding_ratio(c);
+                match b.site {
+                    TargetSite::Orthosteric => 1.0 - x / partition,
+                    TargetSite::Allosteric | TargetSite::Pore => 1.0 / (1.0 + x),
+                }
+            })
+            .product::<f64>();
+        1.0 - free
+    }
+
+    /// Occupancy and activation of `target` with all drugs bound
+    pub fn response(&self, target: ReceptorTarget) -> TargetResponse {
+        let tone = self.tone(target);
+        let partition = self.orthosteric_partition(target);
+
+        let mut orthosteric_bound = 0.0;
+        let mut agonism = tone;
+        for (binding, concentration) in self.ligands(target).filter(|(b, _)| b.site == TargetSite::Orthosteric) {
+            let x = binding.binding_ratio(concentration);
+            orthosteric_bound += x;
+            agonism += x * binding.efficacy;
+        }
+        let mut activation = agonism / partition;
+        let mut free = 1.0 - orthosteric_bound / partition;
+
+        for (binding, concentration) in self.ligands(target).filter(|(b, _)| b.site != TargetSite::Orthosteric) {
+            let x = binding.binding_ratio(concentration);
+            let phi = x / (1.0 + x);
+            free *= 1.0 - phi;
+            activation = match binding.site {
+                TargetSite::Allosteric => {
+                    let modulated = activation * (1.0 + phi * (binding.cooperativity - 1.0));
+                    modulated + phi * binding.efficacy * (1.0 - modulated).max(0.0)
+                }
+                _ => activation * (1.0 - phi),
+            };
+        }
+
+        TargetResponse {
+            target,
+            occupancy: 1.0 - free,
+            activation: activation.clamp(0.0, 1.0),
+            baseline: tone / (1.0 + tone),
+        }
+    }
+
+    /// Responses of all targets
+    pub fn responses(&self) -> Vec<TargetResponse> {
+        ReceptorTarget::ALL.iter().map(|&t| self.response(t)).collect()
+    }
+
+    /// GABA_A drugs with their occupancy of their binding site. Drugs at
+    /// one site compete: with `x_i = (C_i/Ki_i)^n_i` each occupies
+    /// `x_i / (1 + sum x)`, summed over the drugs at that site.
+    fn gaba_a_occupancies(&self) -> Vec<(&DrugMolecularProfile, f64)> {
+        let ligands: Vec<(&DrugMolecularProfile, f64)> = self
+            .drugs
+            .iter()
+            .filter_map(|(profile, concentration)| profile.gaba_a.as_ref().map(|p| (p, *concentration)))
+            .map(|(p, c)| (p, (c.max(0.0) / (p.ki_nm / 1000.0)).powf(p.hill_coefficient)))
+            .collect();
+        ligands
+            .iter()
+            .map(|&(profile, x)| {
+                let partition = 1.0
+                    + ligands
+                        .iter()
+                        .filter(|(other, _)| other.binding_site == profile.binding_site)
+                        .map(|(_, x)| x)
+                        .sum::<f64>();
+                (profile, x / partition)
+            })
+            .collect()
+    }
+
+    /// Combined effect of the drugs on GABA_A synapses. Drugs at the same
+    /// site share its occupancy, so together they saturate at the site's
+    /// maximum; drugs at different sites multiply.
+    pub fn gaba_a_modulation(&self) -> IpscModulation {
+        let mut sites: Vec<(BindingSite, f64)> = Vec::new();
+        for (profile, occupancy) in self.gaba_a_occupancies() {
+            let gain = profile.intrinsic_efficacy * occupancy * profile.allosteric_factor;
+            match sites.iter_mut().find(|(site, _)| *site == profile.binding_site) {
+                Some((_, modulation)) => *modulation += gain,
+                None => sites.push((profile.binding_site, 1.0 + gain)),
+            }
+        }
+        sites.into_iter().fold(IpscModulation::default(), |total, (site, modulation)| {
+            let ipsc = IpscModulation::from_modulation(modulation, site);
+            IpscModulation { amplitude: total.amplitude * ipsc.amplitude, decay: total.decay * ipsc.decay }
+        })
+    }
+
+    /// Drug action on synapses of `synapse_type`, relative to drug-free
+    ///
+    /// Ionotropic targets scale the conductance with their activation; Gi
+    /// receptors on the terminals (D2 and alpha2 on glutamatergic, D2 and
+    /// mu-opioid on GABAergic) reduce release, presynaptic nicotinic and
+    /// 5-HT2A receptors enhance glutamate release.
+    pub fn synaptic_modulation(&self, synapse_type: SynapseType) -> SynapticModulation {
+        use ReceptorTarget::*;
+        let r = |t| self.response(t);
+        let glutamate_release = r(D2).scaled(|f| 1.0 - 0.3 * f)
+            * r(Alpha2).scaled(|f| 1.0 - 0.3 * f)
+            * r(Nicotinic).scaled(|f| 1.0 + 0.3 * f)
+            * r(Ht2a).scaled(|f| 1.0 + 0.2 * f);
+        let gaba_release = r(D2).scaled(|f| 1.0 - 0.2 * f) * r(MuOpioid).scaled(|f| 1.0 - 0.5 * f);
+
+        match synapse_type {
+            SynapseType::AMPA => {
+                SynapticModulation { release: glutamate_release, conductance: r(Ampa).relative(), decay: 1.0 }
+            }
+            SynapseType::NMDA => {
+                SynapticModulation { release: glutamate_release, conductance: r(Nmda).relative(), decay: 1.0 }
+            }
+            SynapseType::GABAA => {
+                let ipsc = self.gaba_a_modulation();
+                SynapticModulation { release: gaba_release, conductance: ipsc.amplitude, decay: ipsc.decay }
+            }
+            SynapseType::GABAB | SynapseType::Glycine => {
+                SynapticModulation { release: gaba_release, ..SynapticModulation::default() }
+            }
+            SynapseType::Dopamine => SynapticModulation { conductance: r(D2).relative(), ..SynapticModulation::default() },
+            SynapseType::Serotonin => {
+                SynapticModulation { conductance: r(Ht2a).relative(), ..SynapticModulation::default() }
+            }
+        }
+    }
+
+    /// Drug action on the neurons' membrane conductances, relative to
+    /// drug-free: D2 closes Ca2+ channels, mu-opioid opens GIRK, and 5-HT2A
+    /// and H1 close K+ leak (H1 also the M-current). GABA_A modulators
+    /// enhance the tonic current of extrasynaptic GABA_A receptors, which
+    /// reverses near rest and so adds to the leak like the IPSC charge.
+    pub fn channel_modulation(&self) -> ChannelModulation {
+        use ReceptorTarget::*;
+        let r = |t| self.response(t);
+        ChannelModulation {
+            sodium: 1.0,
+            potassium: r(H1).scaled(|f| 1.0 - 0.3 * f),
+            calcium: r(D2).scaled(|f| 1.0 - 0.3 * f),
+            leak: r(Ht2a).scaled(|f| 1.0 - 0.2 * f)
+                * r(H1).scaled(|f| 1.0 - 0.3 * f)
+                * r(MuOpioid).scaled(|f| 1.0 + 0.5 * f)
+                * self.gaba_a_modulation().charge(),
+        }
+    }
+
+    /// Effect strengths (0-1) of all drugs together: independent
+    /// contributions from every target and from GABA_A combine as
+    /// `1 - prod(1 - e)`
+    pub fn effects(&self) -> HashMap<EffectType, f64> {
+        let mut unaffected: HashMap<EffectType, f64> = HashMap::new();
+        let mut add = |effect: EffectType, strength: f64| {
+            *unaffected.entry(effect).or_insert(1.0) *= 1.0 - strength.clamp(0.0, 1.0);
+        };
+
+        for response in self.responses() {
+            let target = response.target;
+            for &(effect, weight) in target.agonist_effects() {
+                add(effect, weight * response.agonism());
+            }
+            for &(effect, weight) in target.antagonist_effects() {
+                add(effect, weight * response.antagonism());
+            }
+        }
+        for (gaba_a, occupancy) in self.gaba_a_occupancies() {
+            for (&effect, &strength) in &gaba_a.effect_profile {
+                add(effect, strength * occupancy);
+            }
+        }
+
+        unaffected
+            .into_iter()
+            .map(|(effect, free)| (effect, 1.0 - free))
+            .filter(|(_, strength)| *strength > 0.0)
+            .collect()
+    }
+
+    /// Strength (0-1) of one effect
+    pub fn effect(&self, effect: EffectType) -> f64 {
+        self.effects().get(&effect).copied().unwrap_or(0.0)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn with(drugs: &[(&str, f64)]) -> MultiReceptorSystem {
+        let db = TargetDatabase::new();
+        let mut system = MultiReceptorSystem::new();
+        for (name, concentration) in drugs {
+            system.bind(db.get(name).unwrap(), *concentration);
+        }
+        system
+    }
+
+    #[test]
+    fn test_orthosteric_competition_and_partial_agonism() {
+        let ki = 1.0;
+        let antagonist = MultiReceptorProfile::new("a").with_binding(TargetBinding::new(ReceptorTarget::D2, ki, 0.0));
+        let partial = MultiReceptorProfile::new("p").with_binding(TargetBinding::new(ReceptorTarget::D2, ki, 0.3));
+
+        let mut system = MultiReceptorSystem::new();
+        let baseline = system.response(ReceptorTarget::D2);
+        assert!((baseline.baseline - 5.0 / 6.0).abs() < 1e-12 && baseline.relative() == 1.0);
+
+        // Dopamine tone shifts the apparent affinity: C = Ki (1 + tone) for half occupancy
+        system.bind(&antagonist, 0.006);
+        assert!((system.occupancy("a", ReceptorTarget::D2) - 0.5).abs() < 1e-9);
+        let blocked = system.response(ReceptorTarget::D2);
+        assert!(blocked.antagonism() > 0.4 && blocked.agonism() == 0.0);
+
+        // Under high tone a partial agonist lowers activation; a second ligand
+        // displaces the first
+        system.reset();
+        system.bind(&partial, 1.0);
+        assert!(system.response(ReceptorTarget::D2).activation < 0.35);
+        let alone = system.occupancy("p", ReceptorTarget::D2);
+        system.bind(&antagonist, 1.0);
+        assert!(system.occupancy("p", ReceptorTarget::D2) < 0.6 * alone);
+    }
+
+    #[test]
+    fn test_channel_block_and_allosteric_modulation() {
+        let ketamine = with(&[("ketamine", 5.0)]);
+        let nmda = ketamine.response(ReceptorTarget::Nmda);
+        assert!(nmda.occupancy > 0.85 && nmda.relative() < 0.15);
+        assert!(ketamine.synaptic_modulation(SynapseType::NMDA).conductance < 0.15);
+        assert_eq!(ketamine.synaptic_modulation(SynapseType::AMPA), SynapticModulation::default());
+        assert!(ketamine.effect(EffectType::Dissociation) > 0.7);
+
+        // Memantine is a weaker, lower-affinity blocker at the same dose
+        let memantine = with(&[("memantine", 1.0)]).response(ReceptorTarget::Nmda);
+        assert!(memantine.relative() > with(&[("ketamine", 1.0)]).response(ReceptorTarget::Nmda).relative());
+
+        let pam = MultiReceptorProfile::new("pam")
+            .with_binding(TargetBinding::allosteric(ReceptorTarget::Ampa, 100.0, 1.6));
+        let mut system = MultiReceptorSystem::new();
+        system.bind(&pam, 10.0);
+        let modulation = system.synaptic_modulation(SynapseType::AMPA);
+        assert!(modulation.conductance > 1.5 && modulation.conductance <= 2.0);
+        assert!(with(&[("perampanel", 1.0)]).synaptic_modulation(SynapseType::AMPA).conductance < 0.2);
+    }
+
+    #[test]
+    fn test_targets_map_to_synapses_channels_and_effects() {
+        // Therapeutic haloperidol occupies most D2 receptors
+        let haloperidol = with(&[("haloperidol", 0.006)]);
+        let occupancy = haloperidol.occupancy("haloperidol", ReceptorTarget::D2);
+        assert!((0.6..0.9).contains(&occupancy));
+        assert!(haloperidol.synaptic_modulation(SynapseType::AMPA).release > 1.0);
+        assert!(haloperidol.channel_modulation().calcium > 1.0);
+        assert!(haloperidol.effect(EffectType::Antipsychotic) > 0.5);
+
+        let morphine = with(&[("morphine", 0.05)]);
+        assert!(morphine.synaptic_modulation(SynapseType::GABAA).release < 0.8);
+        assert!(morphine.channel_modulation().leak > 1.0);
+        assert!(morphine.effect(EffectType::Analgesia) > 0.5);
+        // Buprenorphine, a partial agonist of higher affinity, displaces
+        // morphine and caps its respiratory depression
+        let both = with(&[("morphine", 0.05), ("buprenorphine", 0.05)]);
+        assert!(both.effect(EffectType::RespiratoryDepression) < morphine.effect(EffectType::RespiratoryDepression));
+
+        let diphenhydramine = with(&[("diphenhydramine", 0.5)]);
+        // Blocking histamine tone reopens K+ leak
+        assert!(diphenhydramine.channel_modulation().leak > 1.0);
+        assert!(diphenhydramine.effect(EffectType::Sedation) > 0.4);
+        assert!(with(&[("dexmedetomidine", 0.01)]).synaptic_modulation(SynapseType::AMPA).release < 1.0);
+        assert!(with(&[("nicotine", 0.1)]).effect(EffectType::Arousal) > 0.3);
+
+        // GABA_A drugs act through the same system, and combine with the rest
+        let midazolam_ketamine = with(&[("midazolam", 0.1), ("ketamine", 2.0)]);
+        assert!(midazolam_ketamine.synaptic_modulation(SynapseType::GABAA).conductance > 1.0);
+        assert!(midazolam_ketamine.synaptic_modulation(SynapseType::NMDA).conductance < 0.5);
+        let amnesia = |s: &MultiReceptorSystem| s.effect(EffectType::Amnesia);
+        assert!(amnesia(&midazolam_ketamine) > amnesia(&with(&[("midazolam", 0.1)])));
+        assert_eq!(MultiReceptorSystem::new().channel_modulation(), ChannelModulation::default());
+    }
+
+    #[test]
+    fn test_gaba_a_modulators_add_tonic_leak() {
+        // The tonic extrasynaptic current grows by the IPSC charge factor
+        for (drug, concentration) in [("propofol", 5.0), ("midazolam", 0.1)] {
+            let system = with(&[(drug, concentration)]);
+            let charge = system.gaba_a_modulation().charge();
+            assert!(charge > 1.0, "{drug}");
+            assert!((system.channel_modulation().leak - charge).abs() < 1e-12, "{drug}");
+        }
+
+        // ...and multiplies the leak changes from the other targets
+        let morphine = with(&[("morphine", 0.05)]).channel_modulation().leak;
+        let midazolam = with(&[("midazolam", 0.1)]).gaba_a_modulation().charge();
+        let both = with(&[("morphine", 0.05), ("midazolam", 0.1)]).channel_modulation().leak;
+        assert!((both - morphine * midazolam).abs() < 1e-12);
+    }
+
+    #[test]
+    fn test_gaba_a_drugs_compete_at_one_site() {
+        let charge = |drugs: &[(&str, f64)]| with(drugs).gaba_a_modulation().charge();
+
+        // Two full BZ-site agonists saturate at the single-drug maximum
+        let diazepam = charge(&[("diazepam", 100.0)]);
+        let alprazolam = charge(&[("alprazolam", 100.0)]);
+        let saturated = charge(&[("diazepam", 1e9)]);
+        let both = charge(&[("diazepam", 100.0), ("alprazolam", 100.0)]);
+        assert!(both > diazepam.max(alprazolam));
+        assert!(both < saturated + 1e-9, "{both} vs maximum {saturated}");
+
+        // Splitting a dose changes nothing
+        assert!((charge(&[("diazepam", 50.0), ("diazepam", 50.0)]) - diazepam).abs() < 1e-12);
+
+        // Drugs at different sites still multiply
+        let propofol = charge(&[("propofol", 5.0)]);
+        assert!((charge(&[("diazepam", 100.0), ("propofol", 5.0)]) - diazepam * propofol).abs() < 1e-12);
+    }
+}
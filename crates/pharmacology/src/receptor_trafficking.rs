@@ -519,6 +519,21 @@ impl ReceptorDynamics {
         }
     }
 
+    /// Simulate a time-varying agonist occupancy sampled every `dt_h` hours,
+    /// e.g. `ConcentrationProfile::occupancy` of a dosing regimen
+    pub fn simulate_occupancy_profile(&mut self, occupancy: &[f64], dt_h: f64) {
+        let substeps = (dt_h * 3600.0 / 60.0).ceil().max(1.0) as usize;
+        let dt_s = dt_h * 3600.0 / substeps as f64;
+
+        let kinase_activities: HashMap<KinaseType, f64> = HashMap::new();
+
+        for &agonist_occupancy in occupancy {
+            for _ in 0..substeps {
+                self.update(agonist_occupancy, &kinase_activities, dt_s);
+            }
+        }
+    }
+
     /// Simulate drug withdrawal
     pub fn simulate_withdrawal(&mut self, duration_h: f64) {
         let dt_s = 60.0;
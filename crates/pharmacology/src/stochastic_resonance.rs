@@ -36,7 +36,8 @@
 //! - Emergence of new behaviors from complex dynamics
 
 use serde::{Deserialize, Serialize};
-use rand::Rng;
+use rand::{Rng, SeedableRng};
+use rand_chacha::ChaCha8Rng;
 use rand_distr::{Distribution, Normal, Uniform};
 
 /// Type of noise/stochastic process
@@ -95,15 +96,23 @@ pub struct BistableSystem {
     pub time: f64,
     /// Noise generator
     noise: Normal<f64>,
+    /// Random stream driving the Wiener increments
+    rng: ChaCha8Rng,
 }
 
 impl BistableSystem {
     pub fn new(params: StochasticResonanceParams) -> Self {
+        Self::with_seed(params, rand::random())
+    }
+
+    /// Reproducible system: identical seeds give identical trajectories
+    pub fn with_seed(params: StochasticResonanceParams, seed: u64) -> Self {
         Self {
             x: -1.0,  // Start in left well
             params,
             time: 0.0,
             noise: Normal::new(0.0, 1.0).unwrap(),
+            rng: ChaCha8Rng::seed_from_u64(seed),
         }
     }
 
@@ -125,15 +134,13 @@ impl BistableSystem {
 
     /// Update system (Euler-Maruyama integration)
     pub fn step(&mut self, dt: f64) {
-        let mut rng = rand::thread_rng();
-
         // Deterministic part: dx = (x - x³ + signal) dt / τ
         let drift = (self.force(self.x) + self.signal()) / self.params.tau;
 
         // Stochastic part: σ √(2/τ) dW
         let diffusion = self.params.noise_intensity
             * (2.0 / self.params.tau).sqrt()
-            * self.noise.sample(&mut rng)
+            * self.noise.sample(&mut self.rng)
             * dt.sqrt();
 
         self.x += drift * dt + diffusion;
@@ -378,24 +385,32 @@ pub struct LevyFlightGenerator {
     pub scale: f64,
     /// Current position
     pub position: f64,
+    /// Random stream for the Chambers-Mallows-Stuck sampler
+    rng: ChaCha8Rng,
 }
 
 impl LevyFlightGenerator {
     pub fn new(alpha: f64, scale: f64) -> Self {
+        Self::with_seed(alpha, scale, rand::random())
+    }
+
+    /// Reproducible generator
+    pub fn with_seed(alpha: f64, scale: f64, seed: u64) -> Self {
         Self {
             alpha: alpha.clamp(0.5, 2.0),
             scale,
             position: 0.0,
+            rng: ChaCha8Rng::seed_from_u64(seed),
         }
     }
 
     /// Generate a Lévy-distributed random step
     /// Uses Chambers-Mallows-Stuck method
     pub fn step(&mut self) -> f64 {
-        let mut rng = rand::thread_rng();
+        let rng = &mut self.rng;
         let uniform = Uniform::new(-std::f64::consts::FRAC_PI_2, std::f64::consts::FRAC_PI_2);
 
-        let v = uniform.sample(&mut rng);
+        let v = uniform.sample(rng);
         let w = -rng.gen::<f64>().ln();  // Exponential(1)
 
         let step = if (self.alpha - 1.0).abs() < 0.01 {
@@ -404,7 +419,7 @@ impl LevyFlightGenerator {
         } else if (self.alpha - 2.0).abs() < 0.01 {
             // Gaussian case (α = 2)
             let normal = Normal::new(0.0, 1.0).unwrap();
-            normal.sample(&mut rng)
+            normal.sample(rng)
         } else {
             // General case
             let zeta = -(std::f64::consts::FRAC_PI_2 * self.alpha / 2.0).tan();
@@ -440,10 +455,17 @@ pub struct StochasticThreshold {
     pub chaos: OntologicalOscillator,
     /// Enable chaotic modulation
     pub use_chaos: bool,
+    /// Random stream for the OU noise and rare-event direction
+    rng: ChaCha8Rng,
 }
 
 impl StochasticThreshold {
     pub fn new(baseline: f64, noise_sigma: f64) -> Self {
+        Self::with_seed(baseline, noise_sigma, rand::random())
+    }
+
+    /// Reproducible threshold process
+    pub fn with_seed(baseline: f64, noise_sigma: f64, seed: u64) -> Self {
         Self {
             baseline,
             current: baseline,
@@ -451,17 +473,17 @@ impl StochasticThreshold {
             kappa: 1.0,  // Mean-reversion rate
             chaos: OntologicalOscillator::new(),
             use_chaos: true,
+            rng: ChaCha8Rng::seed_from_u64(seed),
         }
     }
 
     /// Update threshold (Ornstein-Uhlenbeck with chaotic modulation)
     pub fn step(&mut self, dt: f64) {
-        let mut rng = rand::thread_rng();
         let normal = Normal::new(0.0, 1.0).unwrap();
 
         // OU process: dθ = κ(μ - θ)dt + σdW
         let mean_reversion = self.kappa * (self.baseline - self.current);
-        let noise = self.noise_sigma * normal.sample(&mut rng) * dt.sqrt();
+        let noise = self.noise_sigma * normal.sample(&mut self.rng) * dt.sqrt();
 
         self.current += mean_reversion * dt + noise;
 
@@ -473,7 +495,7 @@ impl StochasticThreshold {
             if rare > 0.0 {
                 // Rare event: temporarily lower threshold (increased sensitivity)
                 // or raise threshold (decreased sensitivity)
-                let direction = if rng.gen::<bool>() { 1.0 } else { -1.0 };
+                let direction = if self.rng.gen::<bool>() { 1.0 } else { -1.0 };
                 self.current -= direction * rare * self.baseline * 0.2;
             }
         }
@@ -531,8 +553,13 @@ pub struct StochasticPDResponse {
 
 impl StochasticPDResponse {
     pub fn new(ec50: f64, hill: f64, emax: f64) -> Self {
+        Self::with_seed(ec50, hill, emax, rand::random())
+    }
+
+    /// Reproducible response (seeds the stochastic EC50 process)
+    pub fn with_seed(ec50: f64, hill: f64, emax: f64, seed: u64) -> Self {
         Self {
-            stochastic_ec50: StochasticThreshold::new(ec50, ec50 * 0.1),
+            stochastic_ec50: StochasticThreshold::with_seed(ec50, ec50 * 0.1, seed),
             hill,
             emax,
             oscillator: OntologicalOscillator::new(),
@@ -678,4 +705,23 @@ mod tests {
         // Should have some crossings with appropriate noise
         assert!(crossings > 0, "Expected threshold crossings with stochastic resonance");
     }
+
+    #[test]
+    fn test_seeded_systems_are_reproducible() {
+        let params = StochasticResonanceParams::default();
+        let mut a = BistableSystem::with_seed(params.clone(), 11);
+        let mut b = BistableSystem::with_seed(params, 11);
+
+        for _ in 0..1000 {
+            a.step(0.001);
+            b.step(0.001);
+        }
+        assert_eq!(a.x.to_bits(), b.x.to_bits());
+
+        let mut la = LevyFlightGenerator::with_seed(1.5, 1.0, 3);
+        let mut lb = LevyFlightGenerator::with_seed(1.5, 1.0, 3);
+        for _ in 0..100 {
+            assert_eq!(la.step().to_bits(), lb.step().to_bits());
+        }
+    }
 }
@@ -0,0 +1,100 @@
+//! Event-driven spike delivery with axonal conduction delays.
+//!
+//! A presynaptic spike is not broadcast to every synapse: it is scheduled on
+//! the outgoing synapses of its neuron only, each in the delay bin matching
+//! its axonal delay. Bins form a ring indexed by simulation step, so
+//! scheduling and delivery are O(1) per synaptic event and silent neurons
+//! cost nothing.
+
+use serde::{Deserialize, Serialize};
+
+/// Ring of delay bins: slot -> synapses whose presynaptic spike arrives in
+/// that step
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SpikeQueue {
+    bins: Vec<Vec<usize>>,
+
+    /// Slot of the upcoming step
+    head: usize,
+}
+
+impl Default for SpikeQueue {
+    fn default() -> Self {
+        Self::new(0)
+    }
+}
+
+impl SpikeQueue {
+    /// Create a queue able to hold delays of up to `max_delay_steps`
+    pub fn new(max_delay_steps: usize) -> Self {
+        Self {
+            bins: vec![Vec::new(); max_delay_steps + 1],
+            head: 0,
+        }
+    }
+
+    /// Longest delay (in steps) the ring can currently represent
+    pub fn max_delay_steps(&self) -> usize {
+        self.bins.len() - 1
+    }
+
+    /// Grow the ring so that delays of up to `delay_steps` can be scheduled
+    pub fn ensure_capacity(&mut self, delay_steps: usize) {
+        if delay_steps <= self.max_delay_steps() {
+            return;
+        }
+
+        // Unroll so the upcoming step comes first, then append empty bins
+        self.bins.rotate_left(self.head);
+        self.bins.resize(delay_steps + 1, Vec::new());
+        self.head = 0;
+    }
+
+    /// Deliver to `synapse` `delay_steps` steps from now (0 = this step)
+    pub fn schedule(&mut self, synapse: usize, delay_steps: usize) {
+        self.ensure_capacity(delay_steps);
+        let slot = (self.head + delay_steps) % self.bins.len();
+        self.bins[slot].push(synapse);
+    }
+
+    /// Take the synapses receiving a spike this step and move to the next step
+    pub fn advance(&mut self) -> Vec<usize> {
+        let arrivals = std::mem::take(&mut self.bins[self.head]);
+        self.head = (self.head + 1) % self.bins.len();
+        arrivals
+    }
+
+    /// Number of spikes still in flight
+    pub fn pending(&self) -> usize {
+        self.bins.iter().map(Vec::len).sum()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_spikes_arrive_after_delay() {
+        let mut queue = SpikeQueue::new(2);
+        queue.schedule(7, 0);
+        queue.schedule(3, 2);
+        assert_eq!(queue.advance(), vec![7]);
+        assert!(queue.advance().is_empty());
+        assert_eq!(queue.advance(), vec![3]);
+        assert_eq!(queue.pending(), 0);
+    }
+
+    #[test]
+    fn test_growing_ring_preserves_pending_spikes() {
+        let mut queue = SpikeQueue::new(1);
+        queue.advance();
+        queue.schedule(1, 1);
+
+        // Longer delay than the ring holds: grows without reordering
+        queue.schedule(2, 4);
+        assert_eq!(queue.max_delay_steps(), 4);
+        let arrivals: Vec<_> = (0..5).map(|_| queue.advance()).collect();
+        assert_eq!(arrivals, vec![vec![], vec![1], vec![], vec![], vec![2]]);
+    }
+}
@@ -4,13 +4,29 @@
 //! - Short-term plasticity (facilitation and depression)
 //! - Long-term plasticity (LTP/LTD, STDP)
 //! - Multiple neurotransmitter systems
-//! - Neuromodulation
+//! - Neuromodulation by volume transmission, with dopamine-gated plasticity
+//! - Pluggable long-term learning rules per synapse type
+//! - Event-driven spike delivery with axonal conduction delays
+//! - Electrical synapses (gap junctions) between compartments
 
 pub mod plasticity;
+pub mod delivery;
+pub mod electrical;
 pub mod neurotransmitters;
+pub mod volume_transmission;
+
+pub use neurotransmitters::{ModulatorReceptor, NeuromodulatorState, SynapticModulation};
+pub use plasticity::{
+    CalciumPlasticity, DopamineGatedSTDP, Plasticity, PlasticityContext, PlasticityRule,
+    PlasticityTraces, STDPParams, TripletSTDP,
+};
+pub use delivery::SpikeQueue;
+pub use electrical::{ElectricalCoupling, GapJunction};
+pub use volume_transmission::{ExtracellularSpace, VolumeTransmission};
 
 use serde::{Deserialize, Serialize};
-use rand::Rng;
+use rand::{Rng, SeedableRng};
+use rand_chacha::ChaCha8Rng;
 use rand_distr::{Distribution, Normal};
 use thiserror::Error;
 
@@ -37,6 +53,19 @@ pub enum SynapseType {
     Serotonin, // Neuromodulatory
 }
 
+impl SynapseType {
+    /// All synapse types
+    pub const ALL: [SynapseType; 7] = [
+        SynapseType::AMPA,
+        SynapseType::NMDA,
+        SynapseType::GABAA,
+        SynapseType::GABAB,
+        SynapseType::Glycine,
+        SynapseType::Dopamine,
+        SynapseType::Serotonin,
+    ];
+}
+
 /// Synapse model with realistic dynamics
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct Synapse {
@@ -87,8 +116,32 @@ pub struct Synapse {
 
     /// Calcium concentration at synapse (mM)
     pub calcium: f64,
+
+    /// Traces of the long-term plasticity rules
+    #[serde(default)]
+    pub traces: PlasticityTraces,
+
+    /// Axonal conduction delay from the presynaptic soma (ms)
+    #[serde(default)]
+    pub delay: f64,
+
+    /// Time up to which the state has been advanced (ms)
+    #[serde(default)]
+    pub last_update: f64,
 }
 
+/// Recovery rate of depleted vesicle resources (1/ms)
+const RESOURCE_RECOVERY: f64 = 0.01;
+
+/// Decay rate of short-term facilitation (1/ms)
+const FACILITATION_DECAY: f64 = 0.002;
+
+/// Clearance rate of presynaptic calcium (1/ms)
+const CALCIUM_CLEARANCE: f64 = 0.01;
+
+/// Cleft gating below which a synapse is treated as closed and left idle
+const GATING_FLOOR: f64 = 1e-6;
+
 impl Synapse {
     /// Create a new synapse
     pub fn new(
@@ -125,38 +178,59 @@ impl Synapse {
             last_pre_spike: -1000.0,
             last_post_spike: -1000.0,
             calcium: 0.0,
+            traces: PlasticityTraces::default(),
+            delay: 0.0,
+            last_update: 0.0,
         }
     }
 
-    /// Update synapse state
-    pub fn step(&mut self, dt: f64, pre_spike: bool, post_spike: bool, current_time: f64) {
-        // Update gating variable (neurotransmitter in cleft)
-        let decay_rate = 1.0 / self.tau_decay;
-        self.gating *= (-decay_rate * dt).exp();
+    /// Builder: set the axonal conduction delay (ms)
+    pub fn with_delay(mut self, delay: f64) -> Self {
+        self.delay = delay;
+        self
+    }
 
-        // Handle pre-synaptic spike
-        if pre_spike {
-            self.handle_presynaptic_spike(current_time);
-        }
+    /// Conduction delay in whole simulation steps (0 = same step)
+    pub fn delay_steps(&self, dt: f64) -> usize {
+        (self.delay / dt).round() as usize
+    }
 
-        // Update resources (recovery from depletion)
-        let recovery_rate = 0.01; // Recovery time constant
-        self.resources += (1.0 - self.resources) * recovery_rate * dt;
-        self.resources = self.resources.min(1.0);
+    /// Update synapse state
+    ///
+    /// `rng` drives stochastic vesicle release; pass a seeded generator for
+    /// reproducible runs.
+    pub fn step<R: Rng + ?Sized>(
+        &mut self,
+        dt: f64,
+        pre_spike: bool,
+        post_spike: bool,
+        current_time: f64,
+        rng: &mut R,
+    ) {
+        self.step_modulated(dt, pre_spike, post_spike, current_time, &SynapticModulation::default(), rng);
+    }
 
-        // Update facilitation (decay back to baseline)
-        let fac_decay = 0.002;
-        self.facilitation -= (self.facilitation - 1.0) * fac_decay * dt;
+    /// Update synapse state under neuromodulation of release probability
+    /// and maximal conductance
+    pub fn step_modulated<R: Rng + ?Sized>(
+        &mut self,
+        dt: f64,
+        pre_spike: bool,
+        post_spike: bool,
+        current_time: f64,
+        modulation: &SynapticModulation,
+        rng: &mut R,
+    ) {
+        self.relax(dt, modulation.decay);
 
-        // Update calcium concentration
+        // Handle pre-synaptic spike
         if pre_spike {
+            self.handle_presynaptic_spike(current_time, modulation.release, rng);
             self.calcium += 0.1; // Calcium influx during spike
         }
-        let ca_decay = 0.01;
-        self.calcium *= (-ca_decay * dt).exp();
 
         // Calculate conductance
-        self.conductance = self.g_max * self.weight * self.gating * self.facilitation;
+        self.conductance = self.g_max * modulation.conductance * self.weight * self.gating * self.facilitation;
 
         // Store spike times for plasticity
         if pre_spike {
@@ -167,11 +241,21 @@ impl Synapse {
         }
     }
 
+    /// Let the cleft, vesicle pool, facilitation and calcium relax for
+    /// `elapsed` ms without spikes, with the decay time constant scaled by
+    /// `decay_scale`. Exact for any interval, so silent synapses can be
+    /// caught up lazily at their next event.
+    fn relax(&mut self, elapsed: f64, decay_scale: f64) {
+        self.gating *= (-elapsed / (self.tau_decay * decay_scale)).exp();
+        self.resources = 1.0 - (1.0 - self.resources) * (-RESOURCE_RECOVERY * elapsed).exp();
+        self.facilitation = 1.0 + (self.facilitation - 1.0) * (-FACILITATION_DECAY * elapsed).exp();
+        self.calcium *= (-CALCIUM_CLEARANCE * elapsed).exp();
+    }
+
     /// Handle pre-synaptic spike event
-    fn handle_presynaptic_spike(&mut self, _current_time: f64) {
+    fn handle_presynaptic_spike<R: Rng + ?Sized>(&mut self, _current_time: f64, release_scale: f64, rng: &mut R) {
         // Stochastic release
-        let mut rng = rand::thread_rng();
-        if rng.gen::<f64>() < self.release_probability * self.resources {
+        if rng.gen::<f64>() < self.release_probability * release_scale * self.resources {
             // Neurotransmitter release
             self.gating += 0.5 * self.resources;
             self.gating = self.gating.min(1.0);
@@ -206,18 +290,15 @@ impl Synapse {
     /// Apply spike-timing-dependent plasticity (STDP)
     pub fn apply_stdp(&mut self, dt_spike: f64) {
         // dt_spike = t_post - t_pre
-        let a_plus = 0.01;  // LTP magnitude
-        let a_minus = 0.012; // LTD magnitude
-        let tau_plus = 20.0; // LTP time window (ms)
-        let tau_minus = 20.0; // LTD time window (ms)
+        let params = STDPParams::default();
 
         if dt_spike > 0.0 {
             // Post after pre - LTP
-            let dw = a_plus * (-dt_spike / tau_plus).exp();
+            let dw = params.window(dt_spike);
             self.weight += dw * (1.0 - self.weight); // Soft bound at 1.0
         } else {
             // Pre after post - LTD
-            let dw = -a_minus * (dt_spike / tau_minus).exp();
+            let dw = params.window(dt_spike);
             self.weight += dw * self.weight; // Soft bound at 0.0
         }
 
@@ -245,18 +326,79 @@ pub struct SynapticNetwork {
 
     /// Connection matrix: post_neuron -> list of synapse indices
     pub post_to_synapses: Vec<Vec<usize>>,
+
+    /// Random stream for stochastic release (owned so parallel regions stay reproducible)
+    pub rng: ChaCha8Rng,
+
+    /// Extracellular neuromodulators acting on the synapses (None = unmodulated)
+    #[serde(default)]
+    pub neuromodulators: Option<NeuromodulatorState>,
+
+    /// Long-term plasticity rule of each synapse type (unlisted types are fixed)
+    #[serde(default)]
+    pub plasticity: Vec<(SynapseType, Plasticity)>,
+
+    /// Drug action on each synapse type, on top of the neuromodulators
+    /// (unlisted types are unaffected)
+    #[serde(default)]
+    pub drug_modulation: Vec<(SynapseType, SynapticModulation)>,
+
+    /// Presynaptic spikes travelling along the axons
+    #[serde(default)]
+    pub spike_queue: SpikeQueue,
+
+    /// Synapses with an open cleft or a plasticity rule still at work
+    #[serde(default)]
+    active: Vec<usize>,
+
+    /// Gap junctions between the neurons
+    #[serde(default)]
+    pub electrical: ElectricalCoupling,
 }
 
 impl SynapticNetwork {
-    /// Create a new synaptic network
+    /// Create a new synaptic network with an entropy-seeded release stream
     pub fn new(num_neurons: usize) -> Self {
+        Self::with_seed(num_neurons, rand::random())
+    }
+
+    /// Create a new synaptic network with a reproducible release stream
+    pub fn with_seed(num_neurons: usize, seed: u64) -> Self {
         Self {
             synapses: Vec::new(),
             pre_to_synapses: vec![Vec::new(); num_neurons],
             post_to_synapses: vec![Vec::new(); num_neurons],
+            rng: ChaCha8Rng::seed_from_u64(seed),
+            neuromodulators: None,
+            plasticity: Vec::new(),
+            drug_modulation: Vec::new(),
+            spike_queue: SpikeQueue::default(),
+            active: Vec::new(),
+            electrical: ElectricalCoupling::new(),
+        }
+    }
+
+    /// Builder: attach a learning rule to all synapses of one type
+    pub fn with_plasticity(mut self, synapse_type: SynapseType, rule: impl Into<Plasticity>) -> Self {
+        self.set_plasticity(synapse_type, rule);
+        self
+    }
+
+    /// Attach a learning rule to all synapses of one type, replacing any
+    /// rule it already had
+    pub fn set_plasticity(&mut self, synapse_type: SynapseType, rule: impl Into<Plasticity>) {
+        let rule = rule.into();
+        match self.plasticity.iter_mut().find(|(t, _)| *t == synapse_type) {
+            Some((_, existing)) => *existing = rule,
+            None => self.plasticity.push((synapse_type, rule)),
         }
     }
 
+    /// Learning rule of a synapse type, if it has one
+    pub fn plasticity_for(&self, synapse_type: SynapseType) -> Option<&Plasticity> {
+        self.plasticity.iter().find(|(t, _)| *t == synapse_type).map(|(_, rule)| rule)
+    }
+
     /// Add a synapse to the network
     pub fn add_synapse(&mut self, synapse: Synapse) {
         let syn_idx = self.synapses.len();
@@ -268,6 +410,11 @@ impl SynapticNetwork {
         self.post_to_synapses[post_id].push(syn_idx);
     }
 
+    /// Add a gap junction to the network
+    pub fn add_gap_junction(&mut self, junction: GapJunction) {
+        self.electrical.add(junction);
+    }
+
     /// Get all synapses from a pre-synaptic neuron
     pub fn get_outgoing_synapses(&self, pre_neuron_id: usize) -> Vec<&Synapse> {
         self.pre_to_synapses[pre_neuron_id]
@@ -284,14 +431,92 @@ impl SynapticNetwork {
             .collect()
     }
 
+    /// Set the drug action on all synapses of one type, replacing any it
+    /// already had. The synapses' own parameters are left untouched, so a
+    /// time-varying exposure is applied by calling this again.
+    pub fn set_drug_modulation(&mut self, synapse_type: SynapseType, modulation: SynapticModulation) {
+        match self.drug_modulation.iter_mut().find(|(t, _)| *t == synapse_type) {
+            Some((_, existing)) => *existing = modulation,
+            None => self.drug_modulation.push((synapse_type, modulation)),
+        }
+    }
+
+    /// Drug action on a synapse type (unmodulated if none is set)
+    pub fn drug_modulation_for(&self, synapse_type: SynapseType) -> SynapticModulation {
+        self.drug_modulation
+            .iter()
+            .find(|(t, _)| *t == synapse_type)
+            .map(|(_, modulation)| *modulation)
+            .unwrap_or_default()
+    }
+
+    /// Remove all drug action
+    pub fn clear_drug_modulation(&mut self) {
+        self.drug_modulation.clear();
+    }
+
     /// Update all synapses
+    ///
+    /// Event-driven: spikes are scheduled on the emitting neuron's outgoing
+    /// synapses and delivered after each synapse's axonal delay. Only
+    /// synapses receiving a spike, onto a spiking neuron or still active are
+    /// touched; idle synapses are caught up analytically at their next event.
     pub fn step(&mut self, dt: f64, spikes: &[bool], current_time: f64) {
-        for synapse in &mut self.synapses {
-            let pre_spike = spikes[synapse.pre_neuron_id];
+        let spiking: Vec<usize> = (0..spikes.len()).filter(|&n| spikes[n]).collect();
+        for &neuron in &spiking {
+            for &idx in &self.pre_to_synapses[neuron] {
+                self.spike_queue.schedule(idx, self.synapses[idx].delay_steps(dt));
+            }
+        }
+        let mut arrivals = self.spike_queue.advance();
+        arrivals.sort_unstable();
+
+        let mut touched = std::mem::take(&mut self.active);
+        touched.extend_from_slice(&arrivals);
+        for &neuron in &spiking {
+            touched.extend_from_slice(&self.post_to_synapses[neuron]);
+        }
+        touched.sort_unstable();
+        touched.dedup();
+
+        let modulators = self.neuromodulators.as_ref();
+        let dopamine = modulators.map(|m| m.dopamine);
+
+        for idx in touched {
+            let synapse = &mut self.synapses[idx];
+            let pre_spike = arrivals.binary_search(&idx).is_ok();
             let post_spike = spikes[synapse.post_neuron_id];
-            synapse.step(dt, pre_spike, post_spike, current_time);
+            let elapsed = current_time - synapse.last_update;
+
+            let rule = self.plasticity.iter().find(|(t, _)| *t == synapse.synapse_type).map(|(_, rule)| rule);
+            if let Some(rule) = rule {
+                let ctx = PlasticityContext { dt, elapsed, pre_spike, post_spike, time: current_time, dopamine };
+                rule.update(synapse, &ctx);
+            }
+
+            let modulation = modulators
+                .map(|m| m.synaptic_modulation(synapse.synapse_type))
+                .unwrap_or_default();
+            let modulation = match self.drug_modulation.iter().find(|(t, _)| *t == synapse.synapse_type) {
+                Some((_, drug)) => modulation.combine(drug),
+                None => modulation,
+            };
+            synapse.step_modulated(elapsed, pre_spike, post_spike, current_time, &modulation, &mut self.rng);
+            synapse.last_update = current_time;
+
+            if synapse.gating > GATING_FLOOR || rule.is_some_and(|rule| !rule.is_quiescent(synapse)) {
+                self.active.push(idx);
+            } else {
+                synapse.gating = 0.0;
+                synapse.conductance = 0.0;
+            }
         }
     }
+
+    /// Number of synapses that will be stepped even without new spikes
+    pub fn num_active(&self) -> usize {
+        self.active.len()
+    }
 }
 
 #[cfg(test)]
@@ -310,14 +535,15 @@ mod tests {
     fn test_synapse_dynamics() {
         let mut syn = Synapse::new(0, 0, 1, SynapseType::AMPA, 1.0);
         syn.release_probability = 1.0; // Deterministic for testing
+        let mut rng = ChaCha8Rng::seed_from_u64(0);
 
         // Simulate pre-synaptic spike
-        syn.step(0.1, true, false, 0.0);
+        syn.step(0.1, true, false, 0.0, &mut rng);
         assert!(syn.gating > 0.0);
 
         // Decay
         for _ in 0..100 {
-            syn.step(0.1, false, false, 1.0);
+            syn.step(0.1, false, false, 1.0, &mut rng);
         }
         assert!(syn.gating < 0.1);
     }
@@ -337,6 +563,202 @@ mod tests {
         assert!(syn.weight < 0.5);
     }
 
+    #[test]
+    fn test_seeded_release_is_reproducible() {
+        let run = |seed: u64| {
+            let mut network = SynapticNetwork::with_seed(2, seed);
+            for id in 0..50 {
+                network.add_synapse(Synapse::new(id, 0, 1, SynapseType::AMPA, 1.0));
+            }
+            for step in 0..20 {
+                let spikes = [step % 3 == 0, false];
+                network.step(0.1, &spikes, step as f64 * 0.1);
+            }
+            network.synapses.iter().map(|s| s.gating.to_bits()).collect::<Vec<_>>()
+        };
+
+        assert_eq!(run(7), run(7));
+        assert_ne!(run(7), run(8));
+    }
+
+    #[test]
+    fn test_neuromodulators_scale_release() {
+        let released = |modulators: Option<NeuromodulatorState>| {
+            let mut network = SynapticNetwork::with_seed(2, 3);
+            network.neuromodulators = modulators;
+            for id in 0..200 {
+                network.add_synapse(Synapse::new(id, 0, 1, SynapseType::AMPA, 1.0));
+            }
+            network.step(0.1, &[true, false], 0.0);
+            network.synapses.iter().filter(|s| s.gating > 0.0).count()
+        };
+
+        // Presynaptic alpha2 heteroreceptors cut glutamate release
+        let noradrenaline = NeuromodulatorState { norepinephrine: 1.0, ..NeuromodulatorState::zero() };
+        let control = released(None);
+        assert_eq!(control, released(Some(NeuromodulatorState::zero())));
+        assert!((released(Some(noradrenaline)) as f64) < 0.85 * control as f64);
+    }
+
+    #[test]
+    fn test_drug_modulation_prolongs_and_scales_inhibition() {
+        let conductance_after = |modulation: Option<SynapticModulation>| {
+            let mut network = SynapticNetwork::with_seed(2, 0);
+            if let Some(modulation) = modulation {
+                network.set_drug_modulation(SynapseType::GABAA, modulation);
+            }
+            let mut synapse = Synapse::new(0, 0, 1, SynapseType::GABAA, 1.0);
+            synapse.release_probability = 1.0;
+            network.add_synapse(synapse);
+            network.add_synapse(Synapse::new(1, 0, 1, SynapseType::AMPA, 1.0));
+            network.step(0.1, &[true, false], 0.0);
+            let peak = network.synapses[0].conductance;
+            network.step(0.1, &[false, false], 10.0);
+            (peak, network.synapses[0].conductance)
+        };
+
+        let drug = SynapticModulation { conductance: 1.5, decay: 2.0, ..SynapticModulation::default() };
+        let (control_peak, control_late) = conductance_after(None);
+        let (peak, late) = conductance_after(Some(drug));
+        assert!((peak / control_peak - 1.5).abs() < 1e-12);
+        // After two control time constants the prolonged IPSC has decayed by one
+        let expected = 1.5 * control_late * (10.0 / 5.0 / 2.0_f64).exp();
+        assert!((late / expected - 1.0).abs() < 1e-9, "{late} vs {expected}");
+
+        let mut network = SynapticNetwork::with_seed(2, 0);
+        network.set_drug_modulation(SynapseType::GABAA, drug);
+        network.set_drug_modulation(SynapseType::GABAA, SynapticModulation::default());
+        assert_eq!(network.drug_modulation.len(), 1);
+        assert_eq!(network.drug_modulation_for(SynapseType::AMPA), SynapticModulation::default());
+    }
+
+    #[test]
+    fn test_dopamine_gates_stdp() {
+        let paired = |dopamine: f64| {
+            let mut network = SynapticNetwork::with_seed(2, 0)
+                .with_plasticity(SynapseType::AMPA, DopamineGatedSTDP::default());
+            network.neuromodulators = Some(NeuromodulatorState { dopamine, ..NeuromodulatorState::zero() });
+            network.add_synapse(Synapse::new(0, 0, 1, SynapseType::AMPA, 0.5));
+
+            // Pre leads post by 5 ms, then 100 ms of reinforcement
+            for step in 0..1000 {
+                let t = step as f64 * 0.1;
+                network.step(0.1, &[step == 0, step == 50], t);
+            }
+            network.synapses[0].weight
+        };
+
+        assert_eq!(paired(0.1), 0.5);
+        assert!(paired(1.0) > 0.5);
+        assert!(paired(0.0) < 0.5);
+    }
+
+    #[test]
+    fn test_plasticity_attaches_per_synapse_type() {
+        let mut network = SynapticNetwork::with_seed(2, 0)
+            .with_plasticity(SynapseType::AMPA, TripletSTDP::default())
+            .with_plasticity(SynapseType::AMPA, STDPParams::default());
+        network.add_synapse(Synapse::new(0, 0, 1, SynapseType::AMPA, 1.0));
+        network.add_synapse(Synapse::new(1, 0, 1, SynapseType::GABAA, 1.0));
+        assert_eq!(network.plasticity.len(), 1);
+        assert!(network.plasticity_for(SynapseType::GABAA).is_none());
+
+        for step in 0..200 {
+            network.step(0.1, &[step == 0, step == 100], step as f64 * 0.1);
+        }
+        let expected = 1.0 + STDPParams::default().window(10.0);
+        assert!((network.synapses[0].weight - expected).abs() < 1e-9);
+        assert_eq!(network.synapses[1].weight, 1.0);
+    }
+
+    #[test]
+    fn test_spikes_arrive_after_axonal_delay() {
+        let mut network = SynapticNetwork::with_seed(2, 0);
+        let mut synapse = Synapse::new(0, 0, 1, SynapseType::AMPA, 1.0).with_delay(1.5);
+        synapse.release_probability = 1.0;
+        network.add_synapse(synapse);
+
+        let mut first_open = None;
+        for step in 0..30 {
+            network.step(0.1, &[step == 0, false], step as f64 * 0.1);
+            if first_open.is_none() && network.synapses[0].gating > 0.0 {
+                first_open = Some(step);
+            }
+        }
+        assert_eq!(first_open, Some(15));
+    }
+
+    #[test]
+    fn test_idle_synapses_catch_up_analytically() {
+        let mut network = SynapticNetwork::with_seed(3, 0);
+        for post in [1, 2] {
+            let mut synapse = Synapse::new(post - 1, 0, post, SynapseType::AMPA, 1.0);
+            synapse.release_probability = 1.0;
+            network.add_synapse(synapse);
+        }
+        let mut dense = network.synapses[0].clone();
+        let mut rng = ChaCha8Rng::seed_from_u64(0);
+
+        // Two presynaptic spikes 300 ms apart; in between the cleft closes
+        // and the network goes idle
+        let mut idle_steps = 0;
+        for step in 0..=3000 {
+            let t = step as f64 * 0.1;
+            let pre = step == 0 || step == 3000;
+            network.step(0.1, &[pre, false, false], t);
+            dense.step(if step == 0 { 0.0 } else { 0.1 }, pre, false, t, &mut rng);
+            if network.num_active() == 0 {
+                idle_steps += 1;
+            }
+        }
+        assert!(idle_steps > 2500);
+
+        let lazy = &network.synapses[0];
+        assert!((lazy.resources - dense.resources).abs() < 1e-9);
+        assert!((lazy.facilitation - dense.facilitation).abs() < 1e-9);
+        assert!((lazy.calcium - dense.calcium).abs() < 1e-9);
+        assert!((lazy.conductance - dense.conductance).abs() < 1e-9);
+    }
+
+    #[test]
+    fn test_idle_plasticity_catches_up_like_dense_stepping() {
+        let rules: [Plasticity; 2] = [
+            CalciumPlasticity::default().into(),
+            DopamineGatedSTDP { tau_eligibility: 100.0, ..DopamineGatedSTDP::default() }.into(),
+        ];
+        for rule in rules {
+            let mut network = SynapticNetwork::with_seed(2, 0).with_plasticity(SynapseType::AMPA, rule.clone());
+            network.neuromodulators = Some(NeuromodulatorState { dopamine: 0.5, ..NeuromodulatorState::zero() });
+            network.add_synapse(Synapse::new(0, 0, 1, SynapseType::AMPA, 1.0));
+            let mut dense = network.synapses[0].clone();
+
+            // A pairing, then a 2 s silence, then coincident spikes
+            let mut idle_steps = 0;
+            for step in 0..21_000 {
+                let t = step as f64 * 0.1;
+                let (pre, post) = (step == 0 || step == 20_000, step == 50 || step == 20_000);
+                network.step(0.1, &[pre, post], t);
+                let ctx = PlasticityContext {
+                    dt: 0.1,
+                    elapsed: 0.1,
+                    pre_spike: pre,
+                    post_spike: post,
+                    time: t,
+                    dopamine: Some(0.5),
+                };
+                rule.update(&mut dense, &ctx);
+                if network.num_active() == 0 {
+                    idle_steps += 1;
+                }
+            }
+            assert!(idle_steps > 5000, "{rule:?}");
+
+            let lazy = network.synapses[0].weight;
+            assert!(lazy != 1.0, "{rule:?}");
+            assert!((lazy - dense.weight).abs() < 1e-6, "{rule:?}: lazy {lazy}, dense {}", dense.weight);
+        }
+    }
+
     #[test]
     fn test_network() {
         let mut network = SynapticNetwork::new(10);
@@ -1,7 +1,17 @@
 //! Neurotransmitter systems and neuromodulation.
+//!
+//! Monoamines and acetylcholine act through metabotropic receptors rather
+//! than point-to-point synapses. [`ModulatorReceptor`] gives the occupancy of
+//! each subtype at the extracellular concentrations in a
+//! [`NeuromodulatorState`], from which the receptor-specific effects on
+//! synaptic release / conductance ([`SynapticModulation`]) and on membrane
+//! channels ([`ChannelModulation`]) follow.
 
+use neurons::ChannelModulation;
 use serde::{Deserialize, Serialize};
 
+use crate::SynapseType;
+
 /// Neurotransmitter types
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
 pub enum Neurotransmitter {
@@ -14,8 +24,8 @@ pub enum Neurotransmitter {
     Histamine,
 }
 
-/// Neuromodulator concentrations
-#[derive(Debug, Clone, Serialize, Deserialize)]
+/// Neuromodulator concentrations (uM)
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub struct NeuromodulatorState {
     pub dopamine: f64,
     pub serotonin: f64,
@@ -33,3 +43,188 @@ impl Default for NeuromodulatorState {
         }
     }
 }
+
+impl NeuromodulatorState {
+    /// No modulator present
+    pub fn zero() -> Self {
+        Self {
+            dopamine: 0.0,
+            serotonin: 0.0,
+            norepinephrine: 0.0,
+            acetylcholine: 0.0,
+        }
+    }
+
+    /// Concentration of `transmitter` (0 for non-modulatory transmitters)
+    pub fn concentration(&self, transmitter: Neurotransmitter) -> f64 {
+        match transmitter {
+            Neurotransmitter::Dopamine => self.dopamine,
+            Neurotransmitter::Serotonin => self.serotonin,
+            Neurotransmitter::Norepinephrine => self.norepinephrine,
+            Neurotransmitter::Acetylcholine => self.acetylcholine,
+            _ => 0.0,
+        }
+    }
+
+    /// Fractional occupancy (0-1) of a receptor subtype
+    pub fn occupancy(&self, receptor: ModulatorReceptor) -> f64 {
+        let c = self.concentration(receptor.ligand()).max(0.0);
+        c / (c + receptor.ec50())
+    }
+
+    /// Effect on a synapse of type `synapse_type`:
+    /// - glutamatergic release is inhibited by presynaptic D2, 5-HT1A and
+    ///   alpha2 heteroreceptors; D1 potentiates NMDA and beta AMPA currents
+    /// - GABA release is enhanced by alpha1 and reduced by D2
+    pub fn synaptic_modulation(&self, synapse_type: SynapseType) -> SynapticModulation {
+        use ModulatorReceptor::*;
+        let occ = |r| self.occupancy(r);
+
+        match synapse_type {
+            SynapseType::AMPA | SynapseType::NMDA => {
+                let release = (1.0 - 0.3 * occ(D2)) * (1.0 - 0.2 * occ(HT1A)) * (1.0 - 0.3 * occ(Alpha2));
+                let conductance = if synapse_type == SynapseType::NMDA {
+                    1.0 + 0.5 * occ(D1)
+                } else {
+                    1.0 + 0.3 * occ(Beta)
+                };
+                SynapticModulation { release, conductance, ..SynapticModulation::default() }
+            }
+            SynapseType::GABAA | SynapseType::GABAB | SynapseType::Glycine => SynapticModulation {
+                release: (1.0 + 0.3 * occ(Alpha1)) * (1.0 - 0.2 * occ(D2)),
+                ..SynapticModulation::default()
+            },
+            SynapseType::Dopamine | SynapseType::Serotonin => SynapticModulation::default(),
+        }
+    }
+
+    /// Effect on membrane conductances: beta, M1 and 5-HT2A receptors close
+    /// leak and M/AHP-type K+ channels, 5-HT1A opens GIRK channels (extra
+    /// shunt), and D1/D2 oppositely regulate Ca2+ channels.
+    pub fn channel_modulation(&self) -> ChannelModulation {
+        use ModulatorReceptor::*;
+        let occ = |r| self.occupancy(r);
+
+        ChannelModulation {
+            sodium: 1.0 - 0.2 * occ(D1),
+            potassium: (1.0 - 0.3 * occ(Beta)) * (1.0 - 0.4 * occ(M1)),
+            calcium: (1.0 + 0.3 * occ(D1)) * (1.0 - 0.3 * occ(D2)),
+            leak: (1.0 - 0.3 * occ(Beta)) * (1.0 - 0.3 * occ(M1)) * (1.0 - 0.2 * occ(HT2A))
+                * (1.0 + 0.5 * occ(HT1A)),
+        }
+    }
+}
+
+/// Metabotropic receptor subtypes mediating volume transmission
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum ModulatorReceptor {
+    /// Gs-coupled dopamine receptor (low affinity)
+    D1,
+    /// Gi-coupled dopamine receptor (high affinity)
+    D2,
+    /// Gi-coupled serotonin receptor (GIRK activation)
+    HT1A,
+    /// Gq-coupled serotonin receptor
+    HT2A,
+    /// Gq-coupled adrenoceptor
+    Alpha1,
+    /// Gi-coupled adrenoceptor (presynaptic)
+    Alpha2,
+    /// Gs-coupled adrenoceptor
+    Beta,
+    /// Gq-coupled muscarinic receptor (M-current block)
+    M1,
+}
+
+impl ModulatorReceptor {
+    pub fn ligand(self) -> Neurotransmitter {
+        match self {
+            Self::D1 | Self::D2 => Neurotransmitter::Dopamine,
+            Self::HT1A | Self::HT2A => Neurotransmitter::Serotonin,
+            Self::Alpha1 | Self::Alpha2 | Self::Beta => Neurotransmitter::Norepinephrine,
+            Self::M1 => Neurotransmitter::Acetylcholine,
+        }
+    }
+
+    /// Half-maximal occupancy concentration (uM)
+    pub fn ec50(self) -> f64 {
+        match self {
+            Self::D1 => 1.0,
+            Self::D2 => 0.02,
+            Self::HT1A => 0.01,
+            Self::HT2A => 0.05,
+            Self::Alpha1 => 0.3,
+            Self::Alpha2 => 0.05,
+            Self::Beta => 0.3,
+            Self::M1 => 1.0,
+        }
+    }
+}
+
+/// Multiplicative effect of neuromodulators or drugs on one synapse
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct SynapticModulation {
+    /// Scaling of the vesicle release probability
+    pub release: f64,
+    /// Scaling of the maximal conductance
+    pub conductance: f64,
+    /// Scaling of the decay time constant
+    #[serde(default = "unit_scale")]
+    pub decay: f64,
+}
+
+fn unit_scale() -> f64 {
+    1.0
+}
+
+impl Default for SynapticModulation {
+    fn default() -> Self {
+        Self {
+            release: 1.0,
+            conductance: 1.0,
+            decay: 1.0,
+        }
+    }
+}
+
+impl SynapticModulation {
+    /// Both modulations acting together
+    pub fn combine(&self, other: &SynapticModulation) -> SynapticModulation {
+        SynapticModulation {
+            release: self.release * other.release,
+            conductance: self.conductance * other.conductance,
+            decay: self.decay * other.decay,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_receptor_occupancy_follows_affinity() {
+        let state = NeuromodulatorState { dopamine: 0.02, ..NeuromodulatorState::zero() };
+        assert!((state.occupancy(ModulatorReceptor::D2) - 0.5).abs() < 1e-12);
+        assert!(state.occupancy(ModulatorReceptor::D1) < 0.05);
+        assert_eq!(state.occupancy(ModulatorReceptor::Beta), 0.0);
+
+        let none = NeuromodulatorState::zero();
+        assert_eq!(none.synaptic_modulation(SynapseType::NMDA), SynapticModulation::default());
+        assert_eq!(none.channel_modulation(), ChannelModulation::default());
+    }
+
+    #[test]
+    fn test_receptor_specific_effects() {
+        let dopamine = NeuromodulatorState { dopamine: 2.0, ..NeuromodulatorState::zero() };
+        let nmda = dopamine.synaptic_modulation(SynapseType::NMDA);
+        assert!(nmda.release < 0.75 && nmda.conductance > 1.3);
+        assert!(dopamine.channel_modulation().sodium < 0.9);
+
+        // Noradrenaline closes K+ leak and boosts AMPA currents
+        let ne = NeuromodulatorState { norepinephrine: 1.0, ..NeuromodulatorState::zero() };
+        assert!(ne.channel_modulation().leak < 0.8);
+        assert!(ne.synaptic_modulation(SynapseType::AMPA).conductance > 1.2);
+        assert!(ne.synaptic_modulation(SynapseType::GABAA).release > 1.2);
+    }
+}
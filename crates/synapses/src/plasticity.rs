@@ -1,9 +1,77 @@
 //! Synaptic plasticity mechanisms.
+//!
+//! Every long-term learning rule implements [`PlasticityRule`]: once per time
+//! step it reads the pre/post spikes of one synapse, advances that synapse's
+//! [`PlasticityTraces`] and changes its weight. [`Plasticity`] wraps the rules
+//! in a serializable enum so a [`SynapticNetwork`](crate::SynapticNetwork) can
+//! attach a different rule to each synapse type.
+//!
+//! - [`STDPParams`]: pair-based STDP (Bi & Poo 1998; Song et al. 2000)
+//! - [`TripletSTDP`]: triplet rule (Pfister & Gerstner 2006)
+//! - [`CalciumPlasticity`]: calcium-threshold rule (Shouval et al. 2002;
+//!   Graupner & Brunel 2012)
+//! - [`DopamineGatedSTDP`]: reward-modulated three-factor rule (Izhikevich 2007)
 
 use serde::{Deserialize, Serialize};
 
+use crate::Synapse;
+
+/// Upper bound on synaptic weights shared by all rules
+pub const MAX_WEIGHT: f64 = 2.0;
+
+/// What a rule sees of a synapse during one time step
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct PlasticityContext {
+    /// Time step (ms), over which this step's weight change is integrated
+    pub dt: f64,
+    /// Time since the rule last saw this synapse (ms), over which its traces
+    /// decay: one step, or longer if the synapse was skipped while quiescent
+    pub elapsed: f64,
+    pub pre_spike: bool,
+    pub post_spike: bool,
+    /// Simulation time (ms)
+    pub time: f64,
+    /// Extracellular dopamine (uM), if the network is neuromodulated
+    pub dopamine: Option<f64>,
+}
+
+/// Per-synapse state of the learning rules
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct PlasticityTraces {
+    /// Fast presynaptic trace (r1)
+    pub pre: f64,
+    /// Slow presynaptic trace (r2)
+    pub pre_slow: f64,
+    /// Fast postsynaptic trace (o1)
+    pub post: f64,
+    /// Slow postsynaptic trace (o2)
+    pub post_slow: f64,
+    /// Spike-evoked postsynaptic calcium (normalized)
+    pub calcium: f64,
+    /// STDP eligibility awaiting a dopamine signal
+    pub eligibility: f64,
+}
+
+/// A long-term plasticity rule
+pub trait PlasticityRule {
+    /// Decay the synapse's traces over `ctx.elapsed`, add this step's spikes
+    /// and apply the weight change integrated over `ctx.dt`. Called before
+    /// [`Synapse::step`].
+    fn update(&self, synapse: &mut Synapse, ctx: &PlasticityContext);
+
+    /// Whether, until the synapse's next spike, the rule would do nothing
+    /// but decay its traces. Quiescent synapses may be skipped and updated
+    /// once with the elapsed time.
+    fn is_quiescent(&self, _synapse: &Synapse) -> bool {
+        true
+    }
+}
+
+/// Eligibility below which a three-factor synapse no longer needs stepping
+const ELIGIBILITY_FLOOR: f64 = 1e-6;
+
 /// Spike-timing-dependent plasticity parameters
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub struct STDPParams {
     pub a_plus: f64,      // LTP amplitude
     pub a_minus: f64,     // LTD amplitude
@@ -22,20 +90,370 @@ impl Default for STDPParams {
     }
 }
 
-/// Triplet STDP (accounts for spike triplets)
-#[derive(Debug, Clone, Serialize, Deserialize)]
+impl STDPParams {
+    /// Weight change of a single pairing, `dt_spike = t_post - t_pre` (ms)
+    pub fn window(&self, dt_spike: f64) -> f64 {
+        if dt_spike > 0.0 {
+            self.a_plus * (-dt_spike / self.tau_plus).exp()
+        } else {
+            -self.a_minus * (dt_spike / self.tau_minus).exp()
+        }
+    }
+
+    /// Advance the fast traces and return this step's all-to-all pairing
+    /// increment. Coincident spikes count as post-before-pre, as in
+    /// [`STDPParams::window`].
+    fn pairing(&self, traces: &mut PlasticityTraces, ctx: &PlasticityContext) -> f64 {
+        traces.pre *= (-ctx.elapsed / self.tau_plus).exp();
+        traces.post *= (-ctx.elapsed / self.tau_minus).exp();
+
+        let mut dw = 0.0;
+        if ctx.post_spike {
+            dw += self.a_plus * traces.pre;
+            traces.post += 1.0;
+        }
+        if ctx.pre_spike {
+            dw -= self.a_minus * traces.post;
+            traces.pre += 1.0;
+        }
+        dw
+    }
+}
+
+impl PlasticityRule for STDPParams {
+    fn update(&self, synapse: &mut Synapse, ctx: &PlasticityContext) {
+        let dw = self.pairing(&mut synapse.traces, ctx);
+        synapse.weight = (synapse.weight + dw).clamp(0.0, MAX_WEIGHT);
+    }
+}
+
+/// Triplet STDP (accounts for spike triplets). Potentiation at a
+/// postsynaptic spike grows with the slow postsynaptic trace, so pairings
+/// turn from depressing to potentiating as their frequency rises.
+/// Defaults are the all-to-all visual cortex fit of Pfister & Gerstner (2006).
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub struct TripletSTDP {
-    pub params: STDPParams,
-    pub r1: f64,  // Pre-synaptic trace
-    pub r2: f64,  // Pre-synaptic trace (slow)
-    pub o1: f64,  // Post-synaptic trace
-    pub o2: f64,  // Post-synaptic trace (slow)
+    pub a2_plus: f64,     // Pair LTP amplitude
+    pub a3_plus: f64,     // Triplet LTP amplitude
+    pub a2_minus: f64,    // Pair LTD amplitude
+    pub a3_minus: f64,    // Triplet LTD amplitude
+    pub tau_plus: f64,    // Fast presynaptic trace (ms)
+    pub tau_minus: f64,   // Fast postsynaptic trace (ms)
+    pub tau_x: f64,       // Slow presynaptic trace (ms)
+    pub tau_y: f64,       // Slow postsynaptic trace (ms)
+}
+
+impl Default for TripletSTDP {
+    fn default() -> Self {
+        Self {
+            a2_plus: 5e-10,
+            a3_plus: 6.2e-3,
+            a2_minus: 7e-3,
+            a3_minus: 2.3e-4,
+            tau_plus: 16.8,
+            tau_minus: 33.7,
+            tau_x: 101.0,
+            tau_y: 125.0,
+        }
+    }
+}
+
+impl PlasticityRule for TripletSTDP {
+    fn update(&self, synapse: &mut Synapse, ctx: &PlasticityContext) {
+        let t = &mut synapse.traces;
+        t.pre *= (-ctx.elapsed / self.tau_plus).exp();
+        t.pre_slow *= (-ctx.elapsed / self.tau_x).exp();
+        t.post *= (-ctx.elapsed / self.tau_minus).exp();
+        t.post_slow *= (-ctx.elapsed / self.tau_y).exp();
+
+        // The triplet terms use the slow traces from before this spike
+        let mut dw = 0.0;
+        if ctx.pre_spike {
+            dw -= t.post * (self.a2_minus + self.a3_minus * t.pre_slow);
+            t.pre += 1.0;
+            t.pre_slow += 1.0;
+        }
+        if ctx.post_spike {
+            dw += t.pre * (self.a2_plus + self.a3_plus * t.post_slow);
+            t.post += 1.0;
+            t.post_slow += 1.0;
+        }
+        synapse.weight = (synapse.weight + dw).clamp(0.0, MAX_WEIGHT);
+    }
 }
 
-/// Calcium-based plasticity
-#[derive(Debug, Clone, Serialize, Deserialize)]
+/// Calcium-based plasticity. Pre- and postsynaptic spikes add calcium
+/// transients; time spent above `theta_d` depresses the synapse and time
+/// above `theta_p` potentiates it, so weak (low-frequency) input yields LTD
+/// and strong (high-frequency) input LTP.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub struct CalciumPlasticity {
-    pub ca_concentration: f64,
-    pub theta_d: f64,  // LTD threshold
-    pub theta_p: f64,  // LTP threshold
+    pub tau_calcium: f64,  // Calcium decay (ms)
+    pub c_pre: f64,        // Transient per presynaptic spike
+    pub c_post: f64,       // Transient per postsynaptic spike
+    pub theta_d: f64,      // LTD threshold
+    pub theta_p: f64,      // LTP threshold
+    pub gamma_d: f64,      // Depression rate (1/ms)
+    pub gamma_p: f64,      // Potentiation rate (1/ms)
+}
+
+impl Default for CalciumPlasticity {
+    fn default() -> Self {
+        Self {
+            tau_calcium: 20.0,
+            c_pre: 1.0,
+            c_post: 2.0,
+            theta_d: 0.95,
+            theta_p: 1.5,
+            gamma_d: 0.0015,
+            gamma_p: 0.003,
+        }
+    }
+}
+
+impl PlasticityRule for CalciumPlasticity {
+    fn update(&self, synapse: &mut Synapse, ctx: &PlasticityContext) {
+        let t = &mut synapse.traces;
+        t.calcium *= (-ctx.elapsed / self.tau_calcium).exp();
+        if ctx.pre_spike {
+            t.calcium += self.c_pre;
+        }
+        if ctx.post_spike {
+            t.calcium += self.c_post;
+        }
+
+        let w = synapse.weight;
+        let mut dw = 0.0;
+        if t.calcium > self.theta_p {
+            dw += self.gamma_p * (MAX_WEIGHT - w);
+        }
+        if t.calcium > self.theta_d {
+            dw -= self.gamma_d * w;
+        }
+        synapse.weight = (w + dw * ctx.dt).clamp(0.0, MAX_WEIGHT);
+    }
+
+    fn is_quiescent(&self, synapse: &Synapse) -> bool {
+        synapse.traces.calcium <= self.theta_d
+    }
+}
+
+/// Three-factor learning: STDP pairings set an eligibility trace that is
+/// only converted into a weight change by dopamine deviating from its
+/// baseline (reward prediction error), as at corticostriatal synapses.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct DopamineGatedSTDP {
+    pub params: STDPParams,
+    /// Eligibility trace time constant (ms)
+    pub tau_eligibility: f64,
+    /// Weight change per unit eligibility per uM dopamine per ms
+    pub learning_rate: f64,
+    /// Tonic dopamine at which the weights do not change (uM)
+    pub dopamine_baseline: f64,
+}
+
+impl Default for DopamineGatedSTDP {
+    fn default() -> Self {
+        Self {
+            params: STDPParams::default(),
+            tau_eligibility: 1000.0,
+            learning_rate: 0.01,
+            dopamine_baseline: 0.1,
+        }
+    }
+}
+
+impl PlasticityRule for DopamineGatedSTDP {
+    /// Without a dopamine signal the rule sits at baseline and only tags
+    fn update(&self, synapse: &mut Synapse, ctx: &PlasticityContext) {
+        // Old eligibility decays over the whole interval, this step's
+        // pairings only over the step
+        let decay = |interval: f64| (-interval / self.tau_eligibility).exp();
+        let t = &mut synapse.traces;
+        let pairing = self.params.pairing(t, ctx);
+        t.eligibility = t.eligibility * decay(ctx.elapsed) + pairing * decay(ctx.dt);
+
+        let dopamine = ctx.dopamine.unwrap_or(self.dopamine_baseline);
+        let dw = self.learning_rate * (dopamine - self.dopamine_baseline) * t.eligibility * ctx.dt;
+        synapse.weight = (synapse.weight + dw).clamp(0.0, MAX_WEIGHT);
+    }
+
+    fn is_quiescent(&self, synapse: &Synapse) -> bool {
+        synapse.traces.eligibility.abs() < ELIGIBILITY_FLOOR
+    }
+}
+
+/// Any of the learning rules, attachable to a synapse type
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub enum Plasticity {
+    Pair(STDPParams),
+    Triplet(TripletSTDP),
+    Calcium(CalciumPlasticity),
+    RewardModulated(DopamineGatedSTDP),
+}
+
+impl PlasticityRule for Plasticity {
+    fn update(&self, synapse: &mut Synapse, ctx: &PlasticityContext) {
+        match self {
+            Self::Pair(rule) => rule.update(synapse, ctx),
+            Self::Triplet(rule) => rule.update(synapse, ctx),
+            Self::Calcium(rule) => rule.update(synapse, ctx),
+            Self::RewardModulated(rule) => rule.update(synapse, ctx),
+        }
+    }
+
+    fn is_quiescent(&self, synapse: &Synapse) -> bool {
+        match self {
+            Self::Pair(rule) => rule.is_quiescent(synapse),
+            Self::Triplet(rule) => rule.is_quiescent(synapse),
+            Self::Calcium(rule) => rule.is_quiescent(synapse),
+            Self::RewardModulated(rule) => rule.is_quiescent(synapse),
+        }
+    }
+}
+
+impl From<STDPParams> for Plasticity {
+    fn from(rule: STDPParams) -> Self {
+        Self::Pair(rule)
+    }
+}
+
+impl From<TripletSTDP> for Plasticity {
+    fn from(rule: TripletSTDP) -> Self {
+        Self::Triplet(rule)
+    }
+}
+
+impl From<CalciumPlasticity> for Plasticity {
+    fn from(rule: CalciumPlasticity) -> Self {
+        Self::Calcium(rule)
+    }
+}
+
+impl From<DopamineGatedSTDP> for Plasticity {
+    fn from(rule: DopamineGatedSTDP) -> Self {
+        Self::RewardModulated(rule)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::SynapseType;
+
+    const DT: f64 = 0.1;
+
+    /// Drive one synapse with spikes at the given steps and return its
+    /// weight change
+    fn weight_change(rule: &impl PlasticityRule, pre: &[usize], post: &[usize], steps: usize) -> f64 {
+        let train = |spikes: &[usize]| {
+            let mut train = vec![false; steps];
+            spikes.iter().for_each(|&step| train[step] = true);
+            train
+        };
+        let (pre, post) = (train(pre), train(post));
+
+        let mut synapse = Synapse::new(0, 0, 1, SynapseType::AMPA, 1.0);
+        for step in 0..steps {
+            let ctx = PlasticityContext {
+                dt: DT,
+                elapsed: DT,
+                pre_spike: pre[step],
+                post_spike: post[step],
+                time: step as f64 * DT,
+                dopamine: None,
+            };
+            rule.update(&mut synapse, &ctx);
+        }
+        synapse.weight - 1.0
+    }
+
+    /// `pairings` pre/post pairs at `frequency` Hz, post lagging pre by
+    /// `lag` steps (negative: post leads)
+    fn pairing_protocol(pairings: usize, frequency: f64, lag: i64) -> (Vec<usize>, Vec<usize>) {
+        let period = (1000.0 / frequency / DT).round() as i64;
+        (0..pairings as i64)
+            .map(|i| {
+                let pre = lag.abs() + i * period;
+                (pre as usize, (pre + lag) as usize)
+            })
+            .unzip()
+    }
+
+    #[test]
+    fn test_pair_rule_reproduces_stdp_window() {
+        let rule = STDPParams::default();
+        for lag in [-400i64, -200, -100, -50, -10, 10, 50, 100, 200, 400] {
+            let (pre, post) = pairing_protocol(1, 1.0, lag);
+            let dw = weight_change(&rule, &pre, &post, 1000);
+            let expected = rule.window(lag as f64 * DT);
+
+            // Pre-before-post potentiates, post-before-pre depresses, both
+            // decaying exponentially with the interval
+            assert_eq!(dw > 0.0, lag > 0);
+            assert!((dw - expected).abs() < 1e-9, "lag {lag}: {dw} vs {expected}");
+        }
+        assert!(rule.window(1.0) > rule.window(10.0));
+        assert!(rule.window(-1.0) < rule.window(-10.0));
+    }
+
+    #[test]
+    fn test_triplet_rule_frequency_dependence() {
+        // Sjöström et al. (2001): 60 pairings at +-10 ms
+        let frequencies = [0.1, 10.0, 20.0, 40.0, 50.0];
+        let curve = |lag: i64| -> Vec<f64> {
+            frequencies
+                .iter()
+                .map(|&f| {
+                    let (pre, post) = pairing_protocol(60, f, lag);
+                    let steps = post.iter().chain(&pre).max().unwrap() + 1;
+                    weight_change(&TripletSTDP::default(), &pre, &post, steps)
+                })
+                .collect()
+        };
+        let (pre_post, post_pre) = (curve(100), curve(-100));
+
+        // At low frequency only post-before-pre pairings change the synapse
+        assert!(post_pre[0] < 0.0);
+        assert!(pre_post[0].abs() < 1e-6);
+
+        // Potentiation grows with frequency and eventually dominates either order
+        assert!(pre_post.windows(2).all(|w| w[1] > w[0]));
+        assert!(post_pre[4] > 0.0);
+        assert!(pre_post[4] > 0.0);
+    }
+
+    #[test]
+    fn test_calcium_rule_frequency_dependence() {
+        // 100 presynaptic pulses: low-frequency stimulation depresses, tetanic
+        // stimulation potentiates (Dudek & Bear 1992)
+        let stimulate = |frequency: f64| {
+            let period = (1000.0 / frequency / DT).round() as usize;
+            let pre: Vec<usize> = (0..100).map(|i| i * period).collect();
+            weight_change(&CalciumPlasticity::default(), &pre, &[], 100 * period)
+        };
+
+        assert!(stimulate(1.0) < 0.0);
+        assert!(stimulate(5.0) < 0.0);
+        assert!(stimulate(100.0) > 0.0);
+    }
+
+    #[test]
+    fn test_reward_modulated_rule_waits_for_dopamine() {
+        let rule = DopamineGatedSTDP::default();
+        let mut synapse = Synapse::new(0, 0, 1, SynapseType::AMPA, 1.0);
+        for step in 0..100 {
+            let ctx = PlasticityContext {
+                dt: DT,
+                elapsed: DT,
+                pre_spike: step == 0,
+                post_spike: step == 50,
+                time: step as f64 * DT,
+                dopamine: None,
+            };
+            Plasticity::from(rule.clone()).update(&mut synapse, &ctx);
+        }
+        assert_eq!(synapse.weight, 1.0);
+        assert!(synapse.traces.eligibility > 0.0);
+    }
 }
@@ -8,7 +8,8 @@
 //! - Activity-dependent structural remodeling
 
 use serde::{Deserialize, Serialize};
-use rand::Rng;
+use rand::{Rng, SeedableRng};
+use rand_chacha::ChaCha8Rng;
 
 /// Dendritic spine morphology types
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
@@ -140,10 +141,16 @@ pub struct StructuralPlasticityManager {
     pub synaptogenesis_rate: f64,    // New synapses per day
     pub pruning_rate: f64,            // Fraction of weak synapses pruned per day
     pub critical_period: bool,        // Higher plasticity during development
+    pub rng: ChaCha8Rng,              // Stochastic pruning stream
 }
 
 impl StructuralPlasticityManager {
     pub fn new(initial_spines: usize) -> Self {
+        Self::with_seed(initial_spines, rand::random())
+    }
+
+    /// Create a manager whose stochastic pruning is reproducible
+    pub fn with_seed(initial_spines: usize, seed: u64) -> Self {
         let mut spines = Vec::new();
         for i in 0..initial_spines {
             // Mix of spine types
@@ -160,6 +167,7 @@ impl StructuralPlasticityManager {
             synaptogenesis_rate: 5.0,   // 5 new synapses/day
             pruning_rate: 0.02,          // 2% pruned per day
             critical_period: false,
+            rng: ChaCha8Rng::seed_from_u64(seed),
         }
     }
 
@@ -206,9 +214,9 @@ impl StructuralPlasticityManager {
         let rate_factor = if self.critical_period { 0.5 } else { 1.0 };
         let effective_pruning_rate = self.pruning_rate * rate_factor;
 
+        let rng = &mut self.rng;
         self.spines.retain(|spine| {
             let should_keep = !spine.should_prune();
-            let mut rng = rand::thread_rng();
             should_keep && (rng.gen::<f64>() > effective_pruning_rate)
         });
     }
@@ -0,0 +1,138 @@
+//! Volume transmission of neuromodulators.
+//!
+//! Dopamine, serotonin, noradrenaline and acetylcholine are released from
+//! diffuse varicosities and act on extrasynaptic receptors throughout a
+//! region. Each [`ExtracellularSpace`] holds the concentrations seen by one
+//! region; they relax towards the source output weighted by the region's
+//! innervation density, with modulator-specific reuptake / degradation
+//! time constants.
+
+use serde::{Deserialize, Serialize};
+
+use crate::neurotransmitters::NeuromodulatorState;
+
+/// Extracellular compartment of one brain region
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ExtracellularSpace {
+    pub name: String,
+
+    /// Relative innervation density of each modulator system (0-1)
+    pub innervation: NeuromodulatorState,
+
+    /// Current extracellular concentrations (uM)
+    pub concentrations: NeuromodulatorState,
+}
+
+/// Per-region extracellular modulator concentrations
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct VolumeTransmission {
+    pub regions: Vec<ExtracellularSpace>,
+
+    /// Clearance time constant of each modulator (ms): DAT/SERT/NET
+    /// reuptake and acetylcholinesterase hydrolysis
+    pub clearance_tau: NeuromodulatorState,
+}
+
+impl Default for VolumeTransmission {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl VolumeTransmission {
+    pub fn new() -> Self {
+        Self {
+            regions: Vec::new(),
+            clearance_tau: NeuromodulatorState {
+                dopamine: 200.0,
+                serotonin: 500.0,
+                norepinephrine: 300.0,
+                acetylcholine: 5.0,
+            },
+        }
+    }
+
+    /// Add a region, initially at its steady state for zero release.
+    /// Returns its index.
+    pub fn add_region(&mut self, name: impl Into<String>, innervation: NeuromodulatorState) -> usize {
+        self.regions.push(ExtracellularSpace {
+            name: name.into(),
+            innervation,
+            concentrations: NeuromodulatorState::zero(),
+        });
+        self.regions.len() - 1
+    }
+
+    /// Index of the region called `name`
+    pub fn region(&self, name: &str) -> Option<usize> {
+        self.regions.iter().position(|r| r.name == name)
+    }
+
+    /// Concentrations in region `idx`
+    pub fn concentrations(&self, idx: usize) -> &NeuromodulatorState {
+        &self.regions[idx].concentrations
+    }
+
+    /// Set every region to its steady state for a constant `release`
+    pub fn equilibrate(&mut self, release: &NeuromodulatorState) {
+        for region in &mut self.regions {
+            let w = &region.innervation;
+            region.concentrations = NeuromodulatorState {
+                dopamine: w.dopamine * release.dopamine,
+                serotonin: w.serotonin * release.serotonin,
+                norepinephrine: w.norepinephrine * release.norepinephrine,
+                acetylcholine: w.acetylcholine * release.acetylcholine,
+            };
+        }
+    }
+
+    /// Advance all regions by `dt` given the source output (uM at full
+    /// innervation). Exact exponential relaxation, so any dt is stable.
+    pub fn step(&mut self, dt: f64, release: &NeuromodulatorState) {
+        let tau = &self.clearance_tau;
+        let relax = |c: &mut f64, target: f64, tau: f64| {
+            *c = target + (*c - target) * (-dt / tau).exp();
+        };
+
+        for region in &mut self.regions {
+            let (c, w) = (&mut region.concentrations, &region.innervation);
+            relax(&mut c.dopamine, w.dopamine * release.dopamine, tau.dopamine);
+            relax(&mut c.serotonin, w.serotonin * release.serotonin, tau.serotonin);
+            relax(&mut c.norepinephrine, w.norepinephrine * release.norepinephrine, tau.norepinephrine);
+            relax(&mut c.acetylcholine, w.acetylcholine * release.acetylcholine, tau.acetylcholine);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_regions_relax_to_innervation_weighted_release() {
+        let mut volume = VolumeTransmission::new();
+        let striatum = volume.add_region("striatum", NeuromodulatorState { dopamine: 1.0, ..NeuromodulatorState::zero() });
+        let cortex = volume.add_region("cortex", NeuromodulatorState { dopamine: 0.2, ..NeuromodulatorState::zero() });
+        assert_eq!(volume.region("cortex"), Some(cortex));
+
+        let release = NeuromodulatorState { dopamine: 0.5, ..NeuromodulatorState::zero() };
+        volume.step(200.0, &release);
+        let one_tau = 0.5 * (1.0 - (-1.0f64).exp());
+        assert!((volume.concentrations(striatum).dopamine - one_tau).abs() < 1e-12);
+
+        for _ in 0..100 {
+            volume.step(100.0, &release);
+        }
+        assert!((volume.concentrations(striatum).dopamine - 0.5).abs() < 1e-9);
+        assert!((volume.concentrations(cortex).dopamine - 0.1).abs() < 1e-9);
+        assert_eq!(volume.concentrations(cortex).serotonin, 0.0);
+
+        let mut fresh = volume.clone();
+        fresh.equilibrate(&release);
+        assert!((fresh.concentrations(cortex).dopamine - volume.concentrations(cortex).dopamine).abs() < 1e-9);
+
+        // Clearance after release stops
+        volume.step(1000.0, &NeuromodulatorState::zero());
+        assert!(volume.concentrations(striatum).dopamine < 0.01);
+    }
+}
@@ -2,12 +2,14 @@
 //!
 //! The thalamus is the gateway to the cortex. This module implements:
 //! - Specific relay nuclei: VPL/VPM (somatosensory), LGN (visual), MGN (auditory)
-//! - Thalamic reticular nucleus (TRN) - gating and attention
+//! - Thalamic reticular nucleus (TRN) - gating and attention, with
+//!   electrically coupled neurons
 //! - Burst vs tonic firing modes
 //! - Thalamocortical oscillations (sleep spindles, 7-14 Hz)
 //! - Corticothalamic feedback
 
 use serde::{Deserialize, Serialize};
+use synapses::ElectricalCoupling;
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
 pub enum ThalamicNucleusType {
@@ -74,6 +76,7 @@ impl ThalamicNeuron {
     }
 }
 
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct ThalamicNucleus {
     pub neurons: Vec<ThalamicNeuron>,
     pub nucleus_type: ThalamicNucleusType,
@@ -96,9 +99,15 @@ impl ThalamicNucleus {
     }
 }
 
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct ThalamicReticular {
     pub neurons: Vec<ThalamicNeuron>,
     pub inhibitory_weights: Vec<Vec<f64>>,
+
+    /// Dendrodendritic gap junctions between TRN neurons (conductances in
+    /// 1/ms, as the point model has unit capacitance)
+    #[serde(default)]
+    pub gap_junctions: ElectricalCoupling,
 }
 
 impl ThalamicReticular {
@@ -109,16 +118,26 @@ impl ThalamicReticular {
         Self {
             neurons: (0..num_neurons).map(|i| ThalamicNeuron::new(i, ThalamicNucleusType::TRN)).collect(),
             inhibitory_weights,
+            gap_junctions: ElectricalCoupling::new(),
         }
     }
 
+    /// Builder: couple TRN neurons electrically
+    pub fn with_gap_junctions(mut self, gap_junctions: ElectricalCoupling) -> Self {
+        self.gap_junctions = gap_junctions;
+        self
+    }
+
     pub fn step(&mut self, dt: f64, thalamic_activity: &[bool], current_time: f64) -> Vec<f64> {
         let n = self.neurons.len();
         let mut inhibition = vec![0.0; n];
 
+        let voltages: Vec<f64> = self.neurons.iter().map(|neuron| neuron.voltage).collect();
+        let gap_currents = self.gap_junctions.point_currents(&voltages);
+
         for (i, neuron) in self.neurons.iter_mut().enumerate() {
             let excitation = if i < thalamic_activity.len() && thalamic_activity[i] { 5.0 } else { 0.0 };
-            if neuron.step(dt, excitation, current_time) {
+            if neuron.step(dt, excitation + gap_currents[i], current_time) {
                 for j in 0..n {
                     inhibition[j] += self.inhibitory_weights[i][j];
                 }
@@ -128,6 +147,7 @@ impl ThalamicReticular {
     }
 }
 
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct Thalamus {
     pub vpl: ThalamicNucleus,
     pub lgn: ThalamicNucleus,
@@ -159,3 +179,31 @@ impl Thalamus {
         vpl_output
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use synapses::GapJunction;
+
+    #[test]
+    fn test_gap_junctions_synchronize_trn_neurons() {
+        // Two silent cells start 7 mV apart between the T-channel window and
+        // threshold; coupled they also relax towards each other
+        let mismatch = |mut trn: ThalamicReticular| {
+            trn.neurons[0].voltage = -52.0;
+            trn.neurons[1].voltage = -59.0;
+            let dt = 0.1;
+            for k in 0..30 {
+                assert!(trn.step(dt, &[], k as f64 * dt).iter().all(|&inhibition| inhibition == 0.0));
+            }
+            (trn.neurons[0].voltage - trn.neurons[1].voltage).abs()
+        };
+
+        let mut coupling = ElectricalCoupling::new();
+        coupling.add(GapJunction::new(0, 1, 0.1));
+        let coupled = mismatch(ThalamicReticular::new(2).with_gap_junctions(coupling));
+        let uncoupled = mismatch(ThalamicReticular::new(2));
+        assert!(uncoupled > 5.0);
+        assert!(coupled < 0.6 * uncoupled, "coupled {coupled} vs uncoupled {uncoupled} mV");
+    }
+}
@@ -0,0 +1,799 @@
+//! Declarative experiment description for `WholeBrain`.
+//!
+//! A `BrainConfig` is read from TOML or JSON and fixes everything that used to
+//! be hardcoded in `WholeBrain::new` / `step`: region sizes, which pathways are
+//! active together with their gains and conduction delays, the time step and
+//! duration, stimulus protocols and which parts of the state are recorded.
+//!
+//! ```toml
+//! dt = 0.1
+//! duration_ms = 500.0
+//! seed = 42
+//!
+//! [regions]
+//! cortical_columns = 10
+//! thalamic_neurons = 20
+//!
+//! [pathways.thalamocortical]
+//! weight = 0.8
+//! delay_ms = 2.0
+//!
+//! [[stimuli]]
+//! target = "sensory"
+//! protocol = { type = "pulse", amplitude = 20.0, start_ms = 100.0, duration_ms = 50.0 }
+//!
+//! [recording]
+//! targets = ["cortical_layers", "dopamine"]
+//! every = 10
+//! ```
+//!
+//! Every field has a default matching `WholeBrain::with_seed(0.1, 0.1, seed)`,
+//! so a config only lists what it changes.
+
+use crate::TeachingSignals;
+use serde::{Deserialize, Serialize};
+use std::path::Path;
+use thiserror::Error;
+
+#[derive(Debug, Error)]
+pub enum ConfigError {
+    #[error("cannot read {path}: {source}")]
+    Io {
+        path: String,
+        source: std::io::Error,
+    },
+
+    #[error("TOML parse error: {0}")]
+    Toml(#[from] toml::de::Error),
+
+    #[error("JSON parse error: {0}")]
+    Json(#[from] serde_json::Error),
+
+    #[error("unsupported config extension for {0} (expected .toml or .json)")]
+    UnknownFormat(String),
+
+    #[error("{field}: {reason}")]
+    Invalid { field: String, reason: String },
+}
+
+fn invalid(field: impl Into<String>, reason: impl Into<String>) -> ConfigError {
+    ConfigError::Invalid {
+        field: field.into(),
+        reason: reason.into(),
+    }
+}
+
+/// Region sizes
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct RegionConfig {
+    /// Number of cortical columns
+    pub cortical_columns: usize,
+
+    /// Neurons per cortical column
+    pub neurons_per_column: usize,
+
+    /// Neurons per thalamic nucleus (VPL, LGN, MGN, TRN)
+    pub thalamic_neurons: usize,
+
+    /// Striatal medium spiny neurons (split evenly into D1 / D2)
+    pub striatal_neurons: usize,
+
+    /// Cortical inputs per striatal neuron
+    pub striatal_inputs: usize,
+
+    /// Hippocampal scale (DG 1000x, CA3 300x, CA1 400x)
+    pub hippocampal_scale: f64,
+
+    /// Lateral / basal amygdala neurons (central nucleus has half)
+    pub amygdala_neurons: usize,
+
+    /// Cerebellar granule cells (parallel fibers)
+    pub granule_cells: usize,
+
+    /// Purkinje cells (deep nuclei have half)
+    pub purkinje_cells: usize,
+}
+
+impl Default for RegionConfig {
+    fn default() -> Self {
+        Self::scaled(0.1)
+    }
+}
+
+impl RegionConfig {
+    /// Region sizes of `WholeBrain::new(scale, dt)`
+    pub fn scaled(scale: f64) -> Self {
+        Self {
+            cortical_columns: (100.0 * scale) as usize,
+            neurons_per_column: 100,
+            thalamic_neurons: (200.0 * scale) as usize,
+            striatal_neurons: (1000.0 * scale) as usize,
+            striatal_inputs: 100,
+            hippocampal_scale: scale,
+            amygdala_neurons: ((100.0 * scale) as usize).max(2),
+            granule_cells: ((1000.0 * scale) as usize).max(1),
+            purkinje_cells: ((100.0 * scale) as usize).max(2),
+        }
+    }
+}
+
+/// A point-to-point pathway between two regions
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct PathwayConfig {
+    /// Whether the pathway transmits at all
+    #[serde(default = "enabled")]
+    pub enabled: bool,
+
+    /// Gain applied to the transmitted signal (units depend on the pathway)
+    pub weight: f64,
+
+    /// Conduction delay (ms); rounded to whole time steps
+    #[serde(default)]
+    pub delay_ms: f64,
+}
+
+fn enabled() -> bool {
+    true
+}
+
+impl PathwayConfig {
+    fn new(weight: f64) -> Self {
+        Self {
+            enabled: true,
+            weight,
+            delay_ms: 0.0,
+        }
+    }
+
+    /// Effective gain: zero when the pathway is disabled
+    pub fn gain(&self) -> f64 {
+        if self.enabled {
+            self.weight
+        } else {
+            0.0
+        }
+    }
+
+    /// Delay in whole time steps
+    pub fn delay_steps(&self, dt: f64) -> usize {
+        (self.delay_ms / dt).round() as usize
+    }
+}
+
+/// Horizontal L2/3 / L5 projections between cortical columns
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct CorticoCorticalConfig {
+    pub enabled: bool,
+
+    /// Conductance per projecting spike (nS)
+    pub weight: f64,
+
+    /// Fixed delay (ms); distance-dependent axonal delay if omitted
+    pub delay_ms: Option<f64>,
+
+    /// Only connect columns closer than this (µm); all pairs if omitted
+    pub max_distance_um: Option<f64>,
+}
+
+impl Default for CorticoCorticalConfig {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            weight: 0.5,
+            delay_ms: None,
+            max_distance_um: None,
+        }
+    }
+}
+
+/// Inter-regional pathways
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct PathwaysConfig {
+    /// VPL spikes -> L4 (mV per relay spike)
+    pub thalamocortical: PathwayConfig,
+
+    /// L6 activity -> thalamic relay feedback (dimensionless gain)
+    pub corticothalamic: PathwayConfig,
+
+    /// L5 activity -> striatum (dimensionless gain)
+    pub corticostriatal: PathwayConfig,
+
+    /// GPi -> VPL disinhibition (mV at zero GPi activity)
+    pub pallidothalamic: PathwayConfig,
+
+    /// L2/3 activity -> entorhinal / dentate input (dimensionless gain)
+    pub cortico_hippocampal: PathwayConfig,
+
+    /// Column-to-column long-range projections
+    pub cortico_cortical: CorticoCorticalConfig,
+
+    /// VPL spikes -> lateral amygdala (activation per relay spike)
+    pub thalamo_amygdala: PathwayConfig,
+
+    /// Central amygdala output -> PVN stressor intensity (dimensionless gain)
+    pub amygdalo_hypothalamic: PathwayConfig,
+
+    /// Central amygdala output -> locus coeruleus salience (dimensionless gain)
+    pub amygdalo_coerulear: PathwayConfig,
+
+    /// L5 firing fraction -> pontine nuclei (dimensionless gain)
+    pub corticopontine: PathwayConfig,
+
+    /// Pontine mossy fibers -> granule cells (mV per step at full activity)
+    pub pontocerebellar: PathwayConfig,
+
+    /// Deep cerebellar nuclei -> VPL (mV/ms at full activity)
+    pub cerebellothalamic: PathwayConfig,
+
+    /// SNc dopamine / raphe 5-HT / LC NE -> extracellular volume
+    /// transmission (release gain, dimensionless)
+    pub neuromodulatory: PathwayConfig,
+}
+
+impl Default for PathwaysConfig {
+    fn default() -> Self {
+        Self {
+            thalamocortical: PathwayConfig::new(0.5),
+            corticothalamic: PathwayConfig::new(1.0),
+            corticostriatal: PathwayConfig::new(1.0),
+            pallidothalamic: PathwayConfig::new(5.0),
+            cortico_hippocampal: PathwayConfig::new(1.0),
+            cortico_cortical: CorticoCorticalConfig::default(),
+            thalamo_amygdala: PathwayConfig::new(5.0),
+            amygdalo_hypothalamic: PathwayConfig::new(1.0),
+            amygdalo_coerulear: PathwayConfig::new(1.0),
+            corticopontine: PathwayConfig::new(1.0),
+            pontocerebellar: PathwayConfig::new(5.0),
+            cerebellothalamic: PathwayConfig::new(0.1),
+            neuromodulatory: PathwayConfig::new(1.0),
+        }
+    }
+}
+
+/// Input driven by a stimulus
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum StimulusTarget {
+    /// Sensory drive to the thalamic relay (VPL)
+    Sensory,
+    /// Reward signal to the basal ganglia
+    Reward,
+    /// Aversive unconditioned stimulus (e.g. footshock) gating amygdalar
+    /// fear conditioning while above zero
+    Aversive,
+    /// Inferior-olive drive per Purkinje cell; above 0.5 evokes a complex
+    /// spike and parallel-fiber LTD
+    ClimbingFiber,
+}
+
+/// Time course of a stimulus
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(tag = "type", rename_all = "snake_case", deny_unknown_fields)]
+pub enum StimulusProtocol {
+    /// Constant for the whole run
+    Constant { amplitude: f64 },
+
+    /// Single square pulse
+    Pulse {
+        amplitude: f64,
+        start_ms: f64,
+        duration_ms: f64,
+    },
+
+    /// Square pulse train
+    Periodic {
+        amplitude: f64,
+        #[serde(default)]
+        start_ms: f64,
+        period_ms: f64,
+        width_ms: f64,
+    },
+}
+
+impl StimulusProtocol {
+    /// Amplitude at time `t` (ms)
+    pub fn value_at(&self, t: f64) -> f64 {
+        match *self {
+            Self::Constant { amplitude } => amplitude,
+            Self::Pulse {
+                amplitude,
+                start_ms,
+                duration_ms,
+            } => {
+                if t >= start_ms && t < start_ms + duration_ms {
+                    amplitude
+                } else {
+                    0.0
+                }
+            }
+            Self::Periodic {
+                amplitude,
+                start_ms,
+                period_ms,
+                width_ms,
+            } => {
+                if t >= start_ms && (t - start_ms) % period_ms < width_ms {
+                    amplitude
+                } else {
+                    0.0
+                }
+            }
+        }
+    }
+}
+
+/// A stimulus applied during the run
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct Stimulus {
+    pub target: StimulusTarget,
+    pub protocol: StimulusProtocol,
+
+    /// Channels driven (VPL neurons for sensory, Purkinje cells for
+    /// climbing-fiber stimuli); all if empty
+    #[serde(default)]
+    pub channels: Vec<usize>,
+}
+
+/// Parts of `BrainState` that can be recorded
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum RecordingTarget {
+    CorticalLayers,
+    Hippocampus,
+    Thalamus,
+    BasalGanglia,
+    Dopamine,
+    Amygdala,
+    Cerebellum,
+    Hypothalamus,
+    Brainstem,
+    Neuromodulators,
+}
+
+impl RecordingTarget {
+    pub const ALL: [RecordingTarget; 10] = [
+        RecordingTarget::CorticalLayers,
+        RecordingTarget::Hippocampus,
+        RecordingTarget::Thalamus,
+        RecordingTarget::BasalGanglia,
+        RecordingTarget::Dopamine,
+        RecordingTarget::Amygdala,
+        RecordingTarget::Cerebellum,
+        RecordingTarget::Hypothalamus,
+        RecordingTarget::Brainstem,
+        RecordingTarget::Neuromodulators,
+    ];
+}
+
+/// What to keep from each step
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct RecordingConfig {
+    pub targets: Vec<RecordingTarget>,
+
+    /// Record every n-th step
+    pub every: usize,
+}
+
+impl Default for RecordingConfig {
+    fn default() -> Self {
+        Self {
+            targets: RecordingTarget::ALL.to_vec(),
+            every: 1,
+        }
+    }
+}
+
+impl RecordingConfig {
+    pub fn records(&self, target: RecordingTarget) -> bool {
+        self.targets.contains(&target)
+    }
+}
+
+/// Complete whole-brain experiment
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct BrainConfig {
+    /// Time step (ms)
+    pub dt: f64,
+
+    /// Simulated duration (ms)
+    pub duration_ms: f64,
+
+    /// Master seed; fresh entropy if omitted
+    pub seed: Option<u64>,
+
+    /// Position in the environment seen by hippocampal place cells
+    pub position: [f64; 2],
+
+    pub regions: RegionConfig,
+    pub pathways: PathwaysConfig,
+    pub stimuli: Vec<Stimulus>,
+    pub recording: RecordingConfig,
+}
+
+impl Default for BrainConfig {
+    fn default() -> Self {
+        Self::scaled(0.1, 0.1)
+    }
+}
+
+impl BrainConfig {
+    /// Configuration equivalent to `WholeBrain::new(scale, dt)`
+    pub fn scaled(scale: f64, dt: f64) -> Self {
+        Self {
+            dt,
+            duration_ms: 100.0,
+            seed: None,
+            position: [0.0, 0.0],
+            regions: RegionConfig::scaled(scale),
+            pathways: PathwaysConfig::default(),
+            stimuli: Vec::new(),
+            recording: RecordingConfig::default(),
+        }
+    }
+
+    /// Parse and validate a TOML config
+    pub fn from_toml_str(text: &str) -> Result<Self, ConfigError> {
+        let config: Self = toml::from_str(text)?;
+        config.validate()?;
+        Ok(config)
+    }
+
+    /// Parse and validate a JSON config
+    pub fn from_json_str(text: &str) -> Result<Self, ConfigError> {
+        let config: Self = serde_json::from_str(text)?;
+        config.validate()?;
+        Ok(config)
+    }
+
+    /// Load a `.toml` or `.json` config file
+    pub fn from_file(path: &Path) -> Result<Self, ConfigError> {
+        let text = std::fs::read_to_string(path).map_err(|source| ConfigError::Io {
+            path: path.display().to_string(),
+            source,
+        })?;
+
+        match path.extension().and_then(|e| e.to_str()) {
+            Some("toml") => Self::from_toml_str(&text),
+            Some("json") => Self::from_json_str(&text),
+            _ => Err(ConfigError::UnknownFormat(path.display().to_string())),
+        }
+    }
+
+    /// Number of simulation steps in the run
+    pub fn num_steps(&self) -> usize {
+        (self.duration_ms / self.dt).round() as usize
+    }
+
+    /// Sensory drive per VPL channel at time `t` (ms)
+    pub fn sensory_at(&self, t: f64) -> Vec<f64> {
+        self.channel_drive(StimulusTarget::Sensory, self.regions.thalamic_neurons, t)
+    }
+
+    /// Aversive stimulus and climbing-fiber drive at time `t` (ms)
+    pub fn teaching_at(&self, t: f64) -> TeachingSignals {
+        TeachingSignals {
+            aversive: self.total_at(StimulusTarget::Aversive, t),
+            climbing_fiber: self.channel_drive(StimulusTarget::ClimbingFiber, self.regions.purkinje_cells, t),
+        }
+    }
+
+    /// Summed drive of the `target` stimuli on each of `n` channels
+    fn channel_drive(&self, target: StimulusTarget, n: usize, t: f64) -> Vec<f64> {
+        let mut drive = vec![0.0; n];
+        for stimulus in self.stimuli.iter().filter(|s| s.target == target) {
+            let value = stimulus.protocol.value_at(t);
+            if value == 0.0 {
+                continue;
+            }
+            if stimulus.channels.is_empty() {
+                drive.iter_mut().for_each(|d| *d += value);
+            } else {
+                for &ch in &stimulus.channels {
+                    drive[ch] += value;
+                }
+            }
+        }
+        drive
+    }
+
+    /// Total reward signal at time `t` (ms)
+    pub fn reward_at(&self, t: f64) -> f64 {
+        self.total_at(StimulusTarget::Reward, t)
+    }
+
+    /// Summed amplitude of the channel-less `target` stimuli
+    fn total_at(&self, target: StimulusTarget, t: f64) -> f64 {
+        self.stimuli
+            .iter()
+            .filter(|s| s.target == target)
+            .map(|s| s.protocol.value_at(t))
+            .sum()
+    }
+
+    /// Check every field, reporting the first problem with its path
+    pub fn validate(&self) -> Result<(), ConfigError> {
+        positive("dt", self.dt)?;
+        non_negative("duration_ms", self.duration_ms)?;
+        for (i, x) in self.position.iter().enumerate() {
+            finite(&format!("position[{}]", i), *x)?;
+        }
+
+        let r = &self.regions;
+        for (field, n) in [
+            ("regions.cortical_columns", r.cortical_columns),
+            ("regions.neurons_per_column", r.neurons_per_column),
+            ("regions.thalamic_neurons", r.thalamic_neurons),
+            ("regions.striatal_neurons", r.striatal_neurons),
+        ] {
+            if n == 0 {
+                return Err(invalid(field, "must be at least 1"));
+            }
+        }
+        if r.granule_cells == 0 {
+            return Err(invalid("regions.granule_cells", "must be at least 1"));
+        }
+        for (field, n) in [
+            ("regions.amygdala_neurons", r.amygdala_neurons),
+            ("regions.purkinje_cells", r.purkinje_cells),
+        ] {
+            if n < 2 {
+                return Err(invalid(
+                    field,
+                    format!("must be at least 2 to populate the output nucleus (got {})", n),
+                ));
+            }
+        }
+        if r.striatal_neurons < 4 {
+            return Err(invalid(
+                "regions.striatal_neurons",
+                format!("must be at least 4 to populate D1, D2, GP and STN (got {})", r.striatal_neurons),
+            ));
+        }
+        positive("regions.hippocampal_scale", r.hippocampal_scale)?;
+
+        let p = &self.pathways;
+        for (name, pathway) in [
+            ("thalamocortical", &p.thalamocortical),
+            ("corticothalamic", &p.corticothalamic),
+            ("corticostriatal", &p.corticostriatal),
+            ("pallidothalamic", &p.pallidothalamic),
+            ("cortico_hippocampal", &p.cortico_hippocampal),
+            ("thalamo_amygdala", &p.thalamo_amygdala),
+            ("amygdalo_hypothalamic", &p.amygdalo_hypothalamic),
+            ("amygdalo_coerulear", &p.amygdalo_coerulear),
+            ("corticopontine", &p.corticopontine),
+            ("pontocerebellar", &p.pontocerebellar),
+            ("cerebellothalamic", &p.cerebellothalamic),
+            ("neuromodulatory", &p.neuromodulatory),
+        ] {
+            finite(&format!("pathways.{}.weight", name), pathway.weight)?;
+            non_negative(&format!("pathways.{}.delay_ms", name), pathway.delay_ms)?;
+        }
+
+        let cc = &p.cortico_cortical;
+        non_negative("pathways.cortico_cortical.weight", cc.weight)?;
+        if let Some(delay) = cc.delay_ms {
+            non_negative("pathways.cortico_cortical.delay_ms", delay)?;
+        }
+        if let Some(distance) = cc.max_distance_um {
+            positive("pathways.cortico_cortical.max_distance_um", distance)?;
+        }
+
+        for (i, stimulus) in self.stimuli.iter().enumerate() {
+            let field = |name: &str| format!("stimuli[{}].{}", i, name);
+            match stimulus.protocol {
+                StimulusProtocol::Constant { amplitude } => {
+                    finite(&field("protocol.amplitude"), amplitude)?;
+                }
+                StimulusProtocol::Pulse {
+                    amplitude,
+                    start_ms,
+                    duration_ms,
+                } => {
+                    finite(&field("protocol.amplitude"), amplitude)?;
+                    non_negative(&field("protocol.start_ms"), start_ms)?;
+                    non_negative(&field("protocol.duration_ms"), duration_ms)?;
+                }
+                StimulusProtocol::Periodic {
+                    amplitude,
+                    start_ms,
+                    period_ms,
+                    width_ms,
+                } => {
+                    finite(&field("protocol.amplitude"), amplitude)?;
+                    non_negative(&field("protocol.start_ms"), start_ms)?;
+                    positive(&field("protocol.period_ms"), period_ms)?;
+                    non_negative(&field("protocol.width_ms"), width_ms)?;
+                    if width_ms > period_ms {
+                        return Err(invalid(
+                            field("protocol.width_ms"),
+                            format!("must not exceed period_ms ({} > {})", width_ms, period_ms),
+                        ));
+                    }
+                }
+            }
+
+            let channels = match stimulus.target {
+                StimulusTarget::Sensory => Some(("regions.thalamic_neurons", r.thalamic_neurons)),
+                StimulusTarget::ClimbingFiber => Some(("regions.purkinje_cells", r.purkinje_cells)),
+                StimulusTarget::Reward | StimulusTarget::Aversive => None,
+            };
+            match channels {
+                Some((region, n)) => {
+                    if let Some(&ch) = stimulus.channels.iter().find(|&&ch| ch >= n) {
+                        return Err(invalid(
+                            field("channels"),
+                            format!("channel {} out of range ({} = {})", ch, region, n),
+                        ));
+                    }
+                }
+                None => {
+                    if !stimulus.channels.is_empty() {
+                        return Err(invalid(
+                            field("channels"),
+                            format!("{:?} stimuli have no channels", stimulus.target).to_lowercase(),
+                        ));
+                    }
+                }
+            }
+        }
+
+        if self.recording.every == 0 {
+            return Err(invalid("recording.every", "must be at least 1"));
+        }
+
+        Ok(())
+    }
+}
+
+fn finite(field: &str, value: f64) -> Result<(), ConfigError> {
+    if value.is_finite() {
+        Ok(())
+    } else {
+        Err(invalid(field, format!("must be finite (got {})", value)))
+    }
+}
+
+fn non_negative(field: &str, value: f64) -> Result<(), ConfigError> {
+    finite(field, value)?;
+    if value < 0.0 {
+        return Err(invalid(field, format!("must be non-negative (got {})", value)));
+    }
+    Ok(())
+}
+
+fn positive(field: &str, value: f64) -> Result<(), ConfigError> {
+    finite(field, value)?;
+    if value <= 0.0 {
+        return Err(invalid(field, format!("must be positive (got {})", value)));
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const EXAMPLE: &str = r#"
+        dt = 0.1
+        duration_ms = 5.0
+        seed = 42
+
+        [regions]
+        cortical_columns = 4
+        thalamic_neurons = 8
+
+        [pathways.thalamocortical]
+        weight = 0.8
+        delay_ms = 2.0
+
+        [pathways.pallidothalamic]
+        enabled = false
+        weight = 5.0
+
+        [[stimuli]]
+        target = "sensory"
+        channels = [0, 1]
+        protocol = { type = "pulse", amplitude = 20.0, start_ms = 1.0, duration_ms = 2.0 }
+
+        [[stimuli]]
+        target = "reward"
+        protocol = { type = "periodic", amplitude = 1.0, period_ms = 2.0, width_ms = 0.5 }
+
+        [recording]
+        targets = ["cortical_layers", "dopamine"]
+        every = 5
+    "#;
+
+    #[test]
+    fn test_toml_example_parses() {
+        let config = BrainConfig::from_toml_str(EXAMPLE).unwrap();
+        assert_eq!(config.regions.cortical_columns, 4);
+        assert_eq!(config.regions.neurons_per_column, 100);
+        assert_eq!(config.pathways.thalamocortical.delay_steps(config.dt), 20);
+        assert_eq!(config.pathways.pallidothalamic.gain(), 0.0);
+        assert_eq!(config.num_steps(), 50);
+
+        assert_eq!(config.sensory_at(0.5), vec![0.0; 8]);
+        assert_eq!(config.sensory_at(1.5)[..3], [20.0, 20.0, 0.0]);
+        assert_eq!(config.reward_at(0.2), 1.0);
+        assert_eq!(config.reward_at(1.0), 0.0);
+    }
+
+    #[test]
+    fn test_json_round_trip() {
+        let config = BrainConfig::from_toml_str(EXAMPLE).unwrap();
+        let json = serde_json::to_string(&config).unwrap();
+        assert_eq!(BrainConfig::from_json_str(&json).unwrap(), config);
+    }
+
+    #[test]
+    fn test_validation_errors_name_the_field() {
+        let err = BrainConfig::from_toml_str("dt = -0.1").unwrap_err();
+        assert_eq!(err.to_string(), "dt: must be positive (got -0.1)");
+
+        let err = BrainConfig::from_toml_str(
+            "[[stimuli]]\ntarget = \"sensory\"\nchannels = [99]\nprotocol = { type = \"constant\", amplitude = 1.0 }",
+        )
+        .unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "stimuli[0].channels: channel 99 out of range (regions.thalamic_neurons = 20)"
+        );
+
+        let err = BrainConfig::from_toml_str("[pathways.corticostriatal]\nweight = 1.0\ndelay_ms = -2.0").unwrap_err();
+        assert!(err.to_string().starts_with("pathways.corticostriatal.delay_ms"));
+    }
+
+    #[test]
+    fn test_teaching_signals_follow_stimuli() {
+        let config = BrainConfig::from_toml_str(r#"
+            [regions]
+            purkinje_cells = 4
+
+            [[stimuli]]
+            target = "aversive"
+            protocol = { type = "pulse", amplitude = 1.0, start_ms = 2.0, duration_ms = 1.0 }
+
+            [[stimuli]]
+            target = "climbing_fiber"
+            channels = [1, 3]
+            protocol = { type = "constant", amplitude = 1.0 }
+        "#).unwrap();
+
+        assert_eq!(config.teaching_at(1.0).aversive, 0.0);
+        assert_eq!(config.teaching_at(2.5).aversive, 1.0);
+        assert_eq!(config.teaching_at(0.0).climbing_fiber, vec![0.0, 1.0, 0.0, 1.0]);
+        assert_eq!(config.sensory_at(2.5), vec![0.0; 20]);
+
+        let err = BrainConfig::from_toml_str(
+            "[[stimuli]]\ntarget = \"climbing_fiber\"\nchannels = [10]\nprotocol = { type = \"constant\", amplitude = 1.0 }",
+        )
+        .unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "stimuli[0].channels: channel 10 out of range (regions.purkinje_cells = 10)"
+        );
+    }
+
+    #[test]
+    fn test_shipped_configs_are_valid() {
+        let path = Path::new(env!("CARGO_MANIFEST_DIR")).join("configs/sensory_pulse.toml");
+        let config = BrainConfig::from_file(&path).unwrap();
+        assert!(config.pathways.cortico_cortical.enabled);
+    }
+
+    #[test]
+    fn test_unknown_fields_are_rejected() {
+        let err = BrainConfig::from_toml_str("[regions]\ncortical_colums = 4").unwrap_err();
+        assert!(matches!(err, ConfigError::Toml(_)));
+        assert!(err.to_string().contains("cortical_colums"));
+    }
+}
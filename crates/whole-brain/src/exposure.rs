@@ -0,0 +1,141 @@
+//! Drug exposure of the simulated brain.
+//!
+//! A [`DrugExposure`] couples the pharmacology crate to the network: at
+//! every step its brain concentration (constant, or from a dosing regimen
+//! through `RegimenModel`) is bound to a `MultiReceptorSystem` together with
+//! the other drugs acting on the same region. GABA_A occupancy sets the
+//! amplitude and decay of the `SynapseType::GABAA` synapses, the other
+//! receptor targets the release and conductance of the glutamatergic,
+//! inhibitory and monoaminergic synapses and the neurons' membrane
+//! conductances. Sedation and changes in EEG power then follow from the
+//! network's own activity rather than from the receptor model's lookup
+//! formulas.
+//!
+//! Pharmacokinetics run on a clock of their own: `start_h` is the regimen
+//! time at simulation time 0 and `hours_per_ms` the regimen time elapsing
+//! per simulated millisecond (real time by default), so a short run can be
+//! placed at any point of a multi-day regimen or sweep through it quickly.
+//!
+//! Only the cortical columns carry typed synapses and compartmental neurons;
+//! the other regions are not affected yet.
+
+use pharmacology::{DosingRegimen, IpscModulation, MultiReceptorProfile, MultiReceptorSystem, RegimenModel};
+use serde::{Deserialize, Serialize};
+
+/// Milliseconds per hour
+const MS_PER_HOUR: f64 = 3.6e6;
+
+/// Where a drug acts
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum DrugTarget {
+    /// Every cortical column
+    Cortex,
+    /// One cortical column
+    CorticalColumn(usize),
+}
+
+impl DrugTarget {
+    /// Whether cortical column `column` is targeted
+    pub fn covers_column(&self, column: usize) -> bool {
+        match self {
+            DrugTarget::Cortex => true,
+            DrugTarget::CorticalColumn(c) => *c == column,
+        }
+    }
+}
+
+/// Source of the free brain concentration
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub enum ExposureSource {
+    /// Fixed concentration (uM)
+    Constant(f64),
+    /// Concentration time course of a dosing regimen
+    Regimen { model: RegimenModel, regimen: DosingRegimen },
+}
+
+/// A drug acting on the receptors of chosen regions
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DrugExposure {
+    pub drug: MultiReceptorProfile,
+    pub source: ExposureSource,
+    pub targets: Vec<DrugTarget>,
+    /// Regimen time at simulation time 0 (hours)
+    pub start_h: f64,
+    /// Regimen time elapsing per simulated millisecond (hours)
+    pub hours_per_ms: f64,
+}
+
+impl DrugExposure {
+    /// Constant brain concentration (uM) throughout the cortex
+    pub fn constant(drug: impl Into<MultiReceptorProfile>, concentration_um: f64) -> Self {
+        Self::new(drug.into(), ExposureSource::Constant(concentration_um))
+    }
+
+    /// Concentration of `regimen` in the patient described by `model`,
+    /// throughout the cortex
+    pub fn regimen(drug: impl Into<MultiReceptorProfile>, model: RegimenModel, regimen: DosingRegimen) -> Self {
+        Self::new(drug.into(), ExposureSource::Regimen { model, regimen })
+    }
+
+    fn new(drug: MultiReceptorProfile, source: ExposureSource) -> Self {
+        Self {
+            drug,
+            source,
+            targets: vec![DrugTarget::Cortex],
+            start_h: 0.0,
+            hours_per_ms: 1.0 / MS_PER_HOUR,
+        }
+    }
+
+    /// Builder: restrict the drug to `targets`
+    pub fn with_targets(mut self, targets: Vec<DrugTarget>) -> Self {
+        self.targets = targets;
+        self
+    }
+
+    /// Builder: start the simulation `start_h` hours into the regimen and
+    /// advance it `hours_per_ms` hours per simulated millisecond
+    pub fn with_clock(mut self, start_h: f64, hours_per_ms: f64) -> Self {
+        self.start_h = start_h;
+        self.hours_per_ms = hours_per_ms;
+        self
+    }
+
+    /// Regimen time (hours) at simulation time `time_ms`
+    pub fn regimen_time_h(&self, time_ms: f64) -> f64 {
+        self.start_h + time_ms * self.hours_per_ms
+    }
+
+    /// Free brain concentration (uM) at simulation time `time_ms`
+    pub fn brain_concentration_um(&self, time_ms: f64) -> f64 {
+        match &self.source {
+            ExposureSource::Constant(concentration) => *concentration,
+            ExposureSource::Regimen { model, regimen } => {
+                model.brain_um(model.concentration_at(regimen, self.regimen_time_h(time_ms)))
+            }
+        }
+    }
+
+    /// Bind the drug at its concentration at simulation time `time_ms`
+    pub fn bind_to(&self, receptors: &mut MultiReceptorSystem, time_ms: f64) {
+        receptors.bind(&self.drug, self.brain_concentration_um(time_ms));
+    }
+
+    /// Receptors exposed to this drug alone at simulation time `time_ms`
+    pub fn receptors(&self, time_ms: f64) -> MultiReceptorSystem {
+        let mut receptors = MultiReceptorSystem::new();
+        self.bind_to(&mut receptors, time_ms);
+        receptors
+    }
+
+    /// Effect on GABA-A synapses at simulation time `time_ms`
+    pub fn ipsc_modulation(&self, time_ms: f64) -> IpscModulation {
+        self.receptors(time_ms).gaba_a_modulation()
+    }
+
+    /// Whether cortical column `column` is among the targets
+    pub fn targets_column(&self, column: usize) -> bool {
+        self.targets.iter().any(|target| target.covers_column(column))
+    }
+}
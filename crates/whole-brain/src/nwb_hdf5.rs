@@ -0,0 +1,228 @@
+//! HDF5 backend for the recorder, writing the NWB-style layout described in
+//! [`crate::recorder`]. Enabled with the `hdf5` feature (needs libhdf5).
+
+use crate::recorder::{RecorderError, RecordingSink, Result, SeriesSpec, SessionInfo, UnitsTable};
+use hdf5::types::VarLenUnicode;
+use hdf5::{Dataset, File, Location};
+use hdf5_ndarray::ArrayView2;
+use std::collections::HashMap;
+use std::path::Path;
+
+/// NWB schema version the layout follows
+const NWB_VERSION: &str = "2.6.0";
+
+impl From<hdf5::Error> for RecorderError {
+    fn from(e: hdf5::Error) -> Self {
+        RecorderError::Storage(e.to_string())
+    }
+}
+
+fn text(value: &str) -> Result<VarLenUnicode> {
+    value
+        .parse()
+        .map_err(|e| RecorderError::Storage(format!("invalid string {value:?}: {e}")))
+}
+
+fn write_text_attr(location: &Location, name: &str, value: &str) -> Result<()> {
+    location.new_attr::<VarLenUnicode>().create(name)?.write_scalar(&text(value)?)?;
+    Ok(())
+}
+
+/// Extendable `data` / `timestamps` pair of one TimeSeries
+struct Hdf5Series {
+    data: Dataset,
+    timestamps: Dataset,
+    width: usize,
+    len: usize,
+}
+
+/// Streams recorder output into an HDF5 file readable with pynwb / h5py
+pub struct Hdf5Sink {
+    file: Option<File>,
+    series: HashMap<String, Hdf5Series>,
+}
+
+impl Hdf5Sink {
+    /// Create (or truncate) the output file
+    pub fn create(path: impl AsRef<Path>) -> Result<Self> {
+        Ok(Self {
+            file: Some(File::create(path)?),
+            series: HashMap::new(),
+        })
+    }
+
+    fn file(&self) -> Result<&File> {
+        self.file
+            .as_ref()
+            .ok_or_else(|| RecorderError::Storage("recording already finished".into()))
+    }
+}
+
+impl RecordingSink for Hdf5Sink {
+    fn begin(&mut self, session: &SessionInfo) -> Result<()> {
+        let file = self.file()?;
+        write_text_attr(file, "nwb_version", NWB_VERSION)?;
+        write_text_attr(file, "namespace", "core")?;
+        write_text_attr(file, "neurodata_type", "NWBFile")?;
+        write_text_attr(file, "identifier", &session.identifier)?;
+        write_text_attr(file, "session_description", &session.description)?;
+
+        let general = file.create_group("general")?;
+        general.new_dataset::<u64>().create("seed")?.write_scalar(&session.seed)?;
+        general.new_dataset::<f64>().create("dt_ms")?.write_scalar(&session.dt)?;
+        Ok(())
+    }
+
+    fn create_series(&mut self, spec: &SeriesSpec) -> Result<()> {
+        // HDF5 chunks span at least one element along every axis
+        if spec.width == 0 {
+            return Err(RecorderError::InvalidConfig(format!("time series {} has no channels", spec.path)));
+        }
+
+        let group = self.file()?.create_group(&spec.path)?;
+        write_text_attr(&group, "neurodata_type", "TimeSeries")?;
+        write_text_attr(&group, "namespace", "core")?;
+        write_text_attr(&group, "description", &spec.description)?;
+
+        let data = group
+            .new_dataset::<f64>()
+            .chunk((spec.chunk_size, spec.width))
+            .shape((0.., spec.width))
+            .create("data")?;
+        write_text_attr(&data, "unit", spec.unit)?;
+        data.new_attr::<f64>().create("conversion")?.write_scalar(&1.0)?;
+
+        let timestamps = group
+            .new_dataset::<f64>()
+            .chunk(spec.chunk_size)
+            .shape(0..)
+            .create("timestamps")?;
+        // NWB timestamps are in seconds; the simulation clock runs in ms
+        write_text_attr(&timestamps, "unit", "seconds")?;
+
+        self.series.insert(
+            spec.path.clone(),
+            Hdf5Series { data, timestamps, width: spec.width, len: 0 },
+        );
+        Ok(())
+    }
+
+    fn append(&mut self, path: &str, timestamps: &[f64], data: &[f64]) -> Result<()> {
+        let series = self
+            .series
+            .get_mut(path)
+            .ok_or_else(|| RecorderError::UnknownSeries(path.to_string()))?;
+
+        // The buffered samples go out as one block of rows
+        let rows = ArrayView2::from_shape((timestamps.len(), series.width), data).map_err(|_| {
+            RecorderError::Storage(format!(
+                "{path}: {} values for {} samples of width {}",
+                data.len(),
+                timestamps.len(),
+                series.width
+            ))
+        })?;
+
+        let start = series.len;
+        let end = start + timestamps.len();
+        series.data.resize((end, series.width))?;
+        series.timestamps.resize(end)?;
+
+        let seconds: Vec<f64> = timestamps.iter().map(|t| t / 1000.0).collect();
+        series.timestamps.write_slice(&seconds[..], (start..end,))?;
+        series.data.write_slice(rows, (start..end, ..))?;
+
+        series.len = end;
+        Ok(())
+    }
+
+    fn write_units(&mut self, units: &UnitsTable) -> Result<()> {
+        let group = self.file()?.create_group("units")?;
+        write_text_attr(&group, "neurodata_type", "Units")?;
+        write_text_attr(&group, "namespace", "core")?;
+        write_text_attr(&group, "colnames", "spike_times column layer")?;
+        write_text_attr(&group, "description", "Cortical neurons, spike onsets from soma threshold crossings")?;
+
+        let (spike_times, index) = units.ragged();
+        let seconds: Vec<f64> = spike_times.iter().map(|t| t / 1000.0).collect();
+        let layers = units.layer.iter().map(|l| text(l)).collect::<Result<Vec<_>>>()?;
+
+        group.new_dataset_builder().with_data(&units.ids[..]).create("id")?;
+        group.new_dataset_builder().with_data(&seconds[..]).create("spike_times")?;
+        group.new_dataset_builder().with_data(&index[..]).create("spike_times_index")?;
+        group.new_dataset_builder().with_data(&units.column[..]).create("column")?;
+        group.new_dataset_builder().with_data(&layers[..]).create("layer")?;
+        Ok(())
+    }
+
+    fn finish(&mut self) -> Result<()> {
+        self.series.clear();
+        if let Some(file) = self.file.take() {
+            file.flush()?;
+            file.close()?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn series(width: usize) -> SeriesSpec {
+        SeriesSpec {
+            path: "processing/test/ramp".into(),
+            width,
+            unit: "mV",
+            description: "Ramp".into(),
+            chunk_size: 4,
+        }
+    }
+
+    #[test]
+    fn test_recording_round_trips_through_hdf5() {
+        let path = std::env::temp_dir().join(format!("recording-{}.nwb", std::process::id()));
+        let mut sink = Hdf5Sink::create(&path).unwrap();
+        let session = SessionInfo { identifier: "test".into(), description: "Round trip".into(), seed: 7, dt: 0.1 };
+        sink.begin(&session).unwrap();
+
+        let spec = series(3);
+        sink.create_series(&spec).unwrap();
+        let data: Vec<f64> = (0..30).map(f64::from).collect();
+        let times: Vec<f64> = (1..=10).map(|i| f64::from(i) * 0.1).collect();
+        sink.append(&spec.path, &times[..4], &data[..12]).unwrap();
+        sink.append(&spec.path, &times[4..], &data[12..]).unwrap();
+        // A ragged block is refused before the datasets grow
+        assert!(sink.append(&spec.path, &times[..1], &data[..2]).is_err());
+
+        let units = UnitsTable {
+            ids: vec![0, 1],
+            column: vec![0, 0],
+            layer: vec!["layer4".into(), "layer5".into()],
+            spike_times: vec![vec![1.0, 2.5], vec![]],
+        };
+        sink.write_units(&units).unwrap();
+        sink.finish().unwrap();
+
+        let file = File::open(&path).unwrap();
+        let stored = file.dataset("processing/test/ramp/data").unwrap();
+        assert_eq!(stored.shape(), vec![10, 3]);
+        assert_eq!(stored.read_raw::<f64>().unwrap(), data);
+        let seconds = file.dataset("processing/test/ramp/timestamps").unwrap().read_raw::<f64>().unwrap();
+        assert_eq!(seconds.len(), 10);
+        assert!((seconds[9] - 1e-3).abs() < 1e-12);
+        assert_eq!(file.dataset("general/seed").unwrap().read_scalar::<u64>().unwrap(), 7);
+        assert_eq!(file.dataset("units/spike_times_index").unwrap().read_raw::<u64>().unwrap(), vec![2, 2]);
+        assert_eq!(file.dataset("units/spike_times").unwrap().read_raw::<f64>().unwrap(), vec![1e-3, 2.5e-3]);
+        std::fs::remove_file(path).ok();
+    }
+
+    #[test]
+    fn test_series_without_channels_are_rejected() {
+        let path = std::env::temp_dir().join(format!("empty-{}.nwb", std::process::id()));
+        let mut sink = Hdf5Sink::create(&path).unwrap();
+        assert!(matches!(sink.create_series(&series(0)), Err(RecorderError::InvalidConfig(_))));
+        sink.finish().unwrap();
+        std::fs::remove_file(path).ok();
+    }
+}
@@ -0,0 +1,638 @@
+//! Probe-based recording of simulation outputs in an NWB-style layout.
+//!
+//! A [`Recorder`] observes the brain after every step, keeps only the chosen
+//! probes, decimates continuous signals and hands them to a [`RecordingSink`]
+//! in fixed-size chunks, so long runs never hold their whole output in memory.
+//!
+//! ## Layout (Neurodata Without Borders 2.x conventions)
+//! ```text
+//! /                                   nwb_version, identifier, session_description
+//! /general/seed
+//! /acquisition/soma_voltage           TimeSeries [time x cortical neurons] (mV)
+//! /processing/cortex/layer1 .. layer6 TimeSeries [time x columns] (mV)
+//! /processing/neuromodulation/dopamine TimeSeries [time x 1]
+//! /processing/pharmacology/<drug>     TimeSeries [time x 1] (uM)
+//! /units/spike_times, spike_times_index, id, column, layer
+//! ```
+//! Every TimeSeries is a group holding `data` and `timestamps` datasets.
+//! Spike times are detected at full resolution (never decimated) and written
+//! as the NWB ragged `units` table when the recording is finished.
+//!
+//! [`MemorySink`] keeps everything in memory (tests, in-process analysis);
+//! `Hdf5Sink` (feature `hdf5`) writes an HDF5 file readable with pynwb/h5py.
+
+use crate::{BrainState, WholeBrain};
+use cortex::layers::LayerType;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use thiserror::Error;
+
+#[cfg(feature = "hdf5")]
+pub use crate::nwb_hdf5::Hdf5Sink;
+
+#[derive(Debug, Error)]
+pub enum RecorderError {
+    #[error("Storage error: {0}")]
+    Storage(String),
+
+    #[error("Unknown time series: {0}")]
+    UnknownSeries(String),
+
+    #[error("Invalid recorder config: {0}")]
+    InvalidConfig(String),
+}
+
+pub type Result<T> = std::result::Result<T, RecorderError>;
+
+/// Signals a recorder can subscribe to
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(tag = "probe", rename_all = "snake_case")]
+pub enum Probe {
+    /// Spike onset times of every cortical neuron (units table)
+    SpikeTimes,
+    /// Soma voltage of every cortical neuron
+    SomaVoltage,
+    /// Layer-averaged soma voltage per column (`CorticalLayerActivity`)
+    LayerActivity,
+    /// SNc dopamine level
+    Dopamine,
+    /// Brain concentration of a drug across its [`WholeBrain::drug_exposures`]
+    DrugConcentration { drug: String },
+}
+
+/// Which probes to record and how
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct RecorderConfig {
+    pub probes: Vec<Probe>,
+
+    /// Sample continuous signals every n-th step
+    pub every: usize,
+
+    /// Samples buffered per time series before a write
+    pub chunk_size: usize,
+
+    /// Free-text description stored in the file
+    pub session_description: String,
+}
+
+impl Default for RecorderConfig {
+    fn default() -> Self {
+        Self {
+            probes: vec![Probe::SpikeTimes, Probe::LayerActivity, Probe::Dopamine],
+            every: 1,
+            chunk_size: 1024,
+            session_description: "HumanBrain whole-brain simulation".to_string(),
+        }
+    }
+}
+
+/// Static description of one time series
+#[derive(Debug, Clone, PartialEq)]
+pub struct SeriesSpec {
+    /// Group path inside the file, e.g. `processing/cortex/layer5`
+    pub path: String,
+    /// Channels per sample
+    pub width: usize,
+    pub unit: &'static str,
+    pub description: String,
+    /// Rows per storage chunk
+    pub chunk_size: usize,
+}
+
+/// File-level NWB metadata
+#[derive(Debug, Clone, PartialEq)]
+pub struct SessionInfo {
+    pub identifier: String,
+    pub description: String,
+    pub seed: u64,
+    pub dt: f64,
+}
+
+/// NWB units table: one row per recorded neuron
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct UnitsTable {
+    pub ids: Vec<u64>,
+    pub column: Vec<u64>,
+    pub layer: Vec<String>,
+    /// Spike onset times (ms), one list per unit
+    pub spike_times: Vec<Vec<f64>>,
+}
+
+impl UnitsTable {
+    /// Flattened spike times and the NWB `spike_times_index` (exclusive end
+    /// offset of each unit's spikes)
+    pub fn ragged(&self) -> (Vec<f64>, Vec<u64>) {
+        let mut flat = Vec::new();
+        let mut index = Vec::with_capacity(self.spike_times.len());
+        for times in &self.spike_times {
+            flat.extend_from_slice(times);
+            index.push(flat.len() as u64);
+        }
+        (flat, index)
+    }
+}
+
+/// Storage backend of a [`Recorder`]
+pub trait RecordingSink {
+    /// Write file-level metadata
+    fn begin(&mut self, session: &SessionInfo) -> Result<()>;
+
+    /// Declare an extendable time series
+    fn create_series(&mut self, spec: &SeriesSpec) -> Result<()>;
+
+    /// Append `timestamps.len()` samples; `data` is row-major, `width` values per row
+    fn append(&mut self, path: &str, timestamps: &[f64], data: &[f64]) -> Result<()>;
+
+    /// Write the spike units table
+    fn write_units(&mut self, units: &UnitsTable) -> Result<()>;
+
+    /// Flush and close
+    fn finish(&mut self) -> Result<()>;
+}
+
+/// A time series held in memory
+#[derive(Debug, Clone, PartialEq)]
+pub struct MemorySeries {
+    pub spec: SeriesSpec,
+    pub timestamps: Vec<f64>,
+    pub data: Vec<f64>,
+    /// Number of `append` calls received (one per flushed chunk)
+    pub writes: usize,
+}
+
+impl MemorySeries {
+    /// Sample `i` as a slice of `width` channels
+    pub fn row(&self, i: usize) -> &[f64] {
+        let w = self.spec.width;
+        &self.data[i * w..(i + 1) * w]
+    }
+
+    /// Trace of channel `c` across all samples
+    pub fn channel(&self, c: usize) -> Vec<f64> {
+        self.data.iter().skip(c).step_by(self.spec.width).copied().collect()
+    }
+}
+
+/// In-memory sink mirroring the NWB layout
+#[derive(Debug, Clone, Default)]
+pub struct MemorySink {
+    pub session: Option<SessionInfo>,
+    pub series: HashMap<String, MemorySeries>,
+    pub units: Option<UnitsTable>,
+    pub finished: bool,
+}
+
+impl RecordingSink for MemorySink {
+    fn begin(&mut self, session: &SessionInfo) -> Result<()> {
+        self.session = Some(session.clone());
+        Ok(())
+    }
+
+    fn create_series(&mut self, spec: &SeriesSpec) -> Result<()> {
+        self.series.insert(
+            spec.path.clone(),
+            MemorySeries { spec: spec.clone(), timestamps: Vec::new(), data: Vec::new(), writes: 0 },
+        );
+        Ok(())
+    }
+
+    fn append(&mut self, path: &str, timestamps: &[f64], data: &[f64]) -> Result<()> {
+        let series = self
+            .series
+            .get_mut(path)
+            .ok_or_else(|| RecorderError::UnknownSeries(path.to_string()))?;
+        debug_assert_eq!(data.len(), timestamps.len() * series.spec.width);
+        series.timestamps.extend_from_slice(timestamps);
+        series.data.extend_from_slice(data);
+        series.writes += 1;
+        Ok(())
+    }
+
+    fn write_units(&mut self, units: &UnitsTable) -> Result<()> {
+        self.units = Some(units.clone());
+        Ok(())
+    }
+
+    fn finish(&mut self) -> Result<()> {
+        self.finished = true;
+        Ok(())
+    }
+}
+
+/// Pending samples of one time series
+#[derive(Debug)]
+struct SeriesBuffer {
+    path: String,
+    width: usize,
+    timestamps: Vec<f64>,
+    data: Vec<f64>,
+}
+
+impl SeriesBuffer {
+    fn push(&mut self, t: f64, row: impl IntoIterator<Item = f64>) {
+        self.timestamps.push(t);
+        self.data.extend(row);
+        debug_assert_eq!(self.data.len(), self.timestamps.len() * self.width);
+    }
+
+    fn flush<S: RecordingSink>(&mut self, sink: &mut S) -> Result<()> {
+        if self.timestamps.is_empty() {
+            return Ok(());
+        }
+        sink.append(&self.path, &self.timestamps, &self.data)?;
+        self.timestamps.clear();
+        self.data.clear();
+        Ok(())
+    }
+}
+
+const LAYERS: [(LayerType, &str); 5] = [
+    (LayerType::Layer1, "layer1"),
+    (LayerType::Layer2_3, "layer2_3"),
+    (LayerType::Layer4, "layer4"),
+    (LayerType::Layer5, "layer5"),
+    (LayerType::Layer6, "layer6"),
+];
+
+fn layer_name(layer: LayerType) -> &'static str {
+    LAYERS.iter().find(|(l, _)| *l == layer).map(|(_, name)| *name).unwrap_or("unknown")
+}
+
+fn drug_path(drug: &str) -> String {
+    format!("processing/pharmacology/{drug}")
+}
+
+/// Streams chosen probes of a running [`WholeBrain`] to a [`RecordingSink`]
+pub struct Recorder<S: RecordingSink> {
+    sink: S,
+    config: RecorderConfig,
+    steps: usize,
+
+    soma: Option<SeriesBuffer>,
+    layers: Vec<SeriesBuffer>,
+    dopamine: Option<SeriesBuffer>,
+    /// Drug series with the drug they follow
+    drugs: Vec<(SeriesBuffer, String)>,
+
+    /// Spike times, present when recorded
+    units: Option<UnitsTable>,
+}
+
+impl<S: RecordingSink> Recorder<S> {
+    /// Declare the probed series on `sink` for the shape of `brain`
+    pub fn new(mut sink: S, config: RecorderConfig, brain: &WholeBrain) -> Result<Self> {
+        if config.every == 0 {
+            return Err(RecorderError::InvalidConfig("every must be at least 1".into()));
+        }
+        if config.chunk_size == 0 {
+            return Err(RecorderError::InvalidConfig("chunk_size must be at least 1".into()));
+        }
+
+        sink.begin(&SessionInfo {
+            identifier: format!("humanbrain-{:016x}", brain.seed),
+            description: config.session_description.clone(),
+            seed: brain.seed,
+            dt: brain.dt,
+        })?;
+
+        let columns = &brain.cortex.columns;
+        let num_columns = columns.len();
+        let num_neurons: usize = columns.iter().map(|c| c.neurons.len()).sum();
+        let chunk_size = config.chunk_size;
+
+        let mut declare = |path: String, width: usize, unit: &'static str, description: &str| -> Result<SeriesBuffer> {
+            sink.create_series(&SeriesSpec {
+                path: path.clone(),
+                width,
+                unit,
+                description: description.to_string(),
+                chunk_size,
+            })?;
+            Ok(SeriesBuffer {
+                path,
+                width,
+                timestamps: Vec::with_capacity(chunk_size),
+                data: Vec::with_capacity(chunk_size * width),
+            })
+        };
+
+        let mut soma = None;
+        let mut layers = Vec::new();
+        let mut dopamine = None;
+        let mut drugs = Vec::new();
+        let mut units = None;
+
+        for probe in &config.probes {
+            match probe {
+                Probe::SomaVoltage if soma.is_none() => {
+                    soma = Some(declare(
+                        "acquisition/soma_voltage".into(),
+                        num_neurons,
+                        "mV",
+                        "Soma voltage of every cortical neuron, column-major order",
+                    )?);
+                }
+                Probe::LayerActivity if layers.is_empty() => {
+                    for (_, name) in LAYERS {
+                        layers.push(declare(
+                            format!("processing/cortex/{name}"),
+                            num_columns,
+                            "mV",
+                            "Mean soma voltage of the layer in each column",
+                        )?);
+                    }
+                }
+                Probe::Dopamine if dopamine.is_none() => {
+                    dopamine = Some(declare(
+                        "processing/neuromodulation/dopamine".into(),
+                        1,
+                        "normalized",
+                        "SNc dopamine level",
+                    )?);
+                }
+                Probe::DrugConcentration { drug } => {
+                    let path = drug_path(drug);
+                    if drugs.iter().all(|(buffer, _): &(SeriesBuffer, String)| buffer.path != path) {
+                        drugs.push((declare(path, 1, "uM", "Brain concentration")?, drug.clone()));
+                    }
+                }
+                Probe::SpikeTimes if units.is_none() => {
+                    let mut table = UnitsTable::default();
+                    for column in columns {
+                        for &layer in &column.neuron_layers {
+                            table.ids.push(table.ids.len() as u64);
+                            table.column.push(column.id as u64);
+                            table.layer.push(layer_name(layer).to_string());
+                            table.spike_times.push(Vec::new());
+                        }
+                    }
+                    units = Some(table);
+                }
+                // Duplicate probe
+                _ => {}
+            }
+        }
+
+        Ok(Self {
+            sink,
+            config,
+            steps: 0,
+            soma,
+            layers,
+            dopamine,
+            drugs,
+            units,
+        })
+    }
+
+    /// Observe the brain after a step; call once per `WholeBrain::step`
+    pub fn observe(&mut self, brain: &WholeBrain, state: &BrainState) -> Result<()> {
+        let t = state.time;
+        let neurons = || brain.cortex.columns.iter().flat_map(|c| c.neurons.iter());
+
+        // Spikes at full resolution, with their interpolated crossing times
+        if let Some(units) = &mut self.units {
+            for (i, neuron) in neurons().enumerate() {
+                if let Some(spike) = neuron.last_step_spike() {
+                    units.spike_times[i].push(spike.time);
+                }
+            }
+        }
+
+        self.steps += 1;
+        if !self.steps.is_multiple_of(self.config.every) {
+            return Ok(());
+        }
+
+        if let Some(soma) = &mut self.soma {
+            soma.push(t, neurons().map(|n| n.compartments.first().map_or(-70.0, |c| c.voltage)));
+        }
+
+        let layers = &state.cortical_layers;
+        let by_layer = [&layers.layer1, &layers.layer2_3, &layers.layer4, &layers.layer5, &layers.layer6];
+        for (buffer, values) in self.layers.iter_mut().zip(by_layer) {
+            buffer.push(t, values.iter().copied());
+        }
+
+        if let Some(dopamine) = &mut self.dopamine {
+            dopamine.push(t, [state.snc_dopamine]);
+        }
+
+        for (buffer, drug) in &mut self.drugs {
+            buffer.push(t, [brain.drug_concentration_um(drug)]);
+        }
+
+        if self.buffered() >= self.config.chunk_size {
+            self.flush()?;
+        }
+        Ok(())
+    }
+
+    /// Samples waiting in the buffers (all series are sampled together)
+    fn buffered(&self) -> usize {
+        self.buffers().map(|b| b.timestamps.len()).max().unwrap_or(0)
+    }
+
+    fn buffers(&self) -> impl Iterator<Item = &SeriesBuffer> {
+        self.soma
+            .iter()
+            .chain(&self.layers)
+            .chain(&self.dopamine)
+            .chain(self.drugs.iter().map(|(b, _)| b))
+    }
+
+    /// Write all buffered samples to the sink
+    pub fn flush(&mut self) -> Result<()> {
+        let sink = &mut self.sink;
+        for buffer in self
+            .soma
+            .iter_mut()
+            .chain(&mut self.layers)
+            .chain(&mut self.dopamine)
+            .chain(self.drugs.iter_mut().map(|(b, _)| b))
+        {
+            buffer.flush(sink)?;
+        }
+        Ok(())
+    }
+
+    /// Flush remaining samples, write the units table and close the sink
+    pub fn finish(mut self) -> Result<S> {
+        self.flush()?;
+        if let Some(units) = &self.units {
+            self.sink.write_units(units)?;
+        }
+        self.sink.finish()?;
+        Ok(self.sink)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{BrainConfig, DrugExposure};
+
+    fn diazepam(concentration_um: f64) -> DrugExposure {
+        DrugExposure::constant(pharmacology::DrugDatabase::new().get("diazepam").unwrap().clone(), concentration_um)
+    }
+
+    fn recorded(config: RecorderConfig, steps: usize) -> MemorySink {
+        let mut brain = WholeBrain::with_seed(0.1, 0.1, 21).unwrap();
+        brain.add_drug_exposure(diazepam(0.3));
+        let mut recorder = Recorder::new(MemorySink::default(), config, &brain).unwrap();
+
+        for i in 0..steps {
+            let sensory = vec![if i % 4 == 0 { 40.0 } else { 2.0 }; 10];
+            let state = brain.step(&sensory, 0.5, [50.0, 50.0]).unwrap();
+            recorder.observe(&brain, &state).unwrap();
+        }
+        recorder.finish().unwrap()
+    }
+
+    fn all_probes(every: usize, chunk_size: usize) -> RecorderConfig {
+        RecorderConfig {
+            probes: vec![
+                Probe::SpikeTimes,
+                Probe::SomaVoltage,
+                Probe::LayerActivity,
+                Probe::Dopamine,
+                Probe::DrugConcentration { drug: "diazepam".into() },
+            ],
+            every,
+            chunk_size,
+            ..RecorderConfig::default()
+        }
+    }
+
+    #[test]
+    fn test_decimated_chunked_recording() {
+        let sink = recorded(all_probes(2, 4), 20);
+        assert!(sink.finished);
+        assert_eq!(sink.session.as_ref().unwrap().seed, 21);
+
+        // 20 steps every 2 -> 10 samples, in chunks of 4, 4 and 2
+        let dopamine = &sink.series["processing/neuromodulation/dopamine"];
+        assert_eq!(dopamine.timestamps.len(), 10);
+        assert_eq!(dopamine.writes, 3);
+        assert!((dopamine.timestamps[0] - 0.2).abs() < 1e-9);
+
+        let layer5 = &sink.series["processing/cortex/layer5"];
+        assert_eq!(layer5.spec.width, 10);
+        assert_eq!(layer5.data.len(), 10 * 10);
+
+        let soma = &sink.series["acquisition/soma_voltage"];
+        let units = sink.units.as_ref().unwrap();
+        assert_eq!(soma.spec.width, units.ids.len());
+        assert!(soma.row(9).iter().all(|v| v.is_finite()));
+
+        let drug = &sink.series["processing/pharmacology/diazepam"];
+        assert!((drug.row(0)[0] - 0.3).abs() < 1e-12);
+    }
+
+    #[test]
+    fn test_drug_probes_follow_the_exposures() {
+        let mut brain = WholeBrain::with_seed(0.1, 0.1, 21).unwrap();
+        let config = RecorderConfig {
+            probes: vec![
+                Probe::DrugConcentration { drug: "Diazepam".into() },
+                Probe::DrugConcentration { drug: "midazolam".into() },
+            ],
+            ..RecorderConfig::default()
+        };
+        let mut recorder = Recorder::new(MemorySink::default(), config, &brain).unwrap();
+
+        // Two doses of the same drug add up; withdrawal drops the level to 0
+        brain.add_drug_exposure(diazepam(0.2));
+        brain.add_drug_exposure(diazepam(0.1));
+        for i in 0..4 {
+            if i == 2 {
+                brain.clear_drug_exposures();
+            }
+            let state = brain.step(&[2.0; 10], 0.0, [50.0, 50.0]).unwrap();
+            recorder.observe(&brain, &state).unwrap();
+        }
+        let sink = recorder.finish().unwrap();
+
+        let diazepam = sink.series["processing/pharmacology/Diazepam"].channel(0);
+        assert!((diazepam[0] - 0.3).abs() < 1e-12 && (diazepam[1] - 0.3).abs() < 1e-12);
+        assert_eq!(&diazepam[2..], [0.0, 0.0]);
+        assert_eq!(sink.series["processing/pharmacology/midazolam"].channel(0), vec![0.0; 4]);
+    }
+
+    #[test]
+    fn test_spike_times_are_not_decimated() {
+        let coarse = recorded(all_probes(5, 1024), 30);
+        let fine = recorded(all_probes(1, 1024), 30);
+        assert_eq!(coarse.units, fine.units);
+
+        let units = fine.units.unwrap();
+        let (flat, index) = units.ragged();
+        assert_eq!(index.len(), units.ids.len());
+        assert_eq!(*index.last().unwrap() as usize, flat.len());
+    }
+
+    #[test]
+    fn test_recorder_follows_a_configured_run() {
+        let config = BrainConfig { duration_ms: 1.0, seed: Some(21), ..BrainConfig::default() };
+        let mut brain = WholeBrain::from_config(&config).unwrap();
+        let recorder_config = RecorderConfig { every: 5, chunk_size: 1, ..RecorderConfig::default() };
+        let mut recorder = Recorder::new(MemorySink::default(), recorder_config, &brain).unwrap();
+
+        brain
+            .run_until_observed(&config, config.duration_ms, |brain, state| {
+                Ok(recorder.observe(brain, state)?)
+            })
+            .unwrap();
+        let sink = recorder.finish().unwrap();
+
+        let layer4 = &sink.series["processing/cortex/layer4"];
+        assert_eq!(layer4.timestamps.len(), 2);
+        assert_eq!(layer4.writes, 2);
+        assert!(sink.units.is_some());
+    }
+
+    #[test]
+    fn test_recordings_feed_the_analysis_toolkit() {
+        let mut brain = WholeBrain::with_seed(0.1, 0.1, 21).unwrap();
+        let mut recorder = Recorder::new(MemorySink::default(), all_probes(1, 64), &brain).unwrap();
+        let mut states = Vec::new();
+        for i in 0..100 {
+            let sensory = vec![if i % 4 == 0 { 40.0 } else { 2.0 }; 10];
+            let state = brain.step(&sensory, 0.5, [50.0, 50.0]).unwrap();
+            recorder.observe(&brain, &state).unwrap();
+            states.push(state);
+        }
+        let sink = recorder.finish().unwrap();
+
+        // Units table -> spike trains
+        let units = sink.units.as_ref().unwrap();
+        let counts = analysis::population_counts(&units.spike_times, 0.0, 10.0, 1.0);
+        assert_eq!(counts.len(), 10);
+        assert_eq!(counts.iter().sum::<usize>(), units.spike_times.iter().map(Vec::len).sum::<usize>());
+
+        // Continuous series -> per-channel traces
+        let soma = &sink.series["acquisition/soma_voltage"];
+        let traces: Vec<Vec<f64>> = (0..soma.spec.width).map(|c| soma.channel(c)).collect();
+        assert_eq!(traces[0].len(), 100);
+        let chi = analysis::population_synchrony(&traces).unwrap();
+        assert!(chi > 0.0 && chi <= 1.0 + 1e-9);
+        let spectrum = analysis::welch_psd(&traces[0], 1000.0 / 0.1, 64);
+        assert!(spectrum.power.iter().all(|p| p.is_finite()));
+
+        // BrainState sequence -> spike trains of a region
+        let trn = analysis::spike_trains_from_flags(states.iter().map(|s| (s.time, s.trn_activity.as_slice())));
+        assert_eq!(trn.len(), states[0].trn_activity.len());
+    }
+
+    #[test]
+    fn test_unprobed_series_are_absent() {
+        let config = RecorderConfig {
+            probes: vec![Probe::Dopamine, Probe::DrugConcentration { drug: "diazepam".into() }],
+            ..RecorderConfig::default()
+        };
+        let sink = recorded(config, 3);
+        assert_eq!(sink.series.len(), 2);
+        assert!(sink.units.is_none());
+    }
+}